@@ -0,0 +1,204 @@
+//! Botctl - headless control client
+//!
+//! CLI client for the bot's control API: inspect positions, strategies, the
+//! nonce pool and RPC health, and issue the same commands as the GUI.
+
+use anyhow::{bail, Context, Result};
+use bot::components::control_protocol::{ControlCommand, ErrorResponse};
+use bot::types::TradingMode;
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Control a running bot", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Control API base URL
+    #[arg(long, env = "BOTCTL_URL", default_value = "http://127.0.0.1:9091")]
+    url: String,
+
+    /// Bearer token
+    #[arg(long, env = "BOTCTL_TOKEN", hide_env_values = true)]
+    token: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Show pause state, trading mode and open position count
+    Status,
+
+    /// List open positions
+    Positions,
+
+    /// List TP/SL strategies
+    Strategies,
+
+    /// Show nonce pool statistics
+    Nonce,
+
+    /// Show RPC endpoint health
+    Rpc,
+
+    /// Sell part of a position
+    Sell {
+        /// Token mint
+        mint: String,
+
+        /// Fraction of the position to sell (0.0-1.0)
+        #[arg(long, default_value = "1.0")]
+        percent: f64,
+    },
+
+    /// Set the auto-sell mode
+    Mode {
+        #[arg(value_enum)]
+        mode: ModeArg,
+    },
+
+    /// Set a stop loss for a token
+    StopLoss {
+        /// Token mint
+        mint: String,
+
+        /// P&L percentage that triggers the sell (e.g. -10)
+        #[arg(allow_negative_numbers = true)]
+        threshold_percent: f64,
+    },
+
+    /// Set a take profit for a token
+    TakeProfit {
+        /// Token mint
+        mint: String,
+
+        /// P&L percentage that triggers the sell (e.g. 50)
+        threshold_percent: f64,
+
+        /// Fraction of the position to sell (0.0-1.0)
+        #[arg(long, default_value = "1.0")]
+        sell_percent: f64,
+    },
+
+    /// Remove TP/SL rules for a token
+    ClearStrategy {
+        /// Token mint
+        mint: String,
+    },
+
+    /// Enable or disable multi-token trading
+    MultiToken {
+        #[arg(action = clap::ArgAction::Set)]
+        enabled: bool,
+
+        /// Maximum concurrent positions
+        #[arg(long)]
+        max_positions: Option<usize>,
+    },
+
+    /// Pause trading
+    Pause,
+
+    /// Resume trading
+    Resume,
+
+    /// Stop all trading immediately
+    EmergencyStop,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ModeArg {
+    Manual,
+    Auto,
+    Hybrid,
+}
+
+impl From<ModeArg> for TradingMode {
+    fn from(mode: ModeArg) -> Self {
+        match mode {
+            ModeArg::Manual => TradingMode::Manual,
+            ModeArg::Auto => TradingMode::Auto,
+            ModeArg::Hybrid => TradingMode::Hybrid,
+        }
+    }
+}
+
+/// What a subcommand sends to the control API
+enum Call {
+    /// `GET /v1/<route>`
+    Get(&'static str),
+    /// `POST /v1/commands`
+    Post(ControlCommand),
+}
+
+impl Commands {
+    fn into_call(self) -> Call {
+        Call::Post(match self {
+            Self::Status => return Call::Get("status"),
+            Self::Positions => return Call::Get("positions"),
+            Self::Strategies => return Call::Get("strategies"),
+            Self::Nonce => return Call::Get("nonce"),
+            Self::Rpc => return Call::Get("rpc"),
+            Self::Sell { mint, percent } => ControlCommand::Sell { mint, percent },
+            Self::Mode { mode } => ControlCommand::SetTradingMode { mode: mode.into() },
+            Self::StopLoss {
+                mint,
+                threshold_percent,
+            } => ControlCommand::SetStopLoss {
+                mint,
+                threshold_percent,
+            },
+            Self::TakeProfit {
+                mint,
+                threshold_percent,
+                sell_percent,
+            } => ControlCommand::SetTakeProfit {
+                mint,
+                threshold_percent,
+                sell_percent,
+            },
+            Self::ClearStrategy { mint } => ControlCommand::ClearStrategy { mint },
+            Self::MultiToken {
+                enabled,
+                max_positions,
+            } => ControlCommand::SetMultiTokenMode {
+                enabled,
+                max_positions,
+            },
+            Self::Pause => ControlCommand::SetPaused { paused: true },
+            Self::Resume => ControlCommand::SetPaused { paused: false },
+            Self::EmergencyStop => ControlCommand::EmergencyStop,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let base = args.url.trim_end_matches('/');
+    let client = reqwest::Client::new();
+
+    let request = match args.command.into_call() {
+        Call::Get(route) => client.get(format!("{}/v1/{}", base, route)),
+        Call::Post(command) => client.post(format!("{}/v1/commands", base)).json(&command),
+    };
+    let response = request
+        .bearer_auth(&args.token)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach control API at {}", base))?;
+
+    let status = response.status();
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .context("Control API returned an invalid response")?;
+    if !status.is_success() {
+        let message = serde_json::from_value::<ErrorResponse>(body)
+            .map(|e| e.error)
+            .unwrap_or_else(|_| status.to_string());
+        bail!("{} ({})", message, status);
+    }
+
+    println!("{}", serde_json::to_string_pretty(&body)?);
+    Ok(())
+}
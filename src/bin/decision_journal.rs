@@ -0,0 +1,190 @@
+//! Decision-journal - candidate decision history
+//!
+//! CLI tool for querying the per-candidate decision journal the buy engine
+//! writes (`[decision_journal]` in the bot config) by mint, time and reason
+//! code, and exporting the matching candidates to CSV or JSON lines.
+
+use anyhow::{bail, Context, Result};
+use bot::decision_journal::{self, CandidateHistory, JournalQuery, ReasonCode};
+use chrono::{DateTime, NaiveDate, SecondsFormat};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use tracing::{info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Candidate decision journal", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Journal directory (`decision_journal.dir`)
+    #[arg(long, env = "DECISION_JOURNAL_DIR", default_value = "journal")]
+    dir: PathBuf,
+
+    /// Enable verbose logging
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// List matching candidates with their outcome and reason
+    Query {
+        #[command(flatten)]
+        filter: Filter,
+    },
+
+    /// Write matching candidates to a file or stdout
+    Export {
+        #[command(flatten)]
+        filter: Filter,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+
+        /// File to write (defaults to stdout)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum Format {
+    Csv,
+    Jsonl,
+}
+
+#[derive(ClapArgs, Debug)]
+struct Filter {
+    /// Token mint
+    #[arg(long)]
+    mint: Option<String>,
+
+    /// Received at or after (RFC 3339 or YYYY-MM-DD, UTC)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Received before (RFC 3339 or YYYY-MM-DD, UTC)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Rejection or failure reason code (e.g. `mint_rate_limited`)
+    #[arg(long)]
+    reason: Option<ReasonCode>,
+}
+
+impl Filter {
+    fn query(&self) -> Result<JournalQuery> {
+        Ok(JournalQuery {
+            mint: self.mint.clone(),
+            since_ms: self.since.as_deref().map(parse_time).transpose()?,
+            until_ms: self.until.as_deref().map(parse_time).transpose()?,
+            reason: self.reason,
+        })
+    }
+}
+
+/// Unix ms of an RFC 3339 timestamp or the start of a UTC date
+fn parse_time(value: &str) -> Result<u64> {
+    let millis = match DateTime::parse_from_rfc3339(value) {
+        Ok(time) => time.timestamp_millis(),
+        Err(_) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .with_context(|| format!("Invalid time (RFC 3339 or YYYY-MM-DD): {}", value))?
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc()
+            .timestamp_millis(),
+    };
+    if millis < 0 {
+        bail!("Time before the Unix epoch: {}", value);
+    }
+    Ok(millis as u64)
+}
+
+fn format_time(millis: u64) -> String {
+    DateTime::from_timestamp_millis(millis as i64)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+fn print_histories(histories: &[CandidateHistory]) {
+    let mut outcomes: BTreeMap<&str, usize> = BTreeMap::new();
+    for history in histories {
+        let outcome = history.outcome().as_str();
+        *outcomes.entry(outcome).or_default() += 1;
+        println!(
+            "{}  {}  {:<9}  {:<20}  {}",
+            format_time(history.received_at_ms),
+            history.mint,
+            outcome,
+            history.reason().map_or("-", ReasonCode::as_str),
+            history
+                .pnl_lamports
+                .map_or_else(|| "-".to_string(), |pnl| pnl.to_string())
+        );
+    }
+
+    let summary: Vec<String> = outcomes
+        .iter()
+        .map(|(outcome, count)| format!("{} {}", count, outcome))
+        .collect();
+    println!("\n{} candidates ({})", histories.len(), summary.join(", "));
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // Initialize logging
+    let level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(level)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    if !args.dir.is_dir() {
+        bail!("Journal directory not found: {}", args.dir.display());
+    }
+
+    match args.command {
+        Commands::Query { filter } => {
+            let histories = decision_journal::query(&args.dir, &filter.query()?)?;
+            print_histories(&histories);
+        }
+        Commands::Export {
+            filter,
+            format,
+            output,
+        } => {
+            let histories = decision_journal::query(&args.dir, &filter.query()?)?;
+            let out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(
+                    File::create(path)
+                        .with_context(|| format!("Failed to create {}", path.display()))?,
+                ),
+                None => Box::new(io::stdout().lock()),
+            };
+            let mut out = BufWriter::new(out);
+            match format {
+                Format::Csv => decision_journal::write_csv(&histories, &mut out)?,
+                Format::Jsonl => decision_journal::write_jsonl(&histories, &mut out)?,
+            }
+            out.flush()?;
+            if let Some(path) = &output {
+                info!(candidates = histories.len(), "Wrote {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+//! Keystore - encrypted key file management
+//!
+//! CLI tool for importing plaintext Solana keypairs into the bot's encrypted
+//! keystore format, exporting them back, and rotating the passphrase.
+
+use anyhow::{Context, Result};
+use bot::keystore::{self, EncryptedKeystore, KdfParams, PassphraseSource};
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use solana_sdk::signature::Signer;
+use std::path::{Path, PathBuf};
+use tracing::{info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Encrypted keystore management", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Enable verbose logging
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Encrypt a plaintext keypair file into a keystore
+    Import {
+        /// Plaintext keypair (64 raw bytes or Solana CLI JSON)
+        input: PathBuf,
+
+        /// Keystore file to write
+        output: PathBuf,
+
+        /// Passphrase for the new keystore
+        #[command(flatten)]
+        passphrase: NewPassphrase,
+
+        /// Argon2 cost parameters
+        #[command(flatten)]
+        kdf: KdfArgs,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Decrypt a keystore into a plaintext Solana CLI keypair file
+    Export {
+        /// Keystore file to read
+        input: PathBuf,
+
+        /// Plaintext keypair file to write
+        output: PathBuf,
+
+        /// Passphrase of the keystore
+        #[command(flatten)]
+        passphrase: CurrentPassphrase,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Re-encrypt a keystore with a new passphrase, salt and nonce
+    Rotate {
+        /// Keystore file to rotate in place
+        path: PathBuf,
+
+        /// Current passphrase
+        #[command(flatten)]
+        passphrase: CurrentPassphrase,
+
+        /// Passphrase for the rotated keystore
+        #[command(flatten)]
+        new_passphrase: NewPassphrase,
+
+        /// Argon2 cost parameters (defaults to the current ones)
+        #[command(flatten)]
+        kdf: KdfArgs,
+    },
+
+    /// Print the public key of a keystore or keypair file
+    Pubkey {
+        /// Keystore or keypair file
+        path: PathBuf,
+    },
+}
+
+#[derive(ClapArgs, Debug)]
+struct CurrentPassphrase {
+    /// Read the passphrase from this environment variable
+    #[arg(long, conflicts_with = "passphrase_fd")]
+    passphrase_env: Option<String>,
+
+    /// Read the passphrase from this file descriptor
+    #[arg(long)]
+    passphrase_fd: Option<i32>,
+}
+
+#[derive(ClapArgs, Debug)]
+struct NewPassphrase {
+    /// Read the new passphrase from this environment variable
+    #[arg(long, conflicts_with = "new_passphrase_fd")]
+    new_passphrase_env: Option<String>,
+
+    /// Read the new passphrase from this file descriptor
+    #[arg(long)]
+    new_passphrase_fd: Option<i32>,
+}
+
+#[derive(ClapArgs, Debug)]
+struct KdfArgs {
+    /// Argon2 memory cost in KiB
+    #[arg(long)]
+    m_cost_kib: Option<u32>,
+
+    /// Argon2 iterations
+    #[arg(long)]
+    t_cost: Option<u32>,
+
+    /// Argon2 parallelism
+    #[arg(long)]
+    p_cost: Option<u32>,
+}
+
+impl KdfArgs {
+    fn apply(&self, base: KdfParams) -> KdfParams {
+        KdfParams {
+            m_cost_kib: self.m_cost_kib.unwrap_or(base.m_cost_kib),
+            t_cost: self.t_cost.unwrap_or(base.t_cost),
+            p_cost: self.p_cost.unwrap_or(base.p_cost),
+        }
+    }
+}
+
+fn source(env: &Option<String>, fd: Option<i32>) -> PassphraseSource {
+    match (fd, env) {
+        (Some(fd), _) => PassphraseSource::Fd(fd),
+        (None, Some(var)) => PassphraseSource::Env(var.clone()),
+        (None, None) => PassphraseSource::Prompt,
+    }
+}
+
+fn ensure_writable(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // Initialize logging
+    let level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(level)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    match args.command {
+        Commands::Import {
+            input,
+            output,
+            passphrase,
+            kdf,
+            force,
+        } => {
+            ensure_writable(&output, force)?;
+            let keypair = keystore::load_keypair(&input, &PassphraseSource::Prompt)
+                .with_context(|| format!("Failed to load {}", input.display()))?;
+
+            let new = source(&passphrase.new_passphrase_env, passphrase.new_passphrase_fd)
+                .read_new("New keystore passphrase: ")?;
+            EncryptedKeystore::encrypt(&keypair, &new, kdf.apply(KdfParams::default()))?
+                .save(&output)?;
+
+            info!("Imported {} into {}", keypair.pubkey(), output.display());
+            println!("{}", keypair.pubkey());
+        }
+
+        Commands::Export {
+            input,
+            output,
+            passphrase,
+            force,
+        } => {
+            ensure_writable(&output, force)?;
+            let keypair = keystore::load_keypair(
+                &input,
+                &source(&passphrase.passphrase_env, passphrase.passphrase_fd),
+            )
+            .with_context(|| format!("Failed to unlock {}", input.display()))?;
+
+            keystore::save_plaintext(&output, &keypair)?;
+            info!("Exported {} to {}", keypair.pubkey(), output.display());
+            println!("{}", keypair.pubkey());
+        }
+
+        Commands::Rotate {
+            path,
+            passphrase,
+            new_passphrase,
+            kdf,
+        } => {
+            let data = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let current = EncryptedKeystore::from_json(&data)
+                .with_context(|| format!("{} is not an encrypted keystore", path.display()))?;
+            let keypair = keystore::load_keypair(
+                &path,
+                &source(&passphrase.passphrase_env, passphrase.passphrase_fd),
+            )
+            .with_context(|| format!("Failed to unlock {}", path.display()))?;
+
+            let new = source(
+                &new_passphrase.new_passphrase_env,
+                new_passphrase.new_passphrase_fd,
+            )
+            .read_new("New keystore passphrase: ")?;
+            EncryptedKeystore::encrypt(&keypair, &new, kdf.apply(current.kdf_params()))?
+                .save(&path)?;
+
+            info!("Rotated keystore {} ({})", path.display(), keypair.pubkey());
+            println!("{}", keypair.pubkey());
+        }
+
+        Commands::Pubkey { path } => {
+            let data = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let pubkey = if keystore::is_encrypted(&data) {
+                EncryptedKeystore::from_json(&data)?.pubkey()?
+            } else {
+                keystore::load_keypair(&path, &PassphraseSource::Prompt)?.pubkey()
+            };
+            println!("{}", pubkey);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,392 @@
+//! Nonce-admin - durable nonce pool provisioning
+//!
+//! CLI tool for creating and funding nonce accounts, discovering existing ones
+//! by authority, advancing, withdrawing from and closing them, and verifying
+//! the pool file the bot loads at startup against the chain.
+
+use anyhow::{bail, Context, Result};
+use bot::keystore::{self, PassphraseSource};
+use bot::nonce_manager::nonce_provisioning::{NonceAccountInfo, NoncePoolFile, NonceProvisioner};
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Durable nonce pool provisioning", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// RPC endpoint
+    #[arg(
+        long,
+        env = "NONCE_ADMIN_RPC_URL",
+        default_value = "https://api.mainnet-beta.solana.com"
+    )]
+    rpc_url: String,
+
+    /// Fee payer keystore or keypair file
+    #[arg(long, env = "NONCE_ADMIN_PAYER")]
+    payer: Option<PathBuf>,
+
+    /// Enable verbose logging
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Create and fund nonce accounts and write the pool file
+    Create {
+        /// Number of accounts to create
+        #[arg(long)]
+        count: usize,
+
+        /// Nonce authority (defaults to the payer)
+        #[arg(long)]
+        authority: Option<String>,
+
+        /// Lamports per account (defaults to the rent-exempt minimum)
+        #[arg(long)]
+        lamports: Option<u64>,
+
+        /// Pool file to write
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// List nonce accounts controlled by an authority
+    Discover {
+        /// Nonce authority
+        authority: String,
+    },
+
+    /// Write every nonce account under an authority to a pool file
+    Export {
+        /// Nonce authority
+        authority: String,
+
+        /// Pool file to write
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Advance nonces, invalidating transactions signed against them
+    Advance {
+        #[command(flatten)]
+        target: Target,
+
+        #[command(flatten)]
+        authority: AuthorityKey,
+    },
+
+    /// Withdraw lamports from a nonce account
+    Withdraw {
+        /// Nonce account
+        account: String,
+
+        /// Lamports to withdraw
+        lamports: u64,
+
+        /// Recipient (defaults to the payer)
+        #[arg(long)]
+        to: Option<String>,
+
+        #[command(flatten)]
+        authority: AuthorityKey,
+    },
+
+    /// Close nonce accounts, reclaiming their rent
+    Close {
+        #[command(flatten)]
+        target: Target,
+
+        /// Recipient (defaults to the payer)
+        #[arg(long)]
+        to: Option<String>,
+
+        #[command(flatten)]
+        authority: AuthorityKey,
+    },
+
+    /// Check the pool file against on-chain state
+    Verify {
+        /// Pool file to check
+        #[arg(long, conflicts_with = "config", required_unless_present = "config")]
+        pool: Option<PathBuf>,
+
+        /// Bot config whose `nonce.accounts_file` and `nonce.pool_size` to check
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+}
+
+#[derive(ClapArgs, Debug)]
+struct Target {
+    /// Nonce accounts
+    #[arg(required_unless_present = "pool")]
+    accounts: Vec<String>,
+
+    /// Every account in this pool file
+    #[arg(long, conflicts_with = "accounts")]
+    pool: Option<PathBuf>,
+}
+
+impl Target {
+    fn pubkeys(&self) -> Result<Vec<Pubkey>> {
+        match &self.pool {
+            Some(path) => Ok(NoncePoolFile::load(path)?.pubkeys()?),
+            None => self.accounts.iter().map(|a| parse_pubkey(a)).collect(),
+        }
+    }
+}
+
+#[derive(ClapArgs, Debug)]
+struct AuthorityKey {
+    /// Nonce authority keystore or keypair file (defaults to the payer)
+    #[arg(long)]
+    authority_keypair: Option<PathBuf>,
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    value
+        .parse()
+        .with_context(|| format!("Invalid pubkey: {}", value))
+}
+
+fn load_key(path: &Path) -> Result<Keypair> {
+    keystore::load_keypair(path, &PassphraseSource::from_env())
+        .with_context(|| format!("Failed to load {}", path.display()))
+}
+
+fn ensure_writable(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        bail!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn print_accounts(accounts: &[NonceAccountInfo]) {
+    for info in accounts {
+        println!(
+            "{}  {}  {:.6} SOL",
+            info.pubkey,
+            info.blockhash,
+            info.lamports as f64 / LAMPORTS_PER_SOL as f64
+        );
+    }
+}
+
+/// Pool file path and expected size from a bot config
+fn pool_from_config(path: &Path) -> Result<(PathBuf, Option<usize>)> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let value: toml::Value =
+        toml::from_str(&text).with_context(|| format!("Invalid TOML in {}", path.display()))?;
+    let nonce = value.get("nonce");
+    let Some(file) = nonce
+        .and_then(|n| n.get("accounts_file"))
+        .and_then(|f| f.as_str())
+    else {
+        bail!("{} does not set nonce.accounts_file", path.display());
+    };
+    let pool_size = nonce
+        .and_then(|n| n.get("pool_size"))
+        .and_then(|s| s.as_integer())
+        .map(|s| s as usize);
+    Ok((PathBuf::from(file), pool_size))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // Initialize logging
+    let level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(level)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+    let payer = args.payer.as_deref().map(load_key).transpose()?;
+    let payer_pubkey = payer.as_ref().map(Keypair::pubkey);
+    let authority_key = |key: &AuthorityKey| -> Result<Keypair> {
+        match (&key.authority_keypair, &args.payer) {
+            (Some(path), _) | (None, Some(path)) => load_key(path),
+            (None, None) => bail!("--authority-keypair or --payer is required"),
+        }
+    };
+    let recipient = |to: &Option<String>| -> Result<Pubkey> {
+        match (to, payer_pubkey) {
+            (Some(to), _) => parse_pubkey(to),
+            (None, Some(payer)) => Ok(payer),
+            (None, None) => bail!("--to or --payer is required"),
+        }
+    };
+
+    let mut provisioner = NonceProvisioner::new(rpc);
+    if let Some(payer) = payer {
+        provisioner = provisioner.with_payer(payer);
+    }
+
+    match &args.command {
+        Commands::Create {
+            count,
+            authority,
+            lamports,
+            output,
+            force,
+        } => {
+            ensure_writable(output, *force)?;
+            let authority = match (authority, payer_pubkey) {
+                (Some(authority), _) => parse_pubkey(authority)?,
+                (None, Some(payer)) => payer,
+                (None, None) => bail!("--payer is required"),
+            };
+
+            let created = provisioner.create(*count, &authority, *lamports).await?;
+            NoncePoolFile::new(&authority, &created).save(output)?;
+            info!(
+                "Created {} nonce accounts under {}, pool written to {}",
+                created.len(),
+                authority,
+                output.display()
+            );
+            for pubkey in &created {
+                println!("{}", pubkey);
+            }
+        }
+
+        Commands::Discover { authority } => {
+            let accounts = provisioner.discover(&parse_pubkey(authority)?).await?;
+            print_accounts(&accounts);
+            info!("{} nonce accounts under {}", accounts.len(), authority);
+        }
+
+        Commands::Export {
+            authority,
+            output,
+            force,
+        } => {
+            ensure_writable(output, *force)?;
+            let authority = parse_pubkey(authority)?;
+            let accounts = provisioner.discover(&authority).await?;
+            let pubkeys: Vec<Pubkey> = accounts.iter().map(|info| info.pubkey).collect();
+            NoncePoolFile::new(&authority, &pubkeys).save(output)?;
+            info!(
+                "Exported {} nonce accounts to {}",
+                pubkeys.len(),
+                output.display()
+            );
+        }
+
+        Commands::Advance { target, authority } => {
+            let authority = authority_key(authority)?;
+            for pubkey in target.pubkeys()? {
+                let signature = provisioner.advance(&pubkey, &authority).await?;
+                println!("{}  {}", pubkey, signature);
+            }
+        }
+
+        Commands::Withdraw {
+            account,
+            lamports,
+            to,
+            authority,
+        } => {
+            let authority = authority_key(authority)?;
+            let signature = provisioner
+                .withdraw(
+                    &parse_pubkey(account)?,
+                    &authority,
+                    &recipient(to)?,
+                    *lamports,
+                )
+                .await?;
+            println!("{}", signature);
+        }
+
+        Commands::Close {
+            target,
+            to,
+            authority,
+        } => {
+            let authority = authority_key(authority)?;
+            let to = recipient(to)?;
+            let mut reclaimed = 0u64;
+            for pubkey in target.pubkeys()? {
+                reclaimed += provisioner.close(&pubkey, &authority, &to).await?;
+                println!("{}  closed", pubkey);
+            }
+            info!(
+                "Reclaimed {:.6} SOL to {}",
+                reclaimed as f64 / LAMPORTS_PER_SOL as f64,
+                to
+            );
+        }
+
+        Commands::Verify { pool, config } => {
+            let (path, pool_size) = match (pool, config) {
+                (Some(pool), _) => (pool.clone(), None),
+                (None, Some(config)) => pool_from_config(config)?,
+                (None, None) => unreachable!("clap requires --pool or --config"),
+            };
+            let pool = NoncePoolFile::load(&path)?;
+            let mut issues: Vec<String> = provisioner
+                .verify(&pool)
+                .await?
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            if let Some(pool_size) = pool_size {
+                if pool_size != pool.accounts.len() {
+                    issues.push(format!(
+                        "nonce.pool_size is {} but {} lists {} accounts",
+                        pool_size,
+                        path.display(),
+                        pool.accounts.len()
+                    ));
+                }
+            }
+
+            if issues.is_empty() {
+                info!(
+                    "{}: {} nonce accounts match on-chain state",
+                    path.display(),
+                    pool.accounts.len()
+                );
+            } else {
+                for issue in &issues {
+                    warn!("{}", issue);
+                }
+                bail!("{} issues found in {}", issues.len(), path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! Control API wire types
+//!
+//! JSON bodies shared by the headless control server and the `botctl` client.
+//! Mints travel as base58 strings; every [`ControlCommand`] maps one-to-one
+//! onto a [`GuiCommand`].
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::components::gui_bridge::GuiCommand;
+use crate::types::{PortfolioConfig, SellStrategy, TradingMode};
+
+/// Access level granted to a control API token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlRole {
+    /// Query positions, strategies and health
+    ReadOnly,
+    /// Everything `ReadOnly` can do, plus issuing commands
+    Trader,
+}
+
+/// A runtime command, as sent to `POST /v1/commands`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Sell `percent` (0.0-1.0) of a position
+    Sell { mint: String, percent: f64 },
+    /// Change the auto-sell mode
+    SetTradingMode { mode: TradingMode },
+    /// Set a stop loss for a token
+    SetStopLoss {
+        mint: String,
+        threshold_percent: f64,
+    },
+    /// Set a take profit for a token
+    SetTakeProfit {
+        mint: String,
+        threshold_percent: f64,
+        sell_percent: f64,
+    },
+    /// Remove TP/SL rules for a token
+    ClearStrategy { mint: String },
+    /// Enable or disable multi-token trading
+    SetMultiTokenMode {
+        enabled: bool,
+        max_positions: Option<usize>,
+    },
+    /// Pause or resume trading
+    SetPaused { paused: bool },
+    /// Stop all trading
+    EmergencyStop,
+    /// Replace the portfolio limits
+    UpdatePortfolioConfig { config: PortfolioConfig },
+}
+
+impl ControlCommand {
+    /// Validate and convert into the command the engine executes
+    pub fn into_gui_command(self) -> Result<GuiCommand, String> {
+        Ok(match self {
+            Self::Sell { mint, percent } => {
+                check_fraction("percent", percent)?;
+                GuiCommand::Sell {
+                    mint: parse_mint(&mint)?,
+                    percent,
+                }
+            }
+            Self::SetTradingMode { mode } => GuiCommand::SetTradingMode(mode),
+            Self::SetStopLoss {
+                mint,
+                threshold_percent,
+            } => GuiCommand::SetStopLoss {
+                mint: parse_mint(&mint)?,
+                threshold_percent,
+            },
+            Self::SetTakeProfit {
+                mint,
+                threshold_percent,
+                sell_percent,
+            } => {
+                check_fraction("sell_percent", sell_percent)?;
+                GuiCommand::SetTakeProfit {
+                    mint: parse_mint(&mint)?,
+                    threshold_percent,
+                    sell_percent,
+                }
+            }
+            Self::ClearStrategy { mint } => GuiCommand::ClearStrategy {
+                mint: parse_mint(&mint)?,
+            },
+            Self::SetMultiTokenMode {
+                enabled,
+                max_positions,
+            } => GuiCommand::SetMultiTokenMode {
+                enabled,
+                max_positions,
+            },
+            Self::SetPaused { paused } => GuiCommand::SetPaused(paused),
+            Self::EmergencyStop => GuiCommand::EmergencyStop,
+            Self::UpdatePortfolioConfig { config } => GuiCommand::UpdatePortfolioConfig(config),
+        })
+    }
+}
+
+fn parse_mint(mint: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(mint).map_err(|e| format!("invalid mint '{}': {}", mint, e))
+}
+
+fn check_fraction(name: &str, value: f64) -> Result<(), String> {
+    if value > 0.0 && value <= 1.0 {
+        Ok(())
+    } else {
+        Err(format!("{} must be in (0.0, 1.0], got {}", name, value))
+    }
+}
+
+/// Bot state returned by `GET /v1/status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusView {
+    pub paused: bool,
+    pub trading_mode: TradingMode,
+    pub open_positions: usize,
+}
+
+/// An open position returned by `GET /v1/positions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionView {
+    pub mint: String,
+    pub entry_timestamp: u64,
+    pub remaining_token_amount: u64,
+    pub sold_percent: f64,
+    pub initial_sol_cost: u64,
+    pub last_seen_price: f64,
+    pub pnl_sol: f64,
+    pub pnl_percent: f64,
+    /// Copy-trading leader the position was mirrored from
+    #[serde(default)]
+    pub leader: Option<String>,
+}
+
+/// A TP/SL strategy returned by `GET /v1/strategies`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyView {
+    /// Token the strategy applies to, `None` for the `[strategy]` default
+    pub mint: Option<String>,
+    pub strategy: SellStrategy,
+}
+
+/// Nonce pool counters returned by `GET /v1/nonce`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceStatsView {
+    pub total_accounts: usize,
+    pub available: usize,
+    pub in_use: usize,
+    pub tainted: usize,
+    pub total_acquires: u64,
+    pub total_releases: u64,
+    pub total_refreshes: u64,
+    #[serde(default)]
+    pub classes: Vec<NonceClassView>,
+}
+
+/// Nonce pool usage of one operation class
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceClassView {
+    pub class: String,
+    pub reserved: usize,
+    pub in_use: usize,
+    pub waiting: usize,
+}
+
+/// One RPC endpoint in `GET /v1/rpc`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcEndpointView {
+    /// Host and port only; credentials in the configured URL are stripped
+    pub url: String,
+    pub health: String,
+    pub success_rate: f64,
+    pub total_requests: u64,
+    pub in_cooldown: bool,
+}
+
+/// RPC pool health returned by `GET /v1/rpc`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcHealthView {
+    pub healthy: usize,
+    pub degraded: usize,
+    pub unhealthy: usize,
+    pub endpoints: Vec<RpcEndpointView>,
+}
+
+/// Error body for non-2xx responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_round_trip_and_validation() {
+        let mint = Pubkey::new_unique();
+        let json = format!(r#"{{"command":"sell","mint":"{}","percent":0.5}}"#, mint);
+        let cmd: ControlCommand = serde_json::from_str(&json).unwrap();
+        match cmd.into_gui_command().unwrap() {
+            GuiCommand::Sell { mint: m, percent } => {
+                assert_eq!(m, mint);
+                assert_eq!(percent, 0.5);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cmd: ControlCommand = serde_json::from_str(r#"{"command":"emergency_stop"}"#).unwrap();
+        assert!(matches!(
+            cmd.into_gui_command(),
+            Ok(GuiCommand::EmergencyStop)
+        ));
+
+        let bad_mint = ControlCommand::ClearStrategy {
+            mint: "not-a-mint".to_string(),
+        };
+        assert!(bad_mint.into_gui_command().is_err());
+
+        let bad_percent = ControlCommand::Sell {
+            mint: mint.to_string(),
+            percent: 1.5,
+        };
+        assert!(bad_percent.into_gui_command().is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! GUI integration components module
 
+pub mod control_protocol;
 pub mod gui_bridge;
+pub mod onchain_price;
 pub mod price_stream;
 
 // Universe-grade features
@@ -0,0 +1,512 @@
+//! On-chain Price Feed - Prices for open positions straight from chain state
+//!
+//! Watches the bonding-curve (or AMM pool vault) account of every position in
+//! the [`PositionTracker`], decodes the reserves into a SOL price and publishes
+//! it to the [`PriceStreamManager`] with `source = "onchain"`. The tracker's
+//! `last_seen_price` is updated on the same path so TP/SL evaluation never runs
+//! on the entry price alone.
+//!
+//! ## Architecture
+//!
+//! - **Polling**: One `getMultipleAccounts` per slot (400ms) through the
+//!   [`RpcPool`] at `processed` commitment, so a price is at most one slot old
+//! - **Push**: [`OnchainPriceFeed::apply_account_update`] accepts account data
+//!   from any subscription (`accountSubscribe`, Geyser) and shares the decode path
+//! - **Auto-discovery**: New positions are watched through their pump.fun
+//!   bonding curve; migrated tokens can be re-pointed at a pool with
+//!   [`OnchainPriceFeed::register`]
+//! - **Slot ordering**: Updates older than the last published slot are dropped
+//!
+//! ## Usage Example
+//!
+//! ```no_run
+//! use bot::components::onchain_price::{OnchainPriceConfig, OnchainPriceFeed};
+//! use bot::components::price_stream::PriceStreamManager;
+//! use bot::position_tracker::PositionTracker;
+//! # use bot::rpc_manager::RpcPool;
+//! use std::sync::Arc;
+//!
+//! # fn example(rpc_pool: Arc<RpcPool>) {
+//! let feed = Arc::new(OnchainPriceFeed::new(
+//!     OnchainPriceConfig::default(),
+//!     Arc::new(PriceStreamManager::default()),
+//!     Arc::new(PositionTracker::new()),
+//! ));
+//! let _handle = feed.spawn(rpc_pool);
+//! # }
+//! ```
+
+use crate::components::price_stream::{PriceStreamManager, PriceUpdate};
+use crate::position_tracker::PositionTracker;
+use crate::rpc_manager::RpcPool;
+use crate::sniffer::events::PUMPFUN_PROGRAM_ID;
+use dashmap::DashMap;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Decimals of pump.fun mints
+pub const DEFAULT_TOKEN_DECIMALS: u8 = 6;
+
+/// Source tag on published price updates
+pub const ONCHAIN_PRICE_SOURCE: &str = "onchain";
+
+/// Lamports per SOL
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Offset of the `amount` field in an SPL token account
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Account a mint's price is derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceAccount {
+    /// pump.fun bonding curve, priced from its virtual reserves
+    BondingCurve(Pubkey),
+    /// AMM pool priced from the balances of its token and WSOL vaults
+    VaultPair {
+        base_vault: Pubkey,
+        quote_vault: Pubkey,
+    },
+}
+
+impl PriceAccount {
+    /// Accounts that have to be fetched to price the mint
+    pub fn accounts(&self) -> Vec<Pubkey> {
+        match self {
+            Self::BondingCurve(curve) => vec![*curve],
+            Self::VaultPair {
+                base_vault,
+                quote_vault,
+            } => vec![*base_vault, *quote_vault],
+        }
+    }
+}
+
+/// pump.fun bonding curve PDA for `mint`
+pub fn bonding_curve_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &PUMPFUN_PROGRAM_ID).0
+}
+
+/// Decoded pump.fun bonding curve account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BondingCurveReserves {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    /// Curve has completed and liquidity migrated
+    pub complete: bool,
+}
+
+impl BondingCurveReserves {
+    /// Anchor discriminator + 5 u64 fields + bool
+    pub const LEN: usize = 8 + 5 * 8 + 1;
+
+    /// Decode from raw account data
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::LEN {
+            return None;
+        }
+        let field = |i: usize| {
+            let start = 8 + i * 8;
+            Some(u64::from_le_bytes(data[start..start + 8].try_into().ok()?))
+        };
+        Some(Self {
+            virtual_token_reserves: field(0)?,
+            virtual_sol_reserves: field(1)?,
+            real_token_reserves: field(2)?,
+            real_sol_reserves: field(3)?,
+            token_total_supply: field(4)?,
+            complete: data[48] != 0,
+        })
+    }
+
+    /// Spot price in SOL per whole token
+    pub fn price_sol(&self, decimals: u8) -> Option<f64> {
+        price_from_reserves(
+            self.virtual_token_reserves,
+            self.virtual_sol_reserves,
+            decimals,
+        )
+    }
+}
+
+/// SOL per whole token from raw token and lamport reserves
+fn price_from_reserves(token_reserves: u64, lamport_reserves: u64, decimals: u8) -> Option<f64> {
+    if token_reserves == 0 {
+        return None;
+    }
+    let tokens = token_reserves as f64 / 10f64.powi(decimals as i32);
+    Some(lamport_reserves as f64 / LAMPORTS_PER_SOL / tokens)
+}
+
+/// Balance of an SPL / Token-2022 token account
+fn token_account_amount(data: &[u8]) -> Option<u64> {
+    let bytes = data.get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Configuration for the on-chain price feed
+#[derive(Debug, Clone)]
+pub struct OnchainPriceConfig {
+    /// Poll interval; one slot keeps prices at most a slot behind
+    pub poll_interval: Duration,
+    /// Commitment used for account reads
+    pub commitment: CommitmentConfig,
+    /// Max accounts per `getMultipleAccounts` request
+    pub max_accounts_per_request: usize,
+    /// Decimals assumed for auto-discovered mints
+    pub default_decimals: u8,
+}
+
+impl Default for OnchainPriceConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(400),
+            commitment: CommitmentConfig::processed(),
+            max_accounts_per_request: 100,
+            default_decimals: DEFAULT_TOKEN_DECIMALS,
+        }
+    }
+}
+
+/// Price source of one watched mint
+#[derive(Debug, Clone, Copy)]
+struct WatchedMint {
+    account: PriceAccount,
+    decimals: u8,
+    /// Registered explicitly rather than discovered from a position
+    pinned: bool,
+}
+
+/// Producer of on-chain prices for open positions
+pub struct OnchainPriceFeed {
+    config: OnchainPriceConfig,
+    price_stream: Arc<PriceStreamManager>,
+    position_tracker: Arc<PositionTracker>,
+    /// Price source per mint
+    watched: DashMap<Pubkey, WatchedMint>,
+    /// Watched account -> mint
+    account_index: DashMap<Pubkey, Pubkey>,
+    /// Latest (amount, slot) of vault accounts
+    vault_balances: DashMap<Pubkey, (u64, u64)>,
+    /// Slot of the last published price per mint
+    last_slot: DashMap<Pubkey, u64>,
+}
+
+impl OnchainPriceFeed {
+    /// Create a new feed publishing into `price_stream` and `position_tracker`
+    pub fn new(
+        config: OnchainPriceConfig,
+        price_stream: Arc<PriceStreamManager>,
+        position_tracker: Arc<PositionTracker>,
+    ) -> Self {
+        Self {
+            config,
+            price_stream,
+            position_tracker,
+            watched: DashMap::new(),
+            account_index: DashMap::new(),
+            vault_balances: DashMap::new(),
+            last_slot: DashMap::new(),
+        }
+    }
+
+    /// Price `mint` from `account`, replacing any discovered source
+    ///
+    /// Registered mints stay watched after their position closes until
+    /// [`unregister`](Self::unregister) is called.
+    pub fn register(&self, mint: Pubkey, account: PriceAccount, decimals: u8) {
+        self.insert_watch(
+            mint,
+            WatchedMint {
+                account,
+                decimals,
+                pinned: true,
+            },
+        );
+    }
+
+    /// Stop watching `mint`
+    pub fn unregister(&self, mint: &Pubkey) {
+        if let Some((_, watch)) = self.watched.remove(mint) {
+            for account in watch.account.accounts() {
+                self.account_index.remove(&account);
+                self.vault_balances.remove(&account);
+            }
+        }
+        self.last_slot.remove(mint);
+    }
+
+    /// Price account currently used for `mint`
+    pub fn price_account(&self, mint: &Pubkey) -> Option<PriceAccount> {
+        self.watched.get(mint).map(|w| w.account)
+    }
+
+    /// Number of mints being priced
+    pub fn watched_count(&self) -> usize {
+        self.watched.len()
+    }
+
+    fn insert_watch(&self, mint: Pubkey, watch: WatchedMint) {
+        if let Some(previous) = self.watched.insert(mint, watch) {
+            for account in previous.account.accounts() {
+                self.account_index.remove(&account);
+            }
+        }
+        for account in watch.account.accounts() {
+            self.account_index.insert(account, mint);
+        }
+    }
+
+    /// Align the watch list with open positions and return accounts to fetch
+    pub fn sync_with_positions(&self) -> Vec<Pubkey> {
+        let open: HashSet<Pubkey> = self
+            .position_tracker
+            .get_all_positions()
+            .into_iter()
+            .map(|p| p.mint)
+            .collect();
+
+        for mint in &open {
+            if !self.watched.contains_key(mint) {
+                debug!(mint = %mint, "Watching bonding curve for new position");
+                self.insert_watch(
+                    *mint,
+                    WatchedMint {
+                        account: PriceAccount::BondingCurve(bonding_curve_address(mint)),
+                        decimals: self.config.default_decimals,
+                        pinned: false,
+                    },
+                );
+            }
+        }
+
+        let closed: Vec<Pubkey> = self
+            .watched
+            .iter()
+            .filter(|e| !e.pinned && !open.contains(e.key()))
+            .map(|e| *e.key())
+            .collect();
+        for mint in closed {
+            self.unregister(&mint);
+        }
+
+        self.account_index.iter().map(|e| *e.key()).collect()
+    }
+
+    /// Feed raw account data observed at `slot`
+    ///
+    /// Returns the published SOL price, or `None` if the account is not
+    /// watched, could not be decoded, or is older than the last published price.
+    pub fn apply_account_update(&self, account: &Pubkey, data: &[u8], slot: u64) -> Option<f64> {
+        let mint = *self.account_index.get(account)?;
+        let watch = *self.watched.get(&mint)?;
+
+        let price = match watch.account {
+            PriceAccount::BondingCurve(_) => {
+                let reserves = BondingCurveReserves::decode(data)?;
+                if reserves.complete {
+                    debug!(mint = %mint, "Bonding curve complete; register the pool to keep pricing");
+                    return None;
+                }
+                reserves.price_sol(watch.decimals)?
+            }
+            PriceAccount::VaultPair {
+                base_vault,
+                quote_vault,
+            } => {
+                self.vault_balances
+                    .insert(*account, (token_account_amount(data)?, slot));
+                let (base, base_slot) = *self.vault_balances.get(&base_vault)?;
+                let (quote, quote_slot) = *self.vault_balances.get(&quote_vault)?;
+                // Only price when both sides come from the same slot
+                if base_slot != quote_slot {
+                    return None;
+                }
+                price_from_reserves(base, quote, watch.decimals)?
+            }
+        };
+
+        if self.last_slot.get(&mint).is_some_and(|last| *last > slot) {
+            return None;
+        }
+        self.last_slot.insert(mint, slot);
+        self.publish(mint, price);
+        Some(price)
+    }
+
+    fn publish(&self, mint: Pubkey, price_sol: f64) {
+        self.price_stream.publish_price(PriceUpdate {
+            mint,
+            price_sol,
+            price_usd: 0.0,
+            volume_24h: 0.0,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            source: ONCHAIN_PRICE_SOURCE.to_string(),
+        });
+        self.position_tracker.update_price(&mint, price_sol);
+    }
+
+    /// Fetch every watched account once and publish prices
+    ///
+    /// Returns the number of prices published.
+    pub async fn poll_once(&self, pool: &RpcPool) -> Result<usize, String> {
+        let accounts = self.sync_with_positions();
+        let mut published = 0;
+
+        for chunk in accounts.chunks(self.config.max_accounts_per_request.max(1)) {
+            let lease = pool
+                .acquire_endpoint()
+                .await
+                .ok_or_else(|| "No healthy endpoints available".to_string())?;
+            let commitment = self.config.commitment;
+            let response = lease
+                .call(|client| async move {
+                    client
+                        .get_multiple_accounts_with_commitment(chunk, commitment)
+                        .await
+                })
+                .await
+                .map_err(|e| e.to_string())?;
+            drop(lease);
+
+            let slot = response.context.slot;
+            for (pubkey, account) in chunk.iter().zip(response.value) {
+                if let Some(account) = account {
+                    if self
+                        .apply_account_update(pubkey, &account.data, slot)
+                        .is_some()
+                    {
+                        published += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(published)
+    }
+
+    /// Run the polling loop until the task is aborted
+    pub fn spawn(self: Arc<Self>, pool: Arc<RpcPool>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.poll_once(&pool).await {
+                    warn!(error = %e, "On-chain price poll failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve_data(virtual_tokens: u64, virtual_sol: u64, complete: bool) -> Vec<u8> {
+        let mut data = vec![0u8; 8];
+        for value in [virtual_tokens, virtual_sol, 0, 0, 1_000_000_000_000_000] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(complete as u8);
+        data
+    }
+
+    fn token_account_data(amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 165];
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data
+    }
+
+    fn feed() -> (
+        OnchainPriceFeed,
+        Arc<PriceStreamManager>,
+        Arc<PositionTracker>,
+    ) {
+        let stream = Arc::new(PriceStreamManager::new(16, Duration::from_millis(333)));
+        let tracker = Arc::new(PositionTracker::new());
+        let feed = OnchainPriceFeed::new(
+            OnchainPriceConfig::default(),
+            stream.clone(),
+            tracker.clone(),
+        );
+        (feed, stream, tracker)
+    }
+
+    #[test]
+    fn test_bonding_curve_price() {
+        // 1B tokens (6 decimals) against 30 SOL
+        let data = curve_data(1_000_000_000_000_000, 30_000_000_000, false);
+        let reserves = BondingCurveReserves::decode(&data).unwrap();
+        let price = reserves.price_sol(6).unwrap();
+        assert!((price - 0.000_000_03).abs() < 1e-15);
+        assert!(BondingCurveReserves::decode(&data[..40]).is_none());
+    }
+
+    #[test]
+    fn test_positions_are_discovered_and_priced() {
+        let (feed, stream, tracker) = feed();
+        let mint = Pubkey::new_unique();
+        tracker.record_buy(mint, 1_000, 10_000_000);
+
+        let accounts = feed.sync_with_positions();
+        let curve = bonding_curve_address(&mint);
+        assert_eq!(accounts, vec![curve]);
+
+        let data = curve_data(1_000_000_000_000, 60_000_000_000, false);
+        let price = feed.apply_account_update(&curve, &data, 10).unwrap();
+
+        let cached = stream.get_cached_price(&mint).unwrap();
+        assert_eq!(cached.source, ONCHAIN_PRICE_SOURCE);
+        assert_eq!(cached.price_sol, price);
+        assert_eq!(tracker.get_position(&mint).unwrap().last_seen_price, price);
+
+        // Older slots never overwrite a newer price
+        let stale = curve_data(1_000_000_000_000, 1_000_000_000, false);
+        assert!(feed.apply_account_update(&curve, &stale, 9).is_none());
+
+        // Closing the position stops watching the curve
+        tracker.remove_position(&mint);
+        assert!(feed.sync_with_positions().is_empty());
+        assert_eq!(feed.watched_count(), 0);
+    }
+
+    #[test]
+    fn test_vault_pair_needs_both_sides_from_same_slot() {
+        let (feed, stream, _tracker) = feed();
+        let mint = Pubkey::new_unique();
+        let base_vault = Pubkey::new_unique();
+        let quote_vault = Pubkey::new_unique();
+        feed.register(
+            mint,
+            PriceAccount::VaultPair {
+                base_vault,
+                quote_vault,
+            },
+            6,
+        );
+
+        // Registered mints survive position sync
+        assert_eq!(feed.sync_with_positions().len(), 2);
+
+        assert!(feed
+            .apply_account_update(&base_vault, &token_account_data(2_000_000), 5)
+            .is_none());
+        let price = feed
+            .apply_account_update(&quote_vault, &token_account_data(1_000_000_000), 5)
+            .unwrap();
+        // 1 SOL for 2 tokens
+        assert!((price - 0.5).abs() < 1e-12);
+        assert!(stream.get_cached_price(&mint).is_some());
+    }
+}
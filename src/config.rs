@@ -1,12 +1,41 @@
 //! Configuration module for the Ultra trading bot
 //!
-//! This module handles all configuration loading from TOML files,
-//! environment variables, and provides structured configuration types.
+//! Configuration is layered: built-in defaults, then the TOML file, then
+//! `BOT__SECTION__KEY` environment variables, then `--set section.key=value`
+//! command-line overrides. Every section has a typed schema that rejects
+//! unknown keys, and [`Config::validate`] runs cross-field checks over the
+//! merged result. Subsystem configs (sniffer, RPC manager, nonce manager, buy
+//! engine, transaction builder) are derived from this single source.
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::components::control_protocol::ControlRole;
+use crate::decision_journal::JournalConfig;
+use crate::nonce_manager::nonce_integration::NonceManagerConfig;
+use crate::nonce_manager::ClassReservations;
+use crate::rpc_manager::rpc_config::RpcManagerConfig;
+use crate::sniffer::config::{BatchSendMode, DropPolicy, SnifferConfig as SnifferPipelineConfig};
+use crate::trace_export::{SpanExporter, TraceExportConfig};
+use crate::types::{PortfolioConfig, SellStrategy, StopLossConfig, TakeProfitConfig};
+
+/// Prefix for environment variable overrides (`BOT__TRADING__MODE=multi`)
+pub const ENV_PREFIX: &str = "BOT__";
+
+/// Separator between key segments in environment variable overrides
+pub const ENV_SEPARATOR: &str = "__";
+
+/// Alternative key spellings accepted in config files, mapped to their schema key
+const KEY_ALIASES: &[(&str, &str, &str)] =
+    &[("trading", "default_buy_amount_sol", "buy_amount_sol")];
+
+/// Placeholder written in place of secrets by [`Config::redacted`]
+pub const REDACTED: &str = "<redacted>";
 
 /// Main application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// RPC endpoints configuration
     pub rpc: RpcConfig,
@@ -17,24 +46,52 @@ pub struct Config {
     /// Trading configuration
     pub trading: TradingConfig,
 
+    /// Multi-token portfolio limits
+    pub portfolio: PortfolioConfig,
+
+    /// Transaction builder configuration
+    pub tx_builder: TxBuilderConfig,
+
     /// Nonce configuration
     pub nonce: NonceConfig,
 
     /// Sniffer configuration
     pub sniffer: SnifferConfig,
 
+    /// Account/transaction streaming source
+    pub streaming: StreamingConfig,
+
     /// Monitoring and metrics
     pub monitoring: MonitoringConfig,
 
+    /// Pre-buy token safety screening
+    pub screening: ScreeningConfig,
+
+    /// Default TP/SL applied to positions without an explicit strategy
+    pub strategy: StrategyConfig,
+
+    /// Headless control API
+    pub control: ControlConfig,
+
+    /// Wallets whose trades are mirrored
+    pub copy_trading: CopyTradingConfig,
+
+    /// Exit transactions signed ahead of time on a durable nonce
+    pub presigned_exits: PresignedExitConfig,
+
+    /// Per-candidate decision journal
+    pub decision_journal: DecisionJournalConfig,
+
     /// Number of nonce accounts to use per transaction (for parallel submission)
     #[serde(default = "default_nonce_count")]
     pub nonce_count: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct RpcConfig {
     /// List of RPC endpoints
-    pub endpoints: Vec<String>,
+    pub endpoints: Vec<RpcEndpoint>,
 
     /// Request timeout in seconds
     #[serde(default = "default_rpc_timeout")]
@@ -44,12 +101,84 @@ pub struct RpcConfig {
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
 
-    /// Rate limit (requests per second)
+    /// Rate limit per endpoint (requests per second, 0 = unlimited)
     #[serde(default = "default_rate_limit")]
     pub rate_limit_rps: u32,
+
+    /// Concurrent in-flight requests per endpoint
+    pub max_concurrency: u32,
+
+    /// Endpoint health check interval in seconds
+    pub health_check_interval_secs: u64,
+
+    /// Consecutive failures before an endpoint's circuit opens
+    pub circuit_breaker_failure_threshold: u32,
+
+    /// Seconds an open circuit stays open
+    pub circuit_breaker_timeout_secs: u64,
+
+    /// Enable predictive failure detection
+    pub enable_predictive_failure: bool,
+
+    /// Predictive failure probability threshold (0.0-1.0)
+    pub predictive_failure_threshold: f64,
+}
+
+/// An `rpc.endpoints` entry: a bare URL or a `[[rpc.endpoints]]` table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RpcEndpoint {
+    Url(String),
+    Table(RpcEndpointTable),
+}
+
+/// Table form of an `rpc.endpoints` entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcEndpointTable {
+    /// Endpoint URL
+    pub url: String,
+
+    /// Endpoint credentials: `header:<Name>=<value>`, `query:<param>=<value>`
+    /// or a bearer token
+    #[serde(default)]
+    pub credentials: Option<String>,
+}
+
+impl RpcEndpoint {
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Url(url) => url,
+            Self::Table(table) => &table.url,
+        }
+    }
+
+    pub fn credentials(&self) -> Option<&str> {
+        match self {
+            Self::Url(_) => None,
+            Self::Table(table) => table.credentials.as_deref(),
+        }
+    }
+}
+
+impl From<&str> for RpcEndpoint {
+    fn from(url: &str) -> Self {
+        Self::Url(url.to_string())
+    }
+}
+
+impl RpcConfig {
+    /// Endpoint URLs in configured order
+    pub fn endpoint_urls(&self) -> Vec<String> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.url().to_string())
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct WalletConfig {
     /// Path to keypair file
     pub keypair_path: String,
@@ -57,17 +186,51 @@ pub struct WalletConfig {
     /// Enable hardware wallet
     #[serde(default)]
     pub use_hardware_wallet: bool,
+
+    /// Environment variable holding the keystore passphrase
+    #[serde(default)]
+    pub passphrase_env: Option<String>,
+
+    /// File descriptor to read the keystore passphrase from
+    #[serde(default)]
+    pub passphrase_fd: Option<i32>,
+}
+
+/// How many tokens the bot trades at once
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PortfolioMode {
+    /// One token at a time (default, safest)
+    #[default]
+    Single,
+    /// Multiple tokens simultaneously (requires `portfolio.enable_multi_token`)
+    Multi,
+    /// Adaptive based on market conditions (experimental)
+    Hybrid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TradingConfig {
+    /// Single-, multi- or hybrid-token trading
+    pub mode: PortfolioMode,
+
     /// Maximum slippage tolerance (basis points)
     #[serde(default = "default_max_slippage")]
     pub max_slippage_bps: u16,
 
-    /// Default buy amount in SOL
+    /// Slippage applied to buys (basis points, <= `max_slippage_bps`)
+    pub slippage_bps: u16,
+
+    /// Taker fee assumed when sizing buys (basis points)
+    pub taker_fee_bps: u16,
+
+    /// Default buy amount in SOL (`default_buy_amount_sol` is accepted as an alias)
     pub buy_amount_sol: f64,
 
+    /// Size positions automatically from portfolio exposure
+    pub auto_position_sizing: bool,
+
     /// Minimum liquidity required (in lamports)
     #[serde(default = "default_min_liquidity")]
     pub min_liquidity_lamports: u64,
@@ -79,9 +242,66 @@ pub struct TradingConfig {
     /// Jito tip in lamports
     #[serde(default = "default_jito_tip")]
     pub jito_tip_lamports: u64,
+
+    /// Refuse all buys
+    pub kill_switch: bool,
+
+    /// Maximum buys per rate-limit window
+    pub max_tx_count_per_window: u32,
+
+    /// Maximum SOL spent per rate-limit window
+    pub max_spend_per_window_sol: f64,
+
+    /// Rate-limit window length in seconds
+    pub window_duration_secs: u64,
 }
 
+/// Transaction building parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TxBuilderConfig {
+    /// Compute unit price used for priority fees
+    pub priority_fee_lamports: u64,
+
+    /// Default compute unit limit
+    pub compute_unit_limit: u32,
+
+    /// Lower bound for simulated compute unit limits
+    pub min_cu_limit: u32,
+
+    /// Upper bound for simulated compute unit limits
+    pub max_cu_limit: u32,
+
+    /// Base for congestion-adjusted priority fees
+    pub adaptive_priority_fee_base: u64,
+
+    /// Multiplier applied to the base fee under congestion
+    pub adaptive_priority_fee_multiplier: f64,
+
+    /// Simulate transactions before sending
+    pub enable_simulation: bool,
+
+    /// Adjust slippage from observed volatility
+    pub enable_ml_slippage: bool,
+
+    /// Maximum transactions built concurrently
+    pub max_concurrent_builds: usize,
+
+    /// PumpPortal HTTP endpoint
+    pub pumpportal_url: Option<String>,
+
+    /// PumpPortal API key
+    pub pumpportal_api_key: Option<String>,
+
+    /// LetsBonk HTTP endpoint
+    pub letsbonk_api_url: Option<String>,
+
+    /// LetsBonk API key
+    pub letsbonk_api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct NonceConfig {
     /// Number of nonce accounts to maintain
     #[serde(default = "default_nonce_pool_size")]
@@ -90,9 +310,38 @@ pub struct NonceConfig {
     /// Nonce refresh interval in seconds
     #[serde(default = "default_nonce_refresh_interval")]
     pub refresh_interval_secs: u64,
+
+    /// Maximum time to wait for a nonce lease (milliseconds)
+    pub acquire_timeout_ms: u64,
+
+    /// Lease lifetime before the watchdog reclaims it (seconds)
+    pub lease_ttl_secs: u64,
+
+    /// Refresh nonces ahead of predicted use
+    pub enable_predictive_refresh: bool,
+
+    /// Nonces held back per operation class (`exit`, `entry`, `background`);
+    /// lower classes never take a higher class's reserved nonces
+    #[serde(default = "default_nonce_reserved")]
+    pub reserved: ClassReservations,
+
+    /// Pool file written by `nonce-admin create`/`export`; when set, the bot
+    /// loads these accounts at startup instead of creating new ones
+    pub accounts_file: Option<String>,
+
+    /// Pool and predictive model snapshot; restored at startup and rewritten
+    /// every `snapshot_interval_secs` and on shutdown
+    pub snapshot_path: Option<String>,
+
+    /// Seconds between snapshot writes
+    pub snapshot_interval_secs: u64,
+
+    /// Seconds between on-chain integrity audits of the pool (0 disables)
+    pub audit_interval_secs: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SnifferConfig {
     /// Geyser gRPC endpoint
     pub geyser_endpoint: String,
@@ -103,9 +352,343 @@ pub struct SnifferConfig {
     /// Buffer size for transaction stream
     #[serde(default = "default_stream_buffer_size")]
     pub stream_buffer_size: usize,
+
+    /// Channel capacity for handoff to the buy engine
+    pub channel_capacity: usize,
+
+    /// Maximum stream reconnect attempts
+    pub max_retry_attempts: u32,
+
+    /// Initial reconnect backoff (milliseconds)
+    pub initial_backoff_ms: u64,
+
+    /// Maximum reconnect backoff (milliseconds)
+    pub max_backoff_ms: u64,
+
+    /// Telemetry export interval in seconds
+    pub telemetry_interval_secs: u64,
+
+    /// EMA alpha for the short window (0.0-1.0)
+    pub ema_alpha_short: f64,
+
+    /// EMA alpha for the long window (0.0-1.0)
+    pub ema_alpha_long: f64,
+
+    /// Initial threshold for priority classification
+    pub initial_threshold: f64,
+
+    /// Candidates per handoff batch
+    pub batch_size: usize,
+
+    /// Batch flush timeout (milliseconds)
+    pub batch_timeout_ms: u64,
+
+    /// What to drop when the handoff channel is full
+    pub drop_policy: DropPolicy,
+
+    /// Synchronous or worker-based batch sending
+    pub batch_send_mode: BatchSendMode,
+
+    /// Graceful shutdown timeout (milliseconds)
+    pub graceful_shutdown_timeout_ms: u64,
+}
+
+/// Streaming transport
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamingMode {
+    /// WebSocket subscriptions (free tier)
+    #[default]
+    Websocket,
+    /// Geyser gRPC (premium)
+    Geyser,
+}
+
+/// Commitment level for subscriptions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitmentLevel {
+    Processed,
+    #[default]
+    Confirmed,
+    Finalized,
+}
+
+impl CommitmentLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Processed => "processed",
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamingConfig {
+    /// WebSocket or Geyser
+    pub mode: StreamingMode,
+
+    /// WebSocket endpoint (used in `websocket` mode)
+    pub websocket_url: String,
+
+    /// Geyser gRPC endpoint (required in `geyser` mode)
+    pub geyser_endpoint: Option<String>,
+
+    /// Commitment level for subscriptions
+    pub commitment: CommitmentLevel,
+}
+
+/// Default take-profit / stop-loss for new positions (0 disables a rule)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StrategyConfig {
+    /// Sell everything once the position is down this many percent
+    pub stop_loss_percent: f64,
+
+    /// Take profit once the position is up this many percent
+    pub take_profit_percent: f64,
+
+    /// Fraction of the position sold on take profit (0.0-1.0)
+    pub take_profit_sell_percent: f64,
+}
+
+impl StrategyConfig {
+    /// Sell strategy for positions without one, or `None` when both rules are off
+    pub fn to_sell_strategy(&self) -> Option<SellStrategy> {
+        let stop_loss = (self.stop_loss_percent > 0.0).then_some(StopLossConfig {
+            enabled: true,
+            threshold_percent: -self.stop_loss_percent,
+        });
+        let take_profit = (self.take_profit_percent > 0.0).then_some(TakeProfitConfig {
+            enabled: true,
+            threshold_percent: self.take_profit_percent,
+            sell_percent: self.take_profit_sell_percent,
+        });
+        if stop_loss.is_none() && take_profit.is_none() {
+            return None;
+        }
+        Some(SellStrategy {
+            stop_loss,
+            take_profit,
+            trailing_stop: None,
+        })
+    }
+}
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            stop_loss_percent: 0.0,
+            take_profit_percent: 0.0,
+            take_profit_sell_percent: 1.0,
+        }
+    }
+}
+
+/// Headless control API used by `botctl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ControlConfig {
+    /// Serve the control API
+    pub enabled: bool,
+
+    /// Listen address; keep it on loopback or a private interface
+    pub bind_addr: String,
+
+    /// Largest accepted request body in bytes
+    pub max_body_bytes: usize,
+
+    /// Bearer tokens and the role each one grants
+    pub tokens: Vec<ControlToken>,
+}
+
+/// A control API credential (`[[control.tokens]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ControlToken {
+    /// Label used in audit logs
+    pub name: String,
+
+    /// What the token may do
+    pub role: ControlRole,
+
+    /// The token itself
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Environment variable holding the token (instead of `token`)
+    #[serde(default)]
+    pub token_env: Option<String>,
 }
 
+impl ControlToken {
+    /// The token value, read from `token_env` when set
+    pub fn secret(&self) -> Option<String> {
+        match &self.token_env {
+            Some(var) => std::env::var(var).ok(),
+            None => self.token.clone(),
+        }
+    }
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_addr: "127.0.0.1:9091".to_string(),
+            max_body_bytes: 64 * 1024,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+/// Pre-signed emergency exits
+///
+/// After each buy a second nonce is leased and a 100% sell of the new
+/// position is built and signed against it, so stop-loss and emergency exits
+/// are a single send.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PresignedExitConfig {
+    /// Keep a signed exit ready for every open position
+    pub enabled: bool,
+
+    /// Slippage accepted by the exit (basis points); deep so it still fills in a crash
+    pub slippage_bps: u16,
+
+    /// Nonce lease of an exit; re-signed on a fresh nonce once 90% has elapsed
+    pub lease_ttl_secs: u64,
+}
+
+impl Default for PresignedExitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            slippage_bps: 5_000,
+            lease_ttl_secs: 6 * 60 * 60,
+        }
+    }
+}
+
+/// Per-candidate decision journal
+///
+/// Every candidate's features, gate results, build, simulation, submission,
+/// landing and PnL are appended to daily JSONL segments under `dir`; query
+/// and export them with the `decision-journal` tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DecisionJournalConfig {
+    /// Journal candidate decisions
+    pub enabled: bool,
+
+    /// Directory holding the segment files
+    pub dir: String,
+
+    /// Days of segments kept
+    pub retention_days: u32,
+}
+
+impl Default for DecisionJournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "journal".to_string(),
+            retention_days: 30,
+        }
+    }
+}
+
+/// Copy trading: mirror the pump.fun trades of selected wallets
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CopyTradingConfig {
+    /// Follow the configured leaders
+    pub enabled: bool,
+
+    /// Wallets to mirror (`[[copy_trading.leaders]]`)
+    pub leaders: Vec<LeaderConfig>,
+}
+
+/// A wallet whose trades are mirrored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LeaderConfig {
+    /// Leader wallet address
+    pub address: String,
+
+    /// Name used in logs
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// How large the mirrored buy is
+    pub sizing: SizingRule,
+
+    /// Wait this long after the leader's trade before mirroring it
+    #[serde(default)]
+    pub delay_ms: u64,
+
+    /// Ignore leader buys smaller than this (SOL)
+    #[serde(default)]
+    pub min_leader_sol: f64,
+
+    /// Sell the same share of the position when the leader sells
+    #[serde(default = "default_true")]
+    pub exit_with_leader: bool,
+}
+
+/// Size of a mirrored buy, relative to the leader's
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SizingRule {
+    /// Always buy `amount_sol`
+    Fixed { amount_sol: f64 },
+    /// Buy `ratio` times the leader's SOL amount
+    Proportional { ratio: f64 },
+    /// Like `Proportional`, but never more than `max_sol`
+    Capped { ratio: f64, max_sol: f64 },
+}
+
+impl SizingRule {
+    /// Lamports to spend when the leader spent `leader_lamports`
+    pub fn size_lamports(&self, leader_lamports: u64) -> u64 {
+        let sol = match *self {
+            Self::Fixed { amount_sol } => amount_sol,
+            Self::Proportional { ratio } => lamports_to_sol(leader_lamports) * ratio,
+            Self::Capped { ratio, max_sol } => {
+                (lamports_to_sol(leader_lamports) * ratio).min(max_sol)
+            }
+        };
+        (sol.max(0.0) * LAMPORTS_PER_SOL) as u64
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let positive = |name: &str, value: f64| {
+            if value > 0.0 {
+                Ok(())
+            } else {
+                Err(format!("{} must be > 0", name))
+            }
+        };
+        match *self {
+            Self::Fixed { amount_sol } => positive("amount_sol", amount_sol),
+            Self::Proportional { ratio } => positive("ratio", ratio),
+            Self::Capped { ratio, max_sol } => {
+                positive("ratio", ratio).and_then(|_| positive("max_sol", max_sol))
+            }
+        }
+    }
+}
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MonitoringConfig {
     /// Enable Prometheus metrics
     #[serde(default = "default_true")]
@@ -118,6 +701,259 @@ pub struct MonitoringConfig {
     /// Enable tracing
     #[serde(default = "default_true")]
     pub enable_tracing: bool,
+
+    /// Deployment region, attached as a `region` label to every metric
+    pub region: Option<String>,
+
+    /// Where pipeline spans are exported (requires `enable_tracing`)
+    pub trace_exporter: TraceExporterKind,
+
+    /// OTLP/HTTP traces endpoint for `trace_exporter = "otlp"`
+    #[serde(default = "default_otlp_endpoint")]
+    pub otlp_endpoint: String,
+
+    /// Output file for `trace_exporter = "file"` (OTLP JSON lines)
+    pub trace_file: Option<String>,
+}
+
+/// Span exporter for the candidate-to-landing pipeline
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceExporterKind {
+    /// Spans are not recorded
+    #[default]
+    None,
+    /// OTLP/HTTP (JSON) to a collector
+    Otlp,
+    /// OTLP JSON lines appended to `trace_file`
+    File,
+    /// OTLP JSON lines on stdout
+    Stdout,
+}
+
+/// Token safety screening rules applied before buying
+///
+/// Each `reject_*` flag turns a detected risk into a hard rejection; when the
+/// flag is off the risk is still recorded on the candidate as a warning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScreeningConfig {
+    /// Enable the screening stage
+    pub enabled: bool,
+
+    /// Reject mints whose mint authority is still set
+    pub reject_mint_authority: bool,
+
+    /// Reject mints whose freeze authority is still set
+    pub reject_freeze_authority: bool,
+
+    /// Reject Token-2022 mints with a transfer fee above `max_transfer_fee_bps`
+    pub reject_transfer_fee: bool,
+
+    /// Highest tolerated transfer fee (basis points)
+    pub max_transfer_fee_bps: u16,
+
+    /// Reject Token-2022 mints with a permanent delegate
+    pub reject_permanent_delegate: bool,
+
+    /// Reject Token-2022 mints with a transfer hook
+    pub reject_transfer_hook: bool,
+
+    /// Reject Token-2022 non-transferable mints
+    pub reject_non_transferable: bool,
+
+    /// Reject Token-2022 mints whose new accounts start frozen
+    pub reject_default_frozen: bool,
+
+    /// Minimum accepted decimals
+    pub min_decimals: u8,
+
+    /// Maximum accepted decimals
+    pub max_decimals: u8,
+
+    /// Minimum supply in whole tokens (0 disables)
+    pub min_supply_tokens: u64,
+
+    /// Maximum supply in whole tokens (0 disables)
+    pub max_supply_tokens: u64,
+
+    /// Check top-holder concentration via `getTokenLargestAccounts`
+    pub check_holders: bool,
+
+    /// Maximum share of supply held by the single largest holder (0.0-1.0)
+    pub max_top_holder_pct: f64,
+
+    /// Maximum share of supply held by the ten largest holders (0.0-1.0)
+    pub max_top10_holder_pct: f64,
+
+    /// Token accounts ignored for concentration (e.g. bonding-curve vaults)
+    pub excluded_holders: Vec<String>,
+
+    /// Treat RPC failures as skipped checks instead of rejections
+    pub fail_open_on_rpc_error: bool,
+
+    /// Upper bound on screening one candidate (ms); a timeout counts as an RPC failure
+    pub timeout_ms: u64,
+}
+
+impl Default for ScreeningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reject_mint_authority: true,
+            reject_freeze_authority: true,
+            reject_transfer_fee: true,
+            max_transfer_fee_bps: 0,
+            reject_permanent_delegate: true,
+            reject_transfer_hook: true,
+            reject_non_transferable: true,
+            reject_default_frozen: true,
+            min_decimals: 0,
+            max_decimals: 9,
+            min_supply_tokens: 0,
+            max_supply_tokens: 0,
+            check_holders: true,
+            max_top_holder_pct: 0.20,
+            max_top10_holder_pct: 0.50,
+            excluded_holders: Vec::new(),
+            fail_open_on_rpc_error: false,
+            timeout_ms: 500,
+        }
+    }
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: vec!["https://api.mainnet-beta.solana.com".into()],
+            timeout_secs: default_rpc_timeout(),
+            max_retries: default_max_retries(),
+            rate_limit_rps: default_rate_limit(),
+            max_concurrency: 100,
+            health_check_interval_secs: 1,
+            circuit_breaker_failure_threshold: 5,
+            circuit_breaker_timeout_secs: 60,
+            enable_predictive_failure: true,
+            predictive_failure_threshold: 0.75,
+        }
+    }
+}
+
+impl Default for WalletConfig {
+    fn default() -> Self {
+        Self {
+            keypair_path: "~/.config/solana/id.json".to_string(),
+            use_hardware_wallet: false,
+            passphrase_env: None,
+            passphrase_fd: None,
+        }
+    }
+}
+
+impl Default for TradingConfig {
+    fn default() -> Self {
+        Self {
+            mode: PortfolioMode::Single,
+            max_slippage_bps: default_max_slippage(),
+            slippage_bps: 100,
+            taker_fee_bps: 25,
+            buy_amount_sol: 0.1,
+            auto_position_sizing: false,
+            min_liquidity_lamports: default_min_liquidity(),
+            enable_jito: false,
+            jito_tip_lamports: default_jito_tip(),
+            kill_switch: false,
+            max_tx_count_per_window: 10,
+            max_spend_per_window_sol: 1.0,
+            window_duration_secs: 60,
+        }
+    }
+}
+
+impl Default for TxBuilderConfig {
+    fn default() -> Self {
+        Self {
+            priority_fee_lamports: 10_000,
+            compute_unit_limit: 200_000,
+            min_cu_limit: 100_000,
+            max_cu_limit: 400_000,
+            adaptive_priority_fee_base: 10_000,
+            adaptive_priority_fee_multiplier: 1.5,
+            enable_simulation: true,
+            enable_ml_slippage: false,
+            max_concurrent_builds: 50,
+            pumpportal_url: None,
+            pumpportal_api_key: None,
+            letsbonk_api_url: None,
+            letsbonk_api_key: None,
+        }
+    }
+}
+
+impl Default for NonceConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: default_nonce_pool_size(),
+            refresh_interval_secs: default_nonce_refresh_interval(),
+            acquire_timeout_ms: 5_000,
+            lease_ttl_secs: 30,
+            enable_predictive_refresh: true,
+            reserved: default_nonce_reserved(),
+            accounts_file: None,
+            snapshot_path: None,
+            snapshot_interval_secs: 60,
+            audit_interval_secs: 60,
+        }
+    }
+}
+
+impl Default for SnifferConfig {
+    fn default() -> Self {
+        let pipeline = SnifferPipelineConfig::default();
+        Self {
+            geyser_endpoint: "http://localhost:10000".to_string(),
+            monitored_programs: vec![],
+            stream_buffer_size: default_stream_buffer_size(),
+            channel_capacity: pipeline.channel_capacity,
+            max_retry_attempts: pipeline.max_retry_attempts,
+            initial_backoff_ms: pipeline.initial_backoff_ms,
+            max_backoff_ms: pipeline.max_backoff_ms,
+            telemetry_interval_secs: pipeline.telemetry_interval_secs,
+            ema_alpha_short: pipeline.ema_alpha_short,
+            ema_alpha_long: pipeline.ema_alpha_long,
+            initial_threshold: pipeline.initial_threshold,
+            batch_size: pipeline.batch_size,
+            batch_timeout_ms: pipeline.batch_timeout_ms,
+            drop_policy: pipeline.drop_policy,
+            batch_send_mode: pipeline.batch_send_mode,
+            graceful_shutdown_timeout_ms: pipeline.graceful_shutdown_timeout_ms,
+        }
+    }
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            mode: StreamingMode::Websocket,
+            websocket_url: "wss://api.mainnet-beta.solana.com".to_string(),
+            geyser_endpoint: None,
+            commitment: CommitmentLevel::Confirmed,
+        }
+    }
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            enable_metrics: default_true(),
+            metrics_port: default_metrics_port(),
+            enable_tracing: default_true(),
+            region: None,
+            trace_exporter: TraceExporterKind::None,
+            otlp_endpoint: default_otlp_endpoint(),
+            trace_file: None,
+        }
+    }
 }
 
 // Default value functions
@@ -142,6 +978,12 @@ fn default_jito_tip() -> u64 {
 fn default_nonce_pool_size() -> usize {
     10
 }
+fn default_nonce_reserved() -> ClassReservations {
+    ClassReservations {
+        exit: 1,
+        ..ClassReservations::default()
+    }
+}
 fn default_nonce_refresh_interval() -> u64 {
     60
 }
@@ -151,6 +993,9 @@ fn default_stream_buffer_size() -> usize {
 fn default_metrics_port() -> u16 {
     9090
 }
+fn default_otlp_endpoint() -> String {
+    "http://localhost:4318/v1/traces".to_string()
+}
 fn default_true() -> bool {
     true
 }
@@ -158,57 +1003,756 @@ fn default_nonce_count() -> usize {
     1
 }
 
+/// Configuration loading errors
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The config file could not be read
+    #[error("cannot read config file {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A layer is not valid TOML
+    #[error("{layer}: {message}")]
+    Parse { layer: String, message: String },
+
+    /// A key is unknown or has the wrong type
+    #[error("{layer}: {path}: {message}")]
+    Schema {
+        layer: String,
+        path: String,
+        message: String,
+    },
+
+    /// An environment or command-line override is malformed
+    #[error("invalid override {0}")]
+    Override(String),
+
+    /// Cross-field validation failed
+    #[error("invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Validation(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+enum FileLayer {
+    Path(PathBuf),
+    Inline { name: String, contents: String },
+}
+
+/// Builds a [`Config`] from defaults, a TOML file, environment and CLI overrides
+///
+/// Later layers win. Each layer is checked against the schema as it is
+/// applied, so errors name the layer that introduced a bad key.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    file: Option<FileLayer>,
+    env: Vec<(String, String)>,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the file layer from `path`
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(FileLayer::Path(path.into()));
+        self
+    }
+
+    /// Use `contents` as the file layer, reporting errors against `name`
+    pub fn with_toml(mut self, name: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.file = Some(FileLayer::Inline {
+            name: name.into(),
+            contents: contents.into(),
+        });
+        self
+    }
+
+    /// Collect `BOT__*` overrides from the process environment (and `.env`)
+    pub fn with_process_env(self) -> Self {
+        dotenvy::dotenv().ok();
+        self.with_env_vars(std::env::vars())
+    }
+
+    /// Collect `BOT__*` overrides from the given variables
+    pub fn with_env_vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (key, value) in vars {
+            let key = key.into();
+            if let Some(path) = key.strip_prefix(ENV_PREFIX) {
+                let path = path
+                    .split(ENV_SEPARATOR)
+                    .map(str::to_ascii_lowercase)
+                    .collect::<Vec<_>>()
+                    .join(".");
+                self.env.push((path, value.into()));
+            }
+        }
+        self.env.sort();
+        self
+    }
+
+    /// Add a command-line override
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// Add `key=value` command-line overrides
+    pub fn with_overrides<S: AsRef<str>>(mut self, pairs: &[S]) -> Result<Self, ConfigError> {
+        for pair in pairs {
+            let pair = pair.as_ref();
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| ConfigError::Override(format!("'{}': expected KEY=VALUE", pair)))?;
+            self = self.with_override(key.trim(), value);
+        }
+        Ok(self)
+    }
+
+    /// Merge all layers and check the schema without cross-field validation
+    pub fn load_unvalidated(&self) -> Result<Config, ConfigError> {
+        let mut merged =
+            toml::Value::try_from(Config::default()).map_err(|e| ConfigError::Parse {
+                layer: "defaults".to_string(),
+                message: e.to_string(),
+            })?;
+
+        if let Some((layer, contents)) = self.read_file()? {
+            let mut value: toml::Value =
+                toml::from_str(&contents).map_err(|e| ConfigError::Parse {
+                    layer: layer.clone(),
+                    message: e.to_string(),
+                })?;
+            apply_aliases(&mut value);
+            merge_values(&mut merged, value);
+            check_schema(&layer, &merged)?;
+        }
+
+        if !self.env.is_empty() {
+            for (path, raw) in &self.env {
+                set_path(&mut merged, path, raw).map_err(|e| {
+                    ConfigError::Override(format!("{}{}: {}", ENV_PREFIX, env_name(path), e))
+                })?;
+            }
+            check_schema("environment", &merged)?;
+        }
+
+        if !self.overrides.is_empty() {
+            for (path, raw) in &self.overrides {
+                set_path(&mut merged, path, raw)
+                    .map_err(|e| ConfigError::Override(format!("--set {}: {}", path, e)))?;
+            }
+            check_schema("command line", &merged)?;
+        }
+
+        check_schema("config", &merged)
+    }
+
+    /// Merge all layers, check the schema and run [`Config::validate`]
+    pub fn load(&self) -> Result<Config, ConfigError> {
+        let config = self.load_unvalidated()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn read_file(&self) -> Result<Option<(String, String)>, ConfigError> {
+        match &self.file {
+            None => Ok(None),
+            Some(FileLayer::Inline { name, contents }) => {
+                Ok(Some((name.clone(), contents.clone())))
+            }
+            Some(FileLayer::Path(path)) => {
+                let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+                    path: path.display().to_string(),
+                    source,
+                })?;
+                Ok(Some((path.display().to_string(), contents)))
+            }
+        }
+    }
+}
+
+fn env_name(path: &str) -> String {
+    path.split('.')
+        .map(str::to_ascii_uppercase)
+        .collect::<Vec<_>>()
+        .join(ENV_SEPARATOR)
+}
+
+/// Deserialize the merged tree, reporting the offending key path
+fn check_schema(layer: &str, value: &toml::Value) -> Result<Config, ConfigError> {
+    serde_path_to_error::deserialize(value.clone()).map_err(|e| ConfigError::Schema {
+        layer: layer.to_string(),
+        path: e.path().to_string(),
+        message: e.into_inner().to_string(),
+    })
+}
+
+/// Rename aliased keys in a file layer to their schema names
+fn apply_aliases(value: &mut toml::Value) {
+    for (section, alias, key) in KEY_ALIASES {
+        if let Some(table) = value.get_mut(*section).and_then(toml::Value::as_table_mut) {
+            if !table.contains_key(*key) {
+                if let Some(v) = table.remove(*alias) {
+                    table.insert(key.to_string(), v);
+                }
+            }
+        }
+    }
+}
+
+/// Recursively merge `overlay` into `base`; tables merge, everything else replaces
+fn merge_values(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Set a dotted key from a raw string, coercing to the type already at that key
+fn set_path(root: &mut toml::Value, path: &str, raw: &str) -> Result<(), String> {
+    let segments: Vec<&str> = path.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err("empty key segment".to_string());
+    }
+
+    let (leaf, parents) = segments.split_last().expect("non-empty path");
+    let mut table = root.as_table_mut().expect("config root is a table");
+    for segment in parents {
+        table = table
+            .entry(segment.to_string())
+            .or_insert_with(|| toml::Value::Table(Default::default()))
+            .as_table_mut()
+            .ok_or_else(|| format!("'{}' is not a section", segment))?;
+    }
+
+    let value = coerce(table.get(*leaf), raw)?;
+    table.insert(leaf.to_string(), value);
+    Ok(())
+}
+
+fn coerce(existing: Option<&toml::Value>, raw: &str) -> Result<toml::Value, String> {
+    match existing {
+        Some(toml::Value::String(_)) => Ok(toml::Value::String(raw.to_string())),
+        Some(toml::Value::Array(items)) if !raw.trim_start().starts_with('[') => {
+            let element = items.first();
+            raw.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|item| coerce(element, item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(toml::Value::Array)
+        }
+        Some(_) => parse_literal(raw).ok_or_else(|| format!("cannot parse '{}'", raw)),
+        // Unset optional values: take a TOML literal if it parses, else a string
+        None => Ok(parse_literal(raw).unwrap_or_else(|| toml::Value::String(raw.to_string()))),
+    }
+}
+
+fn parse_literal(raw: &str) -> Option<toml::Value> {
+    #[derive(Deserialize)]
+    struct Literal {
+        v: toml::Value,
+    }
+    toml::from_str::<Literal>(&format!("v = {}", raw))
+        .ok()
+        .map(|l| l.v)
+}
+
+/// Hide credentials embedded in an endpoint URL (userinfo, path tokens, query)
+fn redact_url(url: &str) -> String {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, url),
+    };
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+
+    let mut redacted = String::new();
+    if let Some(scheme) = scheme {
+        redacted.push_str(scheme);
+        redacted.push_str("://");
+    }
+    if host.len() != authority.len() {
+        redacted.push_str(REDACTED);
+        redacted.push('@');
+    }
+    redacted.push_str(host);
+    if !tail.is_empty() && tail != "/" {
+        redacted.push('/');
+        redacted.push_str(REDACTED);
+    }
+    redacted
+}
+
 impl Config {
     /// Load configuration from TOML file
     pub fn from_file(path: &str) -> anyhow::Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        let config: Config = toml::from_str(&content)?;
-        Ok(config)
+        Ok(ConfigLoader::new().with_file(path).load()?)
     }
 
     /// Load configuration with environment variable overrides
     pub fn from_file_with_env(path: &str) -> anyhow::Result<Self> {
-        dotenvy::dotenv().ok();
-        Self::from_file(path)
+        Ok(ConfigLoader::new()
+            .with_file(path)
+            .with_process_env()
+            .load()?)
     }
 
     /// Create default configuration
     pub fn default() -> Self {
         Self {
-            rpc: RpcConfig {
-                endpoints: vec!["https://api.mainnet-beta.solana.com".to_string()],
-                timeout_secs: default_rpc_timeout(),
-                max_retries: default_max_retries(),
-                rate_limit_rps: default_rate_limit(),
-            },
-            wallet: WalletConfig {
-                keypair_path: "~/.config/solana/id.json".to_string(),
-                use_hardware_wallet: false,
-            },
-            trading: TradingConfig {
-                max_slippage_bps: default_max_slippage(),
-                buy_amount_sol: 0.1,
-                min_liquidity_lamports: default_min_liquidity(),
-                enable_jito: false,
-                jito_tip_lamports: default_jito_tip(),
-            },
-            nonce: NonceConfig {
-                pool_size: default_nonce_pool_size(),
-                refresh_interval_secs: default_nonce_refresh_interval(),
-            },
-            sniffer: SnifferConfig {
-                geyser_endpoint: "http://localhost:10000".to_string(),
-                monitored_programs: vec![],
-                stream_buffer_size: default_stream_buffer_size(),
-            },
-            monitoring: MonitoringConfig {
-                enable_metrics: default_true(),
-                metrics_port: default_metrics_port(),
-                enable_tracing: default_true(),
-            },
+            rpc: RpcConfig::default(),
+            wallet: WalletConfig::default(),
+            trading: TradingConfig::default(),
+            portfolio: PortfolioConfig::default(),
+            tx_builder: TxBuilderConfig::default(),
+            nonce: NonceConfig::default(),
+            sniffer: SnifferConfig::default(),
+            streaming: StreamingConfig::default(),
+            monitoring: MonitoringConfig::default(),
+            screening: ScreeningConfig::default(),
+            strategy: StrategyConfig::default(),
+            control: ControlConfig::default(),
+            copy_trading: CopyTradingConfig::default(),
+            presigned_exits: PresignedExitConfig::default(),
+            decision_journal: DecisionJournalConfig::default(),
             nonce_count: default_nonce_count(),
         }
     }
+
+    /// Cross-field validation; reports every problem at once
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+        let mut check = |ok: bool, message: String| {
+            if !ok {
+                errors.push(message);
+            }
+        };
+
+        // rpc
+        check(
+            self.rpc.timeout_secs > 0,
+            "rpc.timeout_secs must be > 0".to_string(),
+        );
+        if let Err(e) = self.rpc_manager_config().validate() {
+            check(false, format!("rpc: {}", e));
+        }
+
+        // wallet
+        check(
+            self.wallet.use_hardware_wallet || !self.wallet.keypair_path.is_empty(),
+            "wallet.keypair_path must be set".to_string(),
+        );
+        check(
+            self.wallet.passphrase_env.is_none() || self.wallet.passphrase_fd.is_none(),
+            "wallet.passphrase_env and wallet.passphrase_fd are mutually exclusive".to_string(),
+        );
+
+        // trading / portfolio
+        let trading = &self.trading;
+        check(
+            trading.max_slippage_bps <= 10_000,
+            format!(
+                "trading.max_slippage_bps {} exceeds 10000",
+                trading.max_slippage_bps
+            ),
+        );
+        check(
+            trading.slippage_bps <= trading.max_slippage_bps,
+            format!(
+                "trading.slippage_bps {} exceeds trading.max_slippage_bps {}",
+                trading.slippage_bps, trading.max_slippage_bps
+            ),
+        );
+        check(
+            trading.taker_fee_bps <= 10_000,
+            format!(
+                "trading.taker_fee_bps {} exceeds 10000",
+                trading.taker_fee_bps
+            ),
+        );
+        check(
+            trading.buy_amount_sol.is_finite() && trading.buy_amount_sol > 0.0,
+            "trading.buy_amount_sol must be > 0".to_string(),
+        );
+        check(
+            trading.max_spend_per_window_sol >= trading.buy_amount_sol,
+            format!(
+                "trading.max_spend_per_window_sol {} is below trading.buy_amount_sol {}",
+                trading.max_spend_per_window_sol, trading.buy_amount_sol
+            ),
+        );
+        check(
+            trading.window_duration_secs > 0,
+            "trading.window_duration_secs must be > 0".to_string(),
+        );
+        check(
+            trading.mode != PortfolioMode::Multi || self.portfolio.enable_multi_token,
+            "trading.mode = \"multi\" requires portfolio.enable_multi_token = true".to_string(),
+        );
+        check(
+            self.portfolio.max_concurrent_positions > 0,
+            "portfolio.max_concurrent_positions must be > 0".to_string(),
+        );
+        check(
+            self.portfolio.max_total_exposure_sol >= trading.buy_amount_sol,
+            format!(
+                "portfolio.max_total_exposure_sol {} is below trading.buy_amount_sol {}",
+                self.portfolio.max_total_exposure_sol, trading.buy_amount_sol
+            ),
+        );
+
+        // tx_builder
+        let tx = &self.tx_builder;
+        check(
+            tx.min_cu_limit <= tx.compute_unit_limit && tx.compute_unit_limit <= tx.max_cu_limit,
+            format!(
+                "tx_builder compute unit limits must satisfy min_cu_limit ({}) <= compute_unit_limit ({}) <= max_cu_limit ({})",
+                tx.min_cu_limit, tx.compute_unit_limit, tx.max_cu_limit
+            ),
+        );
+        check(
+            tx.max_cu_limit <= 1_400_000,
+            format!(
+                "tx_builder.max_cu_limit {} exceeds 1400000",
+                tx.max_cu_limit
+            ),
+        );
+        check(
+            tx.adaptive_priority_fee_multiplier >= 1.0,
+            "tx_builder.adaptive_priority_fee_multiplier must be >= 1.0".to_string(),
+        );
+        check(
+            tx.max_concurrent_builds > 0,
+            "tx_builder.max_concurrent_builds must be > 0".to_string(),
+        );
+
+        // nonce
+        check(
+            self.nonce.pool_size > 0,
+            "nonce.pool_size must be > 0".to_string(),
+        );
+        check(
+            self.nonce_count > 0 && self.nonce_count <= self.nonce.pool_size,
+            format!(
+                "nonce_count {} must be between 1 and nonce.pool_size {}",
+                self.nonce_count, self.nonce.pool_size
+            ),
+        );
+        check(
+            self.nonce.reserved.total() < self.nonce.pool_size,
+            format!(
+                "nonce.reserved total {} must be below nonce.pool_size {}",
+                self.nonce.reserved.total(),
+                self.nonce.pool_size
+            ),
+        );
+        check(
+            !matches!(self.nonce.accounts_file.as_deref(), Some(path) if path.trim().is_empty()),
+            "nonce.accounts_file must not be empty when set".to_string(),
+        );
+        check(
+            self.nonce.snapshot_path.is_none() || self.nonce.snapshot_interval_secs > 0,
+            "nonce.snapshot_interval_secs must be > 0".to_string(),
+        );
+
+        // sniffer
+        if let Err(e) = self.sniffer_config().validate() {
+            check(false, format!("sniffer: {}", e));
+        }
+
+        // streaming
+        match self.streaming.mode {
+            StreamingMode::Websocket => check(
+                self.streaming.websocket_url.starts_with("ws://")
+                    || self.streaming.websocket_url.starts_with("wss://"),
+                "streaming.websocket_url must start with ws:// or wss://".to_string(),
+            ),
+            StreamingMode::Geyser => check(
+                self.streaming.geyser_endpoint.is_some(),
+                "streaming.mode = \"geyser\" requires streaming.geyser_endpoint".to_string(),
+            ),
+        }
+
+        // monitoring
+        check(
+            !self.monitoring.enable_metrics || self.monitoring.metrics_port != 0,
+            "monitoring.metrics_port must be set when metrics are enabled".to_string(),
+        );
+        match self.monitoring.trace_exporter {
+            TraceExporterKind::Otlp => check(
+                self.monitoring.otlp_endpoint.starts_with("http://")
+                    || self.monitoring.otlp_endpoint.starts_with("https://"),
+                "monitoring.otlp_endpoint must start with http:// or https://".to_string(),
+            ),
+            TraceExporterKind::File => check(
+                self.monitoring.trace_file.is_some(),
+                "monitoring.trace_exporter = \"file\" requires monitoring.trace_file".to_string(),
+            ),
+            TraceExporterKind::None | TraceExporterKind::Stdout => {}
+        }
+
+        // screening
+        let screening = &self.screening;
+        check(
+            screening.min_decimals <= screening.max_decimals,
+            "screening.min_decimals must be <= screening.max_decimals".to_string(),
+        );
+        check(
+            (0.0..=1.0).contains(&screening.max_top_holder_pct)
+                && (0.0..=1.0).contains(&screening.max_top10_holder_pct),
+            "screening holder percentages must be in [0.0, 1.0]".to_string(),
+        );
+        check(
+            screening.max_top_holder_pct <= screening.max_top10_holder_pct,
+            "screening.max_top_holder_pct must be <= screening.max_top10_holder_pct".to_string(),
+        );
+        check(
+            screening.max_supply_tokens == 0
+                || screening.min_supply_tokens <= screening.max_supply_tokens,
+            "screening.min_supply_tokens must be <= screening.max_supply_tokens".to_string(),
+        );
+        check(
+            screening.timeout_ms > 0,
+            "screening.timeout_ms must be > 0".to_string(),
+        );
+
+        // strategy
+        let strategy = &self.strategy;
+        check(
+            strategy.stop_loss_percent >= 0.0 && strategy.take_profit_percent >= 0.0,
+            "strategy percentages must be >= 0 (0 disables the rule)".to_string(),
+        );
+        check(
+            strategy.take_profit_sell_percent > 0.0 && strategy.take_profit_sell_percent <= 1.0,
+            "strategy.take_profit_sell_percent must be in (0.0, 1.0]".to_string(),
+        );
+
+        // control
+        let control = &self.control;
+        if control.enabled {
+            check(
+                control.bind_addr.parse::<std::net::SocketAddr>().is_ok(),
+                format!("control.bind_addr '{}' is not a socket address", control.bind_addr),
+            );
+            check(
+                !control.tokens.is_empty(),
+                "control.tokens must not be empty when the control API is enabled".to_string(),
+            );
+            check(
+                control.max_body_bytes > 0,
+                "control.max_body_bytes must be > 0".to_string(),
+            );
+        }
+        for token in &control.tokens {
+            check(
+                token.token.is_some() != token.token_env.is_some(),
+                format!(
+                    "control token '{}' needs exactly one of token or token_env",
+                    token.name
+                ),
+            );
+            check(
+                token.token.as_ref().is_none_or(|t| t.len() >= 16),
+                format!("control token '{}' must be at least 16 characters", token.name),
+            );
+        }
+
+        // presigned_exits
+        let exits = &self.presigned_exits;
+        check(
+            exits.slippage_bps > 0 && exits.slippage_bps <= 10_000,
+            "presigned_exits.slippage_bps must be in 1..=10000".to_string(),
+        );
+        check(
+            exits.lease_ttl_secs > 0,
+            "presigned_exits.lease_ttl_secs must be > 0".to_string(),
+        );
+        check(
+            !exits.enabled || self.nonce.pool_size >= 2,
+            "presigned_exits needs nonce.pool_size >= 2 (one nonce per open exit)".to_string(),
+        );
+
+        // decision_journal
+        let journal = &self.decision_journal;
+        check(
+            journal.retention_days > 0,
+            "decision_journal.retention_days must be > 0".to_string(),
+        );
+        check(
+            !journal.enabled || !journal.dir.trim().is_empty(),
+            "decision_journal.dir must not be empty when the journal is enabled".to_string(),
+        );
+
+        // copy_trading
+        let copy_trading = &self.copy_trading;
+        check(
+            !copy_trading.enabled || !copy_trading.leaders.is_empty(),
+            "copy_trading.leaders must not be empty when copy trading is enabled".to_string(),
+        );
+        let mut seen = std::collections::HashSet::new();
+        for leader in &copy_trading.leaders {
+            check(
+                leader.address.parse::<solana_sdk::pubkey::Pubkey>().is_ok(),
+                format!("copy_trading leader '{}' is not a valid address", leader.address),
+            );
+            check(
+                seen.insert(leader.address.as_str()),
+                format!("copy_trading leader '{}' is listed twice", leader.address),
+            );
+            if let Err(e) = leader.sizing.validate() {
+                check(false, format!("copy_trading leader '{}': sizing {}", leader.address, e));
+            }
+            check(
+                leader.min_leader_sol >= 0.0,
+                format!("copy_trading leader '{}': min_leader_sol must be >= 0", leader.address),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Validation(errors))
+        }
+    }
+
+    /// Copy of the configuration with credentials replaced by [`REDACTED`]
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        for endpoint in &mut config.rpc.endpoints {
+            *endpoint = match endpoint {
+                RpcEndpoint::Url(url) => RpcEndpoint::Url(redact_url(url)),
+                RpcEndpoint::Table(table) => RpcEndpoint::Table(RpcEndpointTable {
+                    url: redact_url(&table.url),
+                    credentials: table.credentials.as_ref().map(|_| REDACTED.to_string()),
+                }),
+            };
+        }
+        config.sniffer.geyser_endpoint = redact_url(&config.sniffer.geyser_endpoint);
+        config.streaming.websocket_url = redact_url(&config.streaming.websocket_url);
+        if let Some(endpoint) = config.streaming.geyser_endpoint.as_mut() {
+            *endpoint = redact_url(endpoint);
+        }
+        for key in [
+            &mut config.tx_builder.pumpportal_api_key,
+            &mut config.tx_builder.letsbonk_api_key,
+        ] {
+            if key.is_some() {
+                *key = Some(REDACTED.to_string());
+            }
+        }
+        for token in &mut config.control.tokens {
+            if token.token.is_some() {
+                token.token = Some(REDACTED.to_string());
+            }
+        }
+        config
+    }
+
+    /// Render the effective configuration as TOML with secrets redacted
+    pub fn to_redacted_toml(&self) -> anyhow::Result<String> {
+        Ok(toml::to_string_pretty(&self.redacted())?)
+    }
+
+    /// RPC manager configuration derived from `[rpc]`
+    pub fn rpc_manager_config(&self) -> RpcManagerConfig {
+        let mut config = RpcManagerConfig::from_urls(&self.rpc.endpoint_urls());
+        for (endpoint, configured) in config.endpoints.iter_mut().zip(&self.rpc.endpoints) {
+            endpoint.timeout_ms = self.rpc.timeout_secs.saturating_mul(1000);
+            endpoint.rate_limit_rps = self.rpc.rate_limit_rps;
+            endpoint.max_concurrency = self.rpc.max_concurrency;
+            endpoint.credentials = configured.credentials().map(str::to_string);
+        }
+        config.health_check_interval_secs = self.rpc.health_check_interval_secs;
+        config.circuit_breaker_failure_threshold = self.rpc.circuit_breaker_failure_threshold;
+        config.circuit_breaker_timeout_secs = self.rpc.circuit_breaker_timeout_secs;
+        config.enable_predictive_failure = self.rpc.enable_predictive_failure;
+        config.predictive_failure_threshold = self.rpc.predictive_failure_threshold;
+        config.enable_telemetry = self.monitoring.enable_metrics;
+        config
+    }
+
+    /// Span export configuration derived from `[monitoring]`, if enabled
+    pub fn trace_export_config(&self) -> Option<TraceExportConfig> {
+        let monitoring = &self.monitoring;
+        if !monitoring.enable_tracing {
+            return None;
+        }
+        let exporter = match monitoring.trace_exporter {
+            TraceExporterKind::None => return None,
+            TraceExporterKind::Otlp => SpanExporter::Otlp {
+                endpoint: monitoring.otlp_endpoint.clone(),
+            },
+            TraceExporterKind::File => SpanExporter::File(monitoring.trace_file.clone()?.into()),
+            TraceExporterKind::Stdout => SpanExporter::Stdout,
+        };
+        Some(TraceExportConfig::new(exporter))
+    }
+
+    /// Decision journal configuration derived from `[decision_journal]`, if enabled
+    pub fn decision_journal_config(&self) -> Option<JournalConfig> {
+        let journal = &self.decision_journal;
+        journal
+            .enabled
+            .then(|| JournalConfig::new(&journal.dir, journal.retention_days))
+    }
+
+    /// Sniffer pipeline configuration derived from `[sniffer]`
+    pub fn sniffer_config(&self) -> SnifferPipelineConfig {
+        let sniffer = &self.sniffer;
+        SnifferPipelineConfig {
+            grpc_endpoint: sniffer.geyser_endpoint.clone(),
+            channel_capacity: sniffer.channel_capacity,
+            stream_buffer_size: sniffer.stream_buffer_size,
+            max_retry_attempts: sniffer.max_retry_attempts,
+            initial_backoff_ms: sniffer.initial_backoff_ms,
+            max_backoff_ms: sniffer.max_backoff_ms,
+            telemetry_interval_secs: sniffer.telemetry_interval_secs,
+            ema_alpha_short: sniffer.ema_alpha_short,
+            ema_alpha_long: sniffer.ema_alpha_long,
+            initial_threshold: sniffer.initial_threshold,
+            batch_size: sniffer.batch_size,
+            batch_timeout_ms: sniffer.batch_timeout_ms,
+            drop_policy: sniffer.drop_policy,
+            batch_send_mode: sniffer.batch_send_mode,
+            graceful_shutdown_timeout_ms: sniffer.graceful_shutdown_timeout_ms,
+            ..SnifferPipelineConfig::default()
+        }
+    }
+
+    /// Nonce manager configuration derived from `[nonce]`
+    pub fn nonce_manager_config(&self) -> NonceManagerConfig {
+        NonceManagerConfig {
+            pool_size: self.nonce.pool_size,
+            acquire_timeout: std::time::Duration::from_millis(self.nonce.acquire_timeout_ms),
+            lease_ttl: std::time::Duration::from_secs(self.nonce.lease_ttl_secs),
+            refresh_interval: std::time::Duration::from_secs(self.nonce.refresh_interval_secs),
+            enable_predictive_refresh: self.nonce.enable_predictive_refresh,
+            reservations: self.nonce.reserved,
+        }
+    }
 }
 
 impl Default for Config {
@@ -216,3 +1760,402 @@ impl Default for Config {
         Self::default()
     }
 }
+
+/// Keys (or `section.` prefixes) that running subsystems apply without a restart
+pub const LIVE_RELOAD_KEYS: &[&str] = &[
+    "rpc.endpoints",
+    "trading.slippage_bps",
+    "trading.max_slippage_bps",
+    "trading.kill_switch",
+    "tx_builder.priority_fee_lamports",
+    "tx_builder.compute_unit_limit",
+    "nonce.reserved.",
+    "portfolio.",
+    "sniffer.ema_alpha_short",
+    "sniffer.ema_alpha_long",
+    "sniffer.initial_threshold",
+    "strategy.",
+];
+
+/// One key that differs between two configurations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// Dotted key, e.g. `trading.slippage_bps`
+    pub key: String,
+    /// Previous value rendered as TOML (secrets redacted)
+    pub old: String,
+    /// New value rendered as TOML (secrets redacted)
+    pub new: String,
+    /// Whether running subsystems can apply the change without a restart
+    pub live: bool,
+}
+
+impl ConfigChange {
+    /// Whether `key` is covered by [`LIVE_RELOAD_KEYS`]
+    pub fn is_live_key(key: &str) -> bool {
+        LIVE_RELOAD_KEYS.iter().any(|live| {
+            if live.ends_with('.') {
+                key.starts_with(live)
+            } else {
+                key == *live
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {}", self.key, self.old, self.new)
+    }
+}
+
+impl Config {
+    /// Keys whose values differ between `self` and `new`, in key order
+    pub fn diff(&self, new: &Config) -> Vec<ConfigChange> {
+        let flat = |config: &Config| {
+            let mut out = std::collections::BTreeMap::new();
+            if let Ok(value) = toml::Value::try_from(config) {
+                flatten_value("", &value, &mut out);
+            }
+            out
+        };
+        let (old_raw, new_raw) = (flat(self), flat(new));
+        let (old_shown, new_shown) = (flat(&self.redacted()), flat(&new.redacted()));
+
+        let render = |map: &std::collections::BTreeMap<String, toml::Value>, key: &str| {
+            map.get(key)
+                .map_or_else(|| "<unset>".to_string(), |v| v.to_string())
+        };
+
+        let mut keys: Vec<&String> = old_raw.keys().chain(new_raw.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter(|key| old_raw.get(*key) != new_raw.get(*key))
+            .map(|key| ConfigChange {
+                key: key.clone(),
+                old: render(&old_shown, key),
+                new: render(&new_shown, key),
+                live: ConfigChange::is_live_key(key),
+            })
+            .collect()
+    }
+}
+
+fn flatten_value(
+    prefix: &str,
+    value: &toml::Value,
+    out: &mut std::collections::BTreeMap<String, toml::Value>,
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_value(&path, value, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_config_toml_loads() {
+        let config = ConfigLoader::new()
+            .with_toml("Config.toml", include_str!("../Config.toml"))
+            .load()
+            .unwrap();
+
+        assert_eq!(config.streaming.mode, StreamingMode::Websocket);
+        assert_eq!(config.streaming.commitment, CommitmentLevel::Confirmed);
+        assert_eq!(config.trading.mode, PortfolioMode::Single);
+        assert_eq!(config.trading.buy_amount_sol, 0.1);
+        assert_eq!(config.portfolio.max_concurrent_positions, 5);
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        let config = ConfigLoader::new()
+            .with_toml(
+                "test.toml",
+                "[rpc]\ntimeout_secs = 10\nmax_retries = 7\n[monitoring]\nmetrics_port = 9100\n",
+            )
+            .with_env_vars([
+                ("BOT__RPC__TIMEOUT_SECS", "20"),
+                (
+                    "BOT__RPC__ENDPOINTS",
+                    "https://a.example, https://b.example",
+                ),
+                ("UNRELATED", "ignored"),
+            ])
+            .with_override("rpc.timeout_secs", "40")
+            .load()
+            .unwrap();
+
+        assert_eq!(config.rpc.timeout_secs, 40);
+        assert_eq!(config.rpc.max_retries, 7);
+        assert_eq!(
+            config.rpc.endpoint_urls(),
+            vec!["https://a.example", "https://b.example"]
+        );
+        assert_eq!(config.monitoring.metrics_port, 9100);
+        assert_eq!(config.nonce.pool_size, default_nonce_pool_size());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected_with_path() {
+        let err = ConfigLoader::new()
+            .with_toml("test.toml", "[trading]\nslipage_bps = 50\n")
+            .load()
+            .unwrap_err();
+        match err {
+            ConfigError::Schema { layer, path, .. } => {
+                assert_eq!(layer, "test.toml");
+                assert_eq!(path, "trading.slipage_bps");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let err = ConfigLoader::new()
+            .with_env_vars([("BOT__STREAMING__MODE", "carrier-pigeon")])
+            .load()
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Schema { ref layer, .. } if layer == "environment"));
+    }
+
+    #[test]
+    fn test_cross_field_validation_reports_all_errors() {
+        let err = ConfigLoader::new()
+            .with_overrides(&[
+                "trading.mode=multi",
+                "trading.slippage_bps=900",
+                "nonce_count=50",
+            ])
+            .unwrap()
+            .load()
+            .unwrap_err();
+        let ConfigError::Validation(errors) = err else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors.iter().any(|e| e.contains("enable_multi_token")));
+        assert!(errors.iter().any(|e| e.contains("slippage_bps")));
+        assert!(errors.iter().any(|e| e.contains("nonce_count")));
+    }
+
+    #[test]
+    fn test_trace_export_config() {
+        let config = ConfigLoader::new().load().unwrap();
+        assert!(config.trace_export_config().is_none());
+
+        let config = ConfigLoader::new()
+            .with_overrides(&[
+                "monitoring.trace_exporter=file",
+                "monitoring.trace_file=spans.jsonl",
+            ])
+            .unwrap()
+            .load()
+            .unwrap();
+        assert_eq!(
+            config.trace_export_config().unwrap().exporter,
+            SpanExporter::File("spans.jsonl".into())
+        );
+
+        let config = ConfigLoader::new()
+            .with_overrides(&[
+                "monitoring.trace_exporter=otlp",
+                "monitoring.enable_tracing=false",
+            ])
+            .unwrap()
+            .load()
+            .unwrap();
+        assert!(config.trace_export_config().is_none());
+
+        let err = ConfigLoader::new()
+            .with_override("monitoring.trace_exporter", "file")
+            .load()
+            .unwrap_err();
+        let ConfigError::Validation(errors) = err else {
+            panic!("expected validation errors");
+        };
+        assert!(errors.iter().any(|e| e.contains("trace_file")));
+    }
+
+    #[test]
+    fn test_decision_journal_config() {
+        let config = ConfigLoader::new().load().unwrap();
+        assert!(config.decision_journal_config().is_none());
+
+        let config = ConfigLoader::new()
+            .with_overrides(&[
+                "decision_journal.enabled=true",
+                "decision_journal.dir=/var/lib/bot/journal",
+                "decision_journal.retention_days=7",
+            ])
+            .unwrap()
+            .load()
+            .unwrap();
+        let journal = config.decision_journal_config().unwrap();
+        assert_eq!(journal.dir, PathBuf::from("/var/lib/bot/journal"));
+        assert_eq!(journal.retention_days, 7);
+
+        let err = ConfigLoader::new()
+            .with_override("decision_journal.retention_days", "0")
+            .load()
+            .unwrap_err();
+        let ConfigError::Validation(errors) = err else {
+            panic!("expected validation errors");
+        };
+        assert!(errors.iter().any(|e| e.contains("retention_days")));
+    }
+
+    #[test]
+    fn test_copy_trading_leaders_parse_and_validate() {
+        let leader = solana_sdk::pubkey::Pubkey::new_unique();
+        let toml = format!(
+            "[copy_trading]\nenabled = true\n\n\
+             [[copy_trading.leaders]]\naddress = \"{leader}\"\ndelay_ms = 250\n\
+             sizing = {{ mode = \"capped\", ratio = 0.5, max_sol = 0.2 }}\n"
+        );
+        let config = ConfigLoader::new().with_toml("test", &toml).load().unwrap();
+        let parsed = &config.copy_trading.leaders[0];
+        assert_eq!(parsed.sizing, SizingRule::Capped { ratio: 0.5, max_sol: 0.2 });
+        assert_eq!(parsed.delay_ms, 250);
+        assert!(parsed.exit_with_leader);
+
+        let bad = "[[copy_trading.leaders]]\naddress = \"nope\"\n\
+                   sizing = { mode = \"fixed\", amount_sol = 0.0 }\n";
+        let ConfigError::Validation(errors) =
+            ConfigLoader::new().with_toml("test", bad).load().unwrap_err()
+        else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors.iter().any(|e| e.contains("not a valid address")));
+        assert!(errors.iter().any(|e| e.contains("amount_sol must be > 0")));
+    }
+
+    #[test]
+    fn test_nonce_reservations_parse_and_validate() {
+        let config = Config::default();
+        assert_eq!(config.nonce.reserved.exit, 1);
+
+        let toml = "[nonce]\npool_size = 4\n\n[nonce.reserved]\nexit = 2\nentry = 1\n";
+        let config = ConfigLoader::new().with_toml("test", toml).load().unwrap();
+        assert_eq!(config.nonce.reserved.total(), 3);
+        assert_eq!(config.nonce_manager_config().reservations.exit, 2);
+
+        let bad = "[nonce]\npool_size = 2\n\n[nonce.reserved]\nexit = 2\n";
+        let ConfigError::Validation(errors) =
+            ConfigLoader::new().with_toml("test", bad).load().unwrap_err()
+        else {
+            panic!("expected validation errors");
+        };
+        assert!(errors.iter().any(|e| e.contains("nonce.reserved")), "{errors:?}");
+    }
+
+    #[test]
+    fn test_diff_classifies_live_and_restart_keys() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.trading.slippage_bps = 200;
+        new.strategy.stop_loss_percent = 15.0;
+        new.wallet.keypair_path = "/keys/other.json".to_string();
+        new.rpc.endpoints = vec!["https://rpc.example.com/?api-key=SECRET".into()];
+
+        let changes = old.diff(&new);
+        let keys: Vec<_> = changes.iter().map(|c| (c.key.as_str(), c.live)).collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("rpc.endpoints", true),
+                ("strategy.stop_loss_percent", true),
+                ("trading.slippage_bps", true),
+                ("wallet.keypair_path", false),
+            ]
+        );
+        assert!(changes.iter().all(|c| !c.new.contains("SECRET")));
+        assert!(old.diff(&old.clone()).is_empty());
+
+        let strategy = new.strategy.to_sell_strategy().unwrap();
+        assert_eq!(strategy.stop_loss.unwrap().threshold_percent, -15.0);
+        assert!(strategy.take_profit.is_none());
+    }
+
+    #[test]
+    fn test_rpc_endpoint_limits_and_credentials() {
+        let config = ConfigLoader::new()
+            .with_toml(
+                "test",
+                r#"
+                [rpc]
+                max_concurrency = 8
+                rate_limit_rps = 0
+
+                [[rpc.endpoints]]
+                url = "https://a.example"
+                credentials = "header:x-token=abc"
+
+                [[rpc.endpoints]]
+                url = "https://b.example"
+                "#,
+            )
+            .load()
+            .unwrap();
+        let endpoints = config.rpc_manager_config().endpoints;
+        assert_eq!(endpoints[0].max_concurrency, 8);
+        assert_eq!(endpoints[0].rate_limit_rps, 0);
+        assert_eq!(
+            endpoints[0].credentials.as_deref(),
+            Some("header:x-token=abc")
+        );
+        assert_eq!(endpoints[1].url, "https://b.example");
+        assert_eq!(endpoints[1].credentials, None);
+
+        let err = ConfigLoader::new()
+            .with_toml(
+                "test",
+                "[[rpc.endpoints]]\nurl = \"https://a.example\"\ncredentials = \"query:api-key\"\n",
+            )
+            .load()
+            .unwrap_err();
+        let ConfigError::Validation(errors) = err else {
+            panic!("expected validation errors");
+        };
+        assert!(errors.iter().any(|e| e.starts_with("rpc:")), "{errors:?}");
+    }
+
+    #[test]
+    fn test_redacted_output_hides_secrets_and_round_trips() {
+        let mut config = Config::default();
+        config.rpc.endpoints = vec![
+            "https://user:pw@rpc.example.com/?api-key=SECRET".into(),
+            RpcEndpoint::Table(RpcEndpointTable {
+                url: "https://node.example.com".to_string(),
+                credentials: Some("query:api-key=SECRET".to_string()),
+            }),
+        ];
+        config.streaming.websocket_url = "wss://node.example.com/v2/SECRET".to_string();
+        config.tx_builder.pumpportal_api_key = Some("SECRET".to_string());
+
+        let rendered = config.to_redacted_toml().unwrap();
+        assert!(!rendered.contains("SECRET"));
+        assert!(!rendered.contains("pw@"));
+        assert!(rendered.contains("https://<redacted>@rpc.example.com/<redacted>"));
+
+        let reparsed = ConfigLoader::new()
+            .with_toml("printed", toml::to_string_pretty(&config).unwrap())
+            .load()
+            .unwrap();
+        assert_eq!(reparsed.rpc.endpoints, config.rpc.endpoints);
+    }
+}
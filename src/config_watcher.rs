@@ -0,0 +1,374 @@
+//! Hot configuration reload
+//!
+//! [`ConfigWatcher`] re-runs the layered [`ConfigLoader`] when the config file
+//! changes, diffs the result against the running configuration and pushes the
+//! changed values to every registered [`ReloadTarget`]. Only keys listed in
+//! [`LIVE_RELOAD_KEYS`](crate::config::LIVE_RELOAD_KEYS) can change at
+//! runtime; a reload touching any other key is rejected as a whole so the
+//! running configuration is never a mix of old and new values. Every target
+//! validates the new values before any target applies them.
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::config::{Config, ConfigChange, ConfigError, ConfigLoader};
+use crate::sniffer::analytics::PredictiveAnalytics;
+
+/// Default interval between config file modification checks
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A running subsystem that accepts configuration changes
+#[async_trait]
+pub trait ReloadTarget: Send + Sync {
+    /// Name used in logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Check that `config` can be applied, without changing anything.
+    /// Called on every target before any target's [`apply`](Self::apply).
+    async fn validate(&self, _config: &Config, _changes: &[ConfigChange]) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Apply `config` to the subsystem. `changes` lists the keys that differ
+    /// from the previous configuration; all of them are live-reloadable.
+    async fn apply(&self, config: &Config, changes: &[ConfigChange]) -> anyhow::Result<()>;
+}
+
+/// Whether any change falls under `prefix` (a section like `"sniffer."` or a full key)
+pub fn touches(changes: &[ConfigChange], prefix: &str) -> bool {
+    changes.iter().any(|c| c.key.starts_with(prefix))
+}
+
+/// Result of a successful reload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReloadOutcome {
+    /// The effective configuration did not change
+    Unchanged,
+    /// These changes were applied to every target
+    Applied(Vec<ConfigChange>),
+}
+
+#[derive(Debug, Error)]
+pub enum ReloadError {
+    #[error("failed to load configuration: {0}")]
+    Load(#[from] ConfigError),
+
+    #[error("reload rejected, these keys require a restart: {}", format_keys(.0))]
+    RestartRequired(Vec<ConfigChange>),
+
+    #[error("{target} rejected configuration: {error}")]
+    Rejected {
+        target: &'static str,
+        error: anyhow::Error,
+    },
+
+    #[error("{target} failed to apply configuration: {error}")]
+    Apply {
+        target: &'static str,
+        error: anyhow::Error,
+    },
+}
+
+fn format_keys(changes: &[ConfigChange]) -> String {
+    changes
+        .iter()
+        .map(|c| c.key.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Audit record of one applied configuration change
+#[derive(Debug, Clone)]
+pub struct ConfigAuditEvent {
+    pub timestamp: SystemTime,
+    pub key: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Watches the config file and propagates live-reloadable changes
+pub struct ConfigWatcher {
+    loader: ConfigLoader,
+    path: Option<PathBuf>,
+    current: RwLock<Config>,
+    targets: Vec<Arc<dyn ReloadTarget>>,
+    audit_log: Arc<RwLock<Vec<ConfigAuditEvent>>>,
+    poll_interval: Duration,
+}
+
+impl ConfigWatcher {
+    /// Create a watcher for the configuration `loader` produced at startup.
+    /// `path` is the file whose modification time triggers reloads.
+    pub fn new(loader: ConfigLoader, path: Option<PathBuf>, current: Config) -> Self {
+        Self {
+            loader,
+            path,
+            current: RwLock::new(current),
+            targets: Vec::new(),
+            audit_log: Arc::new(RwLock::new(Vec::new())),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Register a subsystem to receive live changes
+    pub fn with_target(mut self, target: Arc<dyn ReloadTarget>) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Set how often the config file is checked for modifications
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// The running configuration
+    pub async fn current(&self) -> Config {
+        self.current.read().await.clone()
+    }
+
+    /// Reload the configuration and apply live changes.
+    ///
+    /// The running configuration only advances once every target has
+    /// accepted the new values; nothing is applied unless every target
+    /// validates them first.
+    pub async fn reload(&self) -> Result<ReloadOutcome, ReloadError> {
+        let new = self.loader.load()?;
+        let mut current = self.current.write().await;
+
+        let changes = current.diff(&new);
+        if changes.is_empty() {
+            return Ok(ReloadOutcome::Unchanged);
+        }
+
+        let restart: Vec<ConfigChange> = changes.iter().filter(|c| !c.live).cloned().collect();
+        if !restart.is_empty() {
+            return Err(ReloadError::RestartRequired(restart));
+        }
+
+        for target in &self.targets {
+            target
+                .validate(&new, &changes)
+                .await
+                .map_err(|error| ReloadError::Rejected {
+                    target: target.name(),
+                    error,
+                })?;
+        }
+
+        for target in &self.targets {
+            target
+                .apply(&new, &changes)
+                .await
+                .map_err(|error| ReloadError::Apply {
+                    target: target.name(),
+                    error,
+                })?;
+        }
+
+        *current = new;
+        drop(current);
+
+        let mut audit_log = self.audit_log.write().await;
+        for change in &changes {
+            info!(
+                target: "audit",
+                key = %change.key,
+                old = %change.old,
+                new = %change.new,
+                "Configuration change applied"
+            );
+            audit_log.push(ConfigAuditEvent {
+                timestamp: SystemTime::now(),
+                key: change.key.clone(),
+                old: change.old.clone(),
+                new: change.new.clone(),
+            });
+        }
+
+        Ok(ReloadOutcome::Applied(changes))
+    }
+
+    /// Get applied configuration changes
+    pub async fn get_audit_log(&self) -> Vec<ConfigAuditEvent> {
+        self.audit_log.read().await.clone()
+    }
+
+    /// Poll the config file and reload whenever its modification time changes
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let Some(path) = self.path.clone() else {
+                warn!("No config file to watch, hot reload disabled");
+                return;
+            };
+            let modified = |path: &PathBuf| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+            let mut last_modified = modified(&path);
+            let mut interval = tokio::time::interval(self.poll_interval);
+            info!("Watching {} for configuration changes", path.display());
+
+            loop {
+                interval.tick().await;
+
+                let now = modified(&path);
+                if now.is_none() || now == last_modified {
+                    continue;
+                }
+                last_modified = now;
+
+                match self.reload().await {
+                    Ok(ReloadOutcome::Unchanged) => {
+                        info!("Config file changed, effective configuration unchanged")
+                    }
+                    Ok(ReloadOutcome::Applied(changes)) => {
+                        info!("Applied {} configuration change(s)", changes.len())
+                    }
+                    Err(e @ (ReloadError::RestartRequired(_) | ReloadError::Rejected { .. })) => {
+                        warn!("{}", e)
+                    }
+                    Err(e) => error!("Configuration reload failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl ReloadTarget for PredictiveAnalytics {
+    fn name(&self) -> &'static str {
+        "sniffer_analytics"
+    }
+
+    async fn apply(&self, config: &Config, changes: &[ConfigChange]) -> anyhow::Result<()> {
+        let sniffer = &config.sniffer;
+        if touches(changes, "sniffer.ema_alpha_") {
+            self.set_alphas(sniffer.ema_alpha_short, sniffer.ema_alpha_long);
+        }
+        if touches(changes, "sniffer.initial_threshold") {
+            self.set_threshold(sniffer.initial_threshold);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingTarget {
+        seen: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ReloadTarget for RecordingTarget {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn apply(&self, _config: &Config, changes: &[ConfigChange]) -> anyhow::Result<()> {
+            let mut seen = self.seen.lock().unwrap();
+            seen.extend(changes.iter().map(|c| c.key.clone()));
+            Ok(())
+        }
+    }
+
+    struct RejectingTarget;
+
+    #[async_trait]
+    impl ReloadTarget for RejectingTarget {
+        fn name(&self) -> &'static str {
+            "rejecting"
+        }
+
+        async fn validate(
+            &self,
+            _config: &Config,
+            _changes: &[ConfigChange],
+        ) -> anyhow::Result<()> {
+            anyhow::bail!("not today")
+        }
+
+        async fn apply(&self, _config: &Config, _changes: &[ConfigChange]) -> anyhow::Result<()> {
+            panic!("apply after a failed validation");
+        }
+    }
+
+    fn watcher(toml: &str) -> ConfigWatcher {
+        let loader = ConfigLoader::new().with_toml("test", toml);
+        ConfigWatcher::new(loader, None, Config::default())
+    }
+
+    #[tokio::test]
+    async fn test_live_changes_are_applied_and_audited() {
+        let target = Arc::new(RecordingTarget::default());
+        let analytics = Arc::new(PredictiveAnalytics::new(0.2, 0.05, 1.5));
+        let watcher = watcher(
+            "[trading]\nslippage_bps = 250\n[sniffer]\nema_alpha_short = 0.3\ninitial_threshold = 2.0\n",
+        )
+        .with_target(target.clone())
+        .with_target(analytics.clone());
+
+        let outcome = watcher.reload().await.unwrap();
+        assert!(matches!(outcome, ReloadOutcome::Applied(ref c) if c.len() == 3));
+        assert_eq!(target.seen.lock().unwrap().len(), 3);
+        assert_eq!(watcher.current().await.trading.slippage_bps, 250);
+        assert_eq!(analytics.get_alphas().0, 0.3);
+        assert_eq!(analytics.get_threshold(), 2.0);
+        assert_eq!(watcher.get_audit_log().await.len(), 3);
+
+        assert_eq!(watcher.reload().await.unwrap(), ReloadOutcome::Unchanged);
+    }
+
+    #[tokio::test]
+    async fn test_restart_required_change_rejects_whole_reload() {
+        let target = Arc::new(RecordingTarget::default());
+        let watcher = watcher("[trading]\nslippage_bps = 250\n[nonce]\npool_size = 32\n")
+            .with_target(target.clone());
+
+        let err = watcher.reload().await.unwrap_err();
+        match &err {
+            ReloadError::RestartRequired(changes) => {
+                assert_eq!(changes.len(), 1);
+                assert_eq!(changes[0].key, "nonce.pool_size");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(err.to_string().contains("nonce.pool_size"));
+        assert!(target.seen.lock().unwrap().is_empty());
+        assert_eq!(
+            watcher.current().await.trading.slippage_bps,
+            Config::default().trading.slippage_bps
+        );
+        assert!(watcher.get_audit_log().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_validation_applies_nothing() {
+        let target = Arc::new(RecordingTarget::default());
+        let watcher = watcher("[trading]\nslippage_bps = 250\n")
+            .with_target(target.clone())
+            .with_target(Arc::new(RejectingTarget));
+
+        let err = watcher.reload().await.unwrap_err();
+        assert!(matches!(
+            err,
+            ReloadError::Rejected {
+                target: "rejecting",
+                ..
+            }
+        ));
+        assert!(target.seen.lock().unwrap().is_empty());
+        assert_eq!(
+            watcher.current().await.trading.slippage_bps,
+            Config::default().trading.slippage_bps
+        );
+        assert!(watcher.get_audit_log().await.is_empty());
+    }
+}
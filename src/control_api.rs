@@ -0,0 +1,490 @@
+//! Headless control API
+//!
+//! HTTP+JSON server exposing every [`GuiCommand`] plus read-only views of
+//! positions, strategies, the nonce pool and RPC health, for hosts that run
+//! without the egui window. Each request carries `Authorization: Bearer
+//! <token>`; tokens are configured under `[control]` and grant either
+//! [`ControlRole::ReadOnly`] or [`ControlRole::Trader`]. `botctl` is the
+//! matching client.
+//!
+//! Routes:
+//! - `GET /v1/status`, `/v1/positions`, `/v1/strategies`, `/v1/nonce`, `/v1/rpc`
+//! - `POST /v1/commands` with a [`ControlCommand`] body (trader only)
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{error, info, warn};
+
+use crate::components::control_protocol::{
+    ControlCommand, ControlRole, ErrorResponse, NonceStatsView, PositionView, RpcHealthView,
+    StatusView, StrategyView,
+};
+use crate::components::gui_bridge::GuiCommand;
+use crate::config::ControlConfig;
+
+/// Maximum size of the request line plus headers
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+
+/// Time allowed for a client to send its whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The running bot as seen by the control API
+#[async_trait]
+pub trait ControlBackend: Send + Sync {
+    /// Pause state, trading mode and position count
+    async fn status(&self) -> StatusView;
+
+    /// Open positions
+    async fn positions(&self) -> Vec<PositionView>;
+
+    /// Per-token and default TP/SL strategies
+    async fn strategies(&self) -> Vec<StrategyView>;
+
+    /// Nonce pool counters, `None` when no nonce manager is running
+    async fn nonce_stats(&self) -> Option<NonceStatsView>;
+
+    /// RPC pool health, `None` when no RPC pool is running
+    async fn rpc_health(&self) -> Option<RpcHealthView>;
+
+    /// Execute a runtime command
+    async fn execute(&self, command: GuiCommand) -> Result<()>;
+}
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub authorization: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// An HTTP response with a JSON body
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status, body },
+            Err(e) => Self::error(500, format!("failed to encode response: {}", e)),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        let body = serde_json::to_string(&ErrorResponse {
+            error: message.into(),
+        })
+        .unwrap_or_default();
+        Self { status, body }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            500 => "Internal Server Error",
+            _ => "Error",
+        }
+    }
+
+    /// Serialize as an HTTP/1.1 response that closes the connection
+    pub fn to_http(&self) -> Vec<u8> {
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+struct Credential {
+    name: String,
+    secret: String,
+    role: ControlRole,
+}
+
+/// Control API server
+pub struct ControlServer {
+    bind_addr: String,
+    max_body_bytes: usize,
+    credentials: Vec<Credential>,
+    backend: Arc<dyn ControlBackend>,
+}
+
+impl ControlServer {
+    /// Build from `[control]`, resolving tokens held in environment variables
+    pub fn from_config(config: &ControlConfig, backend: Arc<dyn ControlBackend>) -> Result<Self> {
+        let credentials = config
+            .tokens
+            .iter()
+            .map(|token| {
+                let secret = token
+                    .secret()
+                    .ok_or_else(|| anyhow!("control token '{}' is not set", token.name))?;
+                Ok(Credential {
+                    name: token.name.clone(),
+                    secret,
+                    role: token.role,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            bind_addr: config.bind_addr.clone(),
+            max_body_bytes: config.max_body_bytes,
+            credentials,
+            backend,
+        })
+    }
+
+    /// Bind `control.bind_addr` and serve until the listener fails
+    pub async fn serve(self: Arc<Self>) -> Result<()> {
+        let listener = TcpListener::bind(&self.bind_addr).await?;
+        info!("Control API listening on {}", self.bind_addr);
+        self.serve_listener(listener).await
+    }
+
+    /// Serve connections from an already bound listener
+    pub async fn serve_listener(self: Arc<Self>, listener: TcpListener) -> Result<()> {
+        loop {
+            match listener.accept().await {
+                Ok((socket, _addr)) => {
+                    let server = Arc::clone(&self);
+                    tokio::spawn(async move { server.handle_connection(socket).await });
+                }
+                Err(e) => {
+                    error!("Failed to accept control connection: {}", e);
+                }
+            }
+        }
+    }
+
+    async fn handle_connection(&self, mut socket: TcpStream) {
+        let response = match tokio::time::timeout(
+            READ_TIMEOUT,
+            read_request(&mut socket, self.max_body_bytes),
+        )
+        .await
+        {
+            Ok(Ok(request)) => self.handle(&request).await,
+            Ok(Err(response)) => response,
+            Err(_) => Response::error(400, "request timed out"),
+        };
+        if let Err(e) = socket.write_all(&response.to_http()).await {
+            warn!("Failed to write control response: {}", e);
+        }
+        let _ = socket.shutdown().await;
+    }
+
+    fn authenticate(&self, request: &Request) -> Option<&Credential> {
+        let token = request
+            .authorization
+            .as_deref()?
+            .strip_prefix("Bearer ")?
+            .trim();
+        self.credentials
+            .iter()
+            .find(|c| constant_time_eq(c.secret.as_bytes(), token.as_bytes()))
+    }
+
+    /// Route an authenticated request
+    pub async fn handle(&self, request: &Request) -> Response {
+        let Some(credential) = self.authenticate(request) else {
+            return Response::error(401, "missing or invalid bearer token");
+        };
+
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/v1/status") => Response::json(200, &self.backend.status().await),
+            ("GET", "/v1/positions") => Response::json(200, &self.backend.positions().await),
+            ("GET", "/v1/strategies") => Response::json(200, &self.backend.strategies().await),
+            ("GET", "/v1/nonce") => match self.backend.nonce_stats().await {
+                Some(stats) => Response::json(200, &stats),
+                None => Response::error(404, "nonce manager is not running"),
+            },
+            ("GET", "/v1/rpc") => match self.backend.rpc_health().await {
+                Some(health) => Response::json(200, &health),
+                None => Response::error(404, "RPC pool is not running"),
+            },
+            ("POST", "/v1/commands") => self.handle_command(credential, &request.body).await,
+            (
+                _,
+                "/v1/status" | "/v1/positions" | "/v1/strategies" | "/v1/nonce" | "/v1/rpc"
+                | "/v1/commands",
+            ) => Response::error(405, "method not allowed"),
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    async fn handle_command(&self, credential: &Credential, body: &[u8]) -> Response {
+        if credential.role < ControlRole::Trader {
+            return Response::error(403, "token is read-only");
+        }
+        let command: ControlCommand = match serde_json::from_slice(body) {
+            Ok(command) => command,
+            Err(e) => return Response::error(400, format!("invalid command: {}", e)),
+        };
+        let command = match command.into_gui_command() {
+            Ok(command) => command,
+            Err(e) => return Response::error(400, e),
+        };
+
+        info!(
+            target: "audit",
+            token = %credential.name,
+            command = ?command,
+            "Control command received"
+        );
+        match self.backend.execute(command).await {
+            Ok(()) => Response::json(200, &serde_json::json!({ "ok": true })),
+            Err(e) => Response::error(500, e.to_string()),
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Read one HTTP/1.1 request; errors are returned as ready-to-send responses
+async fn read_request<S>(stream: &mut S, max_body_bytes: usize) -> Result<Request, Response>
+where
+    S: AsyncReadExt + Unpin,
+{
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEADER_BYTES {
+            return Err(Response::error(413, "headers too large"));
+        }
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| Response::error(400, e.to_string()))?;
+        if n == 0 {
+            return Err(Response::error(400, "incomplete request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = std::str::from_utf8(&buf[..header_end])
+        .map_err(|_| Response::error(400, "headers are not UTF-8"))?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.split('?').next().unwrap_or(path).to_string(),
+        ..Request::default()
+    };
+    let mut content_length = 0usize;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| Response::error(400, "invalid Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            request.authorization = Some(value.to_string());
+        }
+    }
+    if content_length > max_body_bytes {
+        return Err(Response::error(413, "request body too large"));
+    }
+
+    let mut body = buf.split_off(header_end + 4);
+    while body.len() < content_length {
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| Response::error(400, e.to_string()))?;
+        if n == 0 {
+            return Err(Response::error(400, "incomplete body"));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+    request.body = body;
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ControlToken;
+    use crate::types::TradingMode;
+    use std::sync::Mutex;
+
+    const READ_TOKEN: &str = "read-only-token-0123";
+    const TRADER_TOKEN: &str = "trader-token-456789";
+
+    #[derive(Default)]
+    struct FakeBackend {
+        executed: Mutex<Vec<GuiCommand>>,
+    }
+
+    #[async_trait]
+    impl ControlBackend for FakeBackend {
+        async fn status(&self) -> StatusView {
+            StatusView {
+                paused: false,
+                trading_mode: TradingMode::Auto,
+                open_positions: 0,
+            }
+        }
+
+        async fn positions(&self) -> Vec<PositionView> {
+            Vec::new()
+        }
+
+        async fn strategies(&self) -> Vec<StrategyView> {
+            Vec::new()
+        }
+
+        async fn nonce_stats(&self) -> Option<NonceStatsView> {
+            None
+        }
+
+        async fn rpc_health(&self) -> Option<RpcHealthView> {
+            None
+        }
+
+        async fn execute(&self, command: GuiCommand) -> Result<()> {
+            self.executed.lock().unwrap().push(command);
+            Ok(())
+        }
+    }
+
+    fn server(backend: Arc<FakeBackend>) -> ControlServer {
+        let token = |name: &str, role, token: &str| ControlToken {
+            name: name.to_string(),
+            role,
+            token: Some(token.to_string()),
+            token_env: None,
+        };
+        let config = ControlConfig {
+            enabled: true,
+            tokens: vec![
+                token("dashboard", ControlRole::ReadOnly, READ_TOKEN),
+                token("ops", ControlRole::Trader, TRADER_TOKEN),
+            ],
+            ..ControlConfig::default()
+        };
+        ControlServer::from_config(&config, backend).unwrap()
+    }
+
+    fn request(method: &str, path: &str, token: Option<&str>, body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            authorization: token.map(|t| format!("Bearer {}", t)),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_and_role_split() {
+        let backend = Arc::new(FakeBackend::default());
+        let server = server(backend.clone());
+        let pause = r#"{"command":"set_paused","paused":true}"#;
+
+        let resp = server.handle(&request("GET", "/v1/status", None, "")).await;
+        assert_eq!(resp.status, 401);
+        let resp = server
+            .handle(&request(
+                "GET",
+                "/v1/status",
+                Some("wrong-token-000000"),
+                "",
+            ))
+            .await;
+        assert_eq!(resp.status, 401);
+
+        let resp = server
+            .handle(&request("GET", "/v1/status", Some(READ_TOKEN), ""))
+            .await;
+        assert_eq!(resp.status, 200);
+        assert!(resp.body.contains("\"Auto\""));
+
+        let resp = server
+            .handle(&request("POST", "/v1/commands", Some(READ_TOKEN), pause))
+            .await;
+        assert_eq!(resp.status, 403);
+        assert!(backend.executed.lock().unwrap().is_empty());
+
+        let resp = server
+            .handle(&request("POST", "/v1/commands", Some(TRADER_TOKEN), pause))
+            .await;
+        assert_eq!(resp.status, 200);
+        assert!(matches!(
+            backend.executed.lock().unwrap()[..],
+            [GuiCommand::SetPaused(true)]
+        ));
+
+        let resp = server
+            .handle(&request("POST", "/v1/commands", Some(TRADER_TOKEN), "{}"))
+            .await;
+        assert_eq!(resp.status, 400);
+        let resp = server
+            .handle(&request("GET", "/v1/nonce", Some(READ_TOKEN), ""))
+            .await;
+        assert_eq!(resp.status, 404);
+        let resp = server
+            .handle(&request("DELETE", "/v1/positions", Some(READ_TOKEN), ""))
+            .await;
+        assert_eq!(resp.status, 405);
+    }
+
+    #[tokio::test]
+    async fn test_serves_requests_over_tcp() {
+        let backend = Arc::new(FakeBackend::default());
+        let server = Arc::new(server(backend.clone()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(server.serve_listener(listener));
+
+        let body = r#"{"command":"emergency_stop"}"#;
+        let raw = format!(
+            "POST /v1/commands HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+            TRADER_TOKEN,
+            body.len(),
+            body
+        );
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(raw.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.ends_with(r#"{"ok":true}"#));
+        assert!(matches!(
+            backend.executed.lock().unwrap()[..],
+            [GuiCommand::EmergencyStop]
+        ));
+    }
+}
@@ -0,0 +1,830 @@
+//! Copy trading
+//!
+//! [`CopyTrader`] follows a set of leader wallets and mirrors their pump.fun
+//! and Raydium trades. Leader transactions arrive as log notifications (a
+//! `logsSubscribe` mentions filter per leader) and are decoded into
+//! [`LeaderTrade`]s:
+//!
+//! - pump.fun trades come straight from the `TradeEvent`s found by
+//!   [`parse_logs`](crate::sniffer::events::parse_logs);
+//! - Raydium AMM swaps are found by
+//!   [`parse_ray_logs`](crate::sniffer::raydium::parse_ray_logs), which only
+//!   yields amounts, so the traded mint and side are resolved from the
+//!   leader's token balance changes ([`TokenChangeSource`]).
+//!
+//! Each leader trade becomes a [`MirrorAction`]:
+//!
+//! - a leader buy is sized with the leader's
+//!   [`SizingRule`](crate::config::SizingRule) and queued on the
+//!   buy engine's candidate channel, so it goes through the same filters,
+//!   screening and limits as sniffed candidates;
+//! - a leader sell closes the same share of our mirrored position when the
+//!   leader has `exit_with_leader` set.
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{UiTransactionEncoding, UiTransactionTokenBalance};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+use crate::config::{CopyTradingConfig, LeaderConfig};
+use crate::metrics::metrics;
+use crate::sniffer::events::{parse_logs, PumpEvent, TradeEvent};
+use crate::sniffer::raydium::{parse_ray_logs, RaydiumSwap};
+use crate::types::{CandidateSender, PremintCandidate, PriorityLevel};
+
+/// How long a prepared mirror buy stays valid if its candidate never buys
+pub const MIRROR_ORDER_TTL: Duration = Duration::from_secs(30);
+
+/// Wrapped SOL mint, the quote side of mirrored Raydium swaps
+const WSOL_MINT: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+
+/// Size and origin of a pending mirrored buy
+#[derive(Debug, Clone)]
+pub struct MirrorOrder {
+    /// Leader whose buy is mirrored
+    pub leader: Pubkey,
+    /// Lamports to spend
+    pub amount_lamports: u64,
+    /// Leader transaction the order mirrors, carried by its candidate
+    pub signature: String,
+    /// When the order was prepared
+    pub created: Instant,
+}
+
+impl MirrorOrder {
+    /// Whether the order is still within [`MIRROR_ORDER_TTL`]
+    pub fn is_fresh(&self) -> bool {
+        self.created.elapsed() < MIRROR_ORDER_TTL
+    }
+
+    /// Whether `candidate` is the mirrored buy this order was prepared for
+    pub fn is_for(&self, candidate: &PremintCandidate) -> bool {
+        candidate.signature.as_deref() == Some(self.signature.as_str())
+    }
+}
+
+/// Where a leader traded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Venue {
+    PumpFun,
+    Raydium,
+}
+
+impl Venue {
+    /// `PremintCandidate::program` of mirrored buys
+    pub fn program(self) -> &'static str {
+        match self {
+            Venue::PumpFun => "pump.fun",
+            Venue::Raydium => "raydium",
+        }
+    }
+}
+
+/// A leader buy or sell, whichever venue it was made on
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderTrade {
+    pub venue: Venue,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    /// Lamports paid or received
+    pub sol_amount: u64,
+    /// Raw token amount bought or sold
+    pub token_amount: u64,
+    /// Price in SOL per token, if known
+    pub price_sol: Option<f64>,
+}
+
+impl From<&TradeEvent> for LeaderTrade {
+    fn from(trade: &TradeEvent) -> Self {
+        Self {
+            venue: Venue::PumpFun,
+            user: trade.user,
+            mint: trade.mint,
+            is_buy: trade.is_buy,
+            sol_amount: trade.sol_amount,
+            token_amount: trade.token_amount,
+            price_sol: trade.price_sol(),
+        }
+    }
+}
+
+impl LeaderTrade {
+    /// The trade `leader` made with `swap`, given the transaction's token
+    /// balance changes
+    ///
+    /// The swap must move exactly one mint besides wrapped SOL in the
+    /// leader's accounts: the mint bought when its balance grew, sold when
+    /// it shrank. Token-to-token and multi-hop swaps yield `None`.
+    pub fn from_raydium(
+        leader: Pubkey,
+        swap: &RaydiumSwap,
+        changes: &[TokenChange],
+    ) -> Option<Self> {
+        let mut traded = changes
+            .iter()
+            .filter(|c| c.owner == leader && c.mint != WSOL_MINT && c.change != 0);
+        let change = traded.next()?;
+        if traded.next().is_some() {
+            return None;
+        }
+
+        let is_buy = change.change > 0;
+        let (sol_amount, token_amount) = if is_buy {
+            (swap.amount_in, swap.amount_out)
+        } else {
+            (swap.amount_out, swap.amount_in)
+        };
+        let tokens = token_amount as f64 / 10f64.powi(change.decimals as i32);
+        Some(Self {
+            venue: Venue::Raydium,
+            user: leader,
+            mint: change.mint,
+            is_buy,
+            sol_amount,
+            token_amount,
+            price_sol: (tokens > 0.0).then(|| lamports_to_sol(sol_amount) / tokens),
+        })
+    }
+}
+
+/// Net change of one wallet's balance of one mint within a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenChange {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// Raw amount after the transaction minus before
+    pub change: i128,
+    pub decimals: u8,
+}
+
+/// Looks up the token balance changes of a transaction
+#[async_trait]
+pub trait TokenChangeSource: Send + Sync {
+    async fn token_changes(&self, signature: &str) -> anyhow::Result<Vec<TokenChange>>;
+}
+
+#[async_trait]
+impl TokenChangeSource for RpcClient {
+    async fn token_changes(&self, signature: &str) -> anyhow::Result<Vec<TokenChange>> {
+        let signature: Signature = signature.parse()?;
+        let tx = self
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        let meta = tx
+            .transaction
+            .meta
+            .ok_or_else(|| anyhow::anyhow!("transaction {} has no status meta", signature))?;
+        let pre: Option<Vec<_>> = meta.pre_token_balances.into();
+        let post: Option<Vec<_>> = meta.post_token_balances.into();
+        Ok(token_changes(
+            &pre.unwrap_or_default(),
+            &post.unwrap_or_default(),
+        ))
+    }
+}
+
+/// Per owner and mint difference between post and pre token balances
+fn token_changes(
+    pre: &[UiTransactionTokenBalance],
+    post: &[UiTransactionTokenBalance],
+) -> Vec<TokenChange> {
+    let mut changes: Vec<TokenChange> = Vec::new();
+    for (balances, sign) in [(pre, -1i128), (post, 1)] {
+        for balance in balances {
+            let owner: Option<&String> = balance.owner.as_ref().into();
+            let (Some(Ok(owner)), Ok(mint), Ok(amount)) = (
+                owner.map(|owner| owner.parse::<Pubkey>()),
+                balance.mint.parse::<Pubkey>(),
+                balance.ui_token_amount.amount.parse::<i128>(),
+            ) else {
+                continue;
+            };
+            match changes
+                .iter_mut()
+                .find(|c| c.owner == owner && c.mint == mint)
+            {
+                Some(change) => change.change += sign * amount,
+                None => changes.push(TokenChange {
+                    owner,
+                    mint,
+                    change: sign * amount,
+                    decimals: balance.ui_token_amount.decimals,
+                }),
+            }
+        }
+    }
+    changes.retain(|c| c.change != 0);
+    changes
+}
+
+/// The trading side of copy trading, implemented by the buy engine
+#[async_trait]
+pub trait MirrorExecutor: Send + Sync {
+    /// Size the next buy of `mint` and remember which leader it mirrors
+    fn prepare_buy(&self, mint: Pubkey, order: MirrorOrder);
+
+    /// Leader the open position in `mint` was mirrored from
+    fn mirrored_from(&self, mint: &Pubkey) -> Option<Pubkey>;
+
+    /// Sell `percent` (0.0-1.0) of the position in `mint`
+    async fn exit(&self, mint: Pubkey, percent: f64) -> anyhow::Result<()>;
+}
+
+/// What to do about one leader trade
+#[derive(Debug, Clone, PartialEq)]
+pub enum MirrorAction {
+    /// Buy `mint` on `venue` for `amount_lamports`
+    Buy {
+        leader: Pubkey,
+        venue: Venue,
+        mint: Pubkey,
+        amount_lamports: u64,
+        price_hint: Option<f64>,
+    },
+    /// Sell `percent` of the mirrored position in `mint`
+    Exit {
+        leader: Pubkey,
+        mint: Pubkey,
+        percent: f64,
+    },
+}
+
+impl MirrorAction {
+    /// Leader whose trade triggered the action
+    pub fn leader(&self) -> Pubkey {
+        match self {
+            Self::Buy { leader, .. } | Self::Exit { leader, .. } => *leader,
+        }
+    }
+}
+
+/// Mirrors the trades of configured leader wallets
+pub struct CopyTrader {
+    leaders: HashMap<Pubkey, LeaderConfig>,
+    executor: Arc<dyn MirrorExecutor>,
+    candidate_tx: CandidateSender,
+    /// Resolves Raydium swaps; without it they are not mirrored
+    token_changes: Option<Arc<dyn TokenChangeSource>>,
+    /// Tokens each leader bought per mint since startup, keyed by (leader, mint)
+    leader_holdings: DashMap<(Pubkey, Pubkey), u64>,
+}
+
+impl CopyTrader {
+    /// Create a copy trader for the leaders in `config`
+    pub fn new(
+        config: &CopyTradingConfig,
+        executor: Arc<dyn MirrorExecutor>,
+        candidate_tx: CandidateSender,
+    ) -> anyhow::Result<Self> {
+        let mut leaders = HashMap::new();
+        for leader in &config.leaders {
+            let address: Pubkey = leader
+                .address
+                .parse()
+                .map_err(|e| anyhow::anyhow!("invalid leader '{}': {}", leader.address, e))?;
+            leaders.insert(address, leader.clone());
+        }
+        Ok(Self {
+            leaders,
+            executor,
+            candidate_tx,
+            token_changes: None,
+            leader_holdings: DashMap::new(),
+        })
+    }
+
+    /// Mirror Raydium swaps, resolving them through `source`
+    pub fn with_token_changes(mut self, source: Arc<dyn TokenChangeSource>) -> Self {
+        self.token_changes = Some(source);
+        self
+    }
+
+    /// Wallets to subscribe to
+    pub fn leaders(&self) -> Vec<Pubkey> {
+        self.leaders.keys().copied().collect()
+    }
+
+    /// Decide how to mirror a trade; `None` for non-leaders and filtered trades
+    pub fn plan(&self, trade: &LeaderTrade) -> Option<MirrorAction> {
+        let leader = self.leaders.get(&trade.user)?;
+        let key = (trade.user, trade.mint);
+
+        if trade.is_buy {
+            *self.leader_holdings.entry(key).or_insert(0) += trade.token_amount;
+
+            if lamports_to_sol(trade.sol_amount) < leader.min_leader_sol {
+                debug!(leader = %trade.user, mint = %trade.mint, sol = trade.sol_amount, "Leader buy below min size, not mirrored");
+                return None;
+            }
+            // The tracker holds one position per mint; adding to it is not mirrored
+            if self.executor.mirrored_from(&trade.mint).is_some() {
+                return None;
+            }
+            let amount_lamports = leader.sizing.size_lamports(trade.sol_amount);
+            if amount_lamports == 0 {
+                return None;
+            }
+            return Some(MirrorAction::Buy {
+                leader: trade.user,
+                venue: trade.venue,
+                mint: trade.mint,
+                amount_lamports,
+                price_hint: trade.price_sol,
+            });
+        }
+
+        // Sell: the share of the leader's observed holding that was sold.
+        // Without an observed holding (bought before we started) exit fully.
+        let held = self.leader_holdings.get(&key).map(|h| *h);
+        match held {
+            Some(held) if held > trade.token_amount => {
+                self.leader_holdings.insert(key, held - trade.token_amount);
+            }
+            Some(_) => {
+                self.leader_holdings.remove(&key);
+            }
+            None => {}
+        }
+
+        if !leader.exit_with_leader || self.executor.mirrored_from(&trade.mint) != Some(trade.user)
+        {
+            return None;
+        }
+        let percent = match held {
+            Some(held) if held > 0 => (trade.token_amount as f64 / held as f64).min(1.0),
+            _ => 1.0,
+        };
+        (percent > 0.0).then_some(MirrorAction::Exit {
+            leader: trade.user,
+            mint: trade.mint,
+            percent,
+        })
+    }
+
+    /// Mirror the leader trades found in one transaction's logs.
+    ///
+    /// Actions run on their own task after the leader's `delay_ms`. A
+    /// Raydium swap is resolved on its own task first. Returns the number of
+    /// pump.fun actions scheduled plus the number of swaps being resolved.
+    pub fn handle_logs<S: AsRef<str>>(self: &Arc<Self>, signature: &str, logs: &[S]) -> usize {
+        let mut scheduled = 0;
+        for event in parse_logs(logs) {
+            let PumpEvent::Trade(trade) = event else {
+                continue;
+            };
+            if let Some(action) = self.plan(&LeaderTrade::from(&trade)) {
+                self.schedule(action, signature);
+                scheduled += 1;
+            }
+        }
+
+        match (parse_ray_logs(logs).as_slice(), &self.token_changes) {
+            ([], _) => {}
+            ([swap], Some(source)) => {
+                let trader = Arc::clone(self);
+                let source = Arc::clone(source);
+                let swap = *swap;
+                let signature = signature.to_string();
+                tokio::spawn(async move {
+                    match source.token_changes(&signature).await {
+                        Ok(changes) => trader.mirror_raydium(&swap, &changes, &signature),
+                        Err(e) => {
+                            warn!(%signature, error = %e, "Raydium swap not resolved, not mirrored")
+                        }
+                    }
+                });
+                scheduled += 1;
+            }
+            ([_], None) => {
+                debug!(%signature, "No token change source, Raydium swap not mirrored");
+            }
+            (swaps, _) => {
+                debug!(%signature, swaps = swaps.len(), "Multi-hop Raydium route, not mirrored");
+            }
+        }
+        scheduled
+    }
+
+    /// Mirror the leader side of one resolved Raydium swap
+    fn mirror_raydium(
+        self: &Arc<Self>,
+        swap: &RaydiumSwap,
+        changes: &[TokenChange],
+        signature: &str,
+    ) {
+        for leader in self.leaders.keys() {
+            let Some(trade) = LeaderTrade::from_raydium(*leader, swap, changes) else {
+                continue;
+            };
+            if let Some(action) = self.plan(&trade) {
+                self.schedule(action, signature);
+            }
+        }
+    }
+
+    /// Run `action` on its own task after the leader's `delay_ms`
+    fn schedule(self: &Arc<Self>, action: MirrorAction, signature: &str) {
+        let delay = self
+            .leaders
+            .get(&action.leader())
+            .map_or(Duration::ZERO, |l| Duration::from_millis(l.delay_ms));
+        let trader = Arc::clone(self);
+        let signature = signature.to_string();
+        tokio::spawn(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            trader.execute(action, signature).await;
+        });
+    }
+
+    /// Subscribe to every leader's transaction logs and mirror them
+    #[cfg(feature = "ws-stream")]
+    pub async fn follow(self: Arc<Self>, websocket_url: &str) -> anyhow::Result<()> {
+        use bot::streaming::websocket_stream::WebSocketStream;
+
+        let stream = WebSocketStream::new(websocket_url.to_string());
+        let client = stream
+            .connect()
+            .await
+            .map_err(|e| anyhow::anyhow!("copy trading websocket: {}", e))?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for leader in self.leaders() {
+            stream
+                .subscribe_logs(client.clone(), &leader, tx.clone())
+                .await
+                .map_err(|e| anyhow::anyhow!("subscribe to leader {}: {}", leader, e))?;
+        }
+        info!("Copy trading {} leader wallet(s)", self.leaders.len());
+
+        tokio::spawn(async move {
+            while let Some(update) = rx.recv().await {
+                if update.err.is_none() {
+                    self.handle_logs(&update.signature, &update.logs);
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn execute(&self, action: MirrorAction, signature: String) {
+        match action {
+            MirrorAction::Buy {
+                leader,
+                venue,
+                mint,
+                amount_lamports,
+                price_hint,
+            } => {
+                self.executor.prepare_buy(
+                    mint,
+                    MirrorOrder {
+                        leader,
+                        amount_lamports,
+                        signature: signature.clone(),
+                        created: Instant::now(),
+                    },
+                );
+                let candidate = PremintCandidate {
+                    mint,
+                    program: venue.program().to_string(),
+                    accounts: vec![leader],
+                    priority: PriorityLevel::High,
+                    timestamp: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs(),
+                    price_hint,
+                    signature: Some(signature),
+                    screening: None,
+                    trace: None,
+                };
+                if self.candidate_tx.send(candidate).is_err() {
+                    warn!(%mint, "Candidate channel closed, mirrored buy dropped");
+                    return;
+                }
+                metrics().increment_counter("copy_trade_buys_total");
+                info!(leader = %self.label(&leader), venue = venue.program(), %mint, amount_lamports, "Mirroring leader buy");
+            }
+            MirrorAction::Exit {
+                leader,
+                mint,
+                percent,
+            } => {
+                info!(leader = %self.label(&leader), %mint, percent, "Mirroring leader sell");
+                match self.executor.exit(mint, percent).await {
+                    Ok(()) => metrics().increment_counter("copy_trade_exits_total"),
+                    Err(e) => warn!(%mint, error = %e, "Mirrored exit failed"),
+                }
+            }
+        }
+    }
+
+    fn label(&self, leader: &Pubkey) -> String {
+        self.leaders
+            .get(leader)
+            .and_then(|l| l.label.clone())
+            .unwrap_or_else(|| leader.to_string())
+    }
+}
+
+fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / 1_000_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SizingRule;
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        orders: Mutex<Vec<(Pubkey, MirrorOrder)>>,
+        mirrored: DashMap<Pubkey, Pubkey>,
+        exits: Mutex<Vec<(Pubkey, f64)>>,
+    }
+
+    #[async_trait]
+    impl MirrorExecutor for RecordingExecutor {
+        fn prepare_buy(&self, mint: Pubkey, order: MirrorOrder) {
+            self.orders.lock().unwrap().push((mint, order));
+        }
+
+        fn mirrored_from(&self, mint: &Pubkey) -> Option<Pubkey> {
+            self.mirrored.get(mint).map(|l| *l)
+        }
+
+        async fn exit(&self, mint: Pubkey, percent: f64) -> anyhow::Result<()> {
+            self.exits.lock().unwrap().push((mint, percent));
+            Ok(())
+        }
+    }
+
+    fn leader_config(address: Pubkey, sizing: SizingRule) -> LeaderConfig {
+        LeaderConfig {
+            address: address.to_string(),
+            label: None,
+            sizing,
+            delay_ms: 0,
+            min_leader_sol: 0.1,
+            exit_with_leader: true,
+        }
+    }
+
+    fn trade(user: Pubkey, mint: Pubkey, is_buy: bool, sol: u64, tokens: u64) -> LeaderTrade {
+        LeaderTrade::from(&TradeEvent {
+            mint,
+            sol_amount: sol,
+            token_amount: tokens,
+            is_buy,
+            user,
+            timestamp: 0,
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_000_000_000_000_000,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            fee: 0,
+            creator_fee: 0,
+        })
+    }
+
+    struct FixedChanges(Vec<TokenChange>);
+
+    #[async_trait]
+    impl TokenChangeSource for FixedChanges {
+        async fn token_changes(&self, _signature: &str) -> anyhow::Result<Vec<TokenChange>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn change(owner: Pubkey, mint: Pubkey, change: i128) -> TokenChange {
+        TokenChange {
+            owner,
+            mint,
+            change,
+            decimals: 6,
+        }
+    }
+
+    fn trader(
+        sizing: SizingRule,
+    ) -> (
+        Pubkey,
+        Arc<RecordingExecutor>,
+        CopyTrader,
+        mpsc::UnboundedReceiver<PremintCandidate>,
+    ) {
+        let leader = Pubkey::new_unique();
+        let config = CopyTradingConfig {
+            enabled: true,
+            leaders: vec![leader_config(leader, sizing)],
+        };
+        let executor = Arc::new(RecordingExecutor::default());
+        let (tx, rx) = mpsc::unbounded_channel();
+        let trader = CopyTrader::new(&config, executor.clone(), tx).unwrap();
+        (leader, executor, trader, rx)
+    }
+
+    #[test]
+    fn test_sizing_rules() {
+        let leader_spend = 2_000_000_000; // 2 SOL
+        let fixed = SizingRule::Fixed { amount_sol: 0.05 };
+        assert_eq!(fixed.size_lamports(leader_spend), 50_000_000);
+
+        let proportional = SizingRule::Proportional { ratio: 0.1 };
+        assert_eq!(proportional.size_lamports(leader_spend), 200_000_000);
+
+        let capped = SizingRule::Capped {
+            ratio: 0.1,
+            max_sol: 0.15,
+        };
+        assert_eq!(capped.size_lamports(leader_spend), 150_000_000);
+        assert_eq!(capped.size_lamports(1_000_000_000), 100_000_000);
+    }
+
+    #[test]
+    fn test_plan_filters_and_sizes_leader_buys() {
+        let (leader, executor, trader, _rx) = trader(SizingRule::Proportional { ratio: 0.5 });
+        let mint = Pubkey::new_unique();
+
+        // Not a leader
+        assert_eq!(
+            trader.plan(&trade(Pubkey::new_unique(), mint, true, 1_000_000_000, 1)),
+            None
+        );
+        // Below min_leader_sol
+        assert_eq!(trader.plan(&trade(leader, mint, true, 50_000_000, 1)), None);
+
+        match trader.plan(&trade(leader, mint, true, 1_000_000_000, 1_000)) {
+            Some(MirrorAction::Buy {
+                amount_lamports, ..
+            }) => assert_eq!(amount_lamports, 500_000_000),
+            other => panic!("unexpected action: {:?}", other),
+        }
+
+        // Already mirrored: adding to the position is not mirrored
+        executor.mirrored.insert(mint, leader);
+        assert_eq!(
+            trader.plan(&trade(leader, mint, true, 1_000_000_000, 1_000)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_plan_exits_with_leader_proportionally() {
+        let (leader, executor, trader, _rx) = trader(SizingRule::Fixed { amount_sol: 0.1 });
+        let mint = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        trader.plan(&trade(leader, mint, true, 1_000_000_000, 1_000));
+        // Sells of positions we did not mirror from this leader are ignored
+        assert_eq!(trader.plan(&trade(leader, mint, false, 0, 250)), None);
+
+        executor.mirrored.insert(mint, leader);
+        assert_eq!(
+            trader.plan(&trade(leader, mint, false, 0, 375)),
+            Some(MirrorAction::Exit {
+                leader,
+                mint,
+                percent: 0.5
+            })
+        );
+        assert_eq!(
+            trader.plan(&trade(leader, mint, false, 0, 375)),
+            Some(MirrorAction::Exit {
+                leader,
+                mint,
+                percent: 1.0
+            })
+        );
+
+        // Holding never observed: full exit
+        executor.mirrored.insert(other, leader);
+        assert_eq!(
+            trader.plan(&trade(leader, other, false, 0, 10)),
+            Some(MirrorAction::Exit {
+                leader,
+                mint: other,
+                percent: 1.0
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_buy_prepares_order_and_queues_candidate() {
+        let (leader, executor, trader, mut rx) = trader(SizingRule::Fixed { amount_sol: 0.1 });
+        let mint = Pubkey::new_unique();
+        let action = trader
+            .plan(&trade(leader, mint, true, 1_000_000_000, 1_000))
+            .unwrap();
+
+        trader.execute(action, "sig".to_string()).await;
+
+        let orders = executor.orders.lock().unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].0, mint);
+        assert_eq!(orders[0].1.leader, leader);
+        assert_eq!(orders[0].1.amount_lamports, 100_000_000);
+        assert_eq!(orders[0].1.signature, "sig");
+
+        let candidate = rx.try_recv().unwrap();
+        assert_eq!(candidate.mint, mint);
+        assert_eq!(candidate.signature.as_deref(), Some("sig"));
+    }
+
+    #[test]
+    fn test_raydium_trade_from_leader_token_changes() {
+        let leader = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let swap = RaydiumSwap {
+            amount_in: 2_000_000_000,
+            amount_out: 4_000_000,
+        };
+
+        let bought = [
+            change(leader, WSOL_MINT, -2_000_000_000),
+            change(leader, mint, 4_000_000),
+            change(Pubkey::new_unique(), mint, -4_000_000),
+        ];
+        let trade = LeaderTrade::from_raydium(leader, &swap, &bought).unwrap();
+        assert_eq!(
+            (trade.venue, trade.mint, trade.is_buy),
+            (Venue::Raydium, mint, true)
+        );
+        assert_eq!(
+            (trade.sol_amount, trade.token_amount),
+            (2_000_000_000, 4_000_000)
+        );
+        assert_eq!(trade.price_sol, Some(0.5));
+
+        let sold = [change(leader, mint, -4_000_000)];
+        let trade = LeaderTrade::from_raydium(leader, &swap, &sold).unwrap();
+        assert!(!trade.is_buy);
+        assert_eq!(
+            (trade.sol_amount, trade.token_amount),
+            (4_000_000, 2_000_000_000)
+        );
+
+        // Token-to-token swaps and other wallets' swaps are not mirrored
+        let routed = [
+            change(leader, mint, 4_000_000),
+            change(leader, Pubkey::new_unique(), -1),
+        ];
+        assert_eq!(LeaderTrade::from_raydium(leader, &swap, &routed), None);
+        assert_eq!(
+            LeaderTrade::from_raydium(Pubkey::new_unique(), &swap, &bought),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_logs_mirrors_raydium_buy() {
+        use crate::sniffer::raydium::RAYDIUM_AMM_PROGRAM_ID;
+        use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+
+        let (leader, _executor, trader, mut rx) = trader(SizingRule::Fixed { amount_sol: 0.1 });
+        let mint = Pubkey::new_unique();
+        let trader = Arc::new(trader.with_token_changes(Arc::new(FixedChanges(vec![
+            change(leader, WSOL_MINT, -1_000_000_000),
+            change(leader, mint, 2_000_000),
+        ]))));
+
+        // SwapBaseIn: 1 SOL in, 2 tokens out
+        let mut ray_log = vec![3u8];
+        for field in [1_000_000_000u64, 0, 1, 0, 0, 0, 2_000_000] {
+            ray_log.extend_from_slice(&field.to_le_bytes());
+        }
+        let logs = [
+            format!("Program {} invoke [1]", RAYDIUM_AMM_PROGRAM_ID),
+            format!("Program log: ray_log: {}", BASE64_STANDARD.encode(ray_log)),
+            format!("Program {} success", RAYDIUM_AMM_PROGRAM_ID),
+        ];
+        assert_eq!(trader.handle_logs("sig", &logs), 1);
+
+        let candidate = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(candidate.mint, mint);
+        assert_eq!(candidate.program, "raydium");
+        assert_eq!(candidate.price_hint, Some(0.5));
+    }
+}
@@ -0,0 +1,1094 @@
+//! Per-candidate decision journal
+//!
+//! Append-only record of every candidate the buy engine sees: its features,
+//! each gate it passed or failed (with a reason code), build and simulation
+//! results, submission, landing and the realized PnL once its position
+//! closes. All entries for a candidate carry its trace id, so a journal row
+//! can be matched to the candidate's exported spans.
+//!
+//! Entries are JSON lines in daily segment files
+//! (`decisions-YYYY-MM-DD.jsonl`, UTC) appended by a background task; segments
+//! older than the retention window are deleted. [`query`] folds the entries of
+//! each candidate into a [`CandidateHistory`], which [`write_csv`] and
+//! [`write_jsonl`] export for analysis.
+
+use crate::types::{PremintCandidate, PriorityLevel};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Days, NaiveDate, SecondsFormat};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+const SEGMENT_PREFIX: &str = "decisions-";
+const SEGMENT_SUFFIX: &str = ".jsonl";
+
+/// Entries written per segment append
+const MAX_BATCH: usize = 1024;
+
+/// Journal location and retention
+#[derive(Debug, Clone)]
+pub struct JournalConfig {
+    /// Directory holding the segment files
+    pub dir: PathBuf,
+
+    /// Days of segments kept, including the current one
+    pub retention_days: u32,
+
+    /// Entries buffered before new ones are dropped
+    pub queue_capacity: usize,
+}
+
+impl JournalConfig {
+    pub fn new(dir: impl Into<PathBuf>, retention_days: u32) -> Self {
+        Self {
+            dir: dir.into(),
+            retention_days,
+            queue_capacity: 16_384,
+        }
+    }
+}
+
+/// Check a candidate must pass before it is bought
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Gate {
+    /// Engine is sniffing (not holding a position)
+    Mode,
+    /// Circuit breaker per-mint rate limit
+    MintRateLimit,
+    /// Circuit breaker per-program rate limit
+    ProgramRateLimit,
+    /// Security validation
+    Validator,
+    /// Validator per-mint rate limit
+    ValidatorRateLimit,
+    /// Program filter
+    Filter,
+    /// Token safety screening
+    Screening,
+    /// Kill switch and buy config
+    BuyEnabled,
+}
+
+impl Gate {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Gate::Mode => "mode",
+            Gate::MintRateLimit => "mint_rate_limit",
+            Gate::ProgramRateLimit => "program_rate_limit",
+            Gate::Validator => "validator",
+            Gate::ValidatorRateLimit => "validator_rate_limit",
+            Gate::Filter => "filter",
+            Gate::Screening => "screening",
+            Gate::BuyEnabled => "buy_enabled",
+        }
+    }
+
+    /// Reason code recorded when this gate rejects a candidate
+    pub fn rejection(self) -> ReasonCode {
+        match self {
+            Gate::Mode => ReasonCode::PositionHeld,
+            Gate::MintRateLimit => ReasonCode::MintRateLimited,
+            Gate::ProgramRateLimit => ReasonCode::ProgramRateLimited,
+            Gate::Validator => ReasonCode::SecurityRejected,
+            Gate::ValidatorRateLimit => ReasonCode::RateLimited,
+            Gate::Filter => ReasonCode::Filtered,
+            Gate::Screening => ReasonCode::ScreeningFailed,
+            Gate::BuyEnabled => ReasonCode::BuyDisabled,
+        }
+    }
+}
+
+/// Why a candidate did not end in a landed buy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    PositionHeld,
+    MintRateLimited,
+    ProgramRateLimited,
+    SecurityRejected,
+    RateLimited,
+    Filtered,
+    ScreeningFailed,
+    BuyDisabled,
+    BuildFailed,
+    SimulationBlocked,
+    SubmitFailed,
+    LandingFailed,
+    LandingExpired,
+}
+
+impl ReasonCode {
+    pub const ALL: [ReasonCode; 13] = [
+        ReasonCode::PositionHeld,
+        ReasonCode::MintRateLimited,
+        ReasonCode::ProgramRateLimited,
+        ReasonCode::SecurityRejected,
+        ReasonCode::RateLimited,
+        ReasonCode::Filtered,
+        ReasonCode::ScreeningFailed,
+        ReasonCode::BuyDisabled,
+        ReasonCode::BuildFailed,
+        ReasonCode::SimulationBlocked,
+        ReasonCode::SubmitFailed,
+        ReasonCode::LandingFailed,
+        ReasonCode::LandingExpired,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReasonCode::PositionHeld => "position_held",
+            ReasonCode::MintRateLimited => "mint_rate_limited",
+            ReasonCode::ProgramRateLimited => "program_rate_limited",
+            ReasonCode::SecurityRejected => "security_rejected",
+            ReasonCode::RateLimited => "rate_limited",
+            ReasonCode::Filtered => "filtered",
+            ReasonCode::ScreeningFailed => "screening_failed",
+            ReasonCode::BuyDisabled => "buy_disabled",
+            ReasonCode::BuildFailed => "build_failed",
+            ReasonCode::SimulationBlocked => "simulation_blocked",
+            ReasonCode::SubmitFailed => "submit_failed",
+            ReasonCode::LandingFailed => "landing_failed",
+            ReasonCode::LandingExpired => "landing_expired",
+        }
+    }
+}
+
+impl std::fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ReasonCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ReasonCode::ALL
+            .into_iter()
+            .find(|reason| reason.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown reason code: {}", s))
+    }
+}
+
+/// Candidate features as it reached the buy engine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateFeatures {
+    pub program: String,
+    pub priority: PriorityLevel,
+    /// Creation timestamp reported by the sniffer (Unix seconds)
+    pub created_at: u64,
+    pub price_hint: Option<f64>,
+    /// Number of associated accounts
+    pub accounts: usize,
+    /// Transaction that created the candidate
+    pub signature: Option<String>,
+}
+
+impl CandidateFeatures {
+    pub fn from_candidate(candidate: &PremintCandidate) -> Self {
+        Self {
+            program: candidate.program.clone(),
+            priority: candidate.priority,
+            created_at: candidate.timestamp,
+            price_hint: candidate.price_hint,
+            accounts: candidate.accounts.len(),
+            signature: candidate.signature.clone(),
+        }
+    }
+}
+
+/// Outcome of one gate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateRecord {
+    pub gate: Gate,
+    pub passed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<ReasonCode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Transaction build (including nonce lease)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildRecord {
+    pub latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Simulation verdict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationOutcome {
+    Success,
+    Advisory,
+    Critical,
+}
+
+/// Simulation result and whether the policy let the buy proceed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationRecord {
+    pub outcome: SimulationOutcome,
+    pub proceeded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Broadcast of the buy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionRecord {
+    /// `bundler` or `rpc`
+    pub route: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Final status of a broadcast buy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LandingStatus {
+    Confirmed,
+    Failed,
+    Expired,
+}
+
+/// Confirmation of a broadcast buy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LandingRecord {
+    pub status: LandingStatus,
+    /// Time from broadcast to the final status
+    pub latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One step of a candidate's path through the pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DecisionEvent {
+    Received(CandidateFeatures),
+    Gate(GateRecord),
+    Built(BuildRecord),
+    Simulated(SimulationRecord),
+    Submitted(SubmissionRecord),
+    Landed(LandingRecord),
+    /// Position opened by the candidate was fully sold
+    Closed {
+        pnl_lamports: i64,
+    },
+}
+
+/// One journal line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unix milliseconds
+    pub at_ms: u64,
+    /// Candidate trace id (hex)
+    pub trace_id: String,
+    pub mint: String,
+    #[serde(flatten)]
+    pub event: DecisionEvent,
+}
+
+/// Append side of the journal
+#[derive(Debug)]
+pub struct DecisionJournal {
+    tx: mpsc::Sender<JournalEntry>,
+    dropped: AtomicU64,
+    /// Trace id of the buy behind each open position
+    positions: DashMap<Pubkey, String>,
+}
+
+impl DecisionJournal {
+    /// Create the journal directory, prune expired segments and spawn the
+    /// writer task
+    ///
+    /// Must be called from within a Tokio runtime. The writer exits once the
+    /// journal is dropped and its queue is drained.
+    pub fn start(config: JournalConfig) -> Result<(Arc<Self>, tokio::task::JoinHandle<()>)> {
+        std::fs::create_dir_all(&config.dir)
+            .with_context(|| format!("Failed to create {}", config.dir.display()))?;
+        prune_segments(&config.dir, config.retention_days, segment_date(now_ms()))?;
+
+        let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+        info!(
+            dir = %config.dir.display(),
+            retention_days = config.retention_days,
+            "Journaling candidate decisions"
+        );
+        let journal = Arc::new(Self {
+            tx,
+            dropped: AtomicU64::new(0),
+            positions: DashMap::new(),
+        });
+        Ok((journal, tokio::spawn(run(config, rx))))
+    }
+
+    /// Entries dropped because the write queue was full
+    pub fn dropped_entries(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Record the realized PnL of a closed position opened by a journaled buy
+    pub fn record_position_closed(&self, mint: &Pubkey, pnl_lamports: i64) {
+        if let Some((_, trace_id)) = self.positions.remove(mint) {
+            self.append(JournalEntry {
+                at_ms: now_ms(),
+                trace_id,
+                mint: mint.to_string(),
+                event: DecisionEvent::Closed { pnl_lamports },
+            });
+        }
+    }
+
+    fn append(&self, entry: JournalEntry) {
+        if self.tx.try_send(entry).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Journal handle for one candidate; does nothing without a journal
+#[derive(Debug, Clone, Default)]
+pub struct CandidateJournal {
+    journal: Option<Arc<DecisionJournal>>,
+    mint: Pubkey,
+    trace_id: String,
+}
+
+impl CandidateJournal {
+    pub fn new(journal: Option<Arc<DecisionJournal>>, mint: Pubkey, trace_id: u128) -> Self {
+        let trace_id = match journal {
+            Some(_) => format!("{:032x}", trace_id),
+            None => String::new(),
+        };
+        Self {
+            journal,
+            mint,
+            trace_id,
+        }
+    }
+
+    /// Whether entries are being written
+    pub fn is_active(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    pub fn record(&self, event: DecisionEvent) {
+        if let Some(journal) = &self.journal {
+            journal.append(JournalEntry {
+                at_ms: now_ms(),
+                trace_id: self.trace_id.clone(),
+                mint: self.mint.to_string(),
+                event,
+            });
+        }
+    }
+
+    pub fn received(&self, candidate: &PremintCandidate) {
+        if self.is_active() {
+            self.record(DecisionEvent::Received(CandidateFeatures::from_candidate(
+                candidate,
+            )));
+        }
+    }
+
+    /// Record a gate outcome; returns `passed`
+    pub fn gate(&self, gate: Gate, passed: bool) -> bool {
+        self.record(DecisionEvent::Gate(GateRecord {
+            gate,
+            passed,
+            reason: (!passed).then_some(gate.rejection()),
+            detail: None,
+        }));
+        passed
+    }
+
+    pub fn gate_passed(&self, gate: Gate) {
+        self.gate(gate, true);
+    }
+
+    pub fn gate_failed(&self, gate: Gate, detail: impl Into<String>) {
+        if self.is_active() {
+            self.record(DecisionEvent::Gate(GateRecord {
+                gate,
+                passed: false,
+                reason: Some(gate.rejection()),
+                detail: Some(detail.into()),
+            }));
+        }
+    }
+
+    pub fn built(&self, latency_ms: u64, error: Option<String>) {
+        self.record(DecisionEvent::Built(BuildRecord { latency_ms, error }));
+    }
+
+    pub fn submitted(&self, route: &str, result: &Result<Signature>) {
+        if self.is_active() {
+            self.record(DecisionEvent::Submitted(SubmissionRecord {
+                route: route.to_string(),
+                signature: result.as_ref().ok().map(ToString::to_string),
+                error: result.as_ref().err().map(ToString::to_string),
+            }));
+        }
+    }
+
+    pub fn landed(&self, status: LandingStatus, latency_ms: u64, error: Option<String>) {
+        self.record(DecisionEvent::Landed(LandingRecord {
+            status,
+            latency_ms,
+            error,
+        }));
+    }
+
+    /// Attribute the mint's position, once closed, to this candidate
+    pub fn opened_position(&self) {
+        if let Some(journal) = &self.journal {
+            journal.positions.insert(self.mint, self.trace_id.clone());
+        }
+    }
+}
+
+async fn run(config: JournalConfig, mut rx: mpsc::Receiver<JournalEntry>) {
+    let mut segment = None;
+    let mut batch = Vec::with_capacity(MAX_BATCH);
+
+    while let Some(entry) = rx.recv().await {
+        batch.push(entry);
+        while batch.len() < MAX_BATCH {
+            match rx.try_recv() {
+                Ok(entry) => batch.push(entry),
+                Err(_) => break,
+            }
+        }
+        if let Err(e) = write_batch(&config, &mut segment, &batch).await {
+            warn!(error = %e, entries = batch.len(), "Decision journal write failed");
+        }
+        batch.clear();
+    }
+}
+
+/// Open segment file
+struct Segment {
+    date: NaiveDate,
+    file: tokio::fs::File,
+}
+
+async fn write_batch(
+    config: &JournalConfig,
+    segment: &mut Option<Segment>,
+    batch: &[JournalEntry],
+) -> Result<()> {
+    let mut lines = String::new();
+    let mut date = None;
+    for entry in batch {
+        let entry_date = segment_date(entry.at_ms);
+        if let Some(date) = date.filter(|date| *date != entry_date) {
+            append_lines(config, segment, date, &lines).await?;
+            lines.clear();
+        }
+        date = Some(entry_date);
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+    if let Some(date) = date {
+        append_lines(config, segment, date, &lines).await?;
+    }
+    Ok(())
+}
+
+async fn append_lines(
+    config: &JournalConfig,
+    segment: &mut Option<Segment>,
+    date: NaiveDate,
+    lines: &str,
+) -> Result<()> {
+    if !matches!(segment, Some(open) if open.date == date) {
+        let path = segment_path(&config.dir, date);
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        if segment.is_some() {
+            prune_segments(&config.dir, config.retention_days, date)?;
+        }
+        *segment = Some(Segment { date, file });
+    }
+    if let Some(segment) = segment {
+        segment.file.write_all(lines.as_bytes()).await?;
+        segment.file.flush().await?;
+    }
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// UTC day of a Unix millisecond timestamp
+fn segment_date(at_ms: u64) -> NaiveDate {
+    DateTime::from_timestamp_millis(at_ms as i64)
+        .unwrap_or_default()
+        .date_naive()
+}
+
+fn segment_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!(
+        "{}{}{}",
+        SEGMENT_PREFIX,
+        date.format("%Y-%m-%d"),
+        SEGMENT_SUFFIX
+    ))
+}
+
+/// Segment files in `dir`, oldest first
+fn segments(dir: &Path) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    let mut segments = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let date = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| {
+                name.strip_prefix(SEGMENT_PREFIX)?
+                    .strip_suffix(SEGMENT_SUFFIX)
+            })
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+        if let Some(date) = date {
+            segments.push((date, path));
+        }
+    }
+    segments.sort();
+    Ok(segments)
+}
+
+/// Delete segments that fall outside `retention_days` ending at `today`
+fn prune_segments(dir: &Path, retention_days: u32, today: NaiveDate) -> Result<usize> {
+    let keep_days = u64::from(retention_days.saturating_sub(1));
+    let Some(oldest_kept) = today.checked_sub_days(Days::new(keep_days)) else {
+        return Ok(0);
+    };
+    let mut removed = 0;
+    for (date, path) in segments(dir)? {
+        if date < oldest_kept {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        info!(removed, "Pruned expired decision journal segments");
+    }
+    Ok(removed)
+}
+
+/// Final stage a candidate reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Rejected by a gate
+    Rejected,
+    /// Passed the gates but failed to build, simulate, submit or land
+    Failed,
+    /// Still in flight, or no later entry was written
+    Pending,
+    /// Broadcast, landing not (yet) confirmed
+    Submitted,
+    /// Buy confirmed on chain
+    Landed,
+    /// Position fully sold
+    Closed,
+}
+
+impl Outcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Rejected => "rejected",
+            Outcome::Failed => "failed",
+            Outcome::Pending => "pending",
+            Outcome::Submitted => "submitted",
+            Outcome::Landed => "landed",
+            Outcome::Closed => "closed",
+        }
+    }
+}
+
+/// Everything journaled for one candidate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateHistory {
+    pub trace_id: String,
+    pub mint: String,
+    /// When the candidate reached the buy engine (Unix ms)
+    pub received_at_ms: u64,
+    pub features: CandidateFeatures,
+    pub gates: Vec<GateRecord>,
+    pub build: Option<BuildRecord>,
+    pub simulation: Option<SimulationRecord>,
+    pub submission: Option<SubmissionRecord>,
+    pub landing: Option<LandingRecord>,
+    pub pnl_lamports: Option<i64>,
+}
+
+impl CandidateHistory {
+    fn apply(&mut self, event: DecisionEvent) {
+        match event {
+            DecisionEvent::Received(_) => {}
+            DecisionEvent::Gate(gate) => self.gates.push(gate),
+            DecisionEvent::Built(build) => self.build = Some(build),
+            DecisionEvent::Simulated(simulation) => self.simulation = Some(simulation),
+            DecisionEvent::Submitted(submission) => self.submission = Some(submission),
+            DecisionEvent::Landed(landing) => self.landing = Some(landing),
+            DecisionEvent::Closed { pnl_lamports } => self.pnl_lamports = Some(pnl_lamports),
+        }
+    }
+
+    /// Why the candidate did not land, if it did not
+    pub fn reason(&self) -> Option<ReasonCode> {
+        if let Some(gate) = self.gates.iter().find(|gate| !gate.passed) {
+            return Some(gate.reason.unwrap_or(gate.gate.rejection()));
+        }
+        if self.build.as_ref().is_some_and(|b| b.error.is_some()) {
+            return Some(ReasonCode::BuildFailed);
+        }
+        if self.simulation.as_ref().is_some_and(|s| !s.proceeded) {
+            return Some(ReasonCode::SimulationBlocked);
+        }
+        if self.submission.as_ref().is_some_and(|s| s.error.is_some()) {
+            return Some(ReasonCode::SubmitFailed);
+        }
+        match self.landing.as_ref().map(|landing| landing.status) {
+            Some(LandingStatus::Failed) => Some(ReasonCode::LandingFailed),
+            Some(LandingStatus::Expired) => Some(ReasonCode::LandingExpired),
+            _ => None,
+        }
+    }
+
+    /// Detail of the failure behind [`reason`](Self::reason)
+    pub fn reason_detail(&self) -> Option<&str> {
+        if let Some(gate) = self.gates.iter().find(|gate| !gate.passed) {
+            return gate.detail.as_deref();
+        }
+        [
+            self.build.as_ref().and_then(|b| b.error.as_deref()),
+            self.simulation
+                .as_ref()
+                .filter(|s| !s.proceeded)
+                .and_then(|s| s.detail.as_deref()),
+            self.submission.as_ref().and_then(|s| s.error.as_deref()),
+            self.landing.as_ref().and_then(|l| l.error.as_deref()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        if self.pnl_lamports.is_some() {
+            Outcome::Closed
+        } else if self
+            .landing
+            .as_ref()
+            .is_some_and(|landing| landing.status == LandingStatus::Confirmed)
+        {
+            Outcome::Landed
+        } else if self.gates.iter().any(|gate| !gate.passed) {
+            Outcome::Rejected
+        } else if self.reason().is_some() {
+            Outcome::Failed
+        } else if self.submission.is_some() {
+            Outcome::Submitted
+        } else {
+            Outcome::Pending
+        }
+    }
+}
+
+/// Filter for [`query`]; unset fields match every candidate
+#[derive(Debug, Clone, Default)]
+pub struct JournalQuery {
+    pub mint: Option<String>,
+    /// Received at or after (Unix ms)
+    pub since_ms: Option<u64>,
+    /// Received before (Unix ms)
+    pub until_ms: Option<u64>,
+    pub reason: Option<ReasonCode>,
+}
+
+impl JournalQuery {
+    pub fn matches(&self, history: &CandidateHistory) -> bool {
+        self.mint.as_ref().is_none_or(|mint| *mint == history.mint)
+            && self
+                .since_ms
+                .is_none_or(|since| history.received_at_ms >= since)
+            && self
+                .until_ms
+                .is_none_or(|until| history.received_at_ms < until)
+            && self
+                .reason
+                .is_none_or(|reason| history.reason() == Some(reason))
+    }
+}
+
+/// Histories of the candidates in `dir` matching `filter`, in arrival order
+///
+/// Candidates whose `received` entry was pruned or dropped are skipped.
+pub fn query(dir: &Path, filter: &JournalQuery) -> Result<Vec<CandidateHistory>> {
+    // Later segments are read whatever `until_ms` is: landing and PnL entries
+    // can follow the day the candidate arrived
+    let first_day = filter.since_ms.map(segment_date);
+
+    let mut histories: Vec<CandidateHistory> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (date, path) in segments(dir)? {
+        if first_day.is_some_and(|first| date < first) {
+            continue;
+        }
+        let file = std::fs::File::open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        for (line_no, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: JournalEntry = match serde_json::from_str(&line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!(
+                        path = %path.display(),
+                        line = line_no + 1,
+                        error = %e,
+                        "Skipping unreadable journal entry"
+                    );
+                    continue;
+                }
+            };
+
+            match (index.get(&entry.trace_id).copied(), entry.event) {
+                (Some(i), event) => histories[i].apply(event),
+                (None, DecisionEvent::Received(features)) => {
+                    index.insert(entry.trace_id.clone(), histories.len());
+                    histories.push(CandidateHistory {
+                        trace_id: entry.trace_id,
+                        mint: entry.mint,
+                        received_at_ms: entry.at_ms,
+                        features,
+                        gates: Vec::new(),
+                        build: None,
+                        simulation: None,
+                        submission: None,
+                        landing: None,
+                        pnl_lamports: None,
+                    });
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    histories.retain(|history| filter.matches(history));
+    Ok(histories)
+}
+
+/// Export row: the history plus its derived outcome and reason
+#[derive(Serialize)]
+struct ExportRow<'a> {
+    #[serde(flatten)]
+    history: &'a CandidateHistory,
+    outcome: Outcome,
+    reason: Option<ReasonCode>,
+}
+
+/// Write one JSON object per candidate
+pub fn write_jsonl(histories: &[CandidateHistory], mut out: impl Write) -> Result<()> {
+    for history in histories {
+        let row = ExportRow {
+            history,
+            outcome: history.outcome(),
+            reason: history.reason(),
+        };
+        serde_json::to_writer(&mut out, &row)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+const CSV_HEADER: &str = "received_at,trace_id,mint,program,priority,price_hint,outcome,reason,\
+                          detail,gates,build_ms,simulation,route,signature,landing,landing_ms,\
+                          pnl_lamports";
+
+/// Write one CSV row per candidate, with a header
+///
+/// `gates` lists `gate=pass|fail` pairs separated by `;`.
+pub fn write_csv(histories: &[CandidateHistory], mut out: impl Write) -> Result<()> {
+    writeln!(out, "{}", CSV_HEADER)?;
+    for history in histories {
+        let received_at = DateTime::from_timestamp_millis(history.received_at_ms as i64)
+            .map(|at| at.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default();
+        let gates: Vec<String> = history
+            .gates
+            .iter()
+            .map(|gate| {
+                format!(
+                    "{}={}",
+                    gate.gate.as_str(),
+                    if gate.passed { "pass" } else { "fail" }
+                )
+            })
+            .collect();
+        let fields = [
+            received_at,
+            history.trace_id.clone(),
+            history.mint.clone(),
+            history.features.program.clone(),
+            format!("{:?}", history.features.priority),
+            optional(history.features.price_hint),
+            history.outcome().as_str().to_string(),
+            history
+                .reason()
+                .map(|reason| reason.as_str().to_string())
+                .unwrap_or_default(),
+            history.reason_detail().unwrap_or_default().to_string(),
+            gates.join(";"),
+            optional(history.build.as_ref().map(|b| b.latency_ms)),
+            history
+                .simulation
+                .as_ref()
+                .map(|s| format!("{:?}", s.outcome).to_lowercase())
+                .unwrap_or_default(),
+            history
+                .submission
+                .as_ref()
+                .map(|s| s.route.clone())
+                .unwrap_or_default(),
+            history
+                .submission
+                .as_ref()
+                .and_then(|s| s.signature.clone())
+                .unwrap_or_default(),
+            history
+                .landing
+                .as_ref()
+                .map(|l| format!("{:?}", l.status).to_lowercase())
+                .unwrap_or_default(),
+            optional(history.landing.as_ref().map(|l| l.latency_ms)),
+            optional(history.pnl_lamports),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        writeln!(out, "{}", row.join(","))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(mint: Pubkey) -> PremintCandidate {
+        PremintCandidate {
+            mint,
+            program: "pump.fun".to_string(),
+            accounts: vec![Pubkey::new_unique()],
+            priority: PriorityLevel::High,
+            timestamp: 1_700_000_000,
+            price_hint: Some(0.000_001),
+            signature: None,
+            screening: None,
+            trace: None,
+        }
+    }
+
+    fn journaled(dir: &Path) -> JournalConfig {
+        JournalConfig::new(dir, 7)
+    }
+
+    #[test]
+    fn test_entry_round_trip() {
+        let entry = JournalEntry {
+            at_ms: 1_700_000_000_000,
+            trace_id: format!("{:032x}", 7u128),
+            mint: Pubkey::new_unique().to_string(),
+            event: DecisionEvent::Gate(GateRecord {
+                gate: Gate::Screening,
+                passed: false,
+                reason: Some(ReasonCode::ScreeningFailed),
+                detail: Some("MintAuthority: not revoked".to_string()),
+            }),
+        };
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(line.contains(r#""event":"gate""#));
+        assert!(line.contains(r#""reason":"screening_failed""#));
+        assert_eq!(serde_json::from_str::<JournalEntry>(&line).unwrap(), entry);
+
+        for reason in ReasonCode::ALL {
+            assert_eq!(reason.as_str().parse::<ReasonCode>().unwrap(), reason);
+            assert_eq!(
+                serde_json::to_value(reason).unwrap(),
+                serde_json::json!(reason.as_str())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_folds_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let (journal, writer) = DecisionJournal::start(journaled(dir.path())).unwrap();
+
+        let bought = Pubkey::new_unique();
+        let buy = CandidateJournal::new(Some(journal.clone()), bought, 1);
+        buy.received(&candidate(bought));
+        buy.gate_passed(Gate::MintRateLimit);
+        buy.built(12, None);
+        buy.submitted("rpc", &Ok(Signature::default()));
+        buy.landed(LandingStatus::Confirmed, 900, None);
+        buy.opened_position();
+        journal.record_position_closed(&bought, -25_000);
+
+        let rejected = Pubkey::new_unique();
+        let reject = CandidateJournal::new(Some(journal.clone()), rejected, 2);
+        reject.received(&candidate(rejected));
+        assert!(!reject.gate(Gate::ProgramRateLimit, false));
+
+        drop((buy, reject, journal));
+        writer.await.unwrap();
+
+        let all = query(dir.path(), &JournalQuery::default()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].outcome(), Outcome::Closed);
+        assert_eq!(all[0].pnl_lamports, Some(-25_000));
+        assert_eq!(all[0].reason(), None);
+        assert_eq!(all[1].outcome(), Outcome::Rejected);
+
+        let by_reason = query(
+            dir.path(),
+            &JournalQuery {
+                reason: Some(ReasonCode::ProgramRateLimited),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(by_reason.len(), 1);
+        assert_eq!(by_reason[0].mint, rejected.to_string());
+
+        let by_mint = query(
+            dir.path(),
+            &JournalQuery {
+                mint: Some(bought.to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(by_mint.len(), 1);
+
+        let future = query(
+            dir.path(),
+            &JournalQuery {
+                since_ms: Some(now_ms() + 60_000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn test_prune_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        for day in 1..=10 {
+            let date = NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+            std::fs::write(segment_path(dir.path(), date), "").unwrap();
+        }
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        assert_eq!(prune_segments(dir.path(), 3, today).unwrap(), 7);
+        let kept: Vec<NaiveDate> = segments(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|(date, _)| date)
+            .collect();
+        assert_eq!(kept.first(), NaiveDate::from_ymd_opt(2024, 3, 8).as_ref());
+        assert_eq!(kept.len(), 3);
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let mint = Pubkey::new_unique();
+        let history = CandidateHistory {
+            trace_id: format!("{:032x}", 3u128),
+            mint: mint.to_string(),
+            received_at_ms: 1_700_000_000_000,
+            features: CandidateFeatures::from_candidate(&candidate(mint)),
+            gates: vec![
+                GateRecord {
+                    gate: Gate::MintRateLimit,
+                    passed: true,
+                    reason: None,
+                    detail: None,
+                },
+                GateRecord {
+                    gate: Gate::Screening,
+                    passed: false,
+                    reason: Some(ReasonCode::ScreeningFailed),
+                    detail: Some("FreezeAuthority: \"set\", not revoked".to_string()),
+                },
+            ],
+            build: None,
+            simulation: None,
+            submission: None,
+            landing: None,
+            pnl_lamports: None,
+        };
+
+        let mut out = Vec::new();
+        write_csv(&[history], &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[0].split(',').count(),
+            17,
+            "header and rows share one column list"
+        );
+        assert!(lines[1].starts_with("2023-11-14T22:13:20.000Z,"));
+        assert!(lines[1].contains(",rejected,screening_failed,"));
+        assert!(lines[1].contains(r#","FreezeAuthority: ""set"", not revoked","#));
+        assert!(lines[1].contains(",mint_rate_limit=pass;screening=fail,"));
+    }
+}
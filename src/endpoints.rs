@@ -19,8 +19,8 @@ pub async fn endpoint_server(port: u16) -> Result<()> {
 
                     let mut buf = [0; 1024];
                     match socket.read(&mut buf).await {
-                        Ok(_) => {
-                            let response = "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nMetrics endpoint";
+                        Ok(n) => {
+                            let response = respond(&buf[..n]);
                             let _ = socket.write_all(response.as_bytes()).await;
                         }
                         Err(e) => {
@@ -35,3 +35,17 @@ pub async fn endpoint_server(port: u16) -> Result<()> {
         }
     }
 }
+
+/// Build the HTTP response for a raw request
+fn respond(request: &[u8]) -> String {
+    if request.starts_with(b"GET /metrics ") {
+        let body = crate::metrics::metrics().render();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nMetrics endpoint".to_string()
+    }
+}
@@ -0,0 +1,552 @@
+//! Lock-free log-linear latency histograms
+//!
+//! [`LogHistogram`] keeps atomic counts in HDR-style log-linear buckets: exact
+//! below 128, and 64 buckets per power of two above that, for a relative error
+//! under 1.6% at any magnitude. Recording is a handful of relaxed atomic
+//! operations, snapshots can be merged, and percentiles cover every sample
+//! rather than a capped ring of recent ones.
+//!
+//! [`WindowedHistogram`] adds rotating time windows next to the all-time
+//! counts, and [`LatencyHistogramVec`] exports labelled series to Prometheus
+//! as real histograms. Latencies are recorded in microseconds throughout.
+use dashmap::DashMap;
+use prometheus::core::{Collector, Desc};
+use prometheus::proto;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Sub-buckets per power of two, as a power of two
+const SUB_BUCKET_BITS: u32 = 6;
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+
+/// Largest trackable value (about 12.7 days in microseconds); larger values
+/// are clamped into the top bucket
+const MAX_VALUE: u64 = (1 << 40) - 1;
+
+const BUCKETS: usize = bucket_index(MAX_VALUE) + 1;
+
+/// Prometheus bucket bounds for exported latency series, in seconds
+pub const LATENCY_BUCKETS_SECONDS: &[f64] = &[
+    0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+    5.0, 10.0,
+];
+
+const fn bucket_index(value: u64) -> usize {
+    let magnitude = 63u32.saturating_sub(value.leading_zeros());
+    let shift = magnitude.saturating_sub(SUB_BUCKET_BITS);
+    (shift as usize) * SUB_BUCKETS + (value >> shift) as usize
+}
+
+/// Smallest and largest value falling into bucket `index`
+fn bucket_bounds(index: usize) -> (u64, u64) {
+    let shift = (index / SUB_BUCKETS).saturating_sub(1);
+    let mantissa = (index - shift * SUB_BUCKETS) as u64;
+    (mantissa << shift, ((mantissa + 1) << shift) - 1)
+}
+
+/// Atomic log-linear histogram
+pub struct LogHistogram {
+    buckets: Box<[AtomicU64]>,
+    count: AtomicU64,
+    sum: AtomicU64,
+    min: AtomicU64,
+    max: AtomicU64,
+}
+
+impl std::fmt::Debug for LogHistogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogHistogram")
+            .field("count", &self.count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for LogHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0),
+            min: AtomicU64::new(u64::MAX),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, value: u64) {
+        let value = value.min(MAX_VALUE);
+        self.buckets[bucket_index(value)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+        self.min.fetch_min(value, Ordering::Relaxed);
+        self.max.fetch_max(value, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Add the samples of `snapshot`
+    pub fn merge(&self, snapshot: &HistogramSnapshot) {
+        if snapshot.count == 0 {
+            return;
+        }
+        for (bucket, &count) in self.buckets.iter().zip(snapshot.counts.iter()) {
+            if count > 0 {
+                bucket.fetch_add(count, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(snapshot.count, Ordering::Relaxed);
+        self.sum.fetch_add(snapshot.sum, Ordering::Relaxed);
+        self.min.fetch_min(snapshot.min, Ordering::Relaxed);
+        self.max.fetch_max(snapshot.max, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        HistogramSnapshot {
+            // Counted from the buckets so quantiles stay consistent with them
+            count: counts.iter().sum(),
+            counts,
+            sum: self.sum.load(Ordering::Relaxed),
+            min: self.min.load(Ordering::Relaxed),
+            max: self.max.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&self) {
+        for bucket in self.buckets.iter() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+        self.sum.store(0, Ordering::Relaxed);
+        self.min.store(u64::MAX, Ordering::Relaxed);
+        self.max.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Point-in-time copy of a histogram's counts
+#[derive(Debug, Clone)]
+pub struct HistogramSnapshot {
+    counts: Vec<u64>,
+    count: u64,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+impl Default for HistogramSnapshot {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; BUCKETS],
+            count: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+}
+
+impl HistogramSnapshot {
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        (!self.is_empty()).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        (!self.is_empty()).then_some(self.max)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.sum as f64 / self.count as f64)
+    }
+
+    /// Value at quantile `q` (0.0..=1.0), accurate to the bucket width
+    pub fn value_at_quantile(&self, q: f64) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let rank = ((q.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        if rank >= self.count {
+            return Some(self.max);
+        }
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let (low, high) = bucket_bounds(index);
+                let midpoint = low + (high - low) / 2;
+                return Some(midpoint.clamp(self.min, self.max));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// Samples in buckets lying entirely at or below `value`
+    pub fn count_at_or_below(&self, value: u64) -> u64 {
+        self.counts
+            .iter()
+            .enumerate()
+            .take_while(|(index, _)| bucket_bounds(*index).1 <= value)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Cumulative counts of microsecond samples at each of
+    /// [`LATENCY_BUCKETS_SECONDS`]
+    pub fn latency_buckets(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        LATENCY_BUCKETS_SECONDS.iter().map(|&upper_bound| {
+            let micros = (upper_bound * 1_000_000.0).round() as u64;
+            (upper_bound, self.count_at_or_below(micros))
+        })
+    }
+
+    /// Add the samples of `other`
+    pub fn merge(&mut self, other: &HistogramSnapshot) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += other;
+        }
+        self.count += other.count;
+        self.sum = self.sum.wrapping_add(other.sum);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
+
+struct Window {
+    /// Rotation epoch this window holds, 0 when never used
+    epoch: AtomicU64,
+    histogram: LogHistogram,
+}
+
+/// All-time histogram plus a ring of rotating time windows
+///
+/// Windows rotate lazily on record: the first sample of a new period claims
+/// the oldest window and clears it. A sample racing that rotation may be lost
+/// from the window, never from the all-time counts.
+pub struct WindowedHistogram {
+    total: LogHistogram,
+    windows: Box<[Window]>,
+    window: Duration,
+    origin: Instant,
+}
+
+impl std::fmt::Debug for WindowedHistogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindowedHistogram")
+            .field("count", &self.count())
+            .field("window", &self.window)
+            .field("windows", &self.windows.len())
+            .finish()
+    }
+}
+
+impl Default for WindowedHistogram {
+    /// Five one-minute windows
+    fn default() -> Self {
+        Self::new(Duration::from_secs(60), 5)
+    }
+}
+
+impl WindowedHistogram {
+    /// `windows` rotating windows of length `window`
+    pub fn new(window: Duration, windows: usize) -> Self {
+        Self {
+            total: LogHistogram::new(),
+            windows: (0..windows.max(1))
+                .map(|_| Window {
+                    epoch: AtomicU64::new(0),
+                    histogram: LogHistogram::new(),
+                })
+                .collect(),
+            window: window.max(Duration::from_millis(1)),
+            origin: Instant::now(),
+        }
+    }
+
+    fn epoch(&self) -> u64 {
+        (self.origin.elapsed().as_nanos() / self.window.as_nanos()) as u64 + 1
+    }
+
+    pub fn record(&self, value: u64) {
+        self.total.record(value);
+
+        let epoch = self.epoch();
+        let window = &self.windows[(epoch % self.windows.len() as u64) as usize];
+        let held = window.epoch.load(Ordering::Acquire);
+        if held != epoch
+            && window
+                .epoch
+                .compare_exchange(held, epoch, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            window.histogram.reset();
+        }
+        window.histogram.record(value);
+    }
+
+    /// Record a duration in microseconds
+    pub fn record_duration(&self, duration: Duration) {
+        self.record(duration.as_micros().min(u64::MAX as u128) as u64);
+    }
+
+    /// Drop all samples, including the all-time counts
+    pub fn reset(&self) {
+        self.total.reset();
+        for window in self.windows.iter() {
+            window.epoch.store(0, Ordering::Release);
+            window.histogram.reset();
+        }
+    }
+
+    /// Samples recorded since creation
+    pub fn count(&self) -> u64 {
+        self.total.count()
+    }
+
+    /// All samples since creation
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        self.total.snapshot()
+    }
+
+    /// Samples from the current and previous windows, i.e. roughly the last
+    /// `windows * window`
+    pub fn recent(&self) -> HistogramSnapshot {
+        let epoch = self.epoch();
+        let oldest = epoch.saturating_sub(self.windows.len() as u64 - 1);
+        let mut snapshot = HistogramSnapshot::default();
+        for window in self.windows.iter() {
+            let held = window.epoch.load(Ordering::Acquire);
+            if held != 0 && held >= oldest {
+                snapshot.merge(&window.histogram.snapshot());
+            }
+        }
+        snapshot
+    }
+}
+
+/// Labelled latency series exported to Prometheus as histograms
+///
+/// Values are recorded in microseconds and exported in seconds against
+/// [`LATENCY_BUCKETS_SECONDS`].
+#[derive(Clone)]
+pub struct LatencyHistogramVec {
+    desc: Desc,
+    series: Arc<DashMap<Vec<String>, Arc<WindowedHistogram>>>,
+}
+
+impl std::fmt::Debug for LatencyHistogramVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LatencyHistogramVec")
+            .field("name", &self.desc.fq_name)
+            .field("series", &self.series.len())
+            .finish()
+    }
+}
+
+impl LatencyHistogramVec {
+    pub fn new(name: &str, help: &str, label_names: &[&str]) -> prometheus::Result<Self> {
+        let desc = Desc::new(
+            name.to_string(),
+            help.to_string(),
+            label_names.iter().map(|label| label.to_string()).collect(),
+            HashMap::new(),
+        )?;
+        Ok(Self {
+            desc,
+            series: Arc::new(DashMap::new()),
+        })
+    }
+
+    /// Series for `values`, created on first use
+    ///
+    /// Panics if the number of values does not match the label names.
+    pub fn with_label_values(&self, values: &[&str]) -> Arc<WindowedHistogram> {
+        assert_eq!(
+            values.len(),
+            self.desc.variable_labels.len(),
+            "label values for {}",
+            self.desc.fq_name
+        );
+        let key: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        if let Some(series) = self.series.get(&key) {
+            return series.clone();
+        }
+        self.series.entry(key).or_default().clone()
+    }
+
+    /// Record `micros` into the series for `values`
+    pub fn record(&self, values: &[&str], micros: u64) {
+        self.with_label_values(values).record(micros);
+    }
+
+    fn metric(&self, values: &[String], snapshot: &HistogramSnapshot) -> proto::Metric {
+        let labels = self
+            .desc
+            .variable_labels
+            .iter()
+            .zip(values)
+            .map(|(name, value)| {
+                let mut label = proto::LabelPair::default();
+                label.set_name(name.clone());
+                label.set_value(value.clone());
+                label
+            })
+            .collect();
+
+        let buckets = snapshot
+            .latency_buckets()
+            .map(|(upper_bound, count)| {
+                let mut bucket = proto::Bucket::default();
+                bucket.set_upper_bound(upper_bound);
+                bucket.set_cumulative_count(count);
+                bucket
+            })
+            .collect();
+        let mut histogram = proto::Histogram::default();
+        histogram.set_sample_count(snapshot.count());
+        histogram.set_sample_sum(snapshot.sum() as f64 / 1_000_000.0);
+        histogram.set_bucket(buckets);
+
+        let mut metric = proto::Metric::from_label(labels);
+        metric.set_histogram(histogram);
+        metric
+    }
+}
+
+impl Collector for LatencyHistogramVec {
+    fn desc(&self) -> Vec<&Desc> {
+        vec![&self.desc]
+    }
+
+    fn collect(&self) -> Vec<proto::MetricFamily> {
+        let mut family = proto::MetricFamily::default();
+        family.set_name(self.desc.fq_name.clone());
+        family.set_help(self.desc.help.clone());
+        family.set_field_type(proto::MetricType::HISTOGRAM);
+        family.set_metric(
+            self.series
+                .iter()
+                .map(|series| self.metric(series.key(), &series.value().snapshot()))
+                .collect(),
+        );
+        vec![family]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_are_contiguous_and_bounded() {
+        assert_eq!(bucket_index(0), 0);
+        assert_eq!(bucket_index(127), 127);
+        let mut expected_low = 0;
+        for index in 0..BUCKETS {
+            let (low, high) = bucket_bounds(index);
+            assert_eq!(low, expected_low, "bucket {}", index);
+            assert_eq!(bucket_index(low), index);
+            assert_eq!(bucket_index(high), index);
+            // Relative width never exceeds 1/64
+            assert!((high - low) as f64 <= low as f64 / SUB_BUCKETS as f64);
+            expected_low = high + 1;
+        }
+        assert_eq!(expected_low, MAX_VALUE + 1);
+    }
+
+    #[test]
+    fn test_quantiles_over_many_samples() {
+        let histogram = LogHistogram::new();
+        for micros in 1..=100_000 {
+            histogram.record(micros);
+        }
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count(), 100_000);
+        assert_eq!(snapshot.min(), Some(1));
+        assert_eq!(snapshot.max(), Some(100_000));
+        for (q, exact) in [(0.5, 50_000.0), (0.9, 90_000.0), (0.999, 99_900.0)] {
+            let value = snapshot.value_at_quantile(q).unwrap() as f64;
+            assert!((value - exact).abs() / exact < 0.016, "q{} = {}", q, value);
+        }
+        assert_eq!(snapshot.value_at_quantile(1.0), Some(100_000));
+        assert!(HistogramSnapshot::default()
+            .value_at_quantile(0.5)
+            .is_none());
+    }
+
+    #[test]
+    fn test_snapshots_merge() {
+        let (fast, slow) = (LogHistogram::new(), LogHistogram::new());
+        for _ in 0..90 {
+            fast.record(100);
+        }
+        for _ in 0..10 {
+            slow.record(10_000);
+        }
+        let mut merged = fast.snapshot();
+        merged.merge(&slow.snapshot());
+        assert_eq!(merged.count(), 100);
+        assert_eq!(merged.value_at_quantile(0.9), Some(100));
+        assert!(merged.value_at_quantile(0.95).unwrap() >= 9_900);
+
+        fast.merge(&slow.snapshot());
+        assert_eq!(fast.snapshot().sum(), merged.sum());
+    }
+
+    #[test]
+    fn test_windows_rotate_out() {
+        let histogram = WindowedHistogram::new(Duration::from_millis(20), 2);
+        histogram.record(1_000);
+        assert_eq!(histogram.recent().count(), 1);
+
+        std::thread::sleep(Duration::from_millis(60));
+        histogram.record(2_000);
+        let recent = histogram.recent();
+        assert_eq!(recent.count(), 1);
+        assert_eq!(recent.max(), Some(2_000));
+        assert_eq!(histogram.snapshot().count(), 2);
+    }
+
+    #[test]
+    fn test_exported_as_prometheus_histogram() {
+        let vec = LatencyHistogramVec::new("test_latency_seconds", "Test", &["stage"]).unwrap();
+        for _ in 0..3 {
+            vec.record(&["fast"], 50);
+        }
+        vec.record(&["fast"], 2_000_000);
+
+        let families = vec.collect();
+        let metric = &families[0].get_metric()[0];
+        assert_eq!(families[0].get_field_type(), proto::MetricType::HISTOGRAM);
+        assert_eq!(metric.get_label()[0].value(), "fast");
+        let histogram = metric.get_histogram();
+        assert_eq!(histogram.get_sample_count(), 4);
+        assert!((histogram.get_sample_sum() - 2.00015).abs() < 1e-9);
+        let buckets = histogram.get_bucket();
+        assert_eq!(buckets[0].cumulative_count(), 3);
+        assert_eq!(buckets.last().unwrap().cumulative_count(), 4);
+    }
+}
@@ -0,0 +1,561 @@
+//! Encrypted keystore for the trading wallet and nonce authority keys
+//!
+//! Keypairs are stored as a small JSON document: the secret key is sealed with
+//! XChaCha20-Poly1305 under a key derived from a passphrase with Argon2id. The
+//! public key is kept in clear text and bound to the ciphertext as associated
+//! data, so the file can be identified without a passphrase but any tampering
+//! with it fails decryption.
+//!
+//! Plaintext Solana CLI keypairs (64 raw bytes or a JSON byte array) are still
+//! accepted by [`load_keypair_bytes`] so existing deployments keep working
+//! until they are imported with the `keystore` binary. Like keystores, they
+//! must be mode 0600 or 0400 to load.
+//!
+//! Passphrases are taken from a [`PassphraseSource`]: an interactive TTY prompt,
+//! an environment variable, or an inherited file descriptor. Every buffer that
+//! holds a passphrase, derived key or secret key is zeroized on drop.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::{debug, info};
+use zeroize::Zeroizing;
+
+use crate::nonce_manager::nonce_security::FilePermissionChecker;
+
+/// Current keystore document version
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Environment variable holding the keystore passphrase
+pub const PASSPHRASE_ENV: &str = "BOT_KEYSTORE_PASSPHRASE";
+
+/// Environment variable holding a file descriptor to read the passphrase from
+pub const PASSPHRASE_FD_ENV: &str = "BOT_KEYSTORE_PASSPHRASE_FD";
+
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "xchacha20poly1305";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const KEYPAIR_LEN: usize = 64;
+
+/// Keystore errors
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    /// Reading or writing the keystore file failed
+    #[error("I/O error on {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The key file is readable by other users
+    #[error("Insecure key file: {0}")]
+    InsecurePermissions(String),
+
+    /// The file is neither a keystore nor a plaintext keypair
+    #[error("Invalid keystore format: {0}")]
+    InvalidFormat(String),
+
+    /// The keystore uses a KDF or cipher this build does not support
+    #[error("Unsupported keystore {0}")]
+    Unsupported(String),
+
+    /// The decrypted secret key is not a usable keypair
+    #[error("Invalid keypair: {0}")]
+    InvalidKeypair(String),
+
+    /// Wrong passphrase or the keystore was modified
+    #[error("Keystore decryption failed (wrong passphrase or corrupted file)")]
+    Decryption,
+
+    /// Key derivation or encryption failed
+    #[error("Keystore crypto error: {0}")]
+    Crypto(String),
+
+    /// No passphrase could be obtained from the configured source
+    #[error("Passphrase unavailable: {0}")]
+    Passphrase(String),
+}
+
+pub type KeystoreResult<T> = Result<T, KeystoreError>;
+
+/// Where to read a keystore passphrase from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseSource {
+    /// Prompt on the controlling terminal
+    Prompt,
+    /// Read from the named environment variable
+    Env(String),
+    /// Read the first line from an inherited file descriptor
+    Fd(i32),
+}
+
+impl PassphraseSource {
+    /// Pick a source from the process environment
+    ///
+    /// `BOT_KEYSTORE_PASSPHRASE_FD` wins over `BOT_KEYSTORE_PASSPHRASE`; without
+    /// either the passphrase is prompted for on the TTY.
+    pub fn from_env() -> Self {
+        if let Some(fd) = std::env::var(PASSPHRASE_FD_ENV)
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+        {
+            return Self::Fd(fd);
+        }
+        if std::env::var_os(PASSPHRASE_ENV).is_some() {
+            return Self::Env(PASSPHRASE_ENV.to_string());
+        }
+        Self::Prompt
+    }
+
+    /// Read the passphrase, showing `prompt` when asking on the TTY
+    pub fn read(&self, prompt: &str) -> KeystoreResult<Zeroizing<String>> {
+        let mut passphrase = match self {
+            Self::Prompt => Zeroizing::new(
+                rpassword::prompt_password(prompt)
+                    .map_err(|e| KeystoreError::Passphrase(format!("TTY prompt failed: {}", e)))?,
+            ),
+            Self::Env(var) => Zeroizing::new(std::env::var(var).map_err(|_| {
+                KeystoreError::Passphrase(format!("environment variable {} is not set", var))
+            })?),
+            Self::Fd(fd) => {
+                let path = format!("/dev/fd/{}", fd);
+                let contents = Zeroizing::new(std::fs::read_to_string(&path).map_err(|e| {
+                    KeystoreError::Passphrase(format!("cannot read file descriptor {}: {}", fd, e))
+                })?);
+                Zeroizing::new(contents.lines().next().unwrap_or_default().to_string())
+            }
+        };
+
+        // Strip a trailing newline left by `echo` or heredocs
+        while passphrase.ends_with('\n') || passphrase.ends_with('\r') {
+            passphrase.pop();
+        }
+        if passphrase.is_empty() {
+            return Err(KeystoreError::Passphrase("empty passphrase".to_string()));
+        }
+        Ok(passphrase)
+    }
+
+    /// Read a new passphrase, asking twice for confirmation on the TTY
+    pub fn read_new(&self, prompt: &str) -> KeystoreResult<Zeroizing<String>> {
+        let passphrase = self.read(prompt)?;
+        if *self == Self::Prompt {
+            let confirm = self.read("Confirm passphrase: ")?;
+            if *confirm != *passphrase {
+                return Err(KeystoreError::Passphrase(
+                    "passphrases do not match".to_string(),
+                ));
+            }
+        }
+        Ok(passphrase)
+    }
+}
+
+/// Argon2id cost parameters stored alongside the ciphertext
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub m_cost_kib: u32,
+    /// Number of passes
+    pub t_cost: u32,
+    /// Degree of parallelism
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost_kib: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfSection {
+    algorithm: String,
+    #[serde(flatten)]
+    params: KdfParams,
+    salt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CipherSection {
+    algorithm: String,
+    nonce: String,
+}
+
+/// On-disk encrypted keystore document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedKeystore {
+    version: u32,
+    pubkey: String,
+    kdf: KdfSection,
+    cipher: CipherSection,
+    ciphertext: String,
+}
+
+impl EncryptedKeystore {
+    /// Seal a keypair under `passphrase`
+    pub fn encrypt(keypair: &Keypair, passphrase: &str, params: KdfParams) -> KeystoreResult<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let pubkey = keypair.pubkey();
+        let key = derive_key(passphrase, &salt, params)?;
+        let secret = Zeroizing::new(keypair.to_bytes());
+        let cipher = XChaCha20Poly1305::new_from_slice(key.as_slice())
+            .map_err(|e| KeystoreError::Crypto(e.to_string()))?;
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: secret.as_slice(),
+                    aad: pubkey.as_ref(),
+                },
+            )
+            .map_err(|e| KeystoreError::Crypto(e.to_string()))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey: pubkey.to_string(),
+            kdf: KdfSection {
+                algorithm: KDF_ALGORITHM.to_string(),
+                params,
+                salt: BASE64.encode(salt),
+            },
+            cipher: CipherSection {
+                algorithm: CIPHER_ALGORITHM.to_string(),
+                nonce: BASE64.encode(nonce),
+            },
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    /// Public key recorded in the keystore (not authenticated until decrypted)
+    pub fn pubkey(&self) -> KeystoreResult<Pubkey> {
+        Pubkey::from_str(&self.pubkey)
+            .map_err(|e| KeystoreError::InvalidFormat(format!("bad pubkey: {}", e)))
+    }
+
+    /// KDF cost parameters the keystore was sealed with
+    pub fn kdf_params(&self) -> KdfParams {
+        self.kdf.params
+    }
+
+    /// Open the keystore and return the 64-byte secret key
+    pub fn decrypt(&self, passphrase: &str) -> KeystoreResult<Zeroizing<Vec<u8>>> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::Unsupported(format!(
+                "version {}",
+                self.version
+            )));
+        }
+        if self.kdf.algorithm != KDF_ALGORITHM {
+            return Err(KeystoreError::Unsupported(format!(
+                "KDF {}",
+                self.kdf.algorithm
+            )));
+        }
+        if self.cipher.algorithm != CIPHER_ALGORITHM {
+            return Err(KeystoreError::Unsupported(format!(
+                "cipher {}",
+                self.cipher.algorithm
+            )));
+        }
+
+        let pubkey = self.pubkey()?;
+        let salt = decode_field("salt", &self.kdf.salt)?;
+        let nonce = decode_field("nonce", &self.cipher.nonce)?;
+        let ciphertext = decode_field("ciphertext", &self.ciphertext)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(KeystoreError::InvalidFormat(format!(
+                "nonce must be {} bytes, got {}",
+                NONCE_LEN,
+                nonce.len()
+            )));
+        }
+
+        let key = derive_key(passphrase, &salt, self.kdf.params)?;
+        let cipher = XChaCha20Poly1305::new_from_slice(key.as_slice())
+            .map_err(|e| KeystoreError::Crypto(e.to_string()))?;
+        let secret = Zeroizing::new(
+            cipher
+                .decrypt(
+                    XNonce::from_slice(&nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: pubkey.as_ref(),
+                    },
+                )
+                .map_err(|_| KeystoreError::Decryption)?,
+        );
+
+        let keypair = keypair_from_secret(&secret)?;
+        if keypair.pubkey() != pubkey {
+            return Err(KeystoreError::InvalidKeypair(
+                "secret key does not match recorded pubkey".to_string(),
+            ));
+        }
+        Ok(secret)
+    }
+
+    /// Parse a keystore document
+    pub fn from_json(data: &[u8]) -> KeystoreResult<Self> {
+        serde_json::from_slice(data).map_err(|e| KeystoreError::InvalidFormat(e.to_string()))
+    }
+
+    /// Serialize the keystore document
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("keystore serializes")
+    }
+
+    /// Write the keystore with 0600 permissions, replacing `path` atomically
+    pub fn save(&self, path: &Path) -> KeystoreResult<()> {
+        write_secret_file(path, self.to_json().as_bytes())
+    }
+}
+
+/// Returns true if `data` looks like an encrypted keystore document
+///
+/// A raw 64-byte keypair may start with `{` and is never a keystore.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() != KEYPAIR_LEN
+        && data
+            .iter()
+            .find(|b| !b.is_ascii_whitespace())
+            .is_some_and(|&b| b == b'{')
+}
+
+/// Load the 64-byte secret key from an encrypted keystore or plaintext keypair
+///
+/// The file must pass [`FilePermissionChecker`]. The passphrase source is only
+/// consulted for encrypted keystores.
+pub fn load_keypair_bytes(
+    path: &Path,
+    passphrase: &PassphraseSource,
+) -> KeystoreResult<Zeroizing<Vec<u8>>> {
+    FilePermissionChecker::check_secure_permissions(path)
+        .map_err(|e| KeystoreError::InsecurePermissions(e.to_string()))?;
+
+    let data = Zeroizing::new(std::fs::read(path).map_err(|source| KeystoreError::Io {
+        path: path.display().to_string(),
+        source,
+    })?);
+
+    if is_encrypted(&data) {
+        let keystore = EncryptedKeystore::from_json(&data)?;
+        let prompt = format!("Passphrase for {} ({}): ", path.display(), keystore.pubkey);
+        let passphrase = passphrase.read(&prompt)?;
+        let secret = keystore.decrypt(&passphrase)?;
+        info!(
+            path = %path.display(),
+            pubkey = %keystore.pubkey,
+            "Encrypted keystore unlocked"
+        );
+        return Ok(secret);
+    }
+
+    let secret = parse_plaintext(&data)?;
+    debug!(path = %path.display(), "Loaded plaintext keypair file");
+    Ok(secret)
+}
+
+/// Load a keypair from an encrypted keystore or plaintext keypair file
+pub fn load_keypair(path: &Path, passphrase: &PassphraseSource) -> KeystoreResult<Keypair> {
+    let secret = load_keypair_bytes(path, passphrase)?;
+    keypair_from_secret(&secret)
+}
+
+/// Write a plaintext Solana CLI keypair (JSON byte array) with 0600 permissions
+pub fn save_plaintext(path: &Path, keypair: &Keypair) -> KeystoreResult<()> {
+    let secret = Zeroizing::new(keypair.to_bytes());
+    // Serialize straight from the zeroized buffer; no intermediate Vec copy
+    let json = Zeroizing::new(serde_json::to_string(&secret[..]).expect("byte array serializes"));
+    write_secret_file(path, json.as_bytes())
+}
+
+/// Build a keypair from a 64-byte secret, rejecting malformed and all-zero keys
+pub fn keypair_from_secret(secret: &[u8]) -> KeystoreResult<Keypair> {
+    if secret.len() != KEYPAIR_LEN {
+        return Err(KeystoreError::InvalidKeypair(format!(
+            "expected {} bytes, got {}",
+            KEYPAIR_LEN,
+            secret.len()
+        )));
+    }
+    if secret.iter().all(|&b| b == 0) {
+        return Err(KeystoreError::InvalidKeypair(
+            "all-zero key rejected".to_string(),
+        ));
+    }
+    Keypair::try_from(secret).map_err(|e| KeystoreError::InvalidKeypair(e.to_string()))
+}
+
+fn parse_plaintext(data: &[u8]) -> KeystoreResult<Zeroizing<Vec<u8>>> {
+    let secret = if data.len() == KEYPAIR_LEN {
+        Zeroizing::new(data.to_vec())
+    } else {
+        Zeroizing::new(
+            serde_json::from_slice::<Vec<u8>>(data)
+                .map_err(|e| KeystoreError::InvalidFormat(format!("keypair JSON: {}", e)))?,
+        )
+    };
+    keypair_from_secret(&secret)?;
+    Ok(secret)
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    params: KdfParams,
+) -> KeystoreResult<Zeroizing<[u8; KEY_LEN]>> {
+    let params = Params::new(
+        params.m_cost_kib,
+        params.t_cost,
+        params.p_cost,
+        Some(KEY_LEN),
+    )
+    .map_err(|e| KeystoreError::Crypto(format!("invalid KDF parameters: {}", e)))?;
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut_slice())
+        .map_err(|e| KeystoreError::Crypto(e.to_string()))?;
+    Ok(key)
+}
+
+fn decode_field(name: &str, value: &str) -> KeystoreResult<Vec<u8>> {
+    BASE64
+        .decode(value)
+        .map_err(|e| KeystoreError::InvalidFormat(format!("{}: {}", name, e)))
+}
+
+/// Write `contents` to a sibling temp file created with 0600 and rename it over `path`
+fn write_secret_file(path: &Path, contents: &[u8]) -> KeystoreResult<()> {
+    let io_err = |source| KeystoreError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let mut tmp = tempfile::NamedTempFile::new_in(dir).map_err(io_err)?;
+    FilePermissionChecker::set_secure_permissions(tmp.path())
+        .map_err(|e| KeystoreError::InsecurePermissions(e.to_string()))?;
+    tmp.write_all(contents).map_err(io_err)?;
+    tmp.as_file().sync_all().map_err(io_err)?;
+    tmp.persist(path).map_err(|e| io_err(e.error))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimum Argon2 cost so the tests stay fast
+    const TEST_PARAMS: KdfParams = KdfParams {
+        m_cost_kib: 8,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let keypair = Keypair::new();
+        let keystore = EncryptedKeystore::encrypt(&keypair, "hunter2", TEST_PARAMS).unwrap();
+        assert_eq!(keystore.pubkey().unwrap(), keypair.pubkey());
+
+        let parsed = EncryptedKeystore::from_json(keystore.to_json().as_bytes()).unwrap();
+        let secret = parsed.decrypt("hunter2").unwrap();
+        assert_eq!(secret.as_slice(), keypair.to_bytes().as_slice());
+
+        assert!(matches!(
+            parsed.decrypt("wrong"),
+            Err(KeystoreError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_swapped_pubkey_fails_authentication() {
+        let keypair = Keypair::new();
+        let mut keystore = EncryptedKeystore::encrypt(&keypair, "pw", TEST_PARAMS).unwrap();
+        keystore.pubkey = Keypair::new().pubkey().to_string();
+
+        assert!(matches!(
+            keystore.decrypt("pw"),
+            Err(KeystoreError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_load_encrypted_and_plaintext_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = Keypair::new();
+
+        let plain = dir.path().join("id.json");
+        save_plaintext(&plain, &keypair).unwrap();
+        let loaded = load_keypair(&plain, &PassphraseSource::Prompt).unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+
+        let encrypted = dir.path().join("id.keystore");
+        EncryptedKeystore::encrypt(&keypair, "s3cret", TEST_PARAMS)
+            .unwrap()
+            .save(&encrypted)
+            .unwrap();
+        std::env::set_var("KEYSTORE_TEST_PASSPHRASE", "s3cret\n");
+        let source = PassphraseSource::Env("KEYSTORE_TEST_PASSPHRASE".to_string());
+        let loaded = load_keypair(&encrypted, &source).unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_raw_keypair_starting_with_brace_is_plaintext() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("id.bin");
+        let keypair = std::iter::repeat_with(Keypair::new)
+            .find(|keypair| keypair.to_bytes()[0] == b'{')
+            .unwrap();
+        write_secret_file(&path, &keypair.to_bytes()).unwrap();
+
+        assert!(!is_encrypted(&keypair.to_bytes()));
+        let loaded = load_keypair(&path, &PassphraseSource::Prompt).unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_world_readable_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("id.json");
+        save_plaintext(&path, &Keypair::new()).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        assert!(matches!(
+            load_keypair(&path, &PassphraseSource::Prompt),
+            Err(KeystoreError::InsecurePermissions(_))
+        ));
+    }
+}
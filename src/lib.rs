@@ -8,9 +8,18 @@ pub mod compat;
 // Export metrics module
 pub mod metrics;
 
+// Export lock-free latency histograms
+pub mod histogram;
+
 // Export observability module
 pub mod observability;
 
+// Export OTLP span export for the candidate pipeline
+pub mod trace_export;
+
+// Export encrypted keystore (wallet and nonce authority keys)
+pub mod keystore;
+
 // Export the nonce_manager module (with path attribute for directory with space)
 #[path = "nonce manager/mod.rs"]
 pub mod nonce_manager;
@@ -28,9 +37,15 @@ pub mod components;
 // Export position tracker module
 pub mod position_tracker;
 
+// Export per-candidate decision journal
+pub mod decision_journal;
+
 // Export types module
 pub mod types;
 
+// Export SPL Token / Token-2022 mint decoding
+pub mod token_program;
+
 // Export streaming module (WebSocket and Geyser support)
 pub mod streaming;
 
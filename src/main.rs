@@ -38,11 +38,20 @@ use components::gui_bridge::GuiCommand;
 mod compat; // Solana SDK compatibility layer
 mod components; // GUI integration components
 mod config;
+mod config_watcher;
+mod control_api;
+mod copy_trading;
+mod decision_journal;
 mod endpoints;
+mod histogram;
+mod keystore;
 mod metrics;
 mod observability;
+mod presigned_exit;
 mod security;
 mod structured_logging;
+mod token_program;
+mod trace_export;
 mod types;
 mod wallet;
 
@@ -86,9 +95,21 @@ struct Args {
     #[arg(short, long)]
     verbose: bool,
 
-    /// Metrics port
-    #[arg(long, default_value = "9090")]
-    metrics_port: u16,
+    /// Metrics port (overrides monitoring.metrics_port)
+    #[arg(long)]
+    metrics_port: Option<u16>,
+
+    /// Override a config key, e.g. `--set trading.mode=multi` (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+
+    /// Print the effective configuration (secrets redacted) and exit
+    #[arg(long)]
+    print_config: bool,
+
+    /// Validate the configuration and exit
+    #[arg(long)]
+    check_config: bool,
 }
 
 #[tokio::main]
@@ -96,6 +117,10 @@ async fn main() -> Result<()> {
     // Parse command line arguments
     let args = Args::parse();
 
+    if args.print_config || args.check_config {
+        return run_config_command(&args);
+    }
+
     // Initialize logging
     init_logging(args.verbose)?;
 
@@ -104,7 +129,8 @@ async fn main() -> Result<()> {
 
     // Load configuration
     info!("📋 Loading configuration from: {}", args.config);
-    let config = load_config(&args.config)?;
+    let loader = config_loader(&args)?;
+    let config = load_config(&loader, &args.config)?;
 
     // Determine operating mode
     let mode = match args.mode.as_str() {
@@ -118,21 +144,21 @@ async fn main() -> Result<()> {
     info!("🎯 Operating Mode: {:?}", mode);
 
     // Initialize application state
-    let app_state = Arc::new(AppState::new(mode));
+    let app_state = Arc::new(AppState::with_config(mode, config.portfolio.clone()));
 
     // Initialize wallet
     info!(
         "🔑 Initializing wallet from: {}",
         config.wallet.keypair_path
     );
-    let wallet =
-        WalletManager::from_file(&config.wallet.keypair_path).context("Failed to load wallet")?;
+    let wallet = WalletManager::from_config(&config.wallet).context("Failed to load wallet")?;
     info!("💼 Wallet address: {}", wallet.pubkey());
 
-    // Initialize metrics
+    // Initialize metrics (one registry; subsystems register their collectors)
+    metrics::init_metrics(config.monitoring.region.as_deref())?;
     if config.monitoring.enable_metrics {
-        info!("📊 Starting metrics server on port {}", args.metrics_port);
-        let metrics_port = args.metrics_port;
+        let metrics_port = config.monitoring.metrics_port;
+        info!("📊 Starting metrics server on port {}", metrics_port);
         tokio::spawn(async move {
             if let Err(e) = endpoints::endpoint_server(metrics_port).await {
                 error!("Metrics server error: {}", e);
@@ -140,33 +166,87 @@ async fn main() -> Result<()> {
         });
     }
 
+    // Pipeline span export (one trace per candidate, sniffer to landing)
+    if let Some(trace_config) = config.trace_export_config() {
+        trace_export::init(trace_config)?;
+    }
+
+    // Decision journal (every candidate's gates and outcome, queried with `decision-journal`)
+    let _decision_journal = match config.decision_journal_config() {
+        Some(journal_config) => {
+            info!(
+                "📓 Journaling candidate decisions to {}",
+                journal_config.dir.display()
+            );
+            let (journal, _) = decision_journal::DecisionJournal::start(journal_config)?;
+            Some(journal)
+        }
+        None => None,
+    };
+
     // Initialize RPC manager
     info!(
         "🌐 Initializing RPC manager with {} endpoints",
         config.rpc.endpoints.len()
     );
     let _rpc_endpoints: Vec<rpc_manager::EndpointConfig> = config
-        .rpc
+        .rpc_manager_config()
         .endpoints
         .iter()
-        .map(|url| rpc_manager::EndpointConfig {
-            url: url.clone(),
-            endpoint_type: rpc_manager::EndpointType::Standard,
-            weight: 1.0,
-            max_requests_per_second: config.rpc.rate_limit_rps,
-        })
+        .map(rpc_manager::EndpointConfig::from)
         .collect();
 
     // Note: Actual RPC pool initialization would happen here
     // let rpc_pool = Arc::new(rpc_manager::RpcPool::new(rpc_endpoints).await?);
+    // metrics::metrics().register_source(rpc_pool.clone())?;
+    // Shared slot clock (slotSubscribe with getSlot fallback, latest blockhash),
+    // handed to the nonce manager, tx builder and buy engine via set_slot_clock():
+    // let slot_clock = Arc::new(rpc_manager::SlotClock::new(rpc_manager::SlotClockConfig {
+    //     ws_url: Some(config.streaming.websocket_url.clone()),
+    //     ..Default::default()
+    // }));
+    // slot_clock.clone().spawn(rpc_pool.clone());
+    // Direct QUIC submission to the current and next leaders' TPU ports; the
+    // buy engine picks RPC, TPU or both per OperationPriority:
+    // let tpu_sender = Arc::new(rpc_manager::TpuSender::new(Default::default(), slot_clock.clone())?);
+    // tpu_sender.clone().spawn(rpc_pool.clone());
+    // metrics::metrics().register_source(tpu_sender.clone())?;
+    // buy_engine.set_tpu_sender(tpu_sender);
 
     // Initialize nonce manager
     info!(
         "🔢 Initializing nonce manager with pool size: {}",
         config.nonce.pool_size
     );
-    // Note: Actual nonce manager initialization would happen here
+    // Note: Actual nonce manager initialization would happen here. With
+    // nonce.accounts_file set, the pool written by `nonce-admin` is loaded:
+    // let pubkeys = NoncePoolFile::load(path)?.pubkeys()?;
+    // let nonce_manager = Arc::new(nonce_manager::NonceManager::from_existing(..., &pubkeys).await?);
+    // otherwise fresh accounts are created:
     // let nonce_manager = Arc::new(nonce_manager::NonceManager::new(...).await?);
+    if let Some(path) = &config.nonce.accounts_file {
+        info!("   Nonce pool file: {}", path);
+    }
+    // A snapshot from the previous run is resumed instead, with any pool-file
+    // accounts it does not know yet merged in:
+    // let store = SnapshotStore::new(path);
+    // if let Some(snapshot) = store.load()? { NonceManager::from_snapshot(..., snapshot, &pubkeys).await? }
+    // nonce_manager.spawn_snapshot_writer(store, Duration::from_secs(config.nonce.snapshot_interval_secs));
+    // Integrity audit: hijacked or closed nonce accounts are evicted
+    // Arc::new(NonceAuditor::new(nonce_manager.clone(), rpc_pool.clone()))
+    //     .spawn(Duration::from_secs(config.nonce.audit_interval_secs));
+    if config.nonce.audit_interval_secs > 0 {
+        info!(
+            "   Nonce audit interval: {}s",
+            config.nonce.audit_interval_secs
+        );
+    }
+    if let Some(path) = &config.nonce.snapshot_path {
+        info!(
+            "   Nonce snapshot: {} (every {}s)",
+            path, config.nonce.snapshot_interval_secs
+        );
+    }
 
     // Initialize sniffer
     info!("👁️ Initializing transaction sniffer");
@@ -182,11 +262,76 @@ async fn main() -> Result<()> {
     // Note: Actual sniffer initialization would happen here
     // let sniffer_config = sniffer::SnifferConfig { ... };
     // let sniffer = sniffer::Sniffer::new(sniffer_config, candidate_tx).await?;
+    // metrics::metrics().register_source(sniffer.get_metrics())?;
+
+    // Pre-buy token screening (`[screening]`) against the first RPC endpoint
+    let _token_screener = match _rpc_endpoints.first() {
+        Some(endpoint) if config.screening.enabled => {
+            info!(
+                "🛡️ Token screening enabled (timeout {}ms)",
+                config.screening.timeout_ms
+            );
+            let sender =
+                rpc_manager::rpc_sender::EndpointSender::new(endpoint, Default::default())?;
+            let rpc = solana_client::nonblocking::rpc_client::RpcClient::new_sender(
+                sender,
+                solana_client::rpc_client::RpcClientConfig::with_commitment(
+                    solana_sdk::commitment_config::CommitmentConfig::confirmed(),
+                ),
+            );
+            Some(Arc::new(security::screening::TokenScreener::new(
+                Arc::new(rpc),
+                config.screening.clone(),
+            )))
+        }
+        _ => None,
+    };
 
     // Initialize buy engine
     info!("💰 Initializing buy engine");
     // Note: Actual buy engine initialization would happen here
     // let buy_engine = buy_engine::BuyEngine::new(...);
+    // buy_engine.register_metrics()?;
+    // if let Some(journal) = _decision_journal.clone() {
+    //     buy_engine.set_decision_journal(journal);
+    // }
+    // if let Some(screener) = _token_screener.clone() {
+    //     buy_engine.set_token_screener(screener);
+    // }
+
+    // Hot reload: live-reloadable keys are pushed to running subsystems
+    let config_path = std::path::Path::new(&args.config)
+        .exists()
+        .then(|| std::path::PathBuf::from(&args.config));
+    let watcher = Arc::new(config_watcher::ConfigWatcher::new(
+        loader,
+        config_path,
+        config.clone(),
+    ));
+    // Note: Subsystems register here once they are constructed
+    //     .with_target(buy_engine.clone())
+    //     .with_target(sniffer.get_analytics())
+    watcher.spawn();
+
+    // Headless control API (`botctl`)
+    if config.control.enabled {
+        info!("🕹️ Control API enabled on {}", config.control.bind_addr);
+        // Note: Served once the buy engine is constructed
+        // let control = control_api::ControlServer::from_config(&config.control, buy_engine.clone())?;
+        // tokio::spawn(Arc::new(control).serve());
+    }
+
+    // Copy trading: leader trades are mirrored through the buy engine
+    if config.copy_trading.enabled {
+        info!(
+            "👥 Copy trading enabled for {} leader wallet(s)",
+            config.copy_trading.leaders.len()
+        );
+        // Note: Started once the buy engine is constructed
+        // let trader = copy_trading::CopyTrader::new(&config.copy_trading, buy_engine.clone(), candidate_tx.clone())?
+        //     .with_token_changes(rpc); // resolves Raydium swaps; an RpcClient built like the screener's
+        // Arc::new(trader).follow(&config.streaming.websocket_url).await?;
+    }
 
     // Create shared components for GUI integration
     #[cfg(feature = "gui_monitor")]
@@ -198,6 +343,14 @@ async fn main() -> Result<()> {
         std::time::Duration::from_millis(333), // 333ms refresh rate
     ));
     
+    // Note: On-chain prices for open positions would be produced here once the RPC pool is live
+    // let price_feed = Arc::new(components::onchain_price::OnchainPriceFeed::new(
+    //     components::onchain_price::OnchainPriceConfig::default(),
+    //     Arc::clone(&price_stream),
+    //     Arc::clone(&position_tracker),
+    // ));
+    // price_feed.spawn(Arc::clone(&rpc_pool));
+
     #[cfg(feature = "gui_monitor")]
     let bot_state = Arc::new(AtomicU8::new(1)); // 1 = Running
 
@@ -276,15 +429,44 @@ fn init_logging(verbose: bool) -> Result<()> {
     Ok(())
 }
 
-/// Load configuration from file with fallback to defaults
-fn load_config(path: &str) -> Result<Config> {
-    if std::path::Path::new(path).exists() {
-        Config::from_file_with_env(path)
-            .with_context(|| format!("Failed to load config from {}", path))
+/// Build the layered config loader: defaults, file, `BOT__*` env, CLI overrides
+fn config_loader(args: &Args) -> Result<config::ConfigLoader> {
+    let mut loader = config::ConfigLoader::new();
+    if std::path::Path::new(&args.config).exists() {
+        loader = loader.with_file(&args.config);
     } else {
-        warn!("Config file '{}' not found, using defaults", path);
-        Ok(Config::default())
+        warn!("Config file '{}' not found, using defaults", args.config);
+    }
+    let mut loader = loader.with_process_env().with_overrides(&args.overrides)?;
+    if let Some(port) = args.metrics_port {
+        loader = loader.with_override("monitoring.metrics_port", port.to_string());
+    }
+    Ok(loader)
+}
+
+/// Load configuration from file with fallback to defaults
+fn load_config(loader: &config::ConfigLoader, path: &str) -> Result<Config> {
+    loader
+        .load()
+        .with_context(|| format!("Failed to load config from {}", path))
+}
+
+/// Handle `--print-config` / `--check-config`
+fn run_config_command(args: &Args) -> Result<()> {
+    let config = match config_loader(args)?.load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if args.print_config {
+        print!("{}", config.to_redacted_toml()?);
     }
+    if args.check_config {
+        eprintln!("Configuration OK");
+    }
+    Ok(())
 }
 
 /// Main event loop
@@ -342,6 +524,7 @@ async fn run_event_loop(
     }
 
     info!("👋 Shutting down gracefully...");
+    // nonce_manager.save_snapshot(&store).await?;
     Ok(())
 }
 
@@ -361,87 +544,8 @@ async fn handle_gui_command(
     engine: &Arc<buy_engine::BuyEngine>,
     cmd: GuiCommand,
 ) -> Result<()> {
-    use components::gui_bridge::GuiCommand;
-    
-    match cmd {
-        GuiCommand::Sell { mint, percent } => {
-            info!(mint = %mint, percent = percent, "GUI: Manual sell requested");
-            engine.sell_manual(&mint, percent).await?;
-        }
-        
-        GuiCommand::SetTradingMode(mode) => {
-            info!(mode = ?mode, "GUI: Trading mode change requested");
-            engine.set_trading_mode(mode).await;
-        }
-        
-        GuiCommand::SetStopLoss { mint, threshold_percent } => {
-            info!(
-                mint = %mint,
-                threshold = threshold_percent,
-                "GUI: Stop loss configuration"
-            );
-            engine.set_stop_loss(mint, threshold_percent).await;
-        }
-        
-        GuiCommand::SetTakeProfit {
-            mint,
-            threshold_percent,
-            sell_percent,
-        } => {
-            info!(
-                mint = %mint,
-                threshold = threshold_percent,
-                sell_pct = sell_percent,
-                "GUI: Take profit configuration"
-            );
-            engine
-                .set_take_profit(mint, threshold_percent, sell_percent)
-                .await;
-        }
-        
-        GuiCommand::ClearStrategy { mint } => {
-            info!(mint = %mint, "GUI: Clear strategy requested");
-            engine.clear_strategy(&mint).await;
-        }
-        
-        GuiCommand::SetMultiTokenMode {
-            enabled,
-            max_positions,
-        } => {
-            info!(
-                enabled = enabled,
-                max_positions = ?max_positions,
-                "GUI: Multi-token mode change"
-            );
-            let mut st = engine.app_state.lock().await;
-            st.portfolio_config.enable_multi_token = enabled;
-            if let Some(max) = max_positions {
-                st.portfolio_config.max_concurrent_positions = max;
-            }
-        }
-        
-        GuiCommand::SetPaused(paused) => {
-            info!(paused = paused, "GUI: Pause state change");
-            let st = engine.app_state.lock().await;
-            *st.is_paused.write().await = paused;
-        }
-        
-        GuiCommand::EmergencyStop => {
-            warn!("GUI: Emergency stop requested");
-            // Emergency stop logic would go here
-            // For now, just pause trading
-            let st = engine.app_state.lock().await;
-            *st.is_paused.write().await = true;
-        }
-        
-        GuiCommand::UpdatePortfolioConfig(config) => {
-            info!(config = ?config, "GUI: Portfolio config update");
-            let mut st = engine.app_state.lock().await;
-            st.portfolio_config = config;
-        }
-    }
-    
-    Ok(())
+    info!(command = ?cmd, "GUI command received");
+    engine.handle_command(cmd).await
 }
 
 
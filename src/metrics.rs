@@ -1,11 +1,31 @@
 //! Metrics collection and export module
-
-use prometheus::{Histogram, HistogramOpts, IntCounter, IntGauge, Opts, Registry};
+//!
+//! Every metric is exposed through the one registry owned by [`Metrics`]:
+//! the process-wide series defined here, plus typed collectors that
+//! subsystems register via [`Metrics::register_source`] for state they keep
+//! themselves (sniffer counters, nonce telemetry, RPC endpoint health, buy
+//! engine outcomes). Labels are shared across subsystems: `endpoint` (host of
+//! an RPC URL), `program`, `reason`, and `region`, which the registry adds to
+//! every series when configured.
+//!
+//! Counters end in `_total` and durations are recorded in seconds.
+
+use crate::histogram::LatencyHistogramVec;
+use parking_lot::Mutex;
+use prometheus::core::{Collector, Desc};
+use prometheus::{
+    proto, Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    Opts, Registry, TextEncoder,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Global metrics registry
 pub struct Metrics {
     registry: Registry,
+    /// Names of every registered metric family
+    names: Mutex<Vec<String>>,
 
     // Counters
     pub trades_total: IntCounter,
@@ -33,61 +53,88 @@ pub struct Metrics {
     pub nonce_lease_lifetime: Histogram,
 
     // Task 5: Additional nonce and tx_builder metrics
-    pub acquire_lease_ms: Histogram,
-    pub prepare_bundle_ms: Histogram,
-    pub build_to_land_ms: Histogram,
+    pub acquire_lease: Histogram,
+    pub prepare_bundle: Histogram,
 
     // Task 5: Additional counters for nonce operations
     pub total_acquires: IntCounter,
     pub total_releases: IntCounter,
     pub total_refreshes: IntCounter,
     pub total_failures: IntCounter,
+
+    // Nonce pool usage by operation class
+    pub nonce_class_in_use: IntGaugeVec,
+    pub nonce_class_waiting: IntGaugeVec,
+    pub nonce_class_acquires: IntCounterVec,
+
+    // Nonce account integrity audit
+    pub nonce_integrity_alerts: IntCounterVec,
+
+    // Named events without a dedicated series, see `increment_counter`
+    pub events: IntCounterVec,
+
+    // Log-linear latency histograms, recorded in microseconds
+    pub sniff_to_buy_latency: LatencyHistogramVec,
+    pub build_to_land_latency: LatencyHistogramVec,
+    pub nonce_acquire_latency: LatencyHistogramVec,
+    pub nonce_refresh_latency: LatencyHistogramVec,
+    pub rpc_endpoint_latency: LatencyHistogramVec,
+    pub sniffer_stage_latency: LatencyHistogramVec,
 }
 
 impl Metrics {
     /// Create new metrics instance
     pub fn new() -> anyhow::Result<Self> {
-        let registry = Registry::new();
+        Self::with_region(None)
+    }
+
+    /// Create a metrics instance whose series all carry a `region` label
+    pub fn with_region(region: Option<&str>) -> anyhow::Result<Self> {
+        let labels =
+            region.map(|region| HashMap::from([("region".to_string(), region.to_string())]));
+        let registry = Registry::new_custom(None, labels)?;
 
         let trades_total = IntCounter::with_opts(Opts::new(
             "trades_total",
             "Total number of trades attempted",
         ))?;
 
-        let trades_success =
-            IntCounter::with_opts(Opts::new("trades_success", "Number of successful trades"))?;
+        let trades_success = IntCounter::with_opts(Opts::new(
+            "trades_success_total",
+            "Number of successful trades",
+        ))?;
 
         let trades_failed =
-            IntCounter::with_opts(Opts::new("trades_failed", "Number of failed trades"))?;
+            IntCounter::with_opts(Opts::new("trades_failed_total", "Number of failed trades"))?;
 
         let candidates_received = IntCounter::with_opts(Opts::new(
-            "candidates_received",
+            "candidates_received_total",
             "Number of candidates received from sniffer",
         ))?;
 
         let candidates_filtered = IntCounter::with_opts(Opts::new(
-            "candidates_filtered",
+            "candidates_filtered_total",
             "Number of candidates filtered out",
         ))?;
 
         // Nonce-related counters
         let nonce_leases_dropped_auto = IntCounter::with_opts(Opts::new(
-            "nonce_leases_dropped_auto",
+            "nonce_leases_dropped_auto_total",
             "Number of nonce leases auto-released via Drop",
         ))?;
 
         let nonce_leases_dropped_explicit = IntCounter::with_opts(Opts::new(
-            "nonce_leases_dropped_explicit",
+            "nonce_leases_dropped_explicit_total",
             "Number of nonce leases explicitly released",
         ))?;
 
         let nonce_sequence_errors = IntCounter::with_opts(Opts::new(
-            "nonce_sequence_errors",
+            "nonce_sequence_errors_total",
             "Number of nonce sequence violations (debug/test)",
         ))?;
 
         let nonce_enforce_paths = IntCounter::with_opts(Opts::new(
-            "nonce_enforce_paths",
+            "nonce_enforce_paths_total",
             "Counter for different code paths in nonce enforcement",
         ))?;
 
@@ -133,83 +180,116 @@ impl Metrics {
         )?;
 
         // Task 5: Additional nonce and tx_builder metrics
-        let acquire_lease_ms = Histogram::with_opts(
-            HistogramOpts::new(
-                "acquire_lease_ms",
-                "Time to acquire nonce lease in milliseconds",
-            )
-            .buckets(vec![0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0]),
+        let acquire_lease = Histogram::with_opts(
+            HistogramOpts::new("acquire_lease_seconds", "Time to acquire nonce lease")
+                .buckets(vec![0.0005, 0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1]),
         )?;
 
-        let prepare_bundle_ms = Histogram::with_opts(
+        let prepare_bundle = Histogram::with_opts(
             HistogramOpts::new(
-                "prepare_bundle_ms",
-                "Time to prepare bundle for submission in milliseconds",
+                "prepare_bundle_seconds",
+                "Time to prepare bundle for submission",
             )
-            .buckets(vec![1.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0]),
-        )?;
-
-        let build_to_land_ms = Histogram::with_opts(
-            HistogramOpts::new(
-                "build_to_land_ms",
-                "Total time from build to transaction landing in milliseconds",
-            )
-            .buckets(vec![
-                10.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 2000.0, 5000.0,
-            ]),
+            .buckets(vec![0.001, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5]),
         )?;
 
         // Task 5: Additional counters for nonce operations
         let total_acquires = IntCounter::with_opts(Opts::new(
-            "total_acquires",
+            "nonce_lease_acquires_total",
             "Total number of nonce lease acquisitions",
         ))?;
 
         let total_releases = IntCounter::with_opts(Opts::new(
-            "total_releases",
+            "nonce_lease_releases_total",
             "Total number of nonce lease releases",
         ))?;
 
         let total_refreshes = IntCounter::with_opts(Opts::new(
-            "total_refreshes",
+            "nonce_lease_refreshes_total",
             "Total number of nonce refreshes",
         ))?;
 
         let total_failures = IntCounter::with_opts(Opts::new(
-            "total_failures",
+            "nonce_operation_failures_total",
             "Total number of nonce operation failures",
         ))?;
 
-        // Register all metrics
-        registry.register(Box::new(trades_total.clone()))?;
-        registry.register(Box::new(trades_success.clone()))?;
-        registry.register(Box::new(trades_failed.clone()))?;
-        registry.register(Box::new(candidates_received.clone()))?;
-        registry.register(Box::new(candidates_filtered.clone()))?;
-        registry.register(Box::new(nonce_leases_dropped_auto.clone()))?;
-        registry.register(Box::new(nonce_leases_dropped_explicit.clone()))?;
-        registry.register(Box::new(nonce_sequence_errors.clone()))?;
-        registry.register(Box::new(nonce_enforce_paths.clone()))?;
-        registry.register(Box::new(active_trades.clone()))?;
-        registry.register(Box::new(nonce_pool_size.clone()))?;
-        registry.register(Box::new(rpc_connections.clone()))?;
-        registry.register(Box::new(nonce_active_leases.clone()))?;
-        registry.register(Box::new(trade_latency.clone()))?;
-        registry.register(Box::new(rpc_latency.clone()))?;
-        registry.register(Box::new(build_latency.clone()))?;
-        registry.register(Box::new(nonce_lease_lifetime.clone()))?;
-
-        // Task 5: Register additional metrics
-        registry.register(Box::new(acquire_lease_ms.clone()))?;
-        registry.register(Box::new(prepare_bundle_ms.clone()))?;
-        registry.register(Box::new(build_to_land_ms.clone()))?;
-        registry.register(Box::new(total_acquires.clone()))?;
-        registry.register(Box::new(total_releases.clone()))?;
-        registry.register(Box::new(total_refreshes.clone()))?;
-        registry.register(Box::new(total_failures.clone()))?;
-
-        Ok(Self {
+        let nonce_class_in_use = IntGaugeVec::new(
+            Opts::new("nonce_class_in_use", "Nonce leases held per operation class"),
+            &["class"],
+        )?;
+
+        let nonce_class_waiting = IntGaugeVec::new(
+            Opts::new(
+                "nonce_class_waiting",
+                "Nonce acquires queued per operation class",
+            ),
+            &["class"],
+        )?;
+
+        let nonce_class_acquires = IntCounterVec::new(
+            Opts::new(
+                "nonce_class_acquires_total",
+                "Nonce acquire outcomes per operation class",
+            ),
+            &["class", "outcome"],
+        )?;
+
+        let nonce_integrity_alerts = IntCounterVec::new(
+            Opts::new(
+                "nonce_integrity_alerts_total",
+                "Nonce accounts failing the on-chain integrity audit",
+            ),
+            &["violation"],
+        )?;
+
+        let events = IntCounterVec::new(
+            Opts::new(
+                "events_total",
+                "Named engine events without a dedicated series",
+            ),
+            &["event"],
+        )?;
+
+        let sniff_to_buy_latency = LatencyHistogramVec::new(
+            "sniff_to_buy_latency_seconds",
+            "Time from candidate detection to buy submission",
+            &[],
+        )?;
+
+        let build_to_land_latency = LatencyHistogramVec::new(
+            "build_to_land_latency_seconds",
+            "Time from transaction build to confirmation",
+            &[],
+        )?;
+
+        let nonce_acquire_latency = LatencyHistogramVec::new(
+            "nonce_acquire_latency_seconds",
+            "Time to acquire a nonce lease per operation class",
+            &["class"],
+        )?;
+
+        let nonce_refresh_latency = LatencyHistogramVec::new(
+            "nonce_refresh_latency_seconds",
+            "Time to confirm a nonce advance",
+            &[],
+        )?;
+
+        let rpc_endpoint_latency = LatencyHistogramVec::new(
+            "rpc_endpoint_latency_seconds",
+            "RPC request latency per endpoint host",
+            &["endpoint"],
+        )?;
+
+        let sniffer_stage_latency = LatencyHistogramVec::new(
+            "sniffer_stage_latency_seconds",
+            "Sniffer pipeline latency per stage",
+            &["stage"],
+        )?;
+
+        let metrics = Self {
             registry,
+            names: Mutex::new(Vec::new()),
             trades_total,
             trades_success,
             trades_failed,
@@ -227,14 +307,101 @@ impl Metrics {
             rpc_latency,
             build_latency,
             nonce_lease_lifetime,
-            acquire_lease_ms,
-            prepare_bundle_ms,
-            build_to_land_ms,
+            acquire_lease,
+            prepare_bundle,
             total_acquires,
             total_releases,
             total_refreshes,
             total_failures,
-        })
+            nonce_class_in_use,
+            nonce_class_waiting,
+            nonce_class_acquires,
+            nonce_integrity_alerts,
+            events,
+            sniff_to_buy_latency,
+            build_to_land_latency,
+            nonce_acquire_latency,
+            nonce_refresh_latency,
+            rpc_endpoint_latency,
+            sniffer_stage_latency,
+        };
+
+        // Register all metrics
+        let collectors: Vec<Box<dyn Collector>> = vec![
+            Box::new(metrics.trades_total.clone()),
+            Box::new(metrics.trades_success.clone()),
+            Box::new(metrics.trades_failed.clone()),
+            Box::new(metrics.candidates_received.clone()),
+            Box::new(metrics.candidates_filtered.clone()),
+            Box::new(metrics.nonce_leases_dropped_auto.clone()),
+            Box::new(metrics.nonce_leases_dropped_explicit.clone()),
+            Box::new(metrics.nonce_sequence_errors.clone()),
+            Box::new(metrics.nonce_enforce_paths.clone()),
+            Box::new(metrics.active_trades.clone()),
+            Box::new(metrics.nonce_pool_size.clone()),
+            Box::new(metrics.rpc_connections.clone()),
+            Box::new(metrics.nonce_active_leases.clone()),
+            Box::new(metrics.trade_latency.clone()),
+            Box::new(metrics.rpc_latency.clone()),
+            Box::new(metrics.build_latency.clone()),
+            Box::new(metrics.nonce_lease_lifetime.clone()),
+            // Task 5: Register additional metrics
+            Box::new(metrics.acquire_lease.clone()),
+            Box::new(metrics.prepare_bundle.clone()),
+            Box::new(metrics.total_acquires.clone()),
+            Box::new(metrics.total_releases.clone()),
+            Box::new(metrics.total_refreshes.clone()),
+            Box::new(metrics.total_failures.clone()),
+            Box::new(metrics.nonce_class_in_use.clone()),
+            Box::new(metrics.nonce_class_waiting.clone()),
+            Box::new(metrics.nonce_class_acquires.clone()),
+            Box::new(metrics.nonce_integrity_alerts.clone()),
+            Box::new(metrics.events.clone()),
+            Box::new(metrics.sniff_to_buy_latency.clone()),
+            Box::new(metrics.build_to_land_latency.clone()),
+            Box::new(metrics.nonce_acquire_latency.clone()),
+            Box::new(metrics.nonce_refresh_latency.clone()),
+            Box::new(metrics.rpc_endpoint_latency.clone()),
+            Box::new(metrics.sniffer_stage_latency.clone()),
+        ];
+        for collector in collectors {
+            metrics.register(collector)?;
+        }
+
+        Ok(metrics)
+    }
+
+    /// Register a collector with the shared registry
+    pub fn register(&self, collector: Box<dyn Collector>) -> prometheus::Result<()> {
+        let names: Vec<String> = collector
+            .desc()
+            .iter()
+            .map(|desc| desc.fq_name.clone())
+            .collect();
+        self.registry.register(collector)?;
+        self.names.lock().extend(names);
+        Ok(())
+    }
+
+    /// Register a subsystem's own state, read on every scrape
+    pub fn register_source<S: MetricSource>(&self, source: Arc<S>) -> prometheus::Result<()> {
+        self.register(Box::new(SourceCollector::new(source)?))
+    }
+
+    /// Names of all registered metric families, sorted
+    pub fn metric_names(&self) -> Vec<String> {
+        let mut names = self.names.lock().clone();
+        names.sort();
+        names
+    }
+
+    /// Render every registered metric in the Prometheus text format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!("Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
     }
 
     /// Get the registry for exporting
@@ -242,26 +409,63 @@ impl Metrics {
         &self.registry
     }
 
+    /// Record the outcome of a nonce acquire for an operation class
+    pub fn record_nonce_class_acquire(&self, class: &str, outcome: &str) {
+        self.nonce_class_acquires
+            .with_label_values(&[class, outcome])
+            .inc();
+    }
+
+    /// Update held and queued nonce counts for an operation class
+    pub fn set_nonce_class_usage(&self, class: &str, in_use: usize, waiting: usize) {
+        self.nonce_class_in_use
+            .with_label_values(&[class])
+            .set(in_use as i64);
+        self.nonce_class_waiting
+            .with_label_values(&[class])
+            .set(waiting as i64);
+    }
+
+    /// Count a nonce account failing the integrity audit
+    pub fn record_nonce_integrity_alert(&self, violation: &str) {
+        self.nonce_integrity_alerts
+            .with_label_values(&[violation])
+            .inc();
+    }
+
+    /// Record time to acquire a nonce lease for an operation class
+    pub fn record_nonce_acquire_latency(&self, class: &str, latency: Duration) {
+        self.nonce_acquire_latency
+            .with_label_values(&[class])
+            .record_duration(latency);
+    }
+
+    /// Record time for a nonce advance to confirm
+    pub fn record_nonce_refresh_latency(&self, latency: Duration) {
+        self.nonce_refresh_latency
+            .with_label_values(&[])
+            .record_duration(latency);
+    }
+
     /// Increment a named counter (for dynamic counter names)
-    /// Falls back to a no-op if the counter doesn't exist
+    ///
+    /// Names without a dedicated series are counted as
+    /// `events_total{event="<name>"}`, minus any `_total` suffix.
     pub fn increment_counter(&self, name: &str) {
         // Map common counter names to their fields
         match name {
             "trades_total" | "buy_attempts_total" => self.trades_total.inc(),
-            "trades_success" | "buy_success_total" => self.trades_success.inc(),
-            "trades_failed" | "buy_failure_total" => self.trades_failed.inc(),
-            "candidates_received" => self.candidates_received.inc(),
-            "candidates_filtered" | "buy_attempts_filtered" => self.candidates_filtered.inc(),
-            "nonce_leases_dropped_auto" => self.nonce_leases_dropped_auto.inc(),
-            "nonce_leases_dropped_explicit" => self.nonce_leases_dropped_explicit.inc(),
-            "nonce_sequence_errors" => self.nonce_sequence_errors.inc(),
-            "nonce_enforce_paths" => self.nonce_enforce_paths.inc(),
-            // For any other counter names that don't map to predefined counters,
-            // we silently ignore (could log a warning in the future)
+            "trades_success_total" | "buy_success_total" => self.trades_success.inc(),
+            "trades_failed_total" | "buy_failure_total" => self.trades_failed.inc(),
+            "candidates_received_total" => self.candidates_received.inc(),
+            "candidates_filtered_total" | "buy_attempts_filtered" => self.candidates_filtered.inc(),
+            "nonce_leases_dropped_auto_total" => self.nonce_leases_dropped_auto.inc(),
+            "nonce_leases_dropped_explicit_total" => self.nonce_leases_dropped_explicit.inc(),
+            "nonce_sequence_errors_total" => self.nonce_sequence_errors.inc(),
+            "nonce_enforce_paths_total" => self.nonce_enforce_paths.inc(),
             _ => {
-                // Use trades_total as a catch-all for unknown counters
-                // This allows the code to compile but may not give accurate metrics
-                tracing::debug!("Unknown counter name: {}", name);
+                let event = name.strip_suffix("_total").unwrap_or(name);
+                self.events.with_label_values(&[event]).inc();
             }
         }
     }
@@ -273,11 +477,152 @@ impl Default for Metrics {
     }
 }
 
+static METRICS: once_cell::sync::OnceCell<Metrics> = once_cell::sync::OnceCell::new();
+
+/// Initialize the global metrics with a `region` label on every series
+///
+/// Must run before the first [`metrics()`] call; fails otherwise.
+pub fn init_metrics(region: Option<&str>) -> anyhow::Result<()> {
+    METRICS
+        .set(Metrics::with_region(region)?)
+        .map_err(|_| anyhow::anyhow!("metrics already initialized"))
+}
+
 /// Global metrics instance
 pub fn metrics() -> &'static Metrics {
-    static METRICS: once_cell::sync::Lazy<Metrics> =
-        once_cell::sync::Lazy::new(|| Metrics::new().expect("Failed to initialize metrics"));
-    &METRICS
+    METRICS.get_or_init(|| Metrics::new().expect("Failed to initialize metrics"))
+}
+
+/// Subsystem state exported through the shared registry on every scrape
+///
+/// Implementations must describe the same families on every call (samples
+/// may come and go), since names and labels are registered up front.
+pub trait MetricSource: Send + Sync + 'static {
+    fn collect_metrics(&self, families: &mut FamilyBuilder);
+}
+
+/// Metric families assembled by a [`MetricSource`]
+#[derive(Debug, Default)]
+pub struct FamilyBuilder {
+    families: Vec<(proto::MetricFamily, Vec<String>)>,
+}
+
+impl FamilyBuilder {
+    /// Start a counter family with the given label names
+    pub fn counter(&mut self, name: &str, help: &str, labels: &[&str]) -> Family<'_> {
+        self.family(name, help, labels, proto::MetricType::COUNTER)
+    }
+
+    /// Start a gauge family with the given label names
+    pub fn gauge(&mut self, name: &str, help: &str, labels: &[&str]) -> Family<'_> {
+        self.family(name, help, labels, proto::MetricType::GAUGE)
+    }
+
+    fn family(
+        &mut self,
+        name: &str,
+        help: &str,
+        labels: &[&str],
+        kind: proto::MetricType,
+    ) -> Family<'_> {
+        let mut family = proto::MetricFamily::default();
+        family.set_name(name.to_string());
+        family.set_help(help.to_string());
+        family.set_field_type(kind);
+        let labels = labels.iter().map(|label| label.to_string()).collect();
+        self.families.push((family, labels));
+        let (family, labels) = self.families.last_mut().expect("just pushed");
+        Family { family, labels }
+    }
+
+    /// Names of the families built so far
+    pub fn names(&self) -> Vec<&str> {
+        self.families
+            .iter()
+            .map(|(family, _)| family.name())
+            .collect()
+    }
+
+    fn into_families(self) -> Vec<proto::MetricFamily> {
+        self.families
+            .into_iter()
+            .map(|(family, _)| family)
+            .collect()
+    }
+}
+
+/// One family being filled by [`FamilyBuilder`]
+pub struct Family<'a> {
+    family: &'a mut proto::MetricFamily,
+    labels: &'a [String],
+}
+
+impl Family<'_> {
+    /// Add a sample; `values` pair up with the family's label names
+    pub fn sample(&mut self, values: &[&str], value: f64) -> &mut Self {
+        debug_assert_eq!(values.len(), self.labels.len(), "{}", self.family.name());
+        let labels = self
+            .labels
+            .iter()
+            .zip(values)
+            .map(|(name, value)| {
+                let mut label = proto::LabelPair::default();
+                label.set_name(name.clone());
+                label.set_value(value.to_string());
+                label
+            })
+            .collect();
+        let mut metric = proto::Metric::from_label(labels);
+        if self.family.get_field_type() == proto::MetricType::COUNTER {
+            let mut counter = proto::Counter::default();
+            counter.set_value(value);
+            metric.set_counter(counter);
+        } else {
+            let mut gauge = proto::Gauge::default();
+            gauge.set_value(value);
+            metric.set_gauge(gauge);
+        }
+        self.family.mut_metric().push(metric);
+        self
+    }
+}
+
+/// Adapts a [`MetricSource`] to the registry
+struct SourceCollector<S> {
+    descs: Vec<Desc>,
+    source: Arc<S>,
+}
+
+impl<S: MetricSource> SourceCollector<S> {
+    fn new(source: Arc<S>) -> prometheus::Result<Self> {
+        let mut families = FamilyBuilder::default();
+        source.collect_metrics(&mut families);
+        let descs = families
+            .families
+            .iter()
+            .map(|(family, labels)| {
+                Desc::new(
+                    family.name().to_string(),
+                    family.help().to_string(),
+                    labels.clone(),
+                    HashMap::new(),
+                )
+            })
+            .collect::<prometheus::Result<_>>()?;
+        Ok(Self { descs, source })
+    }
+}
+
+impl<S: MetricSource> Collector for SourceCollector<S> {
+    fn desc(&self) -> Vec<&Desc> {
+        self.descs.iter().collect()
+    }
+
+    fn collect(&self) -> Vec<proto::MetricFamily> {
+        let mut families = FamilyBuilder::default();
+        self.source.collect_metrics(&mut families);
+        families.into_families()
+    }
 }
 
 /// Timer helper for measuring operation duration
@@ -320,7 +665,8 @@ impl Timer {
     /// Finish the timer and record to the associated histogram
     pub fn finish(self) {
         if let Some(name) = self.histogram_name {
-            let duration = self.start.elapsed().as_secs_f64();
+            let elapsed = self.start.elapsed();
+            let duration = elapsed.as_secs_f64();
             // Map histogram names to actual histograms
             match name.as_str() {
                 "buy_latency_seconds" | "trade_latency_seconds" => {
@@ -333,14 +679,17 @@ impl Timer {
                     metrics().build_latency.observe(duration);
                 }
                 // Task 5: New histogram mappings
-                "acquire_lease_ms" => {
-                    metrics().acquire_lease_ms.observe(duration * 1000.0);
+                "acquire_lease_seconds" => {
+                    metrics().acquire_lease.observe(duration);
                 }
-                "prepare_bundle_ms" => {
-                    metrics().prepare_bundle_ms.observe(duration * 1000.0);
+                "prepare_bundle_seconds" => {
+                    metrics().prepare_bundle.observe(duration);
                 }
-                "build_to_land_ms" => {
-                    metrics().build_to_land_ms.observe(duration * 1000.0);
+                "build_to_land_latency_seconds" => {
+                    metrics()
+                        .build_to_land_latency
+                        .with_label_values(&[])
+                        .record_duration(elapsed);
                 }
                 _ => {
                     tracing::debug!("Unknown histogram name: {}", name);
@@ -370,16 +719,8 @@ impl MetricsExporter {
 
     /// Export current metrics as JSON string
     pub fn export_json(&self) -> anyhow::Result<String> {
-        use prometheus::Encoder;
-
         let metrics = metrics();
-        let encoder = prometheus::TextEncoder::new();
-        let metric_families = metrics.registry().gather();
-        let mut buffer = Vec::new();
-        encoder.encode(&metric_families, &mut buffer)?;
-
-        // Convert to JSON-friendly format
-        let text_format = String::from_utf8(buffer)?;
+        let text_format = metrics.render();
 
         // Create a simple JSON structure with key metrics
         let json = serde_json::json!({
@@ -390,14 +731,14 @@ impl MetricsExporter {
             "metrics": {
                 "counters": {
                     "trades_total": metrics.trades_total.get(),
-                    "trades_success": metrics.trades_success.get(),
-                    "trades_failed": metrics.trades_failed.get(),
-                    "total_acquires": metrics.total_acquires.get(),
-                    "total_releases": metrics.total_releases.get(),
-                    "total_refreshes": metrics.total_refreshes.get(),
-                    "total_failures": metrics.total_failures.get(),
-                    "nonce_leases_dropped_auto": metrics.nonce_leases_dropped_auto.get(),
-                    "nonce_leases_dropped_explicit": metrics.nonce_leases_dropped_explicit.get(),
+                    "trades_success_total": metrics.trades_success.get(),
+                    "trades_failed_total": metrics.trades_failed.get(),
+                    "nonce_lease_acquires_total": metrics.total_acquires.get(),
+                    "nonce_lease_releases_total": metrics.total_releases.get(),
+                    "nonce_lease_refreshes_total": metrics.total_refreshes.get(),
+                    "nonce_operation_failures_total": metrics.total_failures.get(),
+                    "nonce_leases_dropped_auto_total": metrics.nonce_leases_dropped_auto.get(),
+                    "nonce_leases_dropped_explicit_total": metrics.nonce_leases_dropped_explicit.get(),
                 },
                 "gauges": {
                     "active_trades": metrics.active_trades.get(),
@@ -440,3 +781,151 @@ impl MetricsExporter {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nonce_manager::nonce_telemetry::NonceTelemetry;
+    use crate::rpc_manager::RpcPool;
+    use crate::sniffer::telemetry::SnifferMetrics;
+    use std::time::Duration;
+
+    /// Exported metric names are part of the dashboard and alerting contract;
+    /// renames must be deliberate and update this list.
+    const STABLE_NAMES: &[&str] = &[
+        "acquire_lease_seconds",
+        "active_trades",
+        "build_latency_seconds",
+        "build_to_land_latency_seconds",
+        "candidates_filtered_total",
+        "candidates_received_total",
+        "events_total",
+        "nonce_acquire_latency_seconds",
+        "nonce_acquire_total",
+        "nonce_active_leases",
+        "nonce_class_acquires_total",
+        "nonce_class_in_use",
+        "nonce_class_waiting",
+        "nonce_enforce_paths_total",
+        "nonce_integrity_alerts_total",
+        "nonce_lease_acquires_total",
+        "nonce_lease_expired_total",
+        "nonce_lease_lifetime_seconds",
+        "nonce_lease_refreshes_total",
+        "nonce_lease_releases_total",
+        "nonce_leases_dropped_auto_total",
+        "nonce_leases_dropped_explicit_total",
+        "nonce_leases_outstanding",
+        "nonce_operation_failures_total",
+        "nonce_pool_size",
+        "nonce_predictive_failure_prob",
+        "nonce_refresh_attempts_total",
+        "nonce_refresh_failures_total",
+        "nonce_refresh_latency_seconds",
+        "nonce_release_total",
+        "nonce_rotations_total",
+        "nonce_rpc_failures_total",
+        "nonce_rpc_latency_seconds",
+        "nonce_sequence_errors_total",
+        "nonce_tainted_total",
+        "prepare_bundle_seconds",
+        "rpc_active_requests",
+        "rpc_connections",
+        "rpc_endpoint_errors_total",
+        "rpc_endpoint_latency_seconds",
+        "rpc_endpoint_requests_total",
+        "rpc_endpoint_score",
+        "rpc_endpoint_status",
+        "rpc_latency_seconds",
+        "sniff_to_buy_latency_seconds",
+        "sniffer_backpressure_events_total",
+        "sniffer_candidates_dropped_total",
+        "sniffer_candidates_sent_total",
+        "sniffer_extract_errors_total",
+        "sniffer_reconnects_total",
+        "sniffer_stage_latency_seconds",
+        "sniffer_stream_buffer_depth",
+        "sniffer_transactions_filtered_total",
+        "sniffer_transactions_seen_total",
+        "sniffer_worker_panics_total",
+        "sniffer_worker_restarts_total",
+        "trade_latency_seconds",
+        "trades_failed_total",
+        "trades_success_total",
+        "trades_total",
+    ];
+
+    #[test]
+    fn test_metric_names_are_stable() {
+        let metrics = Metrics::new().unwrap();
+        metrics
+            .register_source(Arc::new(SnifferMetrics::new()))
+            .unwrap();
+        metrics
+            .register_source(Arc::new(NonceTelemetry::new()))
+            .unwrap();
+        metrics
+            .register_source(Arc::new(
+                RpcPool::new(
+                    Vec::new(),
+                    Duration::from_secs(30),
+                    3,
+                    Duration::from_millis(500),
+                )
+                .unwrap(),
+            ))
+            .unwrap();
+
+        assert_eq!(metrics.metric_names(), STABLE_NAMES);
+    }
+
+    #[test]
+    fn test_region_label_on_every_series() {
+        let metrics = Metrics::with_region(Some("fra")).unwrap();
+        metrics.trades_total.inc();
+        metrics.sniff_to_buy_latency.record(&[], 1_500);
+
+        let output = metrics.render();
+        assert!(output.contains("trades_total{region=\"fra\"} 1\n"));
+        assert!(output.contains("sniff_to_buy_latency_seconds_count{region=\"fra\"} 1\n"));
+    }
+
+    #[test]
+    fn test_unmapped_counter_names_count_as_events() {
+        let metrics = Metrics::new().unwrap();
+        metrics.increment_counter("copy_trade_buys_total");
+        metrics.increment_counter("presigned_exit_signed");
+        metrics.increment_counter("buy_success_total");
+
+        let output = metrics.render();
+        assert!(output.contains("events_total{event=\"copy_trade_buys\"} 1\n"));
+        assert!(output.contains("events_total{event=\"presigned_exit_signed\"} 1\n"));
+        assert!(output.contains("trades_success_total 1\n"));
+    }
+
+    struct Dropped(u64);
+
+    impl MetricSource for Dropped {
+        fn collect_metrics(&self, families: &mut FamilyBuilder) {
+            families
+                .counter("test_dropped_total", "Dropped items", &["reason"])
+                .sample(&["full"], self.0 as f64);
+            // Families without samples are left out of the scrape
+            families.gauge("test_idle", "Never sampled", &[]);
+        }
+    }
+
+    #[test]
+    fn test_source_rendered_on_scrape() {
+        let metrics = Metrics::new().unwrap();
+        metrics.register_source(Arc::new(Dropped(7))).unwrap();
+
+        let output = metrics.render();
+        assert!(output.contains("# TYPE test_dropped_total counter\n"));
+        assert!(output.contains("test_dropped_total{reason=\"full\"} 7\n"));
+        assert!(!output.contains("test_idle"));
+
+        // A second source with the same family is rejected
+        assert!(metrics.register_source(Arc::new(Dropped(1))).is_err());
+    }
+}
@@ -5,13 +5,17 @@
 #![allow(unused_imports)] // Allow unused imports for re-exports that may not be used in all contexts
 
 // Submodules
+pub mod nonce_auditor;
 pub mod nonce_authority;
 pub mod nonce_circuit_breaker;
+pub mod nonce_classes;
 pub mod nonce_errors;
 pub mod nonce_integration;
 pub mod nonce_lease;
 pub mod nonce_manager_integrated;
+pub mod nonce_persistence;
 pub mod nonce_predictive;
+pub mod nonce_provisioning;
 pub mod nonce_refresh;
 pub mod nonce_retry;
 pub mod nonce_security;
@@ -27,6 +31,7 @@ pub use nonce_manager_integrated::UniverseNonceManager; // Also export the origi
 #[cfg(feature = "zk_enabled")]
 pub use nonce_manager_integrated::ZkProofData;
 
+pub use nonce_classes::{ClassReservations, ClassStats, NonceClass};
 pub use nonce_errors::{NonceError, NonceResult};
 pub use nonce_lease::NonceLease;
 pub use nonce_signer::{LocalSigner, SignerService};
@@ -0,0 +1,424 @@
+//! On-chain nonce account integrity auditor
+//!
+//! Nonce accounts are trusted once they join the pool. The auditor
+//! periodically re-reads every account through the RPC pool and checks that
+//! it still exists, is an initialized system-owned nonce account, is
+//! controlled by the manager's signer and stores the blockhash leases are
+//! handed out with.
+//!
+//! Accounts failing the existence, state or authority checks are tainted and
+//! evicted. A stale blockhash is flagged and corrected instead: the bot's own
+//! transactions advance nonces as well, and evicting on every advance would
+//! drain the pool. Leased accounts are checked against the blockhash their
+//! lease was handed out with. Every finding is recorded as a security audit
+//! event (the most recent [`AUDIT_LOG_CAPACITY`] are kept) and counted in
+//! `nonce_integrity_alerts_total`.
+use super::nonce_errors::{NonceError, NonceResult};
+use super::nonce_manager_integrated::UniverseNonceManager;
+use super::nonce_security::{SecurityAuditLog, SecurityEventType};
+use crate::rpc_manager::rpc_pool::RpcPool;
+#[allow(deprecated)]
+use solana_sdk::system_program;
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    nonce::state::{State, Versions},
+    pubkey::Pubkey,
+};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Accounts per `getMultipleAccounts` request
+const AUDIT_CHUNK: usize = 100;
+
+/// Security audit entries kept by the auditor, oldest dropped first
+pub const AUDIT_LOG_CAPACITY: usize = 1_000;
+
+/// What the manager expects of one pooled nonce account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditTarget {
+    pub pubkey: Pubkey,
+    /// Blockhash new leases are handed out with
+    pub blockhash: Hash,
+    /// Blockhash the outstanding lease, if any, was handed out with
+    pub lease_blockhash: Option<Hash>,
+}
+
+/// A mismatch between a pooled nonce account and the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityViolation {
+    /// The account no longer exists
+    Closed,
+    /// The account is not owned by the system program or is not a nonce account
+    NotNonce,
+    /// The nonce account was de-initialized
+    Uninitialized,
+    /// The nonce authority was changed
+    AuthorityChanged { expected: Pubkey, actual: Pubkey },
+    /// The stored blockhash differs from the one leases are (or the
+    /// outstanding lease was) handed out with
+    BlockhashMismatch { expected: Hash, actual: Hash },
+}
+
+impl IntegrityViolation {
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntegrityViolation::Closed => "closed",
+            IntegrityViolation::NotNonce => "not_nonce",
+            IntegrityViolation::Uninitialized => "uninitialized",
+            IntegrityViolation::AuthorityChanged { .. } => "authority_changed",
+            IntegrityViolation::BlockhashMismatch { .. } => "blockhash_mismatch",
+        }
+    }
+
+    /// Whether the account can no longer be used and must leave the pool
+    pub fn requires_eviction(&self) -> bool {
+        !matches!(self, IntegrityViolation::BlockhashMismatch { .. })
+    }
+}
+
+impl std::fmt::Display for IntegrityViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityViolation::Closed => write!(f, "account closed"),
+            IntegrityViolation::NotNonce => write!(f, "not a nonce account"),
+            IntegrityViolation::Uninitialized => write!(f, "nonce account uninitialized"),
+            IntegrityViolation::AuthorityChanged { expected, actual } => {
+                write!(f, "authority changed from {} to {}", expected, actual)
+            }
+            IntegrityViolation::BlockhashMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "stored blockhash {} differs from expected {}",
+                    actual, expected
+                )
+            }
+        }
+    }
+}
+
+/// Check one fetched account against the manager's expectations
+pub fn check_account(
+    target: &AuditTarget,
+    authority: &Pubkey,
+    account: Option<&Account>,
+) -> Option<IntegrityViolation> {
+    let Some(account) = account else {
+        return Some(IntegrityViolation::Closed);
+    };
+    if account.owner != system_program::id() {
+        return Some(IntegrityViolation::NotNonce);
+    }
+    let Ok(versions) = bincode::deserialize::<Versions>(&account.data) else {
+        return Some(IntegrityViolation::NotNonce);
+    };
+    let data = match versions.state() {
+        State::Initialized(data) => data,
+        State::Uninitialized => return Some(IntegrityViolation::Uninitialized),
+    };
+
+    if data.authority != *authority {
+        return Some(IntegrityViolation::AuthorityChanged {
+            expected: *authority,
+            actual: data.authority,
+        });
+    }
+    let expected = target.lease_blockhash.unwrap_or(target.blockhash);
+    if data.blockhash() != expected {
+        return Some(IntegrityViolation::BlockhashMismatch {
+            expected,
+            actual: data.blockhash(),
+        });
+    }
+    None
+}
+
+/// Outcome of one audit pass
+#[derive(Debug, Default)]
+pub struct AuditReport {
+    pub audited: usize,
+    pub findings: Vec<(Pubkey, IntegrityViolation)>,
+    pub evicted: usize,
+}
+
+/// Periodic on-chain audit of the nonce pool
+pub struct NonceAuditor {
+    manager: Arc<UniverseNonceManager>,
+    rpc_pool: Arc<RpcPool>,
+    audit_log: Arc<RwLock<VecDeque<SecurityAuditLog>>>,
+}
+
+impl NonceAuditor {
+    pub fn new(manager: Arc<UniverseNonceManager>, rpc_pool: Arc<RpcPool>) -> Self {
+        Self {
+            manager,
+            rpc_pool,
+            audit_log: Arc::new(RwLock::new(VecDeque::new())),
+        }
+    }
+
+    /// Fetch every pooled account and act on mismatches
+    pub async fn audit_once(&self) -> NonceResult<AuditReport> {
+        let targets = self.manager.audit_targets().await;
+        let pubkeys: Vec<Pubkey> = targets.iter().map(|t| t.pubkey).collect();
+
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(AUDIT_CHUNK) {
+            let fetched = self
+                .rpc_pool
+                .get_multiple_accounts_batched(chunk, CommitmentConfig::confirmed())
+                .await
+                .map_err(|e| NonceError::Rpc {
+                    endpoint: None,
+                    message: e.to_string(),
+                })?;
+            accounts.extend(fetched);
+        }
+
+        Ok(self.apply(&targets, &accounts).await)
+    }
+
+    /// Compare fetched accounts with their targets, taint/evict or resync
+    async fn apply(&self, targets: &[AuditTarget], accounts: &[Option<Account>]) -> AuditReport {
+        let authority = self.manager.authority().await;
+        let mut report = AuditReport {
+            audited: targets.len(),
+            ..AuditReport::default()
+        };
+        let mut evict = Vec::new();
+
+        for (target, account) in targets.iter().zip(accounts) {
+            let Some(violation) = check_account(target, &authority, account.as_ref()) else {
+                continue;
+            };
+
+            let evicted = violation.requires_eviction();
+            if evicted {
+                evict.push(target.pubkey);
+            } else if let IntegrityViolation::BlockhashMismatch { actual, .. } = violation {
+                self.manager.resync_blockhash(&target.pubkey, actual).await;
+            }
+
+            warn!(
+                target: "audit",
+                nonce = %target.pubkey,
+                violation = %violation,
+                evicted,
+                "Nonce account integrity violation"
+            );
+            crate::metrics::metrics().record_nonce_integrity_alert(violation.as_str());
+            let mut audit_log = self.audit_log.write().await;
+            if audit_log.len() == AUDIT_LOG_CAPACITY {
+                audit_log.pop_front();
+            }
+            audit_log.push_back(SecurityAuditLog {
+                timestamp: SystemTime::now(),
+                event: SecurityEventType::NonceIntegrityViolation {
+                    nonce_account: target.pubkey,
+                    violation: violation.to_string(),
+                    evicted,
+                },
+            });
+            drop(audit_log);
+            report.findings.push((target.pubkey, violation));
+        }
+
+        if !evict.is_empty() {
+            report.evicted = self.manager.evict_accounts(&evict).await;
+        }
+        debug!(
+            audited = report.audited,
+            findings = report.findings.len(),
+            evicted = report.evicted,
+            "Nonce audit complete"
+        );
+        report
+    }
+
+    /// Audit every `interval` until the manager is dropped
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            info!(interval_secs = interval.as_secs(), "Nonce auditor started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if Arc::strong_count(&self.manager) == 1 {
+                    break;
+                }
+                if let Err(e) = self.audit_once().await {
+                    warn!(error = %e, "Nonce audit failed");
+                }
+            }
+        })
+    }
+
+    /// Most recent security audit entries, oldest first
+    pub async fn get_audit_log(&self) -> Vec<SecurityAuditLog> {
+        self.audit_log.read().await.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nonce_manager::LocalSigner;
+    use crate::rpc_manager::rpc_pool::{EndpointConfig, EndpointType};
+    use solana_sdk::nonce::state::{Data, DurableNonce};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn nonce_account(authority: &Pubkey, blockhash: &Hash) -> Account {
+        let durable = DurableNonce::from_blockhash(blockhash);
+        let state = State::Initialized(Data::new(*authority, durable, 5_000));
+        Account {
+            lamports: 1_447_680,
+            data: bincode::serialize(&Versions::new(state)).unwrap(),
+            owner: system_program::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn target(blockhash: Hash, lease_blockhash: Option<Hash>) -> AuditTarget {
+        AuditTarget {
+            pubkey: Pubkey::new_unique(),
+            blockhash,
+            lease_blockhash,
+        }
+    }
+
+    #[test]
+    fn test_check_account_classifies_violations() {
+        let authority = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let stored = *DurableNonce::from_blockhash(&blockhash).as_hash();
+        let idle = target(stored, None);
+
+        assert_eq!(
+            check_account(
+                &idle,
+                &authority,
+                Some(&nonce_account(&authority, &blockhash))
+            ),
+            None
+        );
+        assert_eq!(
+            check_account(&idle, &authority, None),
+            Some(IntegrityViolation::Closed)
+        );
+
+        let thief = Pubkey::new_unique();
+        assert_eq!(
+            check_account(&idle, &authority, Some(&nonce_account(&thief, &blockhash))),
+            Some(IntegrityViolation::AuthorityChanged {
+                expected: authority,
+                actual: thief
+            })
+        );
+
+        let mut uninitialized = nonce_account(&authority, &blockhash);
+        uninitialized.data = bincode::serialize(&Versions::new(State::Uninitialized)).unwrap();
+        assert_eq!(
+            check_account(&idle, &authority, Some(&uninitialized)),
+            Some(IntegrityViolation::Uninitialized)
+        );
+
+        // A leased account is held to the blockhash its lease was handed out with
+        let advanced_hash = Hash::new_unique();
+        let advanced = nonce_account(&authority, &advanced_hash);
+        assert!(matches!(
+            check_account(&idle, &authority, Some(&advanced)),
+            Some(IntegrityViolation::BlockhashMismatch { .. })
+        ));
+        let leased = target(Hash::new_unique(), Some(stored));
+        assert_eq!(
+            check_account(
+                &leased,
+                &authority,
+                Some(&nonce_account(&authority, &blockhash))
+            ),
+            None
+        );
+        assert_eq!(
+            check_account(&leased, &authority, Some(&advanced)),
+            Some(IntegrityViolation::BlockhashMismatch {
+                expected: stored,
+                actual: *DurableNonce::from_blockhash(&advanced_hash).as_hash()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_evicts_hijacked_and_resyncs_advanced() {
+        let keypair = Keypair::new();
+        let authority = keypair.pubkey();
+        let signer = Arc::new(LocalSigner::new(keypair));
+        let pubkeys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let manager = UniverseNonceManager::new_for_testing(
+            signer,
+            pubkeys.clone(),
+            Duration::from_secs(300),
+        )
+        .await;
+        let pool = Arc::new(
+            RpcPool::new(
+                vec![EndpointConfig {
+                    url: "http://localhost:8899".to_string(),
+                    endpoint_type: EndpointType::Standard,
+                    weight: 1.0,
+                    max_requests_per_second: 100,
+                    ..Default::default()
+                }],
+                Duration::from_secs(30),
+                3,
+                Duration::from_millis(500),
+            )
+            .unwrap(),
+        );
+        let auditor = NonceAuditor::new(manager.clone(), pool);
+
+        let targets = manager.audit_targets().await;
+        let advanced = Hash::new_unique();
+        let accounts = vec![
+            Some(nonce_account(&authority, &advanced)),
+            Some(nonce_account(&Pubkey::new_unique(), &Hash::new_unique())),
+            None,
+        ];
+
+        let report = auditor.apply(&targets, &accounts).await;
+        assert_eq!(report.findings.len(), 3);
+        assert_eq!(report.evicted, 2);
+
+        let remaining = manager.audit_targets().await;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].pubkey, pubkeys[0]);
+        assert_eq!(
+            remaining[0].blockhash,
+            *DurableNonce::from_blockhash(&advanced).as_hash()
+        );
+
+        let log = auditor.get_audit_log().await;
+        assert_eq!(log.len(), 3);
+        assert!(matches!(
+            log[1].event,
+            SecurityEventType::NonceIntegrityViolation { evicted: true, .. }
+        ));
+
+        // Only the most recent entries are kept
+        let stray = [target(Hash::new_unique(), None)];
+        let moved = [Some(nonce_account(&authority, &Hash::new_unique()))];
+        for _ in 0..AUDIT_LOG_CAPACITY {
+            auditor.apply(&stray, &moved).await;
+        }
+        let log = auditor.get_audit_log().await;
+        assert_eq!(log.len(), AUDIT_LOG_CAPACITY);
+        assert!(log.iter().all(|entry| matches!(
+            entry.event,
+            SecurityEventType::NonceIntegrityViolation { evicted: false, .. }
+        )));
+    }
+}
@@ -0,0 +1,445 @@
+//! Operation-class nonce reservations
+//!
+//! Every lease is taken on behalf of a [`NonceClass`]. The scheduler in this
+//! module sits in front of the pool semaphore and decides which class may take
+//! the next free nonce:
+//! - Each class can reserve nonces; a class may never take a nonce that would
+//!   leave a higher-priority class short of its (unfilled) reservation
+//! - Waiters are served highest class first, FIFO within a class
+//! - A waiting acquire preempts queued acquires of lower classes, which fail
+//!   with [`NonceError::Preempted`] so they can fall back to a blockhash
+use super::nonce_errors::{NonceError, NonceResult};
+use crate::metrics::metrics;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tracing::debug;
+
+/// Kind of operation a nonce lease is taken for, lowest priority first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceClass {
+    /// Refreshes, maintenance and other non-urgent transactions
+    Background,
+    /// Buys
+    Entry,
+    /// Sells, stop losses and emergency exits
+    Exit,
+}
+
+impl NonceClass {
+    /// All classes, highest priority first
+    pub const ALL: [NonceClass; 3] = [NonceClass::Exit, NonceClass::Entry, NonceClass::Background];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NonceClass::Background => "background",
+            NonceClass::Entry => "entry",
+            NonceClass::Exit => "exit",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for NonceClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Nonces held back for each class (none by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClassReservations {
+    pub exit: usize,
+    pub entry: usize,
+    pub background: usize,
+}
+
+impl ClassReservations {
+    pub fn get(&self, class: NonceClass) -> usize {
+        match class {
+            NonceClass::Exit => self.exit,
+            NonceClass::Entry => self.entry,
+            NonceClass::Background => self.background,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.exit + self.entry + self.background
+    }
+}
+
+/// Point-in-time view of one class
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassStats {
+    pub class: NonceClass,
+    pub reserved: usize,
+    pub in_use: usize,
+    pub waiting: usize,
+    pub granted: u64,
+    pub timed_out: u64,
+    pub preempted: u64,
+}
+
+struct Waiter {
+    id: u64,
+    class: NonceClass,
+    tx: oneshot::Sender<NonceResult<ClassPermit>>,
+}
+
+#[derive(Default)]
+struct ClassCounters {
+    granted: u64,
+    timed_out: u64,
+    preempted: u64,
+}
+
+struct SchedulerState {
+    capacity: usize,
+    reservations: ClassReservations,
+    in_use: [usize; 3],
+    waiters: Vec<Waiter>,
+    counters: [ClassCounters; 3],
+    next_waiter_id: u64,
+}
+
+impl SchedulerState {
+    fn free(&self) -> usize {
+        self.capacity.saturating_sub(self.in_use.iter().sum())
+    }
+
+    /// Whether `class` may take a nonce without eating into the unfilled
+    /// reservation of a higher class
+    fn can_admit(&self, class: NonceClass) -> bool {
+        let protected: usize = NonceClass::ALL
+            .iter()
+            .filter(|other| **other > class)
+            .map(|other| {
+                self.reservations
+                    .get(*other)
+                    .saturating_sub(self.in_use[other.index()])
+            })
+            .sum();
+        self.free() > protected
+    }
+
+    fn has_waiter_at_or_above(&self, class: NonceClass) -> bool {
+        self.waiters.iter().any(|waiter| waiter.class >= class)
+    }
+
+    fn admit(&mut self, class: NonceClass) {
+        self.in_use[class.index()] += 1;
+        self.counters[class.index()].granted += 1;
+        metrics().record_nonce_class_acquire(class.as_str(), "granted");
+    }
+
+    /// Hand free nonces to waiters, highest class first
+    fn dispatch(&mut self, scheduler: &Arc<ClassScheduler>) {
+        loop {
+            let next = self
+                .waiters
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, waiter)| (waiter.class, std::cmp::Reverse(waiter.id)))
+                .map(|(pos, waiter)| (pos, waiter.class));
+            let Some((pos, class)) = next else {
+                return;
+            };
+            // Lower classes are at least as constrained, so stop here
+            if !self.can_admit(class) {
+                return;
+            }
+            let waiter = self.waiters.remove(pos);
+            let permit = ClassPermit {
+                scheduler: Some(scheduler.clone()),
+                class,
+            };
+            match waiter.tx.send(Ok(permit)) {
+                Ok(()) => self.admit(class),
+                // The acquire was cancelled; the slot stays free
+                Err(Ok(mut permit)) => permit.scheduler = None,
+                Err(Err(_)) => {}
+            }
+        }
+    }
+
+    /// Fail every queued acquire of a class below `class`
+    fn preempt_below(&mut self, class: NonceClass) {
+        let (preempted, kept): (Vec<_>, Vec<_>) = self
+            .waiters
+            .drain(..)
+            .partition(|waiter| waiter.class < class);
+        self.waiters = kept;
+        for waiter in preempted {
+            debug!(class = %waiter.class, by = %class, "Queued nonce acquire preempted");
+            self.counters[waiter.class.index()].preempted += 1;
+            metrics().record_nonce_class_acquire(waiter.class.as_str(), "preempted");
+            let _ = waiter.tx.send(Err(NonceError::Preempted(class)));
+        }
+    }
+
+    fn publish(&self) {
+        for class in NonceClass::ALL {
+            let waiting = self.waiters.iter().filter(|w| w.class == class).count();
+            metrics().set_nonce_class_usage(class.as_str(), self.in_use[class.index()], waiting);
+        }
+    }
+}
+
+/// Admission control for the nonce pool by operation class
+pub struct ClassScheduler {
+    state: Mutex<SchedulerState>,
+}
+
+impl ClassScheduler {
+    pub fn new(capacity: usize, reservations: ClassReservations) -> Self {
+        Self {
+            state: Mutex::new(SchedulerState {
+                capacity,
+                reservations,
+                in_use: [0; 3],
+                waiters: Vec::new(),
+                counters: Default::default(),
+                next_waiter_id: 0,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SchedulerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Take a slot for `class` if one is free right now
+    pub fn try_acquire(self: &Arc<Self>, class: NonceClass) -> Option<ClassPermit> {
+        let mut state = self.lock();
+        if state.has_waiter_at_or_above(class) || !state.can_admit(class) {
+            metrics().record_nonce_class_acquire(class.as_str(), "rejected");
+            return None;
+        }
+        state.admit(class);
+        state.publish();
+        Some(self.permit(class))
+    }
+
+    /// Take a slot for `class`, waiting up to `timeout` for one to free up
+    pub async fn acquire(
+        self: &Arc<Self>,
+        class: NonceClass,
+        timeout: Duration,
+    ) -> NonceResult<ClassPermit> {
+        let (id, mut rx) = {
+            let mut state = self.lock();
+            if !state.has_waiter_at_or_above(class) && state.can_admit(class) {
+                state.admit(class);
+                state.publish();
+                return Ok(self.permit(class));
+            }
+            state.preempt_below(class);
+            let (tx, rx) = oneshot::channel();
+            let id = state.next_waiter_id;
+            state.next_waiter_id += 1;
+            state.waiters.push(Waiter { id, class, tx });
+            state.publish();
+            (id, rx)
+        };
+
+        match tokio::time::timeout(timeout, &mut rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(NonceError::Internal("Nonce scheduler dropped".to_string())),
+            Err(_) => {
+                let mut state = self.lock();
+                if let Some(pos) = state.waiters.iter().position(|w| w.id == id) {
+                    state.waiters.remove(pos);
+                    state.counters[class.index()].timed_out += 1;
+                    metrics().record_nonce_class_acquire(class.as_str(), "timeout");
+                    state.publish();
+                    return Err(NonceError::Timeout(timeout.as_millis() as u64));
+                }
+                drop(state);
+                // Served between the timeout firing and taking the lock
+                match rx.try_recv() {
+                    Ok(result) => result,
+                    Err(_) => Err(NonceError::Timeout(timeout.as_millis() as u64)),
+                }
+            }
+        }
+    }
+
+    fn permit(self: &Arc<Self>, class: NonceClass) -> ClassPermit {
+        ClassPermit {
+            scheduler: Some(self.clone()),
+            class,
+        }
+    }
+
+    fn release(self: &Arc<Self>, class: NonceClass) {
+        let mut state = self.lock();
+        state.in_use[class.index()] = state.in_use[class.index()].saturating_sub(1);
+        state.dispatch(self);
+        state.publish();
+    }
+
+    /// Resize after nonce accounts are added to or evicted from the pool
+    pub fn set_capacity(self: &Arc<Self>, capacity: usize) {
+        let mut state = self.lock();
+        state.capacity = capacity;
+        state.dispatch(self);
+        state.publish();
+    }
+
+    pub fn set_reservations(self: &Arc<Self>, reservations: ClassReservations) {
+        let mut state = self.lock();
+        state.reservations = reservations;
+        state.dispatch(self);
+        state.publish();
+    }
+
+    pub fn reservations(&self) -> ClassReservations {
+        self.lock().reservations
+    }
+
+    /// Per-class usage, highest priority first
+    pub fn stats(&self) -> Vec<ClassStats> {
+        let state = self.lock();
+        NonceClass::ALL
+            .iter()
+            .map(|&class| {
+                let counters = &state.counters[class.index()];
+                ClassStats {
+                    class,
+                    reserved: state.reservations.get(class),
+                    in_use: state.in_use[class.index()],
+                    waiting: state.waiters.iter().filter(|w| w.class == class).count(),
+                    granted: counters.granted,
+                    timed_out: counters.timed_out,
+                    preempted: counters.preempted,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A slot held by one class; handed back to the scheduler on drop
+pub struct ClassPermit {
+    /// `None` once the slot has been taken back without a release
+    scheduler: Option<Arc<ClassScheduler>>,
+    class: NonceClass,
+}
+
+impl ClassPermit {
+    pub fn class(&self) -> NonceClass {
+        self.class
+    }
+}
+
+impl Drop for ClassPermit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.scheduler.take() {
+            scheduler.release(self.class);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(capacity: usize, exit: usize, entry: usize) -> Arc<ClassScheduler> {
+        Arc::new(ClassScheduler::new(
+            capacity,
+            ClassReservations {
+                exit,
+                entry,
+                background: 0,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_reservation_is_kept_for_exits() {
+        let scheduler = scheduler(3, 1, 0);
+
+        let _a = scheduler.try_acquire(NonceClass::Entry).unwrap();
+        let _b = scheduler.try_acquire(NonceClass::Entry).unwrap();
+        assert!(scheduler.try_acquire(NonceClass::Entry).is_none());
+        assert!(scheduler.try_acquire(NonceClass::Background).is_none());
+
+        let exit = scheduler.try_acquire(NonceClass::Exit).unwrap();
+        assert_eq!(exit.class(), NonceClass::Exit);
+        assert!(scheduler.try_acquire(NonceClass::Exit).is_none());
+
+        let stats = scheduler.stats();
+        assert_eq!(stats[0].class, NonceClass::Exit);
+        assert_eq!((stats[0].in_use, stats[1].in_use), (1, 2));
+    }
+
+    #[test]
+    fn test_exits_may_use_lower_reservations() {
+        let scheduler = scheduler(2, 0, 1);
+
+        let _a = scheduler.try_acquire(NonceClass::Exit).unwrap();
+        assert!(scheduler.try_acquire(NonceClass::Background).is_none());
+        let _b = scheduler.try_acquire(NonceClass::Exit).unwrap();
+        assert!(scheduler.try_acquire(NonceClass::Entry).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_waiters_served_by_priority_and_lower_ones_preempted() {
+        let scheduler = scheduler(1, 0, 0);
+        let held = scheduler.try_acquire(NonceClass::Entry).unwrap();
+
+        let background = {
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move {
+                scheduler
+                    .acquire(NonceClass::Background, Duration::from_secs(5))
+                    .await
+                    .map(|permit| permit.class())
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let exit = {
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move {
+                scheduler
+                    .acquire(NonceClass::Exit, Duration::from_secs(5))
+                    .await
+                    .map(|permit| permit.class())
+            })
+        };
+
+        assert_eq!(
+            background.await.unwrap().unwrap_err(),
+            NonceError::Preempted(NonceClass::Exit)
+        );
+        drop(held);
+        assert_eq!(exit.await.unwrap().unwrap(), NonceClass::Exit);
+
+        let stats = scheduler.stats();
+        assert_eq!(stats[2].preempted, 1);
+        assert_eq!(stats.iter().map(|s| s.in_use).sum::<usize>(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_times_out_and_leaves_queue() {
+        let scheduler = scheduler(1, 0, 0);
+        let _held = scheduler.try_acquire(NonceClass::Exit).unwrap();
+
+        let err = scheduler
+            .acquire(NonceClass::Entry, Duration::from_millis(20))
+            .await
+            .err()
+            .expect("acquire should time out");
+        assert!(matches!(err, NonceError::Timeout(20)));
+
+        let entry = &scheduler.stats()[1];
+        assert_eq!((entry.waiting, entry.timed_out), (0, 1));
+    }
+}
@@ -1,3 +1,4 @@
+use super::nonce_classes::NonceClass;
 use solana_client::client_error::ClientError;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::SignerError;
@@ -149,6 +150,14 @@ pub enum NonceError {
     /// Solana client error
     #[error("Solana client error: {0}")]
     Client(String),
+
+    /// A queued acquire gave way to a higher-priority operation class
+    #[error("Nonce acquire preempted by a waiting {0} operation")]
+    Preempted(NonceClass),
+
+    /// Pool snapshot could not be read or written
+    #[error("Snapshot error: {0}")]
+    Snapshot(String),
 }
 
 impl NonceError {
@@ -163,6 +172,7 @@ impl NonceError {
             NonceError::NoLeaseAvailable => true, // Can retry after some leases are released
             NonceError::LeaseAcquireFailed(_) => true,
             NonceError::Client(_) => true,
+            NonceError::Preempted(_) => true,
 
             // Permanent errors that should not be retried
             NonceError::InvalidNonceAccount(_) => false,
@@ -175,6 +185,7 @@ impl NonceError {
             NonceError::LeaseExpired(_) => false,
             NonceError::LeaseReleaseFailed(_) => false,
             NonceError::Internal(_) => false,
+            NonceError::Snapshot(_) => false,
         }
     }
 
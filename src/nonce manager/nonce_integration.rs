@@ -15,6 +15,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, warn};
 
+use super::nonce_classes::ClassReservations;
 use super::nonce_errors::{NonceError, NonceResult};
 use super::nonce_lease::NonceLease;
 use super::nonce_signer::SignerService;
@@ -120,6 +121,9 @@ pub struct NonceManagerConfig {
 
     /// Enable predictive refresh
     pub enable_predictive_refresh: bool,
+
+    /// Nonces held back per operation class
+    pub reservations: ClassReservations,
 }
 
 impl Default for NonceManagerConfig {
@@ -130,6 +134,7 @@ impl Default for NonceManagerConfig {
             lease_ttl: Duration::from_secs(30),
             refresh_interval: Duration::from_secs(10),
             enable_predictive_refresh: true,
+            reservations: ClassReservations::default(),
         }
     }
 }
@@ -184,6 +189,11 @@ impl IntegratedNonceManager {
         });
     }
 
+    /// Telemetry collector, for registration with the metrics registry
+    pub fn telemetry(&self) -> Arc<NonceTelemetry> {
+        self.telemetry.clone()
+    }
+
     /// Background refresh loop
     async fn refresh_loop(&self) {
         let mut interval = tokio::time::interval(self.config.refresh_interval);
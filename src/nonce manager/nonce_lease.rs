@@ -138,6 +138,17 @@ impl NonceLease {
         self.last_valid_slot
     }
 
+    /// Slots left before `last_valid_slot` at `current_slot`
+    /// (e.g. [`SlotClock::current_slot`](crate::rpc_manager::SlotClock::current_slot))
+    pub fn slots_remaining(&self, current_slot: u64) -> u64 {
+        self.last_valid_slot.saturating_sub(current_slot)
+    }
+
+    /// Whether the nonce has reached its last valid slot at `current_slot`
+    pub fn is_slot_expired(&self, current_slot: u64) -> bool {
+        current_slot >= self.last_valid_slot
+    }
+
     /// Get the nonce blockhash value
     pub fn nonce_blockhash(&self) -> Hash {
         self.nonce_blockhash
@@ -348,7 +359,9 @@ pub struct LeaseWatchdog {
 #[derive(Clone)]
 struct LeaseInfo {
     nonce_pubkey: Pubkey,
+    nonce_blockhash: Hash,
     acquired_at: Instant,
+    timeout: Duration,
     released: Arc<RwLock<bool>>,
 }
 
@@ -367,18 +380,53 @@ impl LeaseWatchdog {
     pub async fn register_lease(
         &self,
         nonce_pubkey: Pubkey,
+        nonce_blockhash: Hash,
         acquired_at: Instant,
         released: Arc<RwLock<bool>>,
+    ) {
+        self.register_lease_with_timeout(
+            nonce_pubkey,
+            nonce_blockhash,
+            acquired_at,
+            self.lease_timeout,
+            released,
+        )
+        .await;
+    }
+
+    /// Register a lease that may be held longer than the watchdog's default timeout
+    ///
+    /// The lease is reclaimed after `timeout` or the default timeout, whichever
+    /// is longer.
+    pub async fn register_lease_with_timeout(
+        &self,
+        nonce_pubkey: Pubkey,
+        nonce_blockhash: Hash,
+        acquired_at: Instant,
+        timeout: Duration,
+        released: Arc<RwLock<bool>>,
     ) {
         let lease_info = LeaseInfo {
             nonce_pubkey,
+            nonce_blockhash,
             acquired_at,
+            timeout: timeout.max(self.lease_timeout),
             released,
         };
 
         self.leases.lock().await.push(lease_info);
     }
 
+    /// Blockhash of the unreleased lease on `nonce_pubkey`, if one is tracked
+    pub async fn leased_blockhash(&self, nonce_pubkey: &Pubkey) -> Option<Hash> {
+        for lease_info in self.leases.lock().await.iter() {
+            if lease_info.nonce_pubkey == *nonce_pubkey && !*lease_info.released.read().await {
+                return Some(lease_info.nonce_blockhash);
+            }
+        }
+        None
+    }
+
     /// Start the watchdog task
     pub async fn start<F>(self: Arc<Self>, on_expired: F)
     where
@@ -422,8 +470,8 @@ impl LeaseWatchdog {
 
                     // Check if expired
                     let elapsed = now.duration_since(lease_info.acquired_at);
-                    if elapsed >= self.lease_timeout {
-                        expired_leases.push(lease_info.nonce_pubkey);
+                    if elapsed >= lease_info.timeout {
+                        expired_leases.push((lease_info.nonce_pubkey, lease_info.timeout));
                         return false; // Remove from tracking
                     }
 
@@ -435,13 +483,13 @@ impl LeaseWatchdog {
                 // Handle expired leases
                 if !expired_leases.is_empty() {
                     let metrics = crate::metrics::metrics();
-                    for nonce_pubkey in expired_leases {
+                    for (nonce_pubkey, timeout) in expired_leases {
                         // Increment auto-drop counter for expired leases
                         metrics.nonce_leases_dropped_auto.inc();
 
                         warn!(
                             nonce = %nonce_pubkey,
-                            timeout_sec = self.lease_timeout.as_secs(),
+                            timeout_sec = timeout.as_secs(),
                             release_type = "watchdog_expired",
                             "Lease expired, reclaiming nonce account"
                         );
@@ -498,6 +546,22 @@ mod tests {
         assert_eq!(released.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn test_lease_slot_expiry() {
+        let lease = NonceLease::new(
+            Pubkey::new_unique(),
+            1000,
+            Hash::default(),
+            Duration::from_secs(60),
+            || {},
+        );
+
+        assert_eq!(lease.slots_remaining(990), 10);
+        assert!(!lease.is_slot_expired(999));
+        assert!(lease.is_slot_expired(1000));
+        assert_eq!(lease.slots_remaining(1005), 0);
+    }
+
     #[tokio::test]
     async fn test_lease_auto_release_on_drop() {
         let nonce_account = Pubkey::new_unique();
@@ -589,7 +653,12 @@ mod tests {
         // Register a lease
         let released = Arc::new(RwLock::new(false));
         watchdog
-            .register_lease(nonce_account, Instant::now(), released.clone())
+            .register_lease(
+                nonce_account,
+                Hash::default(),
+                Instant::now(),
+                released.clone(),
+            )
             .await;
 
         assert_eq!(watchdog.active_lease_count().await, 1);
@@ -604,6 +673,46 @@ mod tests {
         watchdog.stop().await;
     }
 
+    #[tokio::test]
+    async fn test_watchdog_honors_longer_lease_timeout() {
+        let short = Pubkey::new_unique();
+        let long = Pubkey::new_unique();
+        let expired = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let expired_clone = expired.clone();
+
+        let watchdog = Arc::new(LeaseWatchdog::new(
+            Duration::from_millis(50),
+            Duration::from_millis(100),
+        ));
+        watchdog
+            .clone()
+            .start(move |pubkey| {
+                expired_clone.lock().unwrap().push(pubkey);
+            })
+            .await;
+
+        let now = Instant::now();
+        watchdog
+            .register_lease(short, Hash::default(), now, Arc::new(RwLock::new(false)))
+            .await;
+        watchdog
+            .register_lease_with_timeout(
+                long,
+                Hash::default(),
+                now,
+                Duration::from_secs(60),
+                Arc::new(RwLock::new(false)),
+            )
+            .await;
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(*expired.lock().unwrap(), vec![short]);
+        assert_eq!(watchdog.active_lease_count().await, 1);
+
+        watchdog.stop().await;
+    }
+
     #[tokio::test]
     async fn test_watchdog_ignores_released_lease() {
         let nonce_account = Pubkey::new_unique();
@@ -626,7 +735,12 @@ mod tests {
         // Register a lease
         let released = Arc::new(RwLock::new(false));
         watchdog
-            .register_lease(nonce_account, Instant::now(), released.clone())
+            .register_lease(
+                nonce_account,
+                Hash::default(),
+                Instant::now(),
+                released.clone(),
+            )
             .await;
 
         // Mark as released immediately
@@ -777,7 +891,12 @@ mod tests {
         // Register a lease
         let released = Arc::new(RwLock::new(false));
         watchdog
-            .register_lease(nonce_account, Instant::now(), released.clone())
+            .register_lease(
+                nonce_account,
+                Hash::default(),
+                Instant::now(),
+                released.clone(),
+            )
             .await;
 
         // Wait for watchdog to detect expiry
@@ -7,14 +7,20 @@
 //! - Non-blocking refresh
 //! - Atomic slot validation
 //! - Hardened predictive model
+use super::nonce_auditor::AuditTarget;
+use super::nonce_classes::{ClassReservations, ClassScheduler, ClassStats, NonceClass};
 use super::nonce_errors::{NonceError, NonceResult};
 use super::nonce_lease::{LeaseWatchdog, NonceLease};
+use super::nonce_persistence::{PersistedNonce, PoolSnapshot, SnapshotStore};
 use super::nonce_predictive::UniversePredictiveModel;
+use super::nonce_provisioning::NonceAccountInfo;
 use super::nonce_refresh::{NonBlockingRefresh, RefreshStatus};
 use super::nonce_retry::{retry_with_backoff, RetryConfig};
 use super::nonce_signer::SignerService;
 
+use crate::metrics::metrics;
 use crate::rpc_manager::rpc_pool::RpcPool;
+use crate::rpc_manager::rpc_slot_clock::SlotClock;
 
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
@@ -683,10 +689,16 @@ pub struct UniverseNonceManager {
     // Optional RPC pool for smart endpoint selection
     rpc_pool: Option<Arc<RpcPool>>,
 
+    // Optional shared slot clock; replaces `getSlot` calls while live
+    slot_clock: Option<Arc<SlotClock>>,
+
     // Semaphore for pool management with atomic permit tracking
     available_permits: Arc<Semaphore>,
     permits_in_use: Arc<AtomicUsize>,
 
+    // Per-class reservations and priority ordering in front of the semaphore
+    class_scheduler: Arc<ClassScheduler>,
+
     // Multi-thread runtime handle for explicit spawn
     rt_handle: tokio::runtime::Handle,
 
@@ -766,13 +778,220 @@ impl UniverseNonceManager {
             ));
         }
 
-        let actual_pool_size = accounts_vec.len();
         info!(
             requested = pool_size,
-            actual = actual_pool_size,
+            actual = accounts_vec.len(),
             "Nonce pool initialized"
         );
 
+        Ok(Self::from_accounts(signer, rpc_client, rpc_endpoint, accounts_vec, retry_config).await)
+    }
+
+    /// Create a manager over existing nonce accounts (e.g. from `nonce-admin create`)
+    ///
+    /// Every account must be an initialized nonce account controlled by the signer.
+    pub async fn from_existing(
+        signer: Arc<dyn SignerService>,
+        rpc_client: Arc<RpcClient>,
+        rpc_endpoint: String,
+        pubkeys: &[Pubkey],
+    ) -> NonceResult<Self> {
+        if pubkeys.is_empty() {
+            return Err(NonceError::Configuration(
+                "Nonce pool file lists no accounts".to_string(),
+            ));
+        }
+
+        let retry_config = RetryConfig::default();
+        let authority = signer.pubkey().await;
+        let mut accounts_vec = VecDeque::with_capacity(pubkeys.len());
+        for loaded in Self::load_nonce_accounts(
+            &rpc_client,
+            &rpc_endpoint,
+            &authority,
+            pubkeys,
+            &retry_config,
+        )
+        .await?
+        {
+            accounts_vec.push_back(Arc::new(loaded?));
+        }
+
+        info!(
+            accounts = accounts_vec.len(),
+            "Nonce pool loaded from existing accounts"
+        );
+
+        Ok(Self::from_accounts(signer, rpc_client, rpc_endpoint, accounts_vec, retry_config).await)
+    }
+
+    /// Resume from a pool snapshot written by a previous run
+    ///
+    /// `pool_file` accounts (`nonce.accounts_file`) missing from the snapshot
+    /// are added to it. Accounts are re-read on-chain; ones that no longer
+    /// exist or changed authority are dropped. Taint state and the predictive
+    /// model are restored.
+    pub async fn from_snapshot(
+        signer: Arc<dyn SignerService>,
+        rpc_client: Arc<RpcClient>,
+        rpc_endpoint: String,
+        mut snapshot: PoolSnapshot,
+        pool_file: &[Pubkey],
+    ) -> NonceResult<Self> {
+        let merged = snapshot.merge_accounts(pool_file);
+        let retry_config = RetryConfig::default();
+        let authority = signer.pubkey().await;
+        let pubkeys: Vec<Pubkey> = snapshot.accounts.iter().map(|a| a.pubkey).collect();
+        let mut accounts_vec = VecDeque::with_capacity(pubkeys.len());
+
+        let loaded = Self::load_nonce_accounts(
+            &rpc_client,
+            &rpc_endpoint,
+            &authority,
+            &pubkeys,
+            &retry_config,
+        )
+        .await?;
+        for (persisted, loaded) in snapshot.accounts.iter().zip(loaded) {
+            match loaded {
+                Ok(account) => {
+                    account
+                        .is_tainted
+                        .store(persisted.tainted, Ordering::SeqCst);
+                    accounts_vec.push_back(Arc::new(account));
+                }
+                Err(e) => warn!(
+                    account = %persisted.pubkey,
+                    error = %e,
+                    "Dropping persisted nonce account"
+                ),
+            }
+        }
+
+        if accounts_vec.is_empty() {
+            return Err(NonceError::Snapshot(
+                "No persisted nonce account is still usable".to_string(),
+            ));
+        }
+
+        info!(
+            persisted = snapshot.accounts.len() - merged,
+            from_pool_file = merged,
+            restored = accounts_vec.len(),
+            tainted = accounts_vec
+                .iter()
+                .filter(|a| a.is_tainted.load(Ordering::SeqCst))
+                .count(),
+            "Nonce pool restored from snapshot"
+        );
+
+        let manager =
+            Self::from_accounts(signer, rpc_client, rpc_endpoint, accounts_vec, retry_config).await;
+        manager
+            .predictive_model
+            .lock()
+            .await
+            .restore(snapshot.model);
+        Ok(manager)
+    }
+
+    /// Fetch and check nonce accounts, one result per pubkey
+    async fn load_nonce_accounts(
+        rpc_client: &RpcClient,
+        endpoint: &str,
+        authority: &Pubkey,
+        pubkeys: &[Pubkey],
+        retry_config: &RetryConfig,
+    ) -> NonceResult<Vec<NonceResult<ImprovedNonceAccount>>> {
+        let mut loaded = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(100) {
+            let accounts = retry_with_backoff("get_nonce_accounts", retry_config, || async {
+                rpc_client
+                    .get_multiple_accounts_with_commitment(chunk, rpc_client.commitment())
+                    .await
+                    .map_err(|e| NonceError::from_client_error(e, Some(endpoint.to_string())))
+            })
+            .await?
+            .value;
+
+            loaded.extend(chunk.iter().zip(accounts).map(|(pubkey, account)| {
+                let account = account.ok_or_else(|| {
+                    NonceError::InvalidNonceAccount(format!("{} does not exist", pubkey))
+                })?;
+                let info = NonceAccountInfo::decode(*pubkey, &account).ok_or_else(|| {
+                    NonceError::InvalidNonceAccount(format!(
+                        "{} is not an initialized nonce account",
+                        pubkey
+                    ))
+                })?;
+                if info.authority != *authority {
+                    return Err(NonceError::InvalidNonceAccount(format!(
+                        "{} has authority {}, expected {}",
+                        pubkey, info.authority, authority
+                    )));
+                }
+                Ok(ImprovedNonceAccount::new(*pubkey, info.blockhash, 0))
+            }));
+        }
+        Ok(loaded)
+    }
+
+    /// Capture the pool and predictive model for persistence
+    pub async fn snapshot(&self) -> PoolSnapshot {
+        let mut accounts = Vec::new();
+        for account in self.accounts.read().await.iter() {
+            accounts.push(PersistedNonce {
+                pubkey: account.pubkey,
+                blockhash: *account.last_blockhash.read().await,
+                last_valid_slot: account.last_valid_slot.load(Ordering::SeqCst),
+                tainted: account.is_tainted.load(Ordering::SeqCst),
+            });
+        }
+        let model = self.predictive_model.lock().await.snapshot();
+        PoolSnapshot::new(accounts, model)
+    }
+
+    /// Write a snapshot to `store` (periodically and on shutdown)
+    pub async fn save_snapshot(&self, store: &SnapshotStore) -> NonceResult<()> {
+        let snapshot = self.snapshot().await;
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || store.save(&snapshot))
+            .await
+            .map_err(|e| NonceError::Internal(e.to_string()))?
+    }
+
+    /// Write snapshots to `store` every `interval`
+    pub fn spawn_snapshot_writer(
+        self: &Arc<Self>,
+        store: SnapshotStore,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                if let Err(e) = manager.save_snapshot(&store).await {
+                    warn!(error = %e, path = %store.path().display(), "Nonce snapshot failed");
+                }
+            }
+        })
+    }
+
+    /// Start the watchdog and circuits around an initialized account set
+    async fn from_accounts(
+        signer: Arc<dyn SignerService>,
+        rpc_client: Arc<RpcClient>,
+        rpc_endpoint: String,
+        accounts_vec: VecDeque<Arc<ImprovedNonceAccount>>,
+        retry_config: RetryConfig,
+    ) -> Self {
+        let actual_pool_size = accounts_vec.len();
+
         // Initialize watchdog (Step 3)
         let watchdog = Arc::new(LeaseWatchdog::new(
             Duration::from_secs(5),   // Check interval
@@ -804,14 +1023,19 @@ impl UniverseNonceManager {
         // Initialize ZK circuits (precompile for performance)
         let circuit_config = Self::init_circuits().await;
 
-        Ok(Self {
+        Self {
             accounts: Arc::new(RwLock::new(accounts_vec)),
             signer,
             rpc_client,
             rpc_endpoint,
-            rpc_pool: None, // Can be set later with set_rpc_pool()
+            rpc_pool: None,   // Can be set later with set_rpc_pool()
+            slot_clock: None, // Can be set later with set_slot_clock()
             available_permits: Arc::new(Semaphore::new(actual_pool_size)),
             permits_in_use: Arc::new(AtomicUsize::new(0)),
+            class_scheduler: Arc::new(ClassScheduler::new(
+                actual_pool_size,
+                ClassReservations::default(),
+            )),
             rt_handle: tokio::runtime::Handle::current(),
             watchdog,
             refresh_manager: Arc::new(NonBlockingRefresh::new()),
@@ -821,7 +1045,7 @@ impl UniverseNonceManager {
             total_acquires: AtomicU64::new(0),
             total_releases: AtomicU64::new(0),
             total_refreshes: AtomicU64::new(0),
-        })
+        }
     }
 
     /// Initialize ZK circuits with precompiled proving/verification keys
@@ -930,16 +1154,37 @@ impl UniverseNonceManager {
     }
 
     /// Acquire a nonce with lease model (Step 3)
-    #[instrument(skip(self))]
+    ///
+    /// Leases are taken as [`NonceClass::Background`]; trade paths go through
+    /// [`Self::acquire_nonce_for_class`] with their own class.
     pub async fn acquire_nonce_with_lease(
         &self,
         timeout: Duration,
         network_tps: u32,
+    ) -> NonceResult<NonceLease> {
+        self.acquire_nonce_for_class(NonceClass::Background, timeout, network_tps)
+            .await
+    }
+
+    /// Acquire a nonce on behalf of an operation class
+    ///
+    /// Waits behind queued acquires of the same or a higher class and never
+    /// takes a nonce reserved for a higher class.
+    #[instrument(skip(self))]
+    pub async fn acquire_nonce_for_class(
+        &self,
+        class: NonceClass,
+        timeout: Duration,
+        network_tps: u32,
     ) -> NonceResult<NonceLease> {
         self.total_acquires.fetch_add(1, Ordering::Relaxed);
+        let started = Instant::now();
+
+        let class_permit = self.class_scheduler.acquire(class, timeout).await?;
 
         // Acquire semaphore permit with timeout and track atomically
-        let permit = tokio::time::timeout(timeout, self.available_permits.acquire())
+        let remaining = timeout.saturating_sub(started.elapsed());
+        let permit = tokio::time::timeout(remaining, self.available_permits.acquire())
             .await
             .map_err(|_| NonceError::Timeout(timeout.as_millis() as u64))?
             .map_err(|_| NonceError::Internal("Semaphore closed".to_string()))?;
@@ -1014,7 +1259,12 @@ impl UniverseNonceManager {
 
         // Register with watchdog
         self.watchdog
-            .register_lease(account_pubkey, Instant::now(), released_for_watchdog)
+            .register_lease(
+                account_pubkey,
+                nonce_blockhash,
+                Instant::now(),
+                released_for_watchdog,
+            )
             .await;
 
         let lease = NonceLease::new(
@@ -1026,14 +1276,18 @@ impl UniverseNonceManager {
                 // Release permit back to pool and decrement atomic counter
                 permits.add_permits(1);
                 permits_in_use.fetch_sub(1, Ordering::SeqCst);
+                drop(class_permit);
             },
         );
 
         // Forget the permit (lease now owns it)
         permit.forget();
 
+        metrics().record_nonce_acquire_latency(class.as_str(), started.elapsed());
+
         debug!(
             account = %account_pubkey,
+            class = %class,
             last_valid_slot = last_valid_slot,
             nonce_blockhash = %nonce_blockhash,
             "Nonce lease acquired"
@@ -1065,10 +1319,32 @@ impl UniverseNonceManager {
     ///
     /// * `Some(NonceLease)` - Successfully acquired a nonce
     /// * `None` - No nonce available (pool exhausted or all accounts tainted)
-    #[instrument(skip(self))]
+    ///
+    /// Like [`Self::acquire_nonce_with_lease`], leases are taken as
+    /// [`NonceClass::Background`].
     pub async fn try_acquire_nonce(&self, ttl: Duration, network_tps: u32) -> Option<NonceLease> {
+        self.try_acquire_nonce_for_class(NonceClass::Background, ttl, network_tps)
+            .await
+    }
+
+    /// Non-blocking acquire on behalf of an operation class
+    ///
+    /// Returns `None` when the class would have to wait or would take a nonce
+    /// reserved for a higher class.
+    #[instrument(skip(self))]
+    pub async fn try_acquire_nonce_for_class(
+        &self,
+        class: NonceClass,
+        ttl: Duration,
+        network_tps: u32,
+    ) -> Option<NonceLease> {
         self.total_acquires.fetch_add(1, Ordering::Relaxed);
 
+        let Some(class_permit) = self.class_scheduler.try_acquire(class) else {
+            debug!(class = %class, "No nonce available for class");
+            return None;
+        };
+
         // Try to acquire semaphore permit without blocking (TOCTTOU-safe)
         let permit = match self.available_permits.try_acquire() {
             Ok(permit) => permit,
@@ -1164,9 +1440,15 @@ impl UniverseNonceManager {
         let released_flag = Arc::new(RwLock::new(false));
         let released_for_watchdog = released_flag.clone();
 
-        // Register with watchdog
+        // Register with watchdog; callers may hold the lease longer than the default
         self.watchdog
-            .register_lease(account_pubkey, Instant::now(), released_for_watchdog)
+            .register_lease_with_timeout(
+                account_pubkey,
+                nonce_blockhash,
+                Instant::now(),
+                ttl,
+                released_for_watchdog,
+            )
             .await;
 
         let lease = NonceLease::new(
@@ -1178,6 +1460,7 @@ impl UniverseNonceManager {
                 // Release permit back to pool and decrement atomic counter
                 permits.add_permits(1);
                 permits_in_use.fetch_sub(1, Ordering::SeqCst);
+                drop(class_permit);
             },
         );
 
@@ -1188,6 +1471,7 @@ impl UniverseNonceManager {
             account = %account_pubkey,
             last_valid_slot = last_valid_slot,
             nonce_blockhash = %nonce_blockhash,
+            class = %class,
             "Nonce lease acquired (try_acquire)"
         );
 
@@ -1285,6 +1569,10 @@ impl UniverseNonceManager {
                             }
                         }
 
+                        if let Some(latency) = result.telemetry.latency() {
+                            metrics().record_nonce_refresh_latency(latency);
+                        }
+
                         // Record telemetry in predictive model with full metrics (Step 6)
                         if let Some(latency_ms) = result.telemetry.latency_ms() {
                             let mut model = model.lock().await;
@@ -1336,6 +1624,13 @@ impl UniverseNonceManager {
 
         #[cfg(not(any(test, feature = "test_utils")))]
         {
+            if let Some(slot) = self
+                .slot_clock
+                .as_ref()
+                .and_then(|clock| clock.current_slot())
+            {
+                return Ok(slot);
+            }
             retry_with_backoff("get_current_slot", &self.retry_config, || async {
                 self.rpc_client
                     .get_slot()
@@ -1389,6 +1684,11 @@ impl UniverseNonceManager {
         self.rpc_pool = Some(rpc_pool);
     }
 
+    /// Set the shared slot clock used for nonce expiry checks
+    pub fn set_slot_clock(&mut self, slot_clock: Arc<SlotClock>) {
+        self.slot_clock = Some(slot_clock);
+    }
+
     /// Batch advance nonces for TxBuilder integration
     /// Groups multiple nonce advances into bundles for CU savings (~2k per group)
     /// Returns signatures for each batch
@@ -1504,16 +1804,22 @@ impl UniverseNonceManager {
             let task = tokio::spawn(async move {
                 let mut refreshed = 0;
 
-                // Select best RPC endpoint for this chunk (smart load balancing)
-                let rpc_client = if let Some(pool) = rpc_pool.as_ref() {
-                    pool.select_best_endpoint()
-                        .await
-                        .unwrap_or(fallback_rpc.clone())
-                } else {
-                    fallback_rpc.clone()
+                // Lease best RPC endpoint for this chunk (smart load balancing);
+                // the slot is released when the lease drops at the end of the task
+                let lease = match rpc_pool.as_ref() {
+                    Some(pool) => pool.acquire_endpoint().await,
+                    None => None,
+                };
+                let (rpc_client, current_slot) = match lease.as_ref() {
+                    Some(lease) => (
+                        lease.client().clone(),
+                        lease
+                            .call(|client| async move { client.get_slot().await })
+                            .await
+                            .ok(),
+                    ),
+                    None => (fallback_rpc.clone(), fallback_rpc.get_slot().await.ok()),
                 };
-
-                let current_slot = rpc_client.get_slot().await.ok();
 
                 // Use join_all for concurrent RPC calls within chunk
                 let update_futures: Vec<_> = chunk
@@ -1540,13 +1846,6 @@ impl UniverseNonceManager {
                     }
                 }
 
-                // Release RPC pool request counter if using pool
-                if rpc_pool.is_some() {
-                    if let Some(pool) = rpc_pool.as_ref() {
-                        pool.release_request();
-                    }
-                }
-
                 refreshed
             });
 
@@ -1580,14 +1879,15 @@ impl UniverseNonceManager {
         let nonce_pubkey = nonce_keypair.pubkey();
         let payer_pubkey = self.signer.pubkey().await;
 
-        // Select best RPC endpoint if pool is available
-        let rpc_client = if let Some(pool) = self.rpc_pool.as_ref() {
-            pool.select_best_endpoint()
-                .await
-                .unwrap_or_else(|| self.rpc_client.clone())
-        } else {
-            self.rpc_client.clone()
+        // Lease best RPC endpoint if pool is available
+        let lease = match self.rpc_pool.as_ref() {
+            Some(pool) => pool.acquire_endpoint().await,
+            None => None,
         };
+        let rpc_client = lease
+            .as_ref()
+            .map(|lease| lease.client().clone())
+            .unwrap_or_else(|| self.rpc_client.clone());
 
         // Create nonce account with retry
         let (blockhash, last_valid_slot) = Self::create_nonce_account_with_retry(
@@ -1599,6 +1899,7 @@ impl UniverseNonceManager {
             &self.retry_config,
         )
         .await?;
+        drop(lease);
 
         // Add to pool
         let new_account = Arc::new(ImprovedNonceAccount::new(
@@ -1612,6 +1913,7 @@ impl UniverseNonceManager {
 
         // Add permit to semaphore
         self.available_permits.add_permits(1);
+        self.class_scheduler.set_capacity(accounts.len());
 
         info!(
             nonce = %nonce_pubkey,
@@ -1657,6 +1959,7 @@ impl UniverseNonceManager {
 
         // Replace with kept accounts
         *accounts = accounts_to_keep;
+        self.class_scheduler.set_capacity(accounts.len());
 
         // If we evicted accounts, we need to adjust the semaphore
         // Note: We can't remove permits, so we just track this for monitoring
@@ -1672,6 +1975,62 @@ impl UniverseNonceManager {
         evicted_count
     }
 
+    /// Taint and remove specific accounts (e.g. after a failed integrity audit)
+    /// Returns number of accounts evicted
+    pub async fn evict_accounts(&self, pubkeys: &[Pubkey]) -> usize {
+        let mut accounts = self.accounts.write().await;
+        let before = accounts.len();
+        accounts.retain(|account| {
+            if !pubkeys.contains(&account.pubkey) {
+                return true;
+            }
+            account.is_tainted.store(true, Ordering::SeqCst);
+            false
+        });
+        let evicted = before - accounts.len();
+        self.class_scheduler.set_capacity(accounts.len());
+
+        if evicted > 0 {
+            info!(
+                evicted,
+                remaining = accounts.len(),
+                "Evicted nonce accounts from pool"
+            );
+        }
+        evicted
+    }
+
+    /// Nonce authority of every pooled account
+    pub async fn authority(&self) -> Pubkey {
+        self.signer.pubkey().await
+    }
+
+    /// Pooled accounts with the blockhash leases are handed out with
+    pub async fn audit_targets(&self) -> Vec<AuditTarget> {
+        let mut targets = Vec::new();
+        for account in self.accounts.read().await.iter() {
+            targets.push(AuditTarget {
+                pubkey: account.pubkey,
+                blockhash: *account.last_blockhash.read().await,
+                lease_blockhash: self.watchdog.leased_blockhash(&account.pubkey).await,
+            });
+        }
+        targets
+    }
+
+    /// Replace the stored blockhash of an account with the on-chain one
+    pub async fn resync_blockhash(&self, pubkey: &Pubkey, blockhash: Hash) {
+        if let Some(account) = self
+            .accounts
+            .read()
+            .await
+            .iter()
+            .find(|a| a.pubkey == *pubkey)
+        {
+            *account.last_blockhash.write().await = blockhash;
+        }
+    }
+
     /// Calculate adaptive refresh interval based on network conditions (Scalability Enhancement)
     pub fn calculate_adaptive_interval(&self, network_tps: u32, network_lag_ms: f64) -> Duration {
         let base_interval_secs = 4.0;
@@ -1760,6 +2119,17 @@ impl UniverseNonceManager {
         }
     }
 
+    /// Change how many nonces each operation class holds back
+    pub fn set_class_reservations(&self, reservations: ClassReservations) {
+        info!(?reservations, "Nonce class reservations updated");
+        self.class_scheduler.set_reservations(reservations);
+    }
+
+    /// Per-class pool usage, highest priority first
+    pub fn class_stats(&self) -> Vec<ClassStats> {
+        self.class_scheduler.stats()
+    }
+
     /// Get manager statistics with ML metrics
     pub async fn get_stats(&self) -> ManagerStats {
         let accounts = self.accounts.read().await;
@@ -1783,6 +2153,7 @@ impl UniverseNonceManager {
             model_has_sufficient_data: model_stats.has_sufficient_data,
             ml_accuracy: model_stats.ml_accuracy,
             avg_prediction_error: model_stats.avg_prediction_error,
+            classes: self.class_scheduler.stats(),
         }
     }
 
@@ -1843,8 +2214,13 @@ impl UniverseNonceManager {
             rpc_client,
             rpc_endpoint,
             rpc_pool: None,
+            slot_clock: None,
             available_permits: Arc::new(Semaphore::new(pool_size)),
             permits_in_use: Arc::new(AtomicUsize::new(0)),
+            class_scheduler: Arc::new(ClassScheduler::new(
+                pool_size,
+                ClassReservations::default(),
+            )),
             rt_handle: tokio::runtime::Handle::current(),
             watchdog,
             refresh_manager: Arc::new(NonBlockingRefresh::new()),
@@ -1871,6 +2247,7 @@ pub struct ManagerStats {
     pub model_has_sufficient_data: bool,
     pub ml_accuracy: f64,
     pub avg_prediction_error: f64,
+    pub classes: Vec<ClassStats>,
 }
 
 #[cfg(test)]
@@ -1958,6 +2335,7 @@ mod tests {
             model_has_sufficient_data: true,
             ml_accuracy: 0.95,
             avg_prediction_error: 0.05,
+            classes: Vec::new(),
         };
 
         assert_eq!(stats.permits_in_use, 2);
@@ -1967,6 +2345,101 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_exit_reservation_survives_entry_burst() {
+        let signer = Arc::new(crate::nonce_manager::LocalSigner::new(Keypair::new()));
+        let pubkeys = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let manager =
+            UniverseNonceManager::new_for_testing(signer, pubkeys, Duration::from_secs(300)).await;
+        manager.set_class_reservations(ClassReservations {
+            exit: 1,
+            ..Default::default()
+        });
+
+        let ttl = Duration::from_secs(30);
+        let mut entries = Vec::new();
+        while let Some(lease) = manager
+            .try_acquire_nonce_for_class(NonceClass::Entry, ttl, 2000)
+            .await
+        {
+            entries.push(lease);
+        }
+        assert_eq!(entries.len(), 2);
+
+        let exit = manager
+            .acquire_nonce_for_class(NonceClass::Exit, Duration::from_millis(100), 2000)
+            .await
+            .expect("reserved nonce for exit");
+        let classes = manager.class_stats();
+        assert_eq!((classes[0].class, classes[0].in_use), (NonceClass::Exit, 1));
+        assert_eq!(classes[1].in_use, 2);
+
+        drop(exit);
+        drop(entries);
+        assert!(manager.class_stats().iter().all(|c| c.in_use == 0));
+    }
+
+    #[tokio::test]
+    async fn test_untagged_leases_are_background() {
+        let signer = Arc::new(crate::nonce_manager::LocalSigner::new(Keypair::new()));
+        let pubkeys = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let manager =
+            UniverseNonceManager::new_for_testing(signer, pubkeys, Duration::from_secs(300)).await;
+        manager.set_class_reservations(ClassReservations {
+            exit: 1,
+            ..Default::default()
+        });
+
+        let lease = manager
+            .try_acquire_nonce(Duration::from_secs(30), 2000)
+            .await
+            .expect("unreserved nonce");
+        let classes = manager.class_stats();
+        assert_eq!(
+            (classes[2].class, classes[2].in_use),
+            (NonceClass::Background, 1)
+        );
+        assert!(manager
+            .try_acquire_nonce(Duration::from_secs(30), 2000)
+            .await
+            .is_none());
+
+        drop(lease);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_captures_pool_taint_and_model() {
+        let signer = Arc::new(crate::nonce_manager::LocalSigner::new(Keypair::new()));
+        let pubkeys: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let manager = UniverseNonceManager::new_for_testing(
+            signer,
+            pubkeys.clone(),
+            Duration::from_secs(300),
+        )
+        .await;
+        manager.accounts.read().await[1]
+            .is_tainted
+            .store(true, Ordering::SeqCst);
+        manager
+            .predictive_model
+            .lock()
+            .await
+            .record_refresh(120.0, 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path().join("nonce.snapshot"));
+        manager.save_snapshot(&store).await.unwrap();
+
+        let snapshot = store.load().unwrap().expect("snapshot written");
+        let persisted: Vec<_> = snapshot
+            .accounts
+            .iter()
+            .map(|a| (a.pubkey, a.tainted))
+            .collect();
+        assert_eq!(persisted, vec![(pubkeys[0], false), (pubkeys[1], true)]);
+        assert_eq!(snapshot.model.ema_latency, Some(120.0));
+    }
+
     // =========================================================================
     // ZK Proof Tests (Feature-Gated)
     // =========================================================================
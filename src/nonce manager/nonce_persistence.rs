@@ -0,0 +1,221 @@
+//! Nonce pool snapshots
+//!
+//! Versioned on-disk snapshot of the known nonce accounts, their taint state
+//! and the predictive model's learned parameters, so a restart resumes with
+//! the same pool and a trained model instead of rebuilding both.
+//!
+//! The file is a bincode envelope (magic, schema version, payload). A snapshot
+//! with another schema version is rejected rather than misread; writes go to a
+//! temporary file that is renamed into place.
+use super::nonce_errors::{NonceError, NonceResult};
+use super::nonce_predictive::ModelSnapshot;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{hash::Hash, pubkey::Pubkey};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// Current snapshot schema version; bump on any change to [`PoolSnapshot`]
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const SNAPSHOT_MAGIC: [u8; 4] = *b"NPSN";
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    magic: [u8; 4],
+    version: u32,
+    payload: Vec<u8>,
+}
+
+/// One known nonce account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedNonce {
+    pub pubkey: Pubkey,
+    pub blockhash: Hash,
+    pub last_valid_slot: u64,
+    pub tainted: bool,
+}
+
+/// Persisted state of a nonce manager
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    /// Seconds since UNIX epoch when the snapshot was taken
+    pub saved_at: u64,
+    pub accounts: Vec<PersistedNonce>,
+    pub model: ModelSnapshot,
+}
+
+impl PoolSnapshot {
+    pub fn new(accounts: Vec<PersistedNonce>, model: ModelSnapshot) -> Self {
+        let saved_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self {
+            saved_at,
+            accounts,
+            model,
+        }
+    }
+
+    /// Add pool-file accounts the snapshot does not know yet
+    ///
+    /// Accounts added to the pool file since the snapshot was taken join the
+    /// restored pool untainted; returns how many were added.
+    pub fn merge_accounts(&mut self, pubkeys: &[Pubkey]) -> usize {
+        let before = self.accounts.len();
+        for pubkey in pubkeys {
+            if !self
+                .accounts
+                .iter()
+                .any(|account| account.pubkey == *pubkey)
+            {
+                self.accounts.push(PersistedNonce {
+                    pubkey: *pubkey,
+                    blockhash: Hash::default(),
+                    last_valid_slot: 0,
+                    tainted: false,
+                });
+            }
+        }
+        self.accounts.len() - before
+    }
+
+    pub fn encode(&self) -> NonceResult<Vec<u8>> {
+        let payload = bincode::serialize(self).map_err(|e| NonceError::Snapshot(e.to_string()))?;
+        bincode::serialize(&Envelope {
+            magic: SNAPSHOT_MAGIC,
+            version: SNAPSHOT_VERSION,
+            payload,
+        })
+        .map_err(|e| NonceError::Snapshot(e.to_string()))
+    }
+
+    pub fn decode(bytes: &[u8]) -> NonceResult<Self> {
+        let envelope: Envelope = bincode::deserialize(bytes)
+            .map_err(|e| NonceError::Snapshot(format!("Unreadable snapshot: {}", e)))?;
+        if envelope.magic != SNAPSHOT_MAGIC {
+            return Err(NonceError::Snapshot(
+                "Not a nonce pool snapshot".to_string(),
+            ));
+        }
+        if envelope.version != SNAPSHOT_VERSION {
+            return Err(NonceError::Snapshot(format!(
+                "Snapshot schema version {} is not supported (expected {})",
+                envelope.version, SNAPSHOT_VERSION
+            )));
+        }
+        bincode::deserialize(&envelope.payload)
+            .map_err(|e| NonceError::Snapshot(format!("Corrupt snapshot: {}", e)))
+    }
+}
+
+/// Snapshot file location (`nonce.snapshot_path`)
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    path: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the snapshot; `None` if none has been written yet
+    pub fn load(&self) -> NonceResult<Option<PoolSnapshot>> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => PoolSnapshot::decode(&bytes).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(NonceError::Snapshot(format!(
+                "Failed to read {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Write the snapshot atomically
+    pub fn save(&self, snapshot: &PoolSnapshot) -> NonceResult<()> {
+        let bytes = snapshot.encode()?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, &bytes)
+            .and_then(|_| std::fs::rename(&tmp, &self.path))
+            .map_err(|e| {
+                NonceError::Snapshot(format!("Failed to write {}: {}", self.path.display(), e))
+            })?;
+        debug!(
+            path = %self.path.display(),
+            accounts = snapshot.accounts.len(),
+            bytes = bytes.len(),
+            "Nonce pool snapshot written"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nonce_manager::nonce_predictive::UniversePredictiveModel;
+
+    fn snapshot() -> PoolSnapshot {
+        let mut model = UniversePredictiveModel::new();
+        for i in 0..12 {
+            model.record_refresh_with_volume(100 + i, 80.0, 1500, 0.5, 2);
+        }
+        PoolSnapshot::new(
+            vec![PersistedNonce {
+                pubkey: Pubkey::new_unique(),
+                blockhash: Hash::new_unique(),
+                last_valid_slot: 42,
+                tainted: true,
+            }],
+            model.snapshot(),
+        )
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path().join("nonce-pool.snapshot"));
+        assert!(store.load().unwrap().is_none());
+
+        let snapshot = snapshot();
+        store.save(&snapshot).unwrap();
+        assert_eq!(store.load().unwrap(), Some(snapshot));
+    }
+
+    #[test]
+    fn test_merge_accounts_keeps_snapshot_state() {
+        let mut snapshot = snapshot();
+        let persisted = snapshot.accounts[0].clone();
+        let added = Pubkey::new_unique();
+
+        assert_eq!(snapshot.merge_accounts(&[persisted.pubkey, added]), 1);
+        assert_eq!(snapshot.accounts[0], persisted);
+        assert_eq!(snapshot.accounts[1].pubkey, added);
+        assert!(!snapshot.accounts[1].tainted);
+
+        assert_eq!(snapshot.merge_accounts(&[added]), 0);
+        assert_eq!(snapshot.accounts.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_rejects_other_schema_versions() {
+        let mut envelope: Envelope = bincode::deserialize(&snapshot().encode().unwrap()).unwrap();
+        envelope.version = SNAPSHOT_VERSION + 1;
+        let err = PoolSnapshot::decode(&bincode::serialize(&envelope).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("schema version"), "{err}");
+
+        envelope.magic = *b"JUNK";
+        let err = PoolSnapshot::decode(&bincode::serialize(&envelope).unwrap()).unwrap_err();
+        assert!(
+            err.to_string().contains("Not a nonce pool snapshot"),
+            "{err}"
+        );
+    }
+}
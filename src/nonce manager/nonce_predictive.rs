@@ -6,11 +6,12 @@
 //! - Outlier clipping
 //! - Bounded output (0.0 to 1.0)
 //! - Conservative fallback behavior
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use tracing::{debug, warn};
 
 /// Congestion state for RL Q-learning
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CongestionState {
     Low,    // TPS < 1000
     Medium, // TPS 1000-2500
@@ -30,7 +31,7 @@ impl CongestionState {
 }
 
 /// LSTM state for simple recurrent network
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LstmState {
     hidden: Vec<f64>, // Hidden state (layer_size=16)
     cell: Vec<f64>,   // Cell state (layer_size=16)
@@ -54,6 +55,36 @@ impl LstmState {
             bias: vec![0.0; bias_size],
         }
     }
+
+    fn same_shape(&self, other: &Self) -> bool {
+        self.hidden.len() == other.hidden.len()
+            && self.cell.len() == other.cell.len()
+            && self.weights_ih.len() == other.weights_ih.len()
+            && self.weights_hh.len() == other.weights_hh.len()
+            && self.bias.len() == other.bias.len()
+    }
+}
+
+/// Learned parameters of [`UniversePredictiveModel`], persisted across restarts
+///
+/// Tunables (EMA alpha, history bounds, RL rates) are not included; they come
+/// from the model's construction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelSnapshot {
+    pub history: Vec<(u64, f64, u32, f64)>,
+    pub latency_history: Vec<f64>,
+    pub slot_consumption_history: Vec<u64>,
+    pub ema_latency: Option<f64>,
+    pub ema_slot_consumption: Option<f64>,
+    pub regression_coeffs: [f64; 4],
+    pub lstm_state: LstmState,
+    pub rl_q_table: Vec<(CongestionState, u32, Vec<(u32, f64, f64)>)>,
+    pub lstm_weights: HashMap<String, Vec<f64>>,
+    pub training_counter: usize,
+    pub slot_range: (u64, u64),
+    pub latency_range: (f64, f64),
+    pub tps_range: (u32, u32),
+    pub volume_range: (f64, f64),
 }
 
 /// Universe Predictive Model with enhanced ML depth
@@ -854,6 +885,70 @@ impl UniversePredictiveModel {
     pub fn get_alpha_ema(&self) -> f64 {
         self.alpha_ema
     }
+
+    /// Capture learned parameters for persistence
+    pub fn snapshot(&self) -> ModelSnapshot {
+        ModelSnapshot {
+            history: self.history.iter().copied().collect(),
+            latency_history: self.latency_history.iter().copied().collect(),
+            slot_consumption_history: self.slot_consumption_history.iter().copied().collect(),
+            ema_latency: self.ema_latency,
+            ema_slot_consumption: self.ema_slot_consumption,
+            regression_coeffs: self.regression_coeffs,
+            lstm_state: self.lstm_state.clone(),
+            rl_q_table: self
+                .rl_q_table
+                .iter()
+                .map(|(&(state, failures), actions)| (state, failures, actions.clone()))
+                .collect(),
+            lstm_weights: self.lstm_weights.clone(),
+            training_counter: self.training_counter,
+            slot_range: self.slot_range,
+            latency_range: self.latency_range,
+            tps_range: self.tps_range,
+            volume_range: self.volume_range,
+        }
+    }
+
+    /// Restore learned parameters from a snapshot
+    ///
+    /// Histories are trimmed to this model's bounds; an LSTM state of a
+    /// different shape is ignored.
+    pub fn restore(&mut self, snapshot: ModelSnapshot) {
+        fn tail<T>(items: Vec<T>, max: usize) -> VecDeque<T> {
+            let skip = items.len().saturating_sub(max);
+            items.into_iter().skip(skip).collect()
+        }
+
+        self.history = tail(snapshot.history, self.max_history_size);
+        self.latency_history = tail(snapshot.latency_history, self.max_history_size);
+        self.slot_consumption_history =
+            tail(snapshot.slot_consumption_history, self.max_history_size);
+        self.ema_latency = snapshot.ema_latency;
+        self.ema_slot_consumption = snapshot.ema_slot_consumption;
+        self.regression_coeffs = snapshot.regression_coeffs;
+        if self.lstm_state.same_shape(&snapshot.lstm_state) {
+            self.lstm_state = snapshot.lstm_state;
+        } else {
+            warn!("Ignoring persisted LSTM state with a different shape");
+        }
+        self.rl_q_table = snapshot
+            .rl_q_table
+            .into_iter()
+            .map(|(state, failures, actions)| ((state, failures), actions))
+            .collect();
+        self.lstm_weights = snapshot.lstm_weights;
+        self.training_counter = snapshot.training_counter;
+        self.slot_range = snapshot.slot_range;
+        self.latency_range = snapshot.latency_range;
+        self.tps_range = snapshot.tps_range;
+        self.volume_range = snapshot.volume_range;
+        debug!(
+            samples = self.latency_history.len(),
+            rl_states = self.rl_q_table.len(),
+            "Restored predictive model"
+        );
+    }
 }
 
 impl Default for UniversePredictiveModel {
@@ -1219,4 +1314,31 @@ mod tests {
         let model_custom = UniversePredictiveModel::with_config(100, 10, 0.3);
         assert_eq!(model_custom.get_alpha_ema(), 0.3);
     }
+
+    #[test]
+    fn test_snapshot_restores_learned_state() {
+        let mut model = UniversePredictiveModel::new();
+        for i in 0..15 {
+            model.record_refresh_with_volume(1000 + i, 100.0 + i as f64, 2000, 1.0, 2);
+        }
+        model.predict_failure_probability(2000);
+        model.label_prediction_full(105.0, true, Some(2000), Some(1.0), 3, 0.1);
+
+        let bytes = bincode::serialize(&model.snapshot()).unwrap();
+        let mut restored = UniversePredictiveModel::new();
+        restored.restore(bincode::deserialize(&bytes).unwrap());
+
+        assert_eq!(restored.snapshot(), model.snapshot());
+        assert_eq!(restored.get_rl_table_size(), model.get_rl_table_size());
+        assert_eq!(
+            restored.predict_failure_probability(2000),
+            model.predict_failure_probability(2000)
+        );
+
+        // Histories are trimmed to the restoring model's bound
+        let mut small = UniversePredictiveModel::with_config(5, 3, 0.2);
+        small.restore(model.snapshot());
+        assert_eq!(small.get_history().len(), 5);
+        assert_eq!(small.get_history().back(), model.get_history().back());
+    }
 }
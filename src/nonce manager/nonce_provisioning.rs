@@ -0,0 +1,462 @@
+//! Nonce pool provisioning
+//!
+//! On-chain maintenance of durable nonce accounts, used by the `nonce-admin`
+//! binary:
+//! - Create and fund accounts under a given authority
+//! - Discover existing accounts with `getProgramAccounts` filtered by authority
+//! - Advance, withdraw from and close accounts (reclaiming rent)
+//! - Verify the on-chain pool against the pool file the bot loads at startup
+use super::nonce_errors::{NonceError, NonceResult};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::Instruction,
+    nonce::state::{State, Versions},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+#[allow(deprecated)]
+use solana_sdk::{system_instruction, system_program};
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// Size of a nonce account
+pub const NONCE_ACCOUNT_SIZE: usize = State::size();
+
+/// Offset of the authority in a nonce account: versions tag + state tag
+const AUTHORITY_OFFSET: usize = 8;
+
+/// Accounts per `getMultipleAccounts` request
+const FETCH_CHUNK: usize = 100;
+
+/// Decoded state of one initialized nonce account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceAccountInfo {
+    pub pubkey: Pubkey,
+    pub authority: Pubkey,
+    pub blockhash: Hash,
+    pub lamports: u64,
+}
+
+impl NonceAccountInfo {
+    /// Decode a system-owned nonce account; `None` if it is not initialized
+    pub fn decode(pubkey: Pubkey, account: &Account) -> Option<Self> {
+        if account.owner != system_program::id() {
+            return None;
+        }
+        let versions: Versions = bincode::deserialize(&account.data).ok()?;
+        match versions.state() {
+            State::Initialized(data) => Some(Self {
+                pubkey,
+                authority: data.authority,
+                blockhash: data.blockhash(),
+                lamports: account.lamports,
+            }),
+            State::Uninitialized => None,
+        }
+    }
+}
+
+/// Pool file listing the nonce accounts the bot uses (`nonce.accounts_file`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoncePoolFile {
+    /// Nonce authority (base58)
+    pub authority: String,
+    /// Nonce accounts (base58)
+    pub accounts: Vec<String>,
+}
+
+impl NoncePoolFile {
+    pub fn new(authority: &Pubkey, accounts: &[Pubkey]) -> Self {
+        Self {
+            authority: authority.to_string(),
+            accounts: accounts.iter().map(Pubkey::to_string).collect(),
+        }
+    }
+
+    pub fn load(path: &Path) -> NonceResult<Self> {
+        let data = std::fs::read(path).map_err(|e| {
+            NonceError::Configuration(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_slice(&data).map_err(|e| {
+            NonceError::Configuration(format!("Invalid pool file {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> NonceResult<()> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| NonceError::Internal(e.to_string()))?;
+        std::fs::write(path, json + "\n").map_err(|e| {
+            NonceError::Configuration(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn authority(&self) -> NonceResult<Pubkey> {
+        parse_pubkey(&self.authority)
+    }
+
+    pub fn pubkeys(&self) -> NonceResult<Vec<Pubkey>> {
+        self.accounts.iter().map(|a| parse_pubkey(a)).collect()
+    }
+}
+
+fn parse_pubkey(value: &str) -> NonceResult<Pubkey> {
+    value
+        .parse()
+        .map_err(|_| NonceError::Configuration(format!("Invalid pubkey: {}", value)))
+}
+
+/// A difference between the pool file and the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolIssue {
+    /// Listed account does not exist
+    Missing(Pubkey),
+    /// Listed account exists but is not an initialized nonce account
+    NotNonce(Pubkey),
+    /// Listed account is controlled by another authority
+    WrongAuthority { account: Pubkey, authority: Pubkey },
+    /// Account under the authority that the pool file does not list
+    Unlisted(Pubkey),
+}
+
+impl std::fmt::Display for PoolIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PoolIssue::Missing(account) => write!(f, "{}: account does not exist", account),
+            PoolIssue::NotNonce(account) => {
+                write!(f, "{}: not an initialized nonce account", account)
+            }
+            PoolIssue::WrongAuthority { account, authority } => {
+                write!(f, "{}: authority is {}", account, authority)
+            }
+            PoolIssue::Unlisted(account) => write!(f, "{}: not in the pool file", account),
+        }
+    }
+}
+
+/// Compare the pool file against fetched and discovered accounts
+///
+/// `listed` holds the fetch result for each account in the file (`None` if it
+/// does not exist), `discovered` every nonce account under the authority.
+pub fn verify_pool(
+    authority: &Pubkey,
+    listed: &[(Pubkey, Option<Account>)],
+    discovered: &[NonceAccountInfo],
+) -> Vec<PoolIssue> {
+    let mut issues = Vec::new();
+    for (pubkey, account) in listed {
+        let Some(account) = account else {
+            issues.push(PoolIssue::Missing(*pubkey));
+            continue;
+        };
+        match NonceAccountInfo::decode(*pubkey, account) {
+            None => issues.push(PoolIssue::NotNonce(*pubkey)),
+            Some(info) if info.authority != *authority => issues.push(PoolIssue::WrongAuthority {
+                account: *pubkey,
+                authority: info.authority,
+            }),
+            Some(_) => {}
+        }
+    }
+
+    let known: HashSet<Pubkey> = listed.iter().map(|(pubkey, _)| *pubkey).collect();
+    issues.extend(
+        discovered
+            .iter()
+            .filter(|info| !known.contains(&info.pubkey))
+            .map(|info| PoolIssue::Unlisted(info.pubkey)),
+    );
+    issues
+}
+
+/// On-chain nonce account maintenance
+///
+/// Read-only operations (discover, fetch, verify) need no keys; everything
+/// that sends a transaction needs a fee payer set with [`Self::with_payer`].
+pub struct NonceProvisioner {
+    rpc: RpcClient,
+    payer: Option<Keypair>,
+}
+
+impl NonceProvisioner {
+    pub fn new(rpc: RpcClient) -> Self {
+        Self { rpc, payer: None }
+    }
+
+    pub fn with_payer(mut self, payer: Keypair) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    fn payer(&self) -> NonceResult<&Keypair> {
+        self.payer.as_ref().ok_or_else(|| {
+            NonceError::Configuration("A fee payer is required for this operation".to_string())
+        })
+    }
+
+    fn rpc_error(&self, e: solana_client::client_error::ClientError) -> NonceError {
+        NonceError::from_client_error(e, Some(self.rpc.url()))
+    }
+
+    /// Rent-exempt balance of a nonce account
+    pub async fn rent_exempt_lamports(&self) -> NonceResult<u64> {
+        self.rpc
+            .get_minimum_balance_for_rent_exemption(NONCE_ACCOUNT_SIZE)
+            .await
+            .map_err(|e| self.rpc_error(e))
+    }
+
+    async fn send(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> NonceResult<Signature> {
+        let payer = self.payer()?;
+        let blockhash = self
+            .rpc
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| self.rpc_error(e))?;
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.rpc
+            .send_and_confirm_transaction(&tx)
+            .await
+            .map_err(|e| self.rpc_error(e))
+    }
+
+    /// Create and fund `count` nonce accounts controlled by `authority`
+    ///
+    /// `lamports` defaults to the rent-exempt minimum. Stops at the first
+    /// failure and returns the accounts created so far in the error message.
+    pub async fn create(
+        &self,
+        count: usize,
+        authority: &Pubkey,
+        lamports: Option<u64>,
+    ) -> NonceResult<Vec<Pubkey>> {
+        let lamports = match lamports {
+            Some(lamports) => lamports,
+            None => self.rent_exempt_lamports().await?,
+        };
+
+        let payer = self.payer()?.pubkey();
+        let mut created = Vec::with_capacity(count);
+        for index in 0..count {
+            let nonce = Keypair::new();
+            let instructions = system_instruction::create_nonce_account(
+                &payer,
+                &nonce.pubkey(),
+                authority,
+                lamports,
+            );
+            if let Err(e) = self.send(&instructions, &[&nonce]).await {
+                let done: Vec<String> = created.iter().map(Pubkey::to_string).collect();
+                return Err(NonceError::Internal(format!(
+                    "Creating nonce account {} of {} failed: {} (created: [{}])",
+                    index + 1,
+                    count,
+                    e,
+                    done.join(", ")
+                )));
+            }
+            info!(nonce = %nonce.pubkey(), authority = %authority, lamports, "Nonce account created");
+            created.push(nonce.pubkey());
+        }
+        Ok(created)
+    }
+
+    /// Every initialized nonce account controlled by `authority`
+    pub async fn discover(&self, authority: &Pubkey) -> NonceResult<Vec<NonceAccountInfo>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(NONCE_ACCOUNT_SIZE as u64),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    AUTHORITY_OFFSET,
+                    authority.as_ref(),
+                )),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(&system_program::id(), config)
+            .await
+            .map_err(|e| self.rpc_error(e))?;
+
+        let mut found: Vec<NonceAccountInfo> = accounts
+            .iter()
+            .filter_map(|(pubkey, account)| NonceAccountInfo::decode(*pubkey, account))
+            .filter(|info| info.authority == *authority)
+            .collect();
+        found.sort_by_key(|info| info.pubkey.to_string());
+        debug!(authority = %authority, found = found.len(), "Nonce accounts discovered");
+        Ok(found)
+    }
+
+    /// Fetch the listed accounts (`None` for accounts that do not exist)
+    pub async fn fetch(&self, pubkeys: &[Pubkey]) -> NonceResult<Vec<(Pubkey, Option<Account>)>> {
+        let mut fetched = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(FETCH_CHUNK) {
+            let accounts = self
+                .rpc
+                .get_multiple_accounts_with_commitment(chunk, self.rpc.commitment())
+                .await
+                .map_err(|e| self.rpc_error(e))?
+                .value;
+            fetched.extend(chunk.iter().copied().zip(accounts));
+        }
+        Ok(fetched)
+    }
+
+    /// Verify the pool file against the chain
+    pub async fn verify(&self, pool: &NoncePoolFile) -> NonceResult<Vec<PoolIssue>> {
+        let authority = pool.authority()?;
+        let listed = self.fetch(&pool.pubkeys()?).await?;
+        let discovered = self.discover(&authority).await?;
+        Ok(verify_pool(&authority, &listed, &discovered))
+    }
+
+    /// Advance a nonce, invalidating transactions signed against it
+    pub async fn advance(&self, nonce: &Pubkey, authority: &Keypair) -> NonceResult<Signature> {
+        let instruction = system_instruction::advance_nonce_account(nonce, &authority.pubkey());
+        self.send(&[instruction], &[authority]).await
+    }
+
+    /// Withdraw `lamports` from a nonce account to `to`
+    pub async fn withdraw(
+        &self,
+        nonce: &Pubkey,
+        authority: &Keypair,
+        to: &Pubkey,
+        lamports: u64,
+    ) -> NonceResult<Signature> {
+        let instruction =
+            system_instruction::withdraw_nonce_account(nonce, &authority.pubkey(), to, lamports);
+        self.send(&[instruction], &[authority]).await
+    }
+
+    /// Withdraw the whole balance, closing the account; returns the lamports reclaimed
+    pub async fn close(
+        &self,
+        nonce: &Pubkey,
+        authority: &Keypair,
+        to: &Pubkey,
+    ) -> NonceResult<u64> {
+        let lamports = self
+            .rpc
+            .get_balance(nonce)
+            .await
+            .map_err(|e| self.rpc_error(e))?;
+        if lamports == 0 {
+            return Err(NonceError::InvalidNonceAccount(format!(
+                "{} has no balance",
+                nonce
+            )));
+        }
+        self.withdraw(nonce, authority, to, lamports).await?;
+        info!(nonce = %nonce, lamports, to = %to, "Nonce account closed");
+        Ok(lamports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::nonce::state::{Data, DurableNonce};
+
+    fn nonce_account(authority: &Pubkey) -> Account {
+        let durable = DurableNonce::from_blockhash(&Hash::new_unique());
+        let state = State::Initialized(Data::new(*authority, durable, 5_000));
+        Account {
+            lamports: 1_447_680,
+            data: bincode::serialize(&Versions::new(state)).unwrap(),
+            owner: system_program::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_decode_reads_authority_at_filter_offset() {
+        let authority = Pubkey::new_unique();
+        let account = nonce_account(&authority);
+        assert_eq!(account.data.len(), NONCE_ACCOUNT_SIZE);
+        assert_eq!(
+            &account.data[AUTHORITY_OFFSET..AUTHORITY_OFFSET + 32],
+            authority.as_ref()
+        );
+
+        let info = NonceAccountInfo::decode(Pubkey::new_unique(), &account).unwrap();
+        assert_eq!(info.authority, authority);
+
+        let mut not_system = account.clone();
+        not_system.owner = Pubkey::new_unique();
+        assert!(NonceAccountInfo::decode(Pubkey::new_unique(), &not_system).is_none());
+    }
+
+    #[test]
+    fn test_verify_pool_reports_each_kind_of_drift() {
+        let authority = Pubkey::new_unique();
+        let (ok, missing, foreign, plain, unlisted) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let other = Pubkey::new_unique();
+        let listed = vec![
+            (ok, Some(nonce_account(&authority))),
+            (missing, None),
+            (foreign, Some(nonce_account(&other))),
+            (plain, Some(Account::new(1, 0, &system_program::id()))),
+        ];
+        let discovered = [ok, unlisted]
+            .map(|pubkey| NonceAccountInfo::decode(pubkey, &nonce_account(&authority)).unwrap());
+
+        let issues = verify_pool(&authority, &listed, &discovered);
+        assert_eq!(
+            issues,
+            vec![
+                PoolIssue::Missing(missing),
+                PoolIssue::WrongAuthority {
+                    account: foreign,
+                    authority: other
+                },
+                PoolIssue::NotNonce(plain),
+                PoolIssue::Unlisted(unlisted),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pool_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nonce-pool.json");
+        let accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let pool = NoncePoolFile::new(&Pubkey::new_unique(), &accounts);
+
+        pool.save(&path).unwrap();
+        let loaded = NoncePoolFile::load(&path).unwrap();
+        assert_eq!(loaded, pool);
+        assert_eq!(loaded.pubkeys().unwrap(), accounts);
+    }
+}
@@ -27,9 +27,14 @@ pub struct RefreshTelemetry {
 }
 
 impl RefreshTelemetry {
-    pub fn latency_ms(&self) -> Option<u64> {
+    /// Time from send to confirmation, if confirmed
+    pub fn latency(&self) -> Option<Duration> {
         self.confirmed_at
-            .map(|confirmed| confirmed.duration_since(self.started_at).as_millis() as u64)
+            .map(|confirmed| confirmed.duration_since(self.started_at))
+    }
+
+    pub fn latency_ms(&self) -> Option<u64> {
+        self.latency().map(|latency| latency.as_millis() as u64)
     }
 }
 
@@ -23,6 +23,7 @@ use tracing::{debug, error, info, instrument, warn};
 use zeroize::Zeroize;
 
 use super::nonce_errors::{NonceError, NonceResult};
+use crate::keystore::{self, PassphraseSource};
 
 /// Secure keypair wrapper with automatic zeroization
 #[derive(Debug)]
@@ -63,6 +64,16 @@ impl SecureKeypair {
         Ok(Self { inner: keypair })
     }
 
+    /// Load from an encrypted keystore or plaintext keypair file
+    ///
+    /// Uses the same loader as the trading wallet, including the file
+    /// permission check.
+    pub fn from_keystore(path: &Path, passphrase: &PassphraseSource) -> NonceResult<Self> {
+        let secret = keystore::load_keypair_bytes(path, passphrase)
+            .map_err(|e| NonceError::Signing(e.to_string()))?;
+        Self::from_bytes(secret.to_vec())
+    }
+
     /// Get the public key
     pub fn pubkey(&self) -> Pubkey {
         self.inner.pubkey()
@@ -269,6 +280,11 @@ pub enum SecurityEventType {
         new_authority: Pubkey,
         rotation_count: u64,
     },
+    NonceIntegrityViolation {
+        nonce_account: Pubkey,
+        violation: String,
+        evicted: bool,
+    },
 }
 
 /// Security audit log entry
@@ -812,6 +828,32 @@ mod tests {
         // secure is dropped here, memory should be zeroized
     }
 
+    #[test]
+    fn test_secure_keypair_from_keystore() {
+        use crate::keystore::{EncryptedKeystore, KdfParams};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("authority.keystore");
+        let keypair = Keypair::new();
+        let params = KdfParams {
+            m_cost_kib: 8,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        EncryptedKeystore::encrypt(&keypair, "authority-pw", params)
+            .unwrap()
+            .save(&path)
+            .unwrap();
+
+        std::env::set_var("NONCE_AUTHORITY_TEST_PASSPHRASE", "authority-pw");
+        let source = PassphraseSource::Env("NONCE_AUTHORITY_TEST_PASSPHRASE".to_string());
+        let secure = SecureKeypair::from_keystore(&path, &source).unwrap();
+        assert_eq!(secure.pubkey(), keypair.pubkey());
+
+        std::env::set_var("NONCE_AUTHORITY_TEST_PASSPHRASE", "wrong");
+        assert!(SecureKeypair::from_keystore(&path, &source).is_err());
+    }
+
     #[tokio::test]
     async fn test_rbac_role_assignment() {
         let rbac = RbacManager::new();
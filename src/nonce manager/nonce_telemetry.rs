@@ -5,6 +5,8 @@
 //! - Instrumentation with unique request_id and nonce_id
 //! - Alerting rules and thresholds
 //! - SLA metrics tracking
+use crate::histogram::WindowedHistogram;
+use crate::metrics::{FamilyBuilder, MetricSource};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -62,51 +64,6 @@ impl NonceCounters {
             rpc_failures_total: AtomicU64::new(0),
         }
     }
-
-    /// Export counters as Prometheus-compatible format
-    pub fn export_prometheus(&self) -> String {
-        format!(
-            "# HELP nonce_refresh_attempts_total Total number of nonce refresh attempts\n\
-             # TYPE nonce_refresh_attempts_total counter\n\
-             nonce_refresh_attempts_total {}\n\
-             \n\
-             # HELP nonce_refresh_failures_total Total number of nonce refresh failures\n\
-             # TYPE nonce_refresh_failures_total counter\n\
-             nonce_refresh_failures_total {}\n\
-             \n\
-             # HELP nonce_tainted_total Total number of tainted nonces detected\n\
-             # TYPE nonce_tainted_total counter\n\
-             nonce_tainted_total {}\n\
-             \n\
-             # HELP nonce_rotations_total Total number of nonce authority rotations\n\
-             # TYPE nonce_rotations_total counter\n\
-             nonce_rotations_total {}\n\
-             \n\
-             # HELP nonce_acquire_total Total number of nonce acquisitions\n\
-             # TYPE nonce_acquire_total counter\n\
-             nonce_acquire_total {}\n\
-             \n\
-             # HELP nonce_release_total Total number of nonce releases\n\
-             # TYPE nonce_release_total counter\n\
-             nonce_release_total {}\n\
-             \n\
-             # HELP nonce_lease_expired_total Total number of lease expirations\n\
-             # TYPE nonce_lease_expired_total counter\n\
-             nonce_lease_expired_total {}\n\
-             \n\
-             # HELP nonce_rpc_failures_total Total number of RPC failures\n\
-             # TYPE nonce_rpc_failures_total counter\n\
-             nonce_rpc_failures_total {}\n",
-            self.refresh_attempts_total.load(Ordering::Relaxed),
-            self.refresh_failures_total.load(Ordering::Relaxed),
-            self.tainted_total.load(Ordering::Relaxed),
-            self.rotations_total.load(Ordering::Relaxed),
-            self.acquire_total.load(Ordering::Relaxed),
-            self.release_total.load(Ordering::Relaxed),
-            self.lease_expired_total.load(Ordering::Relaxed),
-            self.rpc_failures_total.load(Ordering::Relaxed),
-        )
-    }
 }
 
 impl Default for NonceCounters {
@@ -116,77 +73,42 @@ impl Default for NonceCounters {
 }
 
 /// Histogram for tracking latency distributions
-#[derive(Debug)]
+///
+/// Backed by a lock-free log-linear histogram; percentiles cover the last
+/// five minutes.
+#[derive(Debug, Default)]
 pub struct LatencyHistogram {
-    samples: Arc<RwLock<VecDeque<f64>>>,
-    max_samples: usize,
+    histogram: WindowedHistogram,
 }
 
 impl LatencyHistogram {
-    pub fn new(max_samples: usize) -> Self {
-        Self {
-            samples: Arc::new(RwLock::new(VecDeque::with_capacity(max_samples))),
-            max_samples,
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 
     /// Record a latency sample in seconds
-    pub async fn record(&self, latency_seconds: f64) {
-        let mut samples = self.samples.write().await;
-        samples.push_back(latency_seconds);
-        if samples.len() > self.max_samples {
-            samples.pop_front();
-        }
+    pub fn record(&self, latency_seconds: f64) {
+        // Saturating cast: negative and NaN samples land in the zero bucket
+        self.histogram
+            .record((latency_seconds * 1_000_000.0) as u64);
     }
 
     /// Calculate percentiles
-    pub async fn percentiles(&self) -> HistogramPercentiles {
-        let samples = self.samples.read().await;
-
-        if samples.is_empty() {
-            return HistogramPercentiles::default();
-        }
-
-        let mut sorted: Vec<f64> = samples.iter().copied().collect();
-        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-        let p50_idx = (sorted.len() as f64 * 0.50) as usize;
-        let p95_idx = (sorted.len() as f64 * 0.95) as usize;
-        let p99_idx = (sorted.len() as f64 * 0.99) as usize;
+    pub fn percentiles(&self) -> HistogramPercentiles {
+        let snapshot = self.histogram.recent();
+        let seconds = |q: f64| {
+            snapshot
+                .value_at_quantile(q)
+                .map_or(0.0, |micros| micros as f64 / 1_000_000.0)
+        };
 
         HistogramPercentiles {
-            p50: sorted.get(p50_idx).copied().unwrap_or(0.0),
-            p95: sorted.get(p95_idx).copied().unwrap_or(0.0),
-            p99: sorted.get(p99_idx).copied().unwrap_or(0.0),
-            count: sorted.len(),
+            p50: seconds(0.50),
+            p95: seconds(0.95),
+            p99: seconds(0.99),
+            count: snapshot.count() as usize,
         }
     }
-
-    /// Export as Prometheus histogram
-    pub async fn export_prometheus(&self, name: &str) -> String {
-        let percentiles = self.percentiles().await;
-        format!(
-            "# HELP {} Latency histogram in seconds\n\
-             # TYPE {} histogram\n\
-             {}_bucket{{le=\"0.050\"}} {}\n\
-             {}_bucket{{le=\"0.100\"}} {}\n\
-             {}_bucket{{le=\"+Inf\"}} {}\n\
-             {}_sum {}\n\
-             {}_count {}\n",
-            name,
-            name,
-            name,
-            percentiles.count,
-            name,
-            percentiles.count,
-            name,
-            percentiles.count,
-            name,
-            percentiles.p50 * percentiles.count as f64,
-            name,
-            percentiles.count,
-        )
-    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -222,31 +144,6 @@ impl NonceGauges {
             rpc_latency_ms: Arc::new(RwLock::new(0.0)),
         }
     }
-
-    /// Export gauges as Prometheus format
-    pub async fn export_prometheus(&self) -> String {
-        format!(
-            "# HELP nonce_pool_size Current nonce pool size\n\
-             # TYPE nonce_pool_size gauge\n\
-             nonce_pool_size {}\n\
-             \n\
-             # HELP nonce_leases_outstanding Current number of outstanding leases\n\
-             # TYPE nonce_leases_outstanding gauge\n\
-             nonce_leases_outstanding {}\n\
-             \n\
-             # HELP nonce_predictive_failure_prob Predictive failure probability\n\
-             # TYPE nonce_predictive_failure_prob gauge\n\
-             nonce_predictive_failure_prob {}\n\
-             \n\
-             # HELP nonce_rpc_latency_ms Current RPC latency in milliseconds\n\
-             # TYPE nonce_rpc_latency_ms gauge\n\
-             nonce_rpc_latency_ms {}\n",
-            *self.pool_size.read().await,
-            *self.leases_outstanding.read().await,
-            *self.predictive_failure_prob.read().await,
-            *self.rpc_latency_ms.read().await,
-        )
-    }
 }
 
 impl Default for NonceGauges {
@@ -270,8 +167,8 @@ impl NonceTelemetry {
         Self {
             counters: NonceCounters::new(),
             gauges: NonceGauges::new(),
-            acquire_latency: LatencyHistogram::new(10000),
-            refresh_latency: LatencyHistogram::new(10000),
+            acquire_latency: LatencyHistogram::new(),
+            refresh_latency: LatencyHistogram::new(),
             alerting: AlertManager::new(),
         }
     }
@@ -279,7 +176,7 @@ impl NonceTelemetry {
     /// Record nonce acquisition
     pub async fn record_acquire(&self, latency: Duration) {
         self.counters.acquire_total.fetch_add(1, Ordering::Relaxed);
-        self.acquire_latency.record(latency.as_secs_f64()).await;
+        self.acquire_latency.record(latency.as_secs_f64());
     }
 
     /// Record nonce refresh
@@ -292,7 +189,7 @@ impl NonceTelemetry {
                 .refresh_failures_total
                 .fetch_add(1, Ordering::Relaxed);
         }
-        self.refresh_latency.record(latency.as_secs_f64()).await;
+        self.refresh_latency.record(latency.as_secs_f64());
 
         // Check alerting rules
         self.check_refresh_failure_rate().await;
@@ -342,7 +239,7 @@ impl NonceTelemetry {
 
     /// Check latency and trigger alerts
     pub async fn check_latency_alerts(&self) {
-        let percentiles = self.acquire_latency.percentiles().await;
+        let percentiles = self.acquire_latency.percentiles();
 
         // Alert if P99 latency > threshold (configurable, e.g., 100ms)
         if percentiles.p99 > 0.100 {
@@ -360,35 +257,10 @@ impl NonceTelemetry {
         }
     }
 
-    /// Export all metrics in Prometheus format
-    pub async fn export_prometheus(&self) -> String {
-        let mut output = String::new();
-
-        output.push_str(&self.counters.export_prometheus());
-        output.push('\n');
-        output.push_str(&self.gauges.export_prometheus().await);
-        output.push('\n');
-        output.push_str(
-            &self
-                .acquire_latency
-                .export_prometheus("nonce_acquire_latency_seconds")
-                .await,
-        );
-        output.push('\n');
-        output.push_str(
-            &self
-                .refresh_latency
-                .export_prometheus("nonce_refresh_latency_seconds")
-                .await,
-        );
-
-        output
-    }
-
     /// Get diagnostic summary
     pub async fn get_diagnostics(&self) -> TelemetryDiagnostics {
-        let acquire_percentiles = self.acquire_latency.percentiles().await;
-        let refresh_percentiles = self.refresh_latency.percentiles().await;
+        let acquire_percentiles = self.acquire_latency.percentiles();
+        let refresh_percentiles = self.refresh_latency.percentiles();
 
         TelemetryDiagnostics {
             refresh_attempts: self.counters.refresh_attempts_total.load(Ordering::Relaxed),
@@ -414,6 +286,86 @@ impl Default for NonceTelemetry {
     }
 }
 
+impl MetricSource for NonceTelemetry {
+    fn collect_metrics(&self, families: &mut FamilyBuilder) {
+        let counters = [
+            (
+                "nonce_refresh_attempts_total",
+                "Total number of nonce refresh attempts",
+                &self.counters.refresh_attempts_total,
+            ),
+            (
+                "nonce_refresh_failures_total",
+                "Total number of nonce refresh failures",
+                &self.counters.refresh_failures_total,
+            ),
+            (
+                "nonce_tainted_total",
+                "Total number of tainted nonces detected",
+                &self.counters.tainted_total,
+            ),
+            (
+                "nonce_rotations_total",
+                "Total number of nonce authority rotations",
+                &self.counters.rotations_total,
+            ),
+            (
+                "nonce_acquire_total",
+                "Total number of nonce acquisitions",
+                &self.counters.acquire_total,
+            ),
+            (
+                "nonce_release_total",
+                "Total number of nonce releases",
+                &self.counters.release_total,
+            ),
+            (
+                "nonce_lease_expired_total",
+                "Total number of lease expirations",
+                &self.counters.lease_expired_total,
+            ),
+            (
+                "nonce_rpc_failures_total",
+                "Total number of RPC failures",
+                &self.counters.rpc_failures_total,
+            ),
+        ];
+        for (name, help, counter) in counters {
+            families
+                .counter(name, help, &[])
+                .sample(&[], counter.load(Ordering::Relaxed) as f64);
+        }
+
+        // Pool size is already exported by the global `nonce_pool_size` gauge,
+        // and latencies by the `nonce_*_latency_seconds` histograms. Gauges
+        // held by a writer are skipped for this scrape rather than awaited.
+        let mut leases = families.gauge(
+            "nonce_leases_outstanding",
+            "Current number of outstanding leases",
+            &[],
+        );
+        if let Ok(value) = self.gauges.leases_outstanding.try_read() {
+            leases.sample(&[], *value as f64);
+        }
+        let mut failure_prob = families.gauge(
+            "nonce_predictive_failure_prob",
+            "Predictive failure probability",
+            &[],
+        );
+        if let Ok(value) = self.gauges.predictive_failure_prob.try_read() {
+            failure_prob.sample(&[], *value);
+        }
+        let mut rpc_latency = families.gauge(
+            "nonce_rpc_latency_seconds",
+            "Current RPC latency in seconds",
+            &[],
+        );
+        if let Ok(value) = self.gauges.rpc_latency_ms.try_read() {
+            rpc_latency.sample(&[], *value / 1000.0);
+        }
+    }
+}
+
 /// Telemetry diagnostics summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetryDiagnostics {
@@ -558,19 +510,43 @@ mod tests {
 
     #[tokio::test]
     async fn test_histogram_percentiles() {
-        let histogram = LatencyHistogram::new(100);
+        let histogram = LatencyHistogram::new();
 
         // Record some samples
         for i in 1..=100 {
-            histogram.record(i as f64 / 1000.0).await;
+            histogram.record(i as f64 / 1000.0);
         }
 
-        let percentiles = histogram.percentiles().await;
+        let percentiles = histogram.percentiles();
         assert_eq!(percentiles.count, 100);
         assert!(percentiles.p50 > 0.0);
         assert!(percentiles.p99 > percentiles.p50);
     }
 
+    #[test]
+    fn test_exported_metric_names() {
+        let telemetry = NonceTelemetry::new();
+        let mut families = FamilyBuilder::default();
+        telemetry.collect_metrics(&mut families);
+
+        assert_eq!(
+            families.names(),
+            [
+                "nonce_refresh_attempts_total",
+                "nonce_refresh_failures_total",
+                "nonce_tainted_total",
+                "nonce_rotations_total",
+                "nonce_acquire_total",
+                "nonce_release_total",
+                "nonce_lease_expired_total",
+                "nonce_rpc_failures_total",
+                "nonce_leases_outstanding",
+                "nonce_predictive_failure_prob",
+                "nonce_rpc_latency_seconds",
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_telemetry_record_acquire() {
         let telemetry = NonceTelemetry::new();
@@ -579,7 +555,7 @@ mod tests {
 
         assert_eq!(telemetry.counters.acquire_total.load(Ordering::Relaxed), 1);
 
-        let percentiles = telemetry.acquire_latency.percentiles().await;
+        let percentiles = telemetry.acquire_latency.percentiles();
         assert_eq!(percentiles.count, 1);
     }
 
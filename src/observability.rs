@@ -1,6 +1,13 @@
 //! Observability module for correlation and tracing
+//!
+//! Every stage of the candidate pipeline shares one W3C trace context
+//! ([`SpanContext`]): the sniffer opens the trace at extraction, the candidate
+//! carries it through the handoff, and the buy engine, builder, bundler and
+//! landing confirmation record child [`Span`]s that are exported as OTLP spans
+//! by [`crate::trace_export`].
 
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 /// Correlation ID for tracking requests across components
@@ -51,7 +58,8 @@ impl From<&str> for CorrelationId {
 /// Task 5: TraceContext for distributed tracing
 ///
 /// Provides trace_id, span_id, and correlation_id for tracking
-/// transaction building operations across the system.
+/// transaction building operations across the system. Ids are the hex form
+/// of a [`SpanContext`], so they match the exported spans.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraceContext {
     /// Unique trace identifier for the entire operation
@@ -76,16 +84,25 @@ pub struct TraceContext {
 impl TraceContext {
     /// Create a new trace context for an operation
     pub fn new(operation: &str) -> Self {
+        Self::from_span_context(operation, SpanContext::new_root(), None)
+    }
+
+    /// Trace context for an operation running inside an exported span
+    pub fn from_span_context(
+        operation: &str,
+        context: SpanContext,
+        parent: Option<SpanContext>,
+    ) -> Self {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
         Self {
-            trace_id: Uuid::new_v4().to_string(),
-            span_id: Uuid::new_v4().to_string(),
+            trace_id: context.trace_id_hex(),
+            span_id: context.span_id_hex(),
             correlation_id: CorrelationId::new(),
-            parent_span_id: None,
+            parent_span_id: parent.map(|parent| parent.span_id_hex()),
             operation: operation.to_string(),
             timestamp: now,
         }
@@ -98,9 +115,14 @@ impl TraceContext {
             .unwrap()
             .as_secs();
 
+        let span_id = match self.span_context() {
+            Some(context) => context.child().span_id_hex(),
+            None => SpanContext::new_root().span_id_hex(),
+        };
+
         Self {
             trace_id: self.trace_id.clone(),
-            span_id: Uuid::new_v4().to_string(),
+            span_id,
             correlation_id: self.correlation_id.clone(),
             parent_span_id: Some(self.span_id.clone()),
             operation: operation.to_string(),
@@ -108,6 +130,14 @@ impl TraceContext {
         }
     }
 
+    /// W3C ids of this context, if `trace_id`/`span_id` are in hex form
+    pub fn span_context(&self) -> Option<SpanContext> {
+        Some(SpanContext {
+            trace_id: u128::from_str_radix(&self.trace_id, 16).ok()?,
+            span_id: u64::from_str_radix(&self.span_id, 16).ok()?,
+        })
+    }
+
     /// Get the trace ID
     pub fn trace_id(&self) -> &str {
         &self.trace_id
@@ -129,3 +159,304 @@ impl Default for TraceContext {
         Self::new("default")
     }
 }
+
+/// W3C trace-context identifiers shared by every stage of one candidate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpanContext {
+    /// Trace id shared by all spans of the candidate
+    pub trace_id: u128,
+
+    /// Id of the span this context belongs to
+    pub span_id: u64,
+}
+
+impl SpanContext {
+    /// Start a new trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: fastrand::u128(1..),
+            span_id: fastrand::u64(1..),
+        }
+    }
+
+    /// Context for a new span in the same trace
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: fastrand::u64(1..),
+        }
+    }
+
+    /// Trace id as 32 lowercase hex digits
+    pub fn trace_id_hex(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+
+    /// Span id as 16 lowercase hex digits
+    pub fn span_id_hex(&self) -> String {
+        format!("{:016x}", self.span_id)
+    }
+
+    /// W3C `traceparent` header value (always sampled)
+    pub fn traceparent(&self) -> String {
+        format!("00-{:032x}-{:016x}-01", self.trace_id, self.span_id)
+    }
+
+    /// Parse a W3C `traceparent` header value
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.split('-');
+        let (version, trace_id, span_id, _flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if version != "00" || trace_id.len() != 32 || span_id.len() != 16 {
+            return None;
+        }
+        let context = Self {
+            trace_id: u128::from_str_radix(trace_id, 16).ok()?,
+            span_id: u64::from_str_radix(span_id, 16).ok()?,
+        };
+        (context.trace_id != 0 && context.span_id != 0).then_some(context)
+    }
+}
+
+/// Outcome recorded on a span
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SpanStatus {
+    #[default]
+    Unset,
+    Ok,
+    Error(String),
+}
+
+/// Span attribute value
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for AttributeValue {
+    fn from(value: u64) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        Self::Double(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// Finished span, as handed to the exporter
+#[derive(Debug, Clone)]
+pub struct SpanData {
+    pub name: &'static str,
+    pub context: SpanContext,
+    pub parent_span_id: Option<u64>,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub attributes: Vec<(&'static str, AttributeValue)>,
+    pub status: SpanStatus,
+}
+
+/// One timed stage of the candidate pipeline
+///
+/// Exported when ended or dropped; recording is skipped entirely while no
+/// exporter is installed.
+#[derive(Debug)]
+pub struct Span {
+    name: &'static str,
+    context: SpanContext,
+    parent_span_id: Option<u64>,
+    started: Instant,
+    start_unix_nanos: u64,
+    attributes: Vec<(&'static str, AttributeValue)>,
+    status: SpanStatus,
+    ended: bool,
+}
+
+impl Span {
+    /// Start a span, continuing `parent`'s trace or opening a new one
+    pub fn start(name: &'static str, parent: Option<SpanContext>) -> Self {
+        match parent {
+            Some(parent) => {
+                Self::started_at(name, parent.child(), Some(parent.span_id), Instant::now())
+            }
+            None => Self::started_at(name, SpanContext::new_root(), None, Instant::now()),
+        }
+    }
+
+    /// Span with known ids that began at `started` (for stages timed before
+    /// their span could be created)
+    pub fn started_at(
+        name: &'static str,
+        context: SpanContext,
+        parent_span_id: Option<u64>,
+        started: Instant,
+    ) -> Self {
+        Self {
+            name,
+            context,
+            parent_span_id,
+            started,
+            start_unix_nanos: unix_nanos().saturating_sub(nanos(started.elapsed())),
+            attributes: Vec::new(),
+            status: SpanStatus::Unset,
+            ended: false,
+        }
+    }
+
+    /// Start a child span of this one
+    pub fn child(&self, name: &'static str) -> Self {
+        Self::start(name, Some(self.context))
+    }
+
+    /// Ids of this span, for propagation to later stages
+    pub fn context(&self) -> SpanContext {
+        self.context
+    }
+
+    /// Trace id in hex, for log correlation
+    pub fn trace_id(&self) -> String {
+        self.context.trace_id_hex()
+    }
+
+    /// Time since the span started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Time since the span started, in microseconds
+    pub fn elapsed_micros(&self) -> u128 {
+        self.started.elapsed().as_micros()
+    }
+
+    /// Attach an attribute
+    pub fn set_attribute(&mut self, key: &'static str, value: impl Into<AttributeValue>) {
+        if crate::trace_export::enabled() {
+            self.attributes.push((key, value.into()));
+        }
+    }
+
+    /// Mark the span as failed
+    pub fn set_error(&mut self, message: impl ToString) {
+        self.status = SpanStatus::Error(message.to_string());
+    }
+
+    /// Mark the span as successful
+    pub fn set_ok(&mut self) {
+        self.status = SpanStatus::Ok;
+    }
+
+    /// End the span now and hand it to the exporter
+    pub fn end(mut self) {
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        if std::mem::replace(&mut self.ended, true) || !crate::trace_export::enabled() {
+            return;
+        }
+        crate::trace_export::export(SpanData {
+            name: self.name,
+            context: self.context,
+            parent_span_id: self.parent_span_id,
+            start_unix_nanos: self.start_unix_nanos,
+            end_unix_nanos: self.start_unix_nanos + nanos(self.started.elapsed()),
+            attributes: std::mem::take(&mut self.attributes),
+            status: std::mem::take(&mut self.status),
+        });
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+fn unix_nanos() -> u64 {
+    nanos(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default(),
+    )
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let context = SpanContext::new_root();
+        let header = context.traceparent();
+        assert_eq!(header.len(), 55);
+        assert_eq!(SpanContext::from_traceparent(&header), Some(context));
+
+        assert_eq!(SpanContext::from_traceparent("00-abc-def-01"), None);
+        assert_eq!(
+            SpanContext::from_traceparent(&format!("00-{}-{}-01", "0".repeat(32), "1".repeat(16))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_child_stays_in_trace() {
+        let root = SpanContext::new_root();
+        let child = root.child();
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_ne!(child.span_id, root.span_id);
+
+        let span = Span::start("test", Some(root));
+        assert_eq!(span.context().trace_id, root.trace_id);
+        assert_eq!(span.parent_span_id, Some(root.span_id));
+    }
+
+    #[test]
+    fn test_trace_context_shares_span_ids() {
+        let context = SpanContext::new_root();
+        let trace = TraceContext::from_span_context("build", context, None);
+        assert_eq!(trace.span_context(), Some(context));
+
+        let child = trace.child_span("sign");
+        assert_eq!(child.trace_id, trace.trace_id);
+        assert_eq!(
+            child.parent_span_id.as_deref(),
+            Some(trace.span_id.as_str())
+        );
+        assert_eq!(
+            child.span_context().map(|c| c.trace_id),
+            Some(context.trace_id)
+        );
+    }
+}
@@ -66,6 +66,9 @@ pub struct ActivePosition {
 
     /// Last update timestamp (monotonic)
     pub last_update: Instant,
+
+    /// Wallet this position was copy-traded from, `None` for own entries
+    pub leader: Option<Pubkey>,
 }
 
 impl ActivePosition {
@@ -115,6 +118,7 @@ impl ActivePosition {
     ///     total_sol_from_sales: 0,
     ///     last_seen_price: 0.00000001, // 0.01 SOL per token
     ///     last_update: Instant::now(),
+    ///     leader: None,
     /// };
     ///
     /// // Price doubles
@@ -225,6 +229,7 @@ impl PositionTracker {
                 total_sol_from_sales: 0,
                 last_seen_price: entry_price,
                 last_update: Instant::now(),
+                leader: None,
             },
         );
     }
@@ -347,6 +352,38 @@ impl PositionTracker {
         self.positions.len()
     }
 
+    /// Tag a position as mirrored from a copy-trading leader
+    ///
+    /// # Arguments
+    /// * `mint` - Token mint address
+    /// * `leader` - Wallet whose trade was mirrored
+    ///
+    /// # Returns
+    /// `true` if the position was found and tagged, `false` if not found
+    pub fn tag_leader(&self, mint: &Pubkey, leader: Pubkey) -> bool {
+        if let Some(mut pos) = self.positions.get_mut(mint) {
+            pos.leader = Some(leader);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Get the positions mirrored from a copy-trading leader
+    ///
+    /// # Arguments
+    /// * `leader` - Leader wallet address
+    ///
+    /// # Returns
+    /// Vector of positions tagged with `leader`
+    pub fn positions_for_leader(&self, leader: &Pubkey) -> Vec<ActivePosition> {
+        self.positions
+            .iter()
+            .filter(|entry| entry.value().leader.as_ref() == Some(leader))
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
     /// Remove a position manually
     ///
     /// Useful for emergency stops or manual position cleanup.
@@ -391,6 +428,7 @@ mod tests {
             total_sol_from_sales: 5_000_000,
             last_seen_price: 0.00000001,
             last_update: Instant::now(),
+            leader: None,
         };
 
         assert_eq!(pos.remaining_token_amount(), 700_000);
@@ -414,6 +452,7 @@ mod tests {
             total_sol_from_sales: 0,
             last_seen_price: 0.0,
             last_update: Instant::now(),
+            leader: None,
         };
 
         assert!((pos.sold_percent() - 25.0).abs() < 0.01);
@@ -428,6 +467,7 @@ mod tests {
             total_sol_from_sales: 0,
             last_seen_price: 0.0,
             last_update: Instant::now(),
+            leader: None,
         };
 
         assert_eq!(pos_zero.sold_percent(), 0.0);
@@ -444,6 +484,7 @@ mod tests {
             total_sol_from_sales: 0,
             last_seen_price: 0.00000001, // Entry price
             last_update: Instant::now(),
+            leader: None,
         };
 
         // Price doubles
@@ -472,6 +513,7 @@ mod tests {
             total_sol_from_sales: 15_000_000, // Got 0.015 SOL back
             last_seen_price: 0.00000002,      // Current price for remaining
             last_update: Instant::now(),
+            leader: None,
         };
 
         // Remaining 500k tokens at 0.00000002 = 0.01 SOL
@@ -497,6 +539,7 @@ mod tests {
             total_sol_from_sales: 0,
             last_seen_price: 0.0,
             last_update: Instant::now(),
+            leader: None,
         };
 
         let entry_price = pos.entry_price();
@@ -607,6 +650,25 @@ mod tests {
         assert_eq!(tracker.position_count(), 0);
     }
 
+    #[test]
+    fn test_position_tracker_tag_leader() {
+        let tracker = PositionTracker::new();
+        let leader = Pubkey::new_unique();
+        let mirrored = Pubkey::new_unique();
+        let own = Pubkey::new_unique();
+
+        tracker.record_buy(mirrored, 1_000_000, 10_000_000);
+        tracker.record_buy(own, 1_000_000, 10_000_000);
+
+        assert!(tracker.tag_leader(&mirrored, leader));
+        assert!(!tracker.tag_leader(&Pubkey::new_unique(), leader));
+
+        let positions = tracker.positions_for_leader(&leader);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].mint, mirrored);
+        assert_eq!(tracker.get_position(&own).unwrap().leader, None);
+    }
+
     #[test]
     fn test_position_tracker_clear_all() {
         let tracker = PositionTracker::new();
@@ -0,0 +1,200 @@
+//! Pre-signed emergency exits
+//!
+//! A durable-nonce transaction stays valid until its nonce advances, so a
+//! 100% sell built and signed right after the buy can be kept for the life of
+//! the position and sent without any build latency when a stop loss or
+//! emergency exit fires. Each [`PresignedExit`] owns the [`NonceLease`] it
+//! was signed against, which keeps the nonce out of the pool.
+//!
+//! An exit that can no longer be used (the position was sold another way or
+//! changed size) is [invalidated](PresignedExit::invalidate): its nonce is
+//! advanced on-chain before the lease goes back to the pool, so the signed
+//! bytes can never land later. The lease watchdog reclaims a nonce whose lease
+//! has run out, so an exit nearing its lease expiry is re-signed on a fresh
+//! nonce ([`PresignedExits::expiring`]).
+
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::metrics::metrics;
+use crate::nonce_manager::{NonceLease, NonceManager, NonceResult};
+
+/// A signed 100% sell of one position
+pub struct PresignedExit {
+    /// The signed transaction
+    pub tx: VersionedTransaction,
+
+    /// Lease on the nonce the transaction was signed against
+    pub nonce_lease: Option<NonceLease>,
+
+    /// Remaining position size (tokens) when the exit was signed
+    pub token_amount: u64,
+
+    /// When the exit was signed
+    pub signed_at: Instant,
+}
+
+impl PresignedExit {
+    pub fn new(
+        tx: VersionedTransaction,
+        nonce_lease: Option<NonceLease>,
+        token_amount: u64,
+    ) -> Self {
+        Self {
+            tx,
+            nonce_lease,
+            token_amount,
+            signed_at: Instant::now(),
+        }
+    }
+
+    /// Nonce account the transaction depends on
+    pub fn nonce_account(&self) -> Option<Pubkey> {
+        self.nonce_lease.as_ref().map(|lease| *lease.nonce_pubkey())
+    }
+
+    /// Whether the nonce lease runs out within `margin`
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        self.nonce_lease.as_ref().is_some_and(|lease| {
+            lease
+                .time_remaining()
+                .is_none_or(|remaining| remaining <= margin)
+        })
+    }
+
+    /// Make the transaction unusable: advance its nonce, then release the lease
+    pub async fn invalidate(self, nonce_manager: &NonceManager) -> NonceResult<()> {
+        metrics().increment_counter("presigned_exit_invalidated");
+        let Some(lease) = self.nonce_lease else {
+            return Ok(());
+        };
+        let nonce = *lease.nonce_pubkey();
+        let advanced = nonce_manager.refresh_nonce_async(nonce).await;
+        if let Err(e) = &advanced {
+            warn!(%nonce, error = %e, "Failed to advance nonce of invalidated exit");
+        }
+        lease.release().await?;
+        advanced.map(|_| ())
+    }
+}
+
+/// Pre-signed exits of open positions, one per mint
+#[derive(Default)]
+pub struct PresignedExits {
+    exits: DashMap<Pubkey, PresignedExit>,
+}
+
+impl PresignedExits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the exit for `mint`, returning the one it replaces.
+    ///
+    /// The replaced exit is still valid on-chain and must be invalidated.
+    pub fn insert(&self, mint: Pubkey, exit: PresignedExit) -> Option<PresignedExit> {
+        debug!(%mint, nonce = ?exit.nonce_account(), "Pre-signed exit stored");
+        self.exits.insert(mint, exit)
+    }
+
+    /// Remove the exit for `mint` (to send or invalidate it)
+    pub fn take(&self, mint: &Pubkey) -> Option<PresignedExit> {
+        self.exits.remove(mint).map(|(_, exit)| exit)
+    }
+
+    /// Whether an exit is ready for `mint`
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.exits.contains_key(mint)
+    }
+
+    /// Whether the exit for `mint` was signed for a position of `token_amount`
+    pub fn is_current(&self, mint: &Pubkey, token_amount: u64) -> bool {
+        self.exits
+            .get(mint)
+            .is_some_and(|exit| exit.token_amount == token_amount)
+    }
+
+    /// Whether the exit for `mint` has to be re-signed before its lease runs out
+    pub fn expires_within(&self, mint: &Pubkey, margin: Duration) -> bool {
+        self.exits
+            .get(mint)
+            .is_some_and(|exit| exit.expires_within(margin))
+    }
+
+    /// Mints whose exit lease runs out within `margin`
+    pub fn expiring(&self, margin: Duration) -> Vec<Pubkey> {
+        self.exits
+            .iter()
+            .filter(|entry| entry.expires_within(margin))
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
+    /// Mints with an exit ready
+    pub fn mints(&self) -> Vec<Pubkey> {
+        self.exits.iter().map(|entry| *entry.key()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.exits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exits.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+
+    fn exit(token_amount: u64) -> PresignedExit {
+        exit_with_ttl(token_amount, Duration::from_secs(60))
+    }
+
+    fn exit_with_ttl(token_amount: u64, ttl: Duration) -> PresignedExit {
+        let lease = NonceLease::new(Pubkey::new_unique(), 0, Hash::new_unique(), ttl, || {});
+        PresignedExit::new(VersionedTransaction::default(), Some(lease), token_amount)
+    }
+
+    #[tokio::test]
+    async fn test_insert_replaces_and_returns_stale_exit() {
+        let exits = PresignedExits::new();
+        let mint = Pubkey::new_unique();
+
+        assert!(exits.insert(mint, exit(1_000)).is_none());
+        assert!(exits.is_current(&mint, 1_000));
+        assert!(!exits.is_current(&mint, 600));
+
+        let stale = exits.insert(mint, exit(600)).expect("replaced exit");
+        assert_eq!(stale.token_amount, 1_000);
+        assert!(exits.is_current(&mint, 600));
+        assert_eq!(exits.len(), 1);
+
+        let taken = exits.take(&mint).unwrap();
+        assert!(taken.nonce_account().is_some());
+        assert!(!exits.contains(&mint));
+        assert!(exits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exits_near_lease_expiry_are_listed() {
+        let exits = PresignedExits::new();
+        let fresh = Pubkey::new_unique();
+        let expired = Pubkey::new_unique();
+        exits.insert(fresh, exit(1_000));
+        exits.insert(expired, exit_with_ttl(1_000, Duration::ZERO));
+
+        assert!(!exits.expires_within(&fresh, Duration::from_secs(30)));
+        assert!(exits.expires_within(&fresh, Duration::from_secs(120)));
+        assert!(exits.expires_within(&expired, Duration::ZERO));
+        assert_eq!(exits.expiring(Duration::from_secs(30)), vec![expired]);
+
+        let unleased = PresignedExit::new(VersionedTransaction::default(), None, 1_000);
+        assert!(!unleased.expires_within(Duration::from_secs(120)));
+    }
+}
@@ -13,12 +13,21 @@ use std::pin::Pin;
 pub mod rpc_atomics;
 pub mod rpc_config;
 pub mod rpc_errors;
+pub mod rpc_hedging;
 pub mod rpc_metrics;
 pub mod rpc_pool;
+pub mod rpc_sender;
+pub mod rpc_slot_clock;
+pub mod rpc_subscriptions;
+pub mod rpc_tpu;
 
 // Re-exports for convenience
 pub use rpc_errors::RpcManagerError;
-pub use rpc_pool::{EndpointConfig, EndpointType, RpcPool};
+pub use rpc_hedging::{HedgeConfig, QuorumConfig, QuorumResponse};
+pub use rpc_pool::{EndpointConfig, EndpointLease, EndpointType, RpcPool};
+pub use rpc_slot_clock::{LatestBlockhash, SlotClock, SlotClockConfig};
+pub use rpc_subscriptions::{AccountSubscriptionConfig, AccountWatch};
+pub use rpc_tpu::{SubmissionRoute, TpuConfig, TpuSender};
 
 /// Trait for RPC broadcasting functionality
 pub trait RpcBroadcaster: Send + Sync + std::fmt::Debug {
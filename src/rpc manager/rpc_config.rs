@@ -15,6 +15,10 @@ pub struct RpcEndpointConfig {
     pub max_concurrency: u32,
 
     /// Optional API key or credentials
+    ///
+    /// `header:<Name>=<value>` sends a request header, `query:<param>=<value>`
+    /// appends a query parameter, anything else is sent as
+    /// `Authorization: Bearer <value>`.
     #[serde(default)]
     pub credentials: Option<String>,
 
@@ -26,7 +30,7 @@ pub struct RpcEndpointConfig {
     #[serde(default = "default_timeout_ms")]
     pub timeout_ms: u64,
 
-    /// Rate limit (requests per second)
+    /// Rate limit (requests per second, 0 = unlimited)
     #[serde(default = "default_rate_limit")]
     pub rate_limit_rps: u32,
 }
@@ -47,6 +51,64 @@ fn default_rate_limit() -> u32 {
     100
 }
 
+/// Parsed form of [`RpcEndpointConfig::credentials`]
+#[derive(Clone, PartialEq, Eq)]
+pub enum EndpointCredentials {
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+    /// Custom request header
+    Header { name: String, value: String },
+    /// Query parameter appended to the endpoint URL
+    Query { name: String, value: String },
+}
+
+impl EndpointCredentials {
+    /// Parse a `credentials` value
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        let split = |rest: &str, kind: &str| -> Result<(String, String), String> {
+            match rest.split_once('=') {
+                Some((name, value)) if !name.trim().is_empty() && !value.is_empty() => {
+                    Ok((name.trim().to_string(), value.to_string()))
+                }
+                _ => Err(format!(
+                    "Invalid {} credentials: expected {}:<name>=<value>",
+                    kind, kind
+                )),
+            }
+        };
+
+        if let Some(rest) = raw.strip_prefix("header:") {
+            let (name, value) = split(rest, "header")?;
+            if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err()
+                || reqwest::header::HeaderValue::from_str(&value).is_err()
+            {
+                return Err(format!("Invalid credentials header: {}", name));
+            }
+            Ok(Self::Header { name, value })
+        } else if let Some(rest) = raw.strip_prefix("query:") {
+            let (name, value) = split(rest, "query")?;
+            Ok(Self::Query { name, value })
+        } else if raw.is_empty() {
+            Err("Credentials must not be empty".to_string())
+        } else if reqwest::header::HeaderValue::from_str(&format!("Bearer {}", raw)).is_err() {
+            Err("Invalid characters in bearer credentials".to_string())
+        } else {
+            Ok(Self::Bearer(raw.to_string()))
+        }
+    }
+}
+
+impl std::fmt::Debug for EndpointCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bearer(_) => f.write_str("Bearer(<redacted>)"),
+            Self::Header { name, .. } => write!(f, "Header({}: <redacted>)", name),
+            Self::Query { name, .. } => write!(f, "Query({}=<redacted>)", name),
+        }
+    }
+}
+
 /// Global RPC manager configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcManagerConfig {
@@ -204,6 +266,21 @@ impl RpcManagerConfig {
                     endpoint.url
                 )));
             }
+
+            // Validate timeout; a zero rate limit means unlimited
+            if endpoint.timeout_ms == 0 {
+                return Err(ConfigError::ValidationError(format!(
+                    "Invalid timeout_ms for {}: must be > 0",
+                    endpoint.url
+                )));
+            }
+
+            // Validate credentials format
+            if let Some(credentials) = &endpoint.credentials {
+                EndpointCredentials::parse(credentials).map_err(|e| {
+                    ConfigError::ValidationError(format!("{} for {}", e, endpoint.url))
+                })?;
+            }
         }
 
         // Validate at least one endpoint
@@ -317,6 +394,39 @@ mod tests {
         assert!(bad_url_config.validate().is_err());
     }
 
+    #[test]
+    fn test_credentials_parsing() {
+        assert_eq!(
+            EndpointCredentials::parse("secret").unwrap(),
+            EndpointCredentials::Bearer("secret".to_string())
+        );
+        assert_eq!(
+            EndpointCredentials::parse("header:x-token=abc=").unwrap(),
+            EndpointCredentials::Header {
+                name: "x-token".to_string(),
+                value: "abc=".to_string()
+            }
+        );
+        assert_eq!(
+            EndpointCredentials::parse("query:api-key=abc").unwrap(),
+            EndpointCredentials::Query {
+                name: "api-key".to_string(),
+                value: "abc".to_string()
+            }
+        );
+        assert!(EndpointCredentials::parse("query:api-key").is_err());
+        assert!(EndpointCredentials::parse("header:bad name=x").is_err());
+        assert!(!format!("{:?}", EndpointCredentials::parse("secret").unwrap()).contains("secret"));
+
+        let mut config = RpcManagerConfig::from_urls(&["https://test.com".to_string()]);
+        config.endpoints[0].credentials = Some("header:=x".to_string());
+        assert!(config.validate().is_err());
+
+        config.endpoints[0].credentials = None;
+        config.endpoints[0].rate_limit_rps = 0;
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_default_values() {
         let config = RpcManagerConfig::from_urls(&vec!["https://test.com".to_string()]);
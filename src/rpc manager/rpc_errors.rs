@@ -49,6 +49,14 @@ pub enum RpcManagerError {
     #[error("No healthy endpoints available (total: {total}, unhealthy: {unhealthy})")]
     NoHealthyEndpoints { total: usize, unhealthy: usize },
 
+    /// Quorum read did not reach k-of-n agreement
+    #[error("Quorum not reached: {agreeing} of {responses} responses agree ({required} required)")]
+    QuorumNotReached {
+        required: usize,
+        agreeing: usize,
+        responses: usize,
+    },
+
     /// Specific Solana errors
     #[error("Blockhash not found (endpoint: {endpoint})")]
     BlockhashNotFound { endpoint: String },
@@ -80,6 +88,7 @@ impl RpcManagerError {
             RpcManagerError::Timeout { .. } => true,
             RpcManagerError::RateLimitExceeded { .. } => true,
             RpcManagerError::NoHealthyEndpoints { .. } => true,
+            RpcManagerError::QuorumNotReached { .. } => true,
             RpcManagerError::BlockhashNotFound { .. } => true,
 
             // Non-retryable errors
@@ -131,6 +140,7 @@ impl RpcManagerError {
 
             // These shouldn't result in blacklisting
             RpcManagerError::NoHealthyEndpoints { .. } => false,
+            RpcManagerError::QuorumNotReached { .. } => false,
             RpcManagerError::NonceExhausted { .. } => false,
             RpcManagerError::Validation(_) => false,
             RpcManagerError::Internal(_) => false,
@@ -146,6 +156,43 @@ impl RpcManagerError {
         }
     }
 
+    /// Check if this error reflects on the endpoint that served the request
+    ///
+    /// Application-level rejections (expired transaction, missing account,
+    /// insufficient funds, unknown blockhash) are answered correctly by a
+    /// healthy endpoint and must not lower its score.
+    pub fn is_endpoint_failure(&self) -> bool {
+        matches!(
+            self,
+            RpcManagerError::Transport { .. }
+                | RpcManagerError::Timeout { .. }
+                | RpcManagerError::RateLimitExceeded { .. }
+                | RpcManagerError::RpcResponse { .. }
+        )
+    }
+
+    /// Short error class used as a per-endpoint stats key
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RpcManagerError::Transport { .. } => "transport",
+            RpcManagerError::Timeout { .. } => "timeout",
+            RpcManagerError::RpcResponse { .. } => "rpc_response",
+            RpcManagerError::NonceExhausted { .. } => "nonce_exhausted",
+            RpcManagerError::Fatal(_) => "fatal",
+            RpcManagerError::Configuration(_) => "configuration",
+            RpcManagerError::CircuitBreakerOpen { .. } => "circuit_breaker_open",
+            RpcManagerError::RateLimitExceeded { .. } => "rate_limited",
+            RpcManagerError::NoHealthyEndpoints { .. } => "no_healthy_endpoints",
+            RpcManagerError::QuorumNotReached { .. } => "quorum_not_reached",
+            RpcManagerError::BlockhashNotFound { .. } => "blockhash_not_found",
+            RpcManagerError::TransactionExpired { .. } => "transaction_expired",
+            RpcManagerError::AccountNotFound { .. } => "account_not_found",
+            RpcManagerError::InsufficientFunds { .. } => "insufficient_funds",
+            RpcManagerError::Validation(_) => "validation",
+            RpcManagerError::Internal(_) => "internal",
+        }
+    }
+
     /// Get the endpoint associated with this error, if any
     pub fn endpoint(&self) -> Option<&str> {
         match self {
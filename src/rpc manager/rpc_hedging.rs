@@ -0,0 +1,484 @@
+//! Hedged and quorum request modes for [`RpcPool`]
+//!
+//! - **Hedged**: the request goes to the best endpoint; if it has not answered
+//!   within that endpoint's recent p95 latency (clamped to configured bounds),
+//!   the same request is fired at the next-best endpoint, and so on. The first
+//!   success wins and the requests still in flight are dropped (cancelled).
+//!   Each endpoint is leased only when its attempt launches, so a request the
+//!   first endpoint answers in time holds a single concurrency slot.
+//!   An endpoint failure launches the next endpoint immediately; an
+//!   application-level rejection is returned as is, since another endpoint
+//!   would answer the same.
+//! - **Quorum**: the request goes to `max_requests` endpoints at once and a
+//!   value is accepted once `min_responses` of them agree on it, optionally
+//!   within `max_slot_diff` slots of each other.
+//!
+//! Every attempt runs through an [`EndpointLease`], so latency and errors feed
+//! the endpoint scores like any other pooled request.
+use super::rpc_errors::RpcManagerError;
+use super::rpc_pool::{EndpointLease, RpcPool};
+use futures::stream::{FuturesUnordered, StreamExt};
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey,
+    signature::Signature, transaction::VersionedTransaction,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash as StdHash;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Hedged request configuration
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    /// Maximum number of endpoints a request is sent to
+    pub max_requests: usize,
+    /// Hedge delay while the endpoint has no latency history
+    pub initial_delay: Duration,
+    /// Lower bound for the p95-based hedge delay
+    pub min_delay: Duration,
+    /// Upper bound for the p95-based hedge delay
+    pub max_delay: Duration,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            max_requests: 2,
+            initial_delay: Duration::from_millis(50),
+            min_delay: Duration::from_millis(5),
+            max_delay: Duration::from_millis(250),
+        }
+    }
+}
+
+impl HedgeConfig {
+    /// How long to wait on `lease` before hedging to the next endpoint
+    pub fn delay_for(&self, lease: &EndpointLease) -> Duration {
+        lease
+            .p95_latency()
+            .unwrap_or(self.initial_delay)
+            .clamp(self.min_delay, self.max_delay.max(self.min_delay))
+    }
+}
+
+/// Quorum configuration for blockhash consensus
+#[derive(Debug, Clone)]
+pub struct QuorumConfig {
+    /// Minimum number of RPC responses required for quorum
+    pub min_responses: usize,
+    /// Maximum allowed slot difference between responses
+    pub max_slot_diff: u64,
+    /// Enable slot-based validation
+    pub enable_slot_validation: bool,
+    /// Endpoints queried by [`RpcPool::quorum`] (the n of k-of-n)
+    pub max_requests: usize,
+}
+
+impl Default for QuorumConfig {
+    fn default() -> Self {
+        Self {
+            min_responses: 2,
+            max_slot_diff: 10,
+            enable_slot_validation: true,
+            max_requests: 3,
+        }
+    }
+}
+
+/// Value agreed on by a quorum read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumResponse<T> {
+    pub value: T,
+    /// Highest slot reported by the agreeing endpoints
+    pub slot: u64,
+    /// Endpoints that returned `value`
+    pub agreeing: usize,
+    /// Successful responses received before quorum was reached
+    pub responses: usize,
+}
+
+/// Votes for one value: (count, min slot, max slot)
+type Votes = (usize, u64, u64);
+
+impl RpcPool {
+    /// Run `f` as a hedged request and return the first success
+    pub async fn hedged<T, F, Fut>(&self, config: &HedgeConfig, f: F) -> Result<T, RpcManagerError>
+    where
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let max_requests = config.max_requests.max(1);
+        let launch = |lease: EndpointLease| {
+            let f = &f;
+            async move {
+                let result = lease.call(f).await;
+                (lease, result)
+            }
+        };
+
+        // Each further endpoint is leased only when the attempt on it launches
+        let Some(first) = self.acquire_endpoint_excluding(&[]).await else {
+            return Err(self.no_healthy_endpoints().await);
+        };
+        let mut launched = vec![first.url().to_string()];
+        let mut delay = config.delay_for(&first);
+        let mut in_flight = FuturesUnordered::new();
+        in_flight.push(launch(first));
+        let mut can_hedge = launched.len() < max_requests;
+        let mut last_error = None;
+
+        loop {
+            let outcome = if can_hedge {
+                tokio::select! {
+                    outcome = in_flight.next(), if !in_flight.is_empty() => outcome,
+                    _ = tokio::time::sleep(delay) => {
+                        match self.acquire_endpoint_excluding(&launched).await {
+                            Some(lease) => {
+                                debug!(
+                                    url = %lease.url(),
+                                    delay_ms = delay.as_millis() as u64,
+                                    "Hedging request to next endpoint"
+                                );
+                                delay = config.delay_for(&lease);
+                                launched.push(lease.url().to_string());
+                                in_flight.push(launch(lease));
+                                can_hedge = launched.len() < max_requests;
+                            }
+                            None => can_hedge = false,
+                        }
+                        continue;
+                    }
+                }
+            } else {
+                in_flight.next().await
+            };
+
+            match outcome {
+                Some((lease, Ok(value))) => {
+                    debug!(
+                        url = %lease.url(),
+                        launched = launched.len(),
+                        "Hedged request answered"
+                    );
+                    return Ok(value);
+                }
+                Some((_, Err(err))) if !err.is_endpoint_failure() => return Err(err),
+                Some((lease, Err(err))) => {
+                    debug!(url = %lease.url(), error = %err, "Hedged attempt failed");
+                    last_error = Some(err);
+                    // Move on to the next endpoint without waiting
+                    delay = Duration::ZERO;
+                }
+                None => break,
+            }
+        }
+
+        Err(last_error.expect("at least one attempt was made"))
+    }
+
+    /// Run `f` on several endpoints and accept the value `min_responses` agree on
+    ///
+    /// `f` returns the value and the slot it was observed at.
+    pub async fn quorum<T, F, Fut>(
+        &self,
+        config: &QuorumConfig,
+        f: F,
+    ) -> Result<QuorumResponse<T>, RpcManagerError>
+    where
+        T: Eq + StdHash + Clone,
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = Result<(T, u64), ClientError>>,
+    {
+        let required = config.min_responses.max(1);
+        let leases = self
+            .acquire_endpoints(config.max_requests.max(required))
+            .await;
+        if leases.is_empty() {
+            return Err(self.no_healthy_endpoints().await);
+        }
+
+        let mut in_flight: FuturesUnordered<_> = leases
+            .iter()
+            .map(|lease| {
+                let f = &f;
+                async move { lease.call(f).await }
+            })
+            .collect();
+        let mut votes: HashMap<T, Votes> = HashMap::new();
+        let mut responses = 0;
+        let mut best = 0;
+
+        while let Some(result) = in_flight.next().await {
+            let Ok((value, slot)) = result else {
+                // Stop once the outstanding requests can no longer make up a quorum
+                if best + in_flight.len() < required {
+                    break;
+                }
+                continue;
+            };
+            responses += 1;
+
+            let (count, min_slot, max_slot) = {
+                let entry = votes.entry(value.clone()).or_insert((0, slot, slot));
+                entry.0 += 1;
+                entry.1 = entry.1.min(slot);
+                entry.2 = entry.2.max(slot);
+                *entry
+            };
+            best = best.max(count);
+
+            if count >= required
+                && (!config.enable_slot_validation
+                    || max_slot.saturating_sub(min_slot) <= config.max_slot_diff)
+            {
+                debug!(
+                    agreeing = count,
+                    responses = responses,
+                    slot = max_slot,
+                    "Quorum reached"
+                );
+                return Ok(QuorumResponse {
+                    value,
+                    slot: max_slot,
+                    agreeing: count,
+                    responses,
+                });
+            }
+            if best + in_flight.len() < required {
+                break;
+            }
+        }
+
+        warn!(
+            required = required,
+            agreeing = best,
+            responses = responses,
+            endpoints = leases.len(),
+            "Quorum not reached"
+        );
+        Err(RpcManagerError::QuorumNotReached {
+            required,
+            agreeing: best,
+            responses,
+        })
+    }
+
+    /// Latest blockhash agreed on by a quorum of endpoints
+    pub async fn get_latest_blockhash_quorum(
+        &self,
+        config: &QuorumConfig,
+        commitment: CommitmentConfig,
+    ) -> Result<QuorumResponse<Hash>, RpcManagerError> {
+        self.quorum(config, |client| async move {
+            let (hash, _) = client
+                .get_latest_blockhash_with_commitment(commitment)
+                .await?;
+            let slot = client.get_slot_with_commitment(commitment).await?;
+            Ok((hash, slot))
+        })
+        .await
+    }
+
+    /// Fetch an account with hedging
+    pub async fn get_account_hedged(
+        &self,
+        config: &HedgeConfig,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> Result<Option<Account>, RpcManagerError> {
+        self.hedged(config, |client| async move {
+            client
+                .get_account_with_commitment(pubkey, commitment)
+                .await
+                .map(|response| response.value)
+        })
+        .await
+    }
+
+    /// Send a transaction with hedging; duplicate sends land at most once
+    pub async fn send_transaction_hedged(
+        &self,
+        config: &HedgeConfig,
+        tx: &VersionedTransaction,
+    ) -> Result<Signature, RpcManagerError> {
+        self.hedged(
+            config,
+            |client| async move { client.send_transaction(tx).await },
+        )
+        .await
+    }
+
+    pub(super) async fn no_healthy_endpoints(&self) -> RpcManagerError {
+        let stats = self.get_stats().await;
+        RpcManagerError::NoHealthyEndpoints {
+            total: stats.total_endpoints,
+            unhealthy: stats.unhealthy_endpoints,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_manager::rpc_pool::{EndpointConfig, EndpointType};
+    use solana_client::client_error::ClientErrorKind;
+    use std::time::Instant;
+
+    const PRIMARY: &str = "http://primary:8899";
+
+    fn pool(urls: &[&str]) -> RpcPool {
+        let configs = urls
+            .iter()
+            .enumerate()
+            .map(|(i, url)| EndpointConfig {
+                url: url.to_string(),
+                // Rank endpoints in the order given
+                endpoint_type: if i == 0 {
+                    EndpointType::Premium
+                } else {
+                    EndpointType::Standard
+                },
+                weight: 1.0,
+                max_requests_per_second: 100,
+                ..Default::default()
+            })
+            .collect();
+        RpcPool::new(
+            configs,
+            Duration::from_secs(30),
+            3,
+            Duration::from_millis(500),
+        )
+        .unwrap()
+    }
+
+    fn hedge_after(delay: Duration) -> HedgeConfig {
+        HedgeConfig {
+            max_requests: 2,
+            initial_delay: delay,
+            min_delay: delay,
+            max_delay: delay,
+        }
+    }
+
+    fn rpc_error(message: &str) -> ClientError {
+        ClientError::from(ClientErrorKind::Custom(message.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_hedged_takes_first_success() {
+        let pool = pool(&[PRIMARY, "http://backup:8899"]);
+        let start = Instant::now();
+
+        let url = pool
+            .hedged(
+                &hedge_after(Duration::from_millis(20)),
+                |client| async move {
+                    if client.url() == PRIMARY {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    Ok(client.url())
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(url, "http://backup:8899");
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(pool.get_active_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_hedged_leases_endpoints_on_launch() {
+        let pool = pool(&[PRIMARY, "http://backup:8899", "http://spare:8899"]);
+        let config = HedgeConfig {
+            max_requests: 3,
+            ..hedge_after(Duration::from_secs(5))
+        };
+
+        let active = pool
+            .hedged(&config, |_| async { Ok(pool.get_active_requests()) })
+            .await
+            .unwrap();
+        assert_eq!(active, 1);
+        assert_eq!(pool.get_active_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_hedged_error_handling() {
+        let pool = pool(&[PRIMARY, "http://backup:8899"]);
+        let config = hedge_after(Duration::from_secs(5));
+
+        // An endpoint failure moves on to the next endpoint without waiting
+        let start = Instant::now();
+        let url = pool
+            .hedged(&config, |client| async move {
+                if client.url() == PRIMARY {
+                    Err(rpc_error("operation timed out"))
+                } else {
+                    Ok(client.url())
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(url, "http://backup:8899");
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // An application-level rejection is returned as is
+        let err = pool
+            .hedged(&config, |_| async {
+                Err::<(), _>(rpc_error("insufficient funds for fee"))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), "insufficient_funds");
+    }
+
+    #[tokio::test]
+    async fn test_quorum_requires_agreement() {
+        let pool = pool(&[PRIMARY, "http://a:8899", "http://b:8899"]);
+        let agreed = Hash::new_unique();
+        let other = Hash::new_unique();
+        let config = QuorumConfig::default();
+
+        let response = pool
+            .quorum(&config, |client| async move {
+                match client.url().as_str() {
+                    PRIMARY => Ok((other, 100)),
+                    "http://a:8899" => Ok((agreed, 100)),
+                    _ => Ok((agreed, 102)),
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.value, agreed);
+        assert_eq!(response.slot, 102);
+        assert_eq!(response.agreeing, 2);
+
+        // Agreeing endpoints that are too far apart do not count
+        let lagging = 100 + config.max_slot_diff + 1;
+        let err = pool
+            .quorum(&config, |client| async move {
+                match client.url().as_str() {
+                    PRIMARY => Ok((other, 100)),
+                    "http://a:8899" => Ok((agreed, 100)),
+                    _ => Ok((agreed, lagging)),
+                }
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RpcManagerError::QuorumNotReached {
+                required: 2,
+                responses: 3,
+                ..
+            }
+        ));
+        assert_eq!(pool.get_active_requests(), 0);
+    }
+}
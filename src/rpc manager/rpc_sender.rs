@@ -0,0 +1,352 @@
+//! Rate-limited, authenticated JSON-RPC transport for pooled endpoints
+//!
+//! [`EndpointSender`] replaces the default HTTP sender behind each pooled
+//! `RpcClient` so that every request, including health checks, honors the
+//! endpoint's limits:
+//! - a `governor` token bucket (`max_requests_per_second`)
+//! - a concurrency semaphore (`max_concurrency`)
+//! - a per-request timeout (`timeout_ms`)
+//! - credentials injected as a header or query parameter
+//!
+//! HTTP 429 is not retried here. It puts the endpoint into cooldown for the
+//! `Retry-After` period (or [`DEFAULT_RATE_LIMIT_COOLDOWN`]) and surfaces as a
+//! rate-limit error, so the pool routes around the endpoint until it expires.
+use super::rpc_config::EndpointCredentials;
+use super::rpc_errors::RpcManagerError;
+use super::rpc_pool::EndpointConfig;
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, RETRY_AFTER};
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use serde_json::Value;
+use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+use solana_client::rpc_custom_error::JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE;
+use solana_client::rpc_request::{RpcError, RpcRequest, RpcResponseErrorData};
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, Semaphore};
+use tracing::{debug, error, warn};
+
+/// Cooldown after a 429 without a usable `Retry-After` header
+pub const DEFAULT_RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(1);
+
+/// Longest `Retry-After` honored; longer values are clamped
+pub const MAX_RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+/// JSON-RPC sender for one pooled endpoint
+pub struct EndpointSender {
+    client: reqwest::Client,
+    /// Endpoint URL as configured (no credentials), reported by `url()`
+    url: String,
+    /// URL requests are posted to (with query credentials)
+    request_url: String,
+    timeout: Option<Duration>,
+    limiter: Option<DefaultDirectRateLimiter>,
+    concurrency: Option<Semaphore>,
+    cooldown_until: Arc<RwLock<Option<Instant>>>,
+    request_id: AtomicU64,
+    stats: parking_lot::Mutex<RpcTransportStats>,
+}
+
+impl EndpointSender {
+    /// Build the sender for `config`; a 429 writes `cooldown_until`
+    pub fn new(
+        config: &EndpointConfig,
+        cooldown_until: Arc<RwLock<Option<Instant>>>,
+    ) -> Result<Self, RpcManagerError> {
+        let mut headers = HeaderMap::new();
+        let mut request_url = config.url.clone();
+
+        match config
+            .credentials
+            .as_deref()
+            .map(EndpointCredentials::parse)
+        {
+            None => {}
+            Some(Ok(EndpointCredentials::Bearer(token))) => {
+                if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                    headers.insert(AUTHORIZATION, value);
+                }
+            }
+            Some(Ok(EndpointCredentials::Header { name, value })) => {
+                if let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(name.as_bytes()),
+                    HeaderValue::from_str(&value),
+                ) {
+                    headers.insert(name, value);
+                }
+            }
+            Some(Ok(EndpointCredentials::Query { name, value })) => {
+                let mut url = Url::parse(&config.url).map_err(|e| {
+                    RpcManagerError::Configuration(format!("Invalid URL {}: {}", config.url, e))
+                })?;
+                url.query_pairs_mut().append_pair(&name, &value);
+                request_url = url.into();
+            }
+            Some(Err(e)) => {
+                error!(url = %config.url, error = %e, "Ignoring invalid endpoint credentials");
+            }
+        }
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| {
+                RpcManagerError::Configuration(format!(
+                    "Failed to build HTTP client for {}: {}",
+                    config.url, e
+                ))
+            })?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            request_url,
+            timeout: (config.timeout_ms > 0).then(|| Duration::from_millis(config.timeout_ms)),
+            limiter: NonZeroU32::new(config.max_requests_per_second)
+                .map(|rps| RateLimiter::direct(Quota::per_second(rps))),
+            concurrency: (config.max_concurrency > 0)
+                .then(|| Semaphore::new(config.max_concurrency as usize)),
+            cooldown_until,
+            request_id: AtomicU64::new(0),
+            stats: parking_lot::Mutex::new(RpcTransportStats::default()),
+        })
+    }
+
+    /// Cooldown requested by a 429 response
+    fn retry_after(headers: &HeaderMap) -> Duration {
+        headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_RATE_LIMIT_COOLDOWN)
+            .min(MAX_RATE_LIMIT_COOLDOWN)
+    }
+
+    async fn enter_cooldown(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut cooldown = self.cooldown_until.write().await;
+        if cooldown.is_none_or(|current| current < until) {
+            *cooldown = Some(until);
+        }
+        warn!(
+            url = %self.url,
+            cooldown_ms = duration.as_millis() as u64,
+            "Endpoint rate limited (HTTP 429), entering cooldown"
+        );
+    }
+
+    /// reqwest errors without the request URL, which may carry credentials
+    fn transport_error(&self, e: reqwest::Error) -> ClientErrorKind {
+        if e.is_timeout() {
+            ClientErrorKind::Custom(format!("Request timed out ({})", self.url))
+        } else {
+            ClientErrorKind::Custom(format!("{} ({})", e.without_url(), self.url))
+        }
+    }
+
+    async fn post(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let request_id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let body = request.build_request_json(request_id, params);
+
+        let mut builder = self.client.post(&self.request_url).json(&body);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder.send().await.map_err(|e| self.transport_error(e))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            self.enter_cooldown(Self::retry_after(response.headers()))
+                .await;
+            return Err(ClientErrorKind::Custom(format!(
+                "HTTP status 429 Too Many Requests ({})",
+                self.url
+            ))
+            .into());
+        }
+        if !status.is_success() {
+            // `code: <status>` lets `RpcManagerError::from_client_error` pick up the status
+            return Err(ClientErrorKind::Custom(format!(
+                "HTTP error code: {} {} ({})",
+                status.as_u16(),
+                status.canonical_reason().unwrap_or_default(),
+                self.url
+            ))
+            .into());
+        }
+
+        let mut json: Value = response.json().await.map_err(|e| self.transport_error(e))?;
+        if json["error"].is_object() {
+            return match serde_json::from_value::<RpcErrorObject>(json["error"].clone()) {
+                Ok(error) => {
+                    let data =
+                        if error.code == JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE {
+                            serde_json::from_value::<RpcSimulateTransactionResult>(
+                                json["error"]["data"].clone(),
+                            )
+                            .map(RpcResponseErrorData::SendTransactionPreflightFailure)
+                            .unwrap_or(RpcResponseErrorData::Empty)
+                        } else {
+                            RpcResponseErrorData::Empty
+                        };
+                    Err(RpcError::RpcResponseError {
+                        code: error.code,
+                        message: error.message,
+                        data,
+                    }
+                    .into())
+                }
+                Err(e) => Err(RpcError::RpcRequestError(format!(
+                    "Failed to deserialize RPC error response: {} [{}]",
+                    json["error"], e
+                ))
+                .into()),
+            };
+        }
+        Ok(json["result"].take())
+    }
+}
+
+#[async_trait::async_trait]
+impl RpcSender for EndpointSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let waiting = Instant::now();
+        if let Some(limiter) = &self.limiter {
+            limiter.until_ready().await;
+        }
+        let _permit = match &self.concurrency {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .map_err(|e| ClientErrorKind::Custom(e.to_string()))?,
+            ),
+            None => None,
+        };
+        let rate_limited_time = waiting.elapsed();
+        if rate_limited_time > Duration::from_millis(100) {
+            debug!(
+                url = %self.url,
+                waited_ms = rate_limited_time.as_millis() as u64,
+                "Request delayed by endpoint limits"
+            );
+        }
+
+        let start = Instant::now();
+        let result = self.post(request, params).await;
+
+        let mut stats = self.stats.lock();
+        stats.request_count += 1;
+        stats.elapsed_time += start.elapsed();
+        stats.rate_limited_time += rate_limited_time;
+        result
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.stats.lock().clone()
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_manager::rpc_errors::RpcManagerError;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one HTTP request with `response` and return the raw request
+    async fn serve_once(response: String) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+        (url, handle)
+    }
+
+    fn endpoint(url: &str, credentials: Option<&str>) -> EndpointConfig {
+        EndpointConfig {
+            url: url.to_string(),
+            credentials: credentials.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_injects_credentials() {
+        let body = r#"{"jsonrpc":"2.0","result":42,"id":0}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+
+        let (url, server) = serve_once(response.clone()).await;
+        let sender = EndpointSender::new(
+            &endpoint(&url, Some("query:api-key=k1&x=#1")),
+            Arc::new(RwLock::new(None)),
+        )
+        .unwrap();
+        let result = sender.send(RpcRequest::GetSlot, Value::Null).await.unwrap();
+        assert_eq!(result, 42);
+        assert_eq!(sender.url(), url);
+        assert!(server
+            .await
+            .unwrap()
+            .starts_with("POST /?api-key=k1%26x%3D%231 "));
+
+        let (url, server) = serve_once(response).await;
+        let sender = EndpointSender::new(
+            &endpoint(&url, Some("header:x-token=t1")),
+            Arc::new(RwLock::new(None)),
+        )
+        .unwrap();
+        sender.send(RpcRequest::GetSlot, Value::Null).await.unwrap();
+        assert!(server.await.unwrap().contains("x-token: t1"));
+        assert_eq!(sender.get_transport_stats().request_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_enters_cooldown() {
+        let (url, _server) = serve_once(
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 5\r\nContent-Length: 0\r\n\r\n"
+                .to_string(),
+        )
+        .await;
+        let cooldown = Arc::new(RwLock::new(None));
+        let sender = EndpointSender::new(&endpoint(&url, None), cooldown.clone()).unwrap();
+
+        let err = sender
+            .send(RpcRequest::GetSlot, Value::Null)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            RpcManagerError::from_client_error(err, &url),
+            RpcManagerError::RateLimitExceeded { .. }
+        ));
+
+        let until = cooldown.read().await.expect("cooldown set");
+        let remaining = until.saturating_duration_since(Instant::now());
+        assert!(remaining > Duration::from_secs(4) && remaining <= Duration::from_secs(5));
+    }
+}
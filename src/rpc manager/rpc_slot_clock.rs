@@ -0,0 +1,363 @@
+//! Shared slot clock and blockhash service
+//!
+//! [`SlotClock`] follows the cluster slot over a websocket `slotSubscribe`,
+//! falling back to polling `getSlot` through the [`RpcPool`] while the
+//! websocket is down or stalled. Alongside it keeps the latest blockhash with
+//! its `last_valid_block_height`, so expiry is expressed in slots rather than
+//! wall-clock age.
+//!
+//! One clock is shared by the buy engine, the transaction builder and the
+//! nonce manager; each falls back to its own RPC calls when no clock is set
+//! or the clock has gone stale.
+use super::rpc_errors::RpcManagerError;
+use super::rpc_hedging::HedgeConfig;
+use super::rpc_pool::RpcPool;
+use futures::StreamExt;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_sdk::{commitment_config::CommitmentConfig, hash::Hash};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Slot clock configuration
+#[derive(Debug, Clone)]
+pub struct SlotClockConfig {
+    /// Websocket endpoint for `slotSubscribe`; `None` polls only
+    pub ws_url: Option<String>,
+    /// Commitment of the tracked blockhash
+    pub commitment: CommitmentConfig,
+    /// `getSlot` polling interval while the subscription is down
+    pub poll_interval: Duration,
+    /// Blockhash refresh interval
+    pub blockhash_refresh_interval: Duration,
+    /// Minimum slots a blockhash must have left to be handed out as fresh
+    pub min_slots_remaining: u64,
+    /// The slot is considered stale (and the subscription stalled) when it
+    /// has not advanced for this long
+    pub stale_after: Duration,
+    /// Delay before re-subscribing after the websocket fails
+    pub reconnect_backoff: Duration,
+    /// Upper bound for the reconnect backoff
+    pub max_reconnect_backoff: Duration,
+}
+
+impl Default for SlotClockConfig {
+    fn default() -> Self {
+        Self {
+            ws_url: None,
+            commitment: CommitmentConfig::confirmed(),
+            poll_interval: Duration::from_millis(400),
+            blockhash_refresh_interval: Duration::from_secs(2),
+            min_slots_remaining: 30,
+            stale_after: Duration::from_secs(5),
+            reconnect_backoff: Duration::from_secs(1),
+            max_reconnect_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A blockhash and the window it is valid for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatestBlockhash {
+    pub hash: Hash,
+    /// Last block height at which a transaction using `hash` can land
+    pub last_valid_block_height: u64,
+    /// Block height when the blockhash was fetched
+    pub block_height: u64,
+    /// Slot when the blockhash was fetched
+    pub slot: u64,
+    pub fetched_at: Instant,
+}
+
+impl LatestBlockhash {
+    /// Earliest slot at which the blockhash may expire
+    ///
+    /// Block height never advances faster than the slot, so the blocks left
+    /// at fetch time are a lower bound on the slots left.
+    pub fn expiry_slot(&self) -> u64 {
+        self.slot.saturating_add(
+            self.last_valid_block_height
+                .saturating_sub(self.block_height),
+        )
+    }
+
+    /// Slots left before [`expiry_slot`](Self::expiry_slot)
+    pub fn slots_remaining(&self, current_slot: u64) -> u64 {
+        self.expiry_slot().saturating_sub(current_slot)
+    }
+
+    /// Whether the blockhash may have expired at `current_slot`
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        self.slots_remaining(current_slot) == 0
+    }
+}
+
+/// Current slot and latest blockhash shared across components
+#[derive(Debug)]
+pub struct SlotClock {
+    config: SlotClockConfig,
+    slot: AtomicU64,
+    slot_updated: parking_lot::Mutex<Option<Instant>>,
+    subscribed: AtomicBool,
+    blockhash: parking_lot::RwLock<Option<LatestBlockhash>>,
+}
+
+impl SlotClock {
+    pub fn new(config: SlotClockConfig) -> Self {
+        Self {
+            config,
+            slot: AtomicU64::new(0),
+            slot_updated: parking_lot::Mutex::new(None),
+            subscribed: AtomicBool::new(false),
+            blockhash: parking_lot::RwLock::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &SlotClockConfig {
+        &self.config
+    }
+
+    /// Current slot, or `None` if it has not advanced within `stale_after`
+    pub fn current_slot(&self) -> Option<u64> {
+        let updated = (*self.slot_updated.lock())?;
+        (updated.elapsed() < self.config.stale_after).then(|| self.slot.load(Ordering::Acquire))
+    }
+
+    /// Last observed slot, however old
+    pub fn last_slot(&self) -> u64 {
+        self.slot.load(Ordering::Acquire)
+    }
+
+    /// Whether slots are currently pushed by `slotSubscribe`
+    pub fn is_subscribed(&self) -> bool {
+        self.subscribed.load(Ordering::Relaxed)
+    }
+
+    /// Record an observed slot; slots never move backwards
+    pub fn observe_slot(&self, slot: u64) {
+        if self.slot.fetch_max(slot, Ordering::AcqRel) < slot {
+            *self.slot_updated.lock() = Some(Instant::now());
+        }
+    }
+
+    /// Latest blockhash, however close to expiry
+    pub fn latest_blockhash(&self) -> Option<LatestBlockhash> {
+        *self.blockhash.read()
+    }
+
+    /// Latest blockhash if it has at least `min_slots_remaining` slots left
+    /// at the current slot
+    pub fn fresh_blockhash(&self) -> Option<LatestBlockhash> {
+        let blockhash = self.latest_blockhash()?;
+        let current_slot = self.current_slot()?;
+        (blockhash.slots_remaining(current_slot) >= self.config.min_slots_remaining)
+            .then_some(blockhash)
+    }
+
+    /// Slots left on `blockhash` at the current slot, if the slot is known
+    pub fn slots_remaining(&self, blockhash: &LatestBlockhash) -> Option<u64> {
+        Some(blockhash.slots_remaining(self.current_slot()?))
+    }
+
+    /// Record a fetched blockhash unless a later one is already held
+    pub fn set_blockhash(&self, blockhash: LatestBlockhash) {
+        self.observe_slot(blockhash.slot);
+        let mut current = self.blockhash.write();
+        if current
+            .is_none_or(|held| held.last_valid_block_height <= blockhash.last_valid_block_height)
+        {
+            *current = Some(blockhash);
+        }
+    }
+
+    /// Fetch the latest blockhash, its validity window and the slot
+    pub async fn refresh_blockhash(
+        &self,
+        pool: &RpcPool,
+    ) -> Result<LatestBlockhash, RpcManagerError> {
+        let commitment = self.config.commitment;
+        let (hash, last_valid_block_height, block_height, slot) = pool
+            .hedged(&HedgeConfig::default(), |client| async move {
+                let ((hash, last_valid_block_height), block_height, slot) = tokio::try_join!(
+                    client.get_latest_blockhash_with_commitment(commitment),
+                    client.get_block_height_with_commitment(commitment),
+                    client.get_slot_with_commitment(commitment),
+                )?;
+                Ok((hash, last_valid_block_height, block_height, slot))
+            })
+            .await?;
+
+        let blockhash = LatestBlockhash {
+            hash,
+            last_valid_block_height,
+            block_height,
+            slot,
+            fetched_at: Instant::now(),
+        };
+        self.set_blockhash(blockhash);
+        debug!(
+            blockhash = %hash,
+            slot,
+            expiry_slot = blockhash.expiry_slot(),
+            "Blockhash refreshed"
+        );
+        Ok(blockhash)
+    }
+
+    async fn poll_slot(&self, pool: &RpcPool) -> Result<u64, RpcManagerError> {
+        let Some(lease) = pool.acquire_endpoint().await else {
+            return Err(pool.no_healthy_endpoints().await);
+        };
+        let slot = lease
+            .call(|client| async move {
+                client
+                    .get_slot_with_commitment(CommitmentConfig::processed())
+                    .await
+            })
+            .await?;
+        self.observe_slot(slot);
+        Ok(slot)
+    }
+
+    /// Follow `slotSubscribe` until the stream ends or stalls
+    async fn follow_subscription(&self, ws_url: &str) -> Result<(), String> {
+        let client = tokio::time::timeout(self.config.stale_after, PubsubClient::new(ws_url))
+            .await
+            .map_err(|_| "connect timed out".to_string())?
+            .map_err(|e| e.to_string())?;
+        let (mut slots, unsubscribe) = client.slot_subscribe().await.map_err(|e| e.to_string())?;
+
+        info!(url = %ws_url, "Slot subscription established");
+        self.subscribed.store(true, Ordering::Relaxed);
+        let result = loop {
+            match tokio::time::timeout(self.config.stale_after, slots.next()).await {
+                Ok(Some(info)) => self.observe_slot(info.slot),
+                Ok(None) => break Err("stream closed".to_string()),
+                Err(_) => break Err("no slot update within stale_after".to_string()),
+            }
+        };
+        self.subscribed.store(false, Ordering::Relaxed);
+        drop(slots);
+        unsubscribe().await;
+        result
+    }
+
+    /// Poll `getSlot` until `until` (forever when `None`)
+    async fn poll_until(&self, pool: &RpcPool, until: Option<Instant>) {
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        while until.is_none_or(|until| Instant::now() < until) {
+            interval.tick().await;
+            if let Err(e) = self.poll_slot(pool).await {
+                debug!(error = %e, "Slot poll failed");
+            }
+        }
+    }
+
+    /// Run the slot tracker and blockhash refresher until aborted
+    pub fn spawn(self: Arc<Self>, pool: Arc<RpcPool>) -> JoinHandle<()> {
+        let blockhash_clock = self.clone();
+        let blockhash_pool = pool.clone();
+        let blockhash_task = tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(blockhash_clock.config.blockhash_refresh_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                if let Err(e) = blockhash_clock.refresh_blockhash(&blockhash_pool).await {
+                    warn!(error = %e, "Blockhash refresh failed");
+                }
+            }
+        });
+
+        tokio::spawn(async move {
+            // Aborting the clock aborts the refresher with it
+            struct AbortOnDrop(JoinHandle<()>);
+            impl Drop for AbortOnDrop {
+                fn drop(&mut self) {
+                    self.0.abort();
+                }
+            }
+            let _blockhash_task = AbortOnDrop(blockhash_task);
+
+            let Some(ws_url) = self.config.ws_url.clone() else {
+                return self.poll_until(&pool, None).await;
+            };
+            let mut backoff = self.config.reconnect_backoff;
+            loop {
+                let started = Instant::now();
+                if let Err(e) = self.follow_subscription(&ws_url).await {
+                    warn!(error = %e, "Slot subscription down; polling getSlot");
+                }
+                // A subscription that ran for a while resets the backoff
+                if started.elapsed() > self.config.max_reconnect_backoff {
+                    backoff = self.config.reconnect_backoff;
+                }
+                self.poll_until(&pool, Some(Instant::now() + backoff)).await;
+                backoff = (backoff * 2).min(self.config.max_reconnect_backoff);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blockhash(last_valid_block_height: u64, block_height: u64, slot: u64) -> LatestBlockhash {
+        LatestBlockhash {
+            hash: Hash::new_unique(),
+            last_valid_block_height,
+            block_height,
+            slot,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_blockhash_expiry_in_slots() {
+        let hash = blockhash(1_150, 1_000, 5_000);
+        assert_eq!(hash.expiry_slot(), 5_150);
+        assert_eq!(hash.slots_remaining(5_100), 50);
+        assert!(!hash.is_expired(5_149));
+        assert!(hash.is_expired(5_150));
+    }
+
+    #[test]
+    fn test_slot_is_monotonic_and_goes_stale() {
+        let clock = SlotClock::new(SlotClockConfig {
+            stale_after: Duration::from_millis(50),
+            ..Default::default()
+        });
+        assert_eq!(clock.current_slot(), None);
+
+        clock.observe_slot(100);
+        clock.observe_slot(90);
+        assert_eq!(clock.current_slot(), Some(100));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(clock.current_slot(), None);
+        assert_eq!(clock.last_slot(), 100);
+    }
+
+    #[test]
+    fn test_fresh_blockhash_requires_slots_remaining() {
+        let clock = SlotClock::new(SlotClockConfig {
+            min_slots_remaining: 30,
+            ..Default::default()
+        });
+        let newer = blockhash(1_150, 1_000, 5_000);
+        clock.set_blockhash(newer);
+        // An older blockhash arriving late does not replace the newer one
+        clock.set_blockhash(blockhash(1_140, 990, 4_990));
+        assert_eq!(clock.latest_blockhash(), Some(newer));
+
+        clock.observe_slot(5_120);
+        assert_eq!(clock.fresh_blockhash(), Some(newer));
+        clock.observe_slot(5_121);
+        assert_eq!(clock.slots_remaining(&newer), Some(29));
+        assert_eq!(clock.fresh_blockhash(), None);
+    }
+}
@@ -0,0 +1,528 @@
+//! Push-invalidated account cache for [`RpcPool`](super::RpcPool)
+//!
+//! The first read of an account opens an `accountSubscribe` on a shared
+//! websocket connection. Every notification overwrites the cached entry,
+//! tagged with its context slot, so while a subscription is live a cached
+//! account is served without a TTL. Subscriptions are reference-counted
+//! ([`AccountWatch`]) and closed once unreferenced and unread for
+//! `idle_timeout`. While the websocket is down reads fall back to the pool's
+//! TTL cache and subscriptions are re-opened on reconnect.
+use super::rpc_pool::{cache_account, AccountCache};
+use dashmap::DashMap;
+use futures::StreamExt;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_client::rpc_response::Response;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Notify};
+use tracing::{debug, info, warn};
+
+/// Account subscription configuration
+#[derive(Debug, Clone)]
+pub struct AccountSubscriptionConfig {
+    /// Websocket endpoint (`ws://` or `wss://`)
+    pub ws_url: String,
+    /// Commitment of the pushed account updates
+    pub commitment: CommitmentConfig,
+    /// Close subscriptions that were not read or watched for this long
+    pub idle_timeout: Duration,
+    /// Maximum open subscriptions; further accounts use the TTL cache only
+    pub max_subscriptions: usize,
+    /// Delay before reconnecting after the websocket fails
+    pub reconnect_backoff: Duration,
+    /// Upper bound for the reconnect backoff
+    pub max_reconnect_backoff: Duration,
+    /// Timeout for establishing the websocket connection
+    pub connect_timeout: Duration,
+}
+
+impl Default for AccountSubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            ws_url: String::new(),
+            commitment: CommitmentConfig::confirmed(),
+            idle_timeout: Duration::from_secs(60),
+            max_subscriptions: 256,
+            reconnect_backoff: Duration::from_secs(1),
+            max_reconnect_backoff: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// State of one account subscription
+#[derive(Debug)]
+struct Subscription {
+    /// Outstanding [`AccountWatch`] guards
+    refs: AtomicUsize,
+    last_used: parking_lot::Mutex<Instant>,
+    /// When the current notification stream was opened; `None` while down
+    live_since: parking_lot::Mutex<Option<Instant>>,
+    closed: AtomicBool,
+    close: Notify,
+}
+
+impl Subscription {
+    fn new() -> Self {
+        Self {
+            refs: AtomicUsize::new(0),
+            last_used: parking_lot::Mutex::new(Instant::now()),
+            live_since: parking_lot::Mutex::new(None),
+            closed: AtomicBool::new(false),
+            close: Notify::new(),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_used.lock() = Instant::now();
+    }
+
+    fn is_idle(&self, idle_timeout: Duration) -> bool {
+        self.refs.load(Ordering::Acquire) == 0 && self.last_used.lock().elapsed() >= idle_timeout
+    }
+
+    fn shut(&self) {
+        self.closed.store(true, Ordering::Release);
+        *self.live_since.lock() = None;
+        self.close.notify_one();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+}
+
+/// Keeps an account's subscription open while held
+#[derive(Debug)]
+pub struct AccountWatch {
+    pubkey: Pubkey,
+    subscription: Arc<Subscription>,
+}
+
+impl AccountWatch {
+    /// Watched account
+    pub fn pubkey(&self) -> &Pubkey {
+        &self.pubkey
+    }
+
+    /// Whether updates for the account are currently being pushed
+    pub fn is_live(&self) -> bool {
+        self.subscription.live_since.lock().is_some()
+    }
+}
+
+impl Drop for AccountWatch {
+    fn drop(&mut self) {
+        self.subscription.touch();
+        self.subscription.refs.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Shared websocket connection with reconnect backoff
+struct Connection {
+    client: Option<Arc<PubsubClient>>,
+    next_attempt: Instant,
+    backoff: Duration,
+}
+
+/// Account subscriptions feeding the pool's account cache
+pub struct AccountSubscriptions {
+    config: AccountSubscriptionConfig,
+    cache: Arc<AccountCache>,
+    subscriptions: DashMap<Pubkey, Arc<Subscription>>,
+    connection: Mutex<Connection>,
+    connected: AtomicBool,
+    sweeper_started: AtomicBool,
+}
+
+impl std::fmt::Debug for AccountSubscriptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountSubscriptions")
+            .field("config", &self.config)
+            .field("subscriptions", &self.subscriptions.len())
+            .field("connected", &self.is_connected())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AccountSubscriptions {
+    pub(super) fn new(config: AccountSubscriptionConfig, cache: Arc<AccountCache>) -> Self {
+        let backoff = config.reconnect_backoff;
+        Self {
+            config,
+            cache,
+            subscriptions: DashMap::new(),
+            connection: Mutex::new(Connection {
+                client: None,
+                next_attempt: Instant::now(),
+                backoff,
+            }),
+            connected: AtomicBool::new(false),
+            sweeper_started: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the websocket is connected
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Number of open subscriptions
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Whether no subscriptions are open
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Number of subscriptions currently receiving updates
+    pub fn live_count(&self) -> usize {
+        self.subscriptions
+            .iter()
+            .filter(|entry| entry.live_since.lock().is_some())
+            .count()
+    }
+
+    /// Since when cached data for `pubkey` is kept current by pushes
+    pub(super) fn live_since(&self, pubkey: &Pubkey) -> Option<Instant> {
+        let subscription = self.subscriptions.get(pubkey)?;
+        let since = *subscription.live_since.lock();
+        since
+    }
+
+    /// Record a read of `pubkey`, subscribing on first use
+    ///
+    /// Returns since when the cached entry is kept current by pushes.
+    pub(super) fn touch(self: &Arc<Self>, pubkey: &Pubkey) -> Option<Instant> {
+        let subscription = self.get_or_subscribe(pubkey, false)?;
+        let since = *subscription.live_since.lock();
+        since
+    }
+
+    /// Hold `pubkey`'s subscription open until the returned guard is dropped
+    ///
+    /// Returns `None` when `max_subscriptions` is reached.
+    pub fn watch(self: &Arc<Self>, pubkey: &Pubkey) -> Option<AccountWatch> {
+        let subscription = self.get_or_subscribe(pubkey, true)?;
+        Some(AccountWatch {
+            pubkey: *pubkey,
+            subscription,
+        })
+    }
+
+    fn get_or_subscribe(
+        self: &Arc<Self>,
+        pubkey: &Pubkey,
+        watch: bool,
+    ) -> Option<Arc<Subscription>> {
+        if !self.subscriptions.contains_key(pubkey)
+            && self.subscriptions.len() >= self.config.max_subscriptions
+        {
+            return None;
+        }
+
+        let mut created = false;
+        let subscription = {
+            let entry = self.subscriptions.entry(*pubkey).or_insert_with(|| {
+                created = true;
+                Arc::new(Subscription::new())
+            });
+            // Updated under the map entry lock so the sweeper cannot close the
+            // subscription between lookup and use
+            entry.touch();
+            if watch {
+                entry.refs.fetch_add(1, Ordering::AcqRel);
+            }
+            entry.value().clone()
+        };
+
+        if created {
+            debug!(pubkey = %pubkey, "Opening account subscription");
+            tokio::spawn(self.clone().run(*pubkey, subscription.clone()));
+            self.start_sweeper();
+        }
+        Some(subscription)
+    }
+
+    /// Close subscriptions that are unreferenced and idle
+    pub fn close_idle(&self) -> usize {
+        let idle_timeout = self.config.idle_timeout;
+        let mut closed = 0;
+        self.subscriptions.retain(|pubkey, subscription| {
+            if subscription.is_idle(idle_timeout) {
+                debug!(pubkey = %pubkey, "Closing idle account subscription");
+                subscription.shut();
+                closed += 1;
+                false
+            } else {
+                true
+            }
+        });
+        closed
+    }
+
+    fn start_sweeper(self: &Arc<Self>) {
+        if self.sweeper_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let this: Weak<Self> = Arc::downgrade(self);
+        let period = (self.config.idle_timeout / 2).max(Duration::from_secs(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let Some(this) = this.upgrade() else { break };
+                this.close_idle();
+            }
+        });
+    }
+
+    fn account_config(&self) -> RpcAccountInfoConfig {
+        RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64Zstd),
+            commitment: Some(self.config.commitment),
+            data_slice: None,
+            min_context_slot: None,
+        }
+    }
+
+    /// Shared websocket client, connecting if due
+    async fn client(&self) -> Option<Arc<PubsubClient>> {
+        let mut connection = self.connection.lock().await;
+        if let Some(client) = &connection.client {
+            return Some(client.clone());
+        }
+        if Instant::now() < connection.next_attempt {
+            return None;
+        }
+
+        match tokio::time::timeout(
+            self.config.connect_timeout,
+            PubsubClient::new(&self.config.ws_url),
+        )
+        .await
+        {
+            Ok(Ok(client)) => {
+                info!(url = %self.config.ws_url, "Account subscription websocket connected");
+                let client = Arc::new(client);
+                connection.client = Some(client.clone());
+                connection.backoff = self.config.reconnect_backoff;
+                self.connected.store(true, Ordering::Relaxed);
+                Some(client)
+            }
+            result => {
+                let error = match result {
+                    Ok(Err(e)) => e.to_string(),
+                    _ => "connect timed out".to_string(),
+                };
+                warn!(
+                    error = %error,
+                    retry_in_ms = connection.backoff.as_millis() as u64,
+                    "Account subscription websocket unavailable; using TTL cache"
+                );
+                connection.next_attempt = Instant::now() + connection.backoff;
+                connection.backoff =
+                    (connection.backoff * 2).min(self.config.max_reconnect_backoff);
+                self.connected.store(false, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Drop `client` so the next subscription attempt reconnects
+    async fn disconnected(&self, client: &Arc<PubsubClient>) {
+        let mut connection = self.connection.lock().await;
+        if connection
+            .client
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(current, client))
+        {
+            warn!(url = %self.config.ws_url, "Account subscription websocket lost; using TTL cache");
+            connection.client = None;
+            connection.next_attempt = Instant::now() + connection.backoff;
+            self.connected.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Subscription task: (re)subscribe and apply updates until closed
+    async fn run(self: Arc<Self>, pubkey: Pubkey, subscription: Arc<Subscription>) {
+        while !subscription.is_closed() {
+            if let Some(client) = self.client().await {
+                match client
+                    .account_subscribe(&pubkey, Some(self.account_config()))
+                    .await
+                {
+                    Ok((mut updates, unsubscribe)) => {
+                        *subscription.live_since.lock() = Some(Instant::now());
+                        loop {
+                            tokio::select! {
+                                _ = subscription.close.notified() => break,
+                                update = updates.next() => match update {
+                                    Some(update) => self.apply(&pubkey, update),
+                                    None => break,
+                                },
+                            }
+                        }
+                        *subscription.live_since.lock() = None;
+                        drop(updates);
+
+                        if subscription.is_closed() {
+                            unsubscribe().await;
+                            break;
+                        }
+                        self.disconnected(&client).await;
+                    }
+                    Err(e) => {
+                        warn!(pubkey = %pubkey, error = %e, "accountSubscribe failed");
+                        self.disconnected(&client).await;
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = subscription.close.notified() => {}
+                _ = tokio::time::sleep(self.config.reconnect_backoff) => {}
+            }
+        }
+        debug!(pubkey = %pubkey, "Account subscription closed");
+    }
+
+    fn apply(&self, pubkey: &Pubkey, update: Response<UiAccount>) {
+        let slot = update.context.slot;
+        match update.value.decode::<Account>() {
+            Some(account) => self.store(pubkey, Some(account), slot),
+            None => {
+                warn!(pubkey = %pubkey, slot, "Undecodable account notification");
+                self.store(pubkey, None, slot);
+            }
+        }
+    }
+
+    /// Apply a pushed account state observed at `slot`
+    ///
+    /// A closed (zero-lamport) or undecodable account evicts the entry so the
+    /// next read goes to RPC.
+    fn store(&self, pubkey: &Pubkey, account: Option<Account>, slot: u64) {
+        match account {
+            Some(account) if account.lamports > 0 => {
+                cache_account(&self.cache, *pubkey, account, slot)
+            }
+            _ => {
+                self.cache.remove_if(pubkey, |_, entry| entry.slot <= slot);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_manager::RpcPool;
+
+    fn account(lamports: u64) -> Account {
+        Account {
+            lamports,
+            ..Account::default()
+        }
+    }
+
+    fn pool(idle_timeout: Duration) -> RpcPool {
+        RpcPool::new(
+            Vec::new(),
+            Duration::from_secs(30),
+            3,
+            Duration::from_millis(100),
+        )
+        .unwrap()
+        .with_account_subscriptions(AccountSubscriptionConfig {
+            ws_url: "ws://127.0.0.1:1".to_string(),
+            idle_timeout,
+            ..Default::default()
+        })
+    }
+
+    /// Insert a subscription that is live without a websocket
+    fn live(subscriptions: &AccountSubscriptions, pubkey: Pubkey) -> Arc<Subscription> {
+        let subscription = Arc::new(Subscription::new());
+        *subscription.live_since.lock() = Some(Instant::now());
+        subscriptions
+            .subscriptions
+            .insert(pubkey, subscription.clone());
+        subscription
+    }
+
+    #[test]
+    fn test_pushed_updates_respect_slot_order() {
+        let subscriptions = AccountSubscriptions::new(
+            AccountSubscriptionConfig::default(),
+            Arc::new(AccountCache::new()),
+        );
+        let pubkey = Pubkey::new_unique();
+
+        subscriptions.store(&pubkey, Some(account(10)), 100);
+        subscriptions.store(&pubkey, Some(account(5)), 90);
+        let entry = subscriptions.cache.get(&pubkey).unwrap();
+        assert_eq!((entry.account.lamports, entry.slot), (10, 100));
+        drop(entry);
+
+        // A closing notification older than the cached state is ignored
+        subscriptions.store(&pubkey, Some(account(0)), 99);
+        assert!(subscriptions.cache.contains_key(&pubkey));
+        subscriptions.store(&pubkey, Some(account(0)), 101);
+        assert!(!subscriptions.cache.contains_key(&pubkey));
+    }
+
+    #[tokio::test]
+    async fn test_live_subscription_bypasses_ttl_but_not_min_slot() {
+        let pool = pool(Duration::from_secs(60));
+        let subscriptions = pool.account_subscriptions().unwrap().clone();
+        let pubkey = Pubkey::new_unique();
+        let subscription = live(&subscriptions, pubkey);
+        subscriptions.store(&pubkey, Some(account(42)), 200);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let cached = pool
+            .get_account_cached_with_min_slot(&pubkey, CommitmentConfig::confirmed(), Some(200))
+            .await
+            .unwrap();
+        assert_eq!(cached.map(|a| a.lamports), Some(42));
+
+        // Newer than the cache: goes to RPC (no endpoints here)
+        assert!(pool
+            .get_account_cached_with_min_slot(&pubkey, CommitmentConfig::confirmed(), Some(201))
+            .await
+            .is_err());
+
+        // Websocket down: TTL applies again
+        *subscription.live_since.lock() = None;
+        assert!(pool
+            .get_account_cached(&pubkey, CommitmentConfig::confirmed())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watched_subscriptions_are_not_closed() {
+        let pool = pool(Duration::ZERO);
+        let subscriptions = pool.account_subscriptions().unwrap().clone();
+        let pubkey = Pubkey::new_unique();
+
+        let watch = pool.watch_account(&pubkey).unwrap();
+        let again = pool.watch_account(&pubkey).unwrap();
+        assert_eq!(subscriptions.len(), 1);
+        assert!(!watch.is_live());
+
+        drop(watch);
+        assert_eq!(subscriptions.close_idle(), 0);
+        drop(again);
+        assert_eq!(subscriptions.close_idle(), 1);
+        assert!(subscriptions.is_empty());
+    }
+}
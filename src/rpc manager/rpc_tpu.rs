@@ -0,0 +1,768 @@
+//! Leader-aware TPU sender
+//!
+//! [`TpuSender`] sends wire transactions over QUIC straight to the TPU ports of
+//! the current and next few leaders, skipping the JSON-RPC hop. The leader
+//! schedule (`getSlotLeaders`) and cluster contact info (`getClusterNodes`)
+//! are fetched through the [`RpcPool`]; the current slot comes from the shared
+//! [`SlotClock`].
+//!
+//! Connections are cached per TPU address and reused until they fail or go
+//! idle. Every send is recorded against the leader it was addressed to, so
+//! leaders that never accept our streams show up in [`TpuSender::leader_stats`]
+//! and the `tpu_leader_sends_total` metric.
+//! Connections use an ephemeral self-signed certificate and are therefore
+//! unstaked.
+use super::rpc_errors::RpcManagerError;
+use super::rpc_hedging::HedgeConfig;
+use super::rpc_pool::RpcPool;
+use super::rpc_slot_clock::SlotClock;
+use crate::metrics::{FamilyBuilder, MetricSource};
+use dashmap::DashMap;
+use quinn::crypto::rustls::QuicClientConfig;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use solana_client::rpc_response::RpcContactInfo;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// ALPN protocol spoken by validator TPU QUIC servers
+pub const TPU_ALPN: &[u8] = b"solana-tpu";
+
+/// Consecutive slots assigned to one leader
+const LEADER_SLOTS: u64 = 4;
+
+/// Which path a transaction takes to the cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionRoute {
+    /// JSON-RPC `sendTransaction` only
+    Rpc,
+    /// QUIC to the upcoming leaders only
+    Tpu,
+    /// Both at once; succeeds if either path does
+    TpuAndRpc,
+}
+
+/// TPU sender configuration
+#[derive(Debug, Clone)]
+pub struct TpuConfig {
+    /// Upcoming leaders sent to in addition to the current one
+    pub leader_fanout: usize,
+    /// Slots of leader schedule fetched per refresh
+    pub schedule_lookahead_slots: u64,
+    /// How often the schedule is checked for running out
+    pub schedule_check_interval: Duration,
+    /// Contact info refresh interval
+    pub cluster_nodes_refresh_interval: Duration,
+    /// QUIC handshake timeout
+    pub connect_timeout: Duration,
+    /// Timeout for writing one transaction to one leader
+    pub send_timeout: Duration,
+    /// Cached connections are closed after this long without traffic
+    pub max_idle_timeout: Duration,
+}
+
+impl Default for TpuConfig {
+    fn default() -> Self {
+        Self {
+            leader_fanout: 2,
+            schedule_lookahead_slots: 512,
+            schedule_check_interval: Duration::from_secs(1),
+            cluster_nodes_refresh_interval: Duration::from_secs(60),
+            connect_timeout: Duration::from_secs(2),
+            send_timeout: Duration::from_secs(1),
+            max_idle_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Slot leaders for a contiguous range of slots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderSchedule {
+    pub first_slot: u64,
+    pub leaders: Vec<Pubkey>,
+}
+
+impl LeaderSchedule {
+    /// One past the last slot covered
+    pub fn end_slot(&self) -> u64 {
+        self.first_slot + self.leaders.len() as u64
+    }
+
+    pub fn leader_at(&self, slot: u64) -> Option<Pubkey> {
+        let index = slot.checked_sub(self.first_slot)?;
+        self.leaders.get(usize::try_from(index).ok()?).copied()
+    }
+
+    /// Distinct leaders of the leader window containing `slot` and the
+    /// `fanout` windows after it
+    pub fn upcoming_leaders(&self, slot: u64, fanout: usize) -> Vec<Pubkey> {
+        let window_start = slot - slot % LEADER_SLOTS;
+        let window_end = window_start.saturating_add((fanout as u64 + 1) * LEADER_SLOTS);
+        let mut leaders = Vec::with_capacity(fanout + 1);
+        for slot in slot..window_end.min(self.end_slot()) {
+            if let Some(leader) = self.leader_at(slot) {
+                if !leaders.contains(&leader) {
+                    leaders.push(leader);
+                }
+            }
+        }
+        leaders
+    }
+}
+
+/// Send outcomes for one leader
+#[derive(Debug, Clone, Default)]
+pub struct LeaderStats {
+    pub sent: u64,
+    pub failed: u64,
+    pub last_sent: Option<Instant>,
+    pub last_error: Option<String>,
+}
+
+/// Result of sending one transaction to the upcoming leaders
+#[derive(Debug, Clone)]
+pub struct TpuSendReport {
+    pub signature: Signature,
+    pub slot: u64,
+    /// Per-leader outcome, in schedule order
+    pub leaders: Vec<(Pubkey, Result<(), String>)>,
+}
+
+impl TpuSendReport {
+    /// Leaders that accepted the transaction stream
+    pub fn delivered(&self) -> usize {
+        self.leaders.iter().filter(|(_, r)| r.is_ok()).count()
+    }
+}
+
+/// Aggregate sender state
+#[derive(Debug, Clone)]
+pub struct TpuStats {
+    pub schedule: Option<(u64, u64)>,
+    pub known_tpu_addrs: usize,
+    pub connections: usize,
+    pub sent: u64,
+    pub failed: u64,
+}
+
+/// Sends transactions to leader TPU ports over QUIC
+pub struct TpuSender {
+    config: TpuConfig,
+    slot_clock: Arc<SlotClock>,
+    endpoint: quinn::Endpoint,
+    schedule: parking_lot::RwLock<Option<LeaderSchedule>>,
+    tpu_addrs: parking_lot::RwLock<HashMap<Pubkey, SocketAddr>>,
+    connections: DashMap<SocketAddr, quinn::Connection>,
+    leader_stats: DashMap<Pubkey, LeaderStats>,
+    /// Transactions accepted by at least one leader
+    delivered: AtomicU64,
+    /// Transactions no leader accepted
+    undelivered: AtomicU64,
+}
+
+impl std::fmt::Debug for TpuSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TpuSender")
+            .field("config", &self.config)
+            .field("connections", &self.connections.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TpuSender {
+    /// Create a sender with its own QUIC client endpoint
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn new(config: TpuConfig, slot_clock: Arc<SlotClock>) -> Result<Self, RpcManagerError> {
+        let endpoint = quinn::Endpoint::client(SocketAddr::from(([0, 0, 0, 0], 0)))
+            .map_err(|e| RpcManagerError::Internal(format!("QUIC endpoint: {}", e)))?;
+        let mut sender = Self {
+            config,
+            slot_clock,
+            endpoint,
+            schedule: parking_lot::RwLock::new(None),
+            tpu_addrs: parking_lot::RwLock::new(HashMap::new()),
+            connections: DashMap::new(),
+            leader_stats: DashMap::new(),
+            delivered: AtomicU64::new(0),
+            undelivered: AtomicU64::new(0),
+        };
+        let client_config = sender.client_config()?;
+        sender.endpoint.set_default_client_config(client_config);
+        Ok(sender)
+    }
+
+    fn client_config(&self) -> Result<quinn::ClientConfig, RpcManagerError> {
+        let tls_error = |e: &dyn std::fmt::Display| {
+            RpcManagerError::Configuration(format!("TPU client TLS: {}", e))
+        };
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+            .map_err(|e| tls_error(&e))?;
+        let key = PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der());
+
+        let mut crypto = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .map_err(|e| tls_error(&e))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(SkipServerVerification(provider)))
+            .with_client_auth_cert(vec![certified.cert.der().clone()], key.into())
+            .map_err(|e| tls_error(&e))?;
+        crypto.alpn_protocols = vec![TPU_ALPN.to_vec()];
+
+        let crypto = QuicClientConfig::try_from(crypto).map_err(|e| tls_error(&e))?;
+        let mut transport = quinn::TransportConfig::default();
+        transport.max_idle_timeout(Some(
+            quinn::IdleTimeout::try_from(self.config.max_idle_timeout)
+                .map_err(|e| tls_error(&e))?,
+        ));
+        let mut client_config = quinn::ClientConfig::new(Arc::new(crypto));
+        client_config.transport_config(Arc::new(transport));
+        Ok(client_config)
+    }
+
+    pub fn config(&self) -> &TpuConfig {
+        &self.config
+    }
+
+    pub fn leader_schedule(&self) -> Option<LeaderSchedule> {
+        self.schedule.read().clone()
+    }
+
+    /// Replace the leader schedule
+    pub fn set_leader_schedule(&self, schedule: LeaderSchedule) {
+        *self.schedule.write() = Some(schedule);
+    }
+
+    /// Replace the known TPU QUIC addresses from cluster contact info
+    pub fn set_cluster_nodes(&self, nodes: &[RpcContactInfo]) {
+        let addrs: HashMap<Pubkey, SocketAddr> = nodes
+            .iter()
+            .filter_map(|node| Some((Pubkey::from_str(&node.pubkey).ok()?, node.tpu_quic?)))
+            .collect();
+        *self.tpu_addrs.write() = addrs;
+    }
+
+    /// TPU QUIC address of `leader`, if it is in the contact info
+    pub fn tpu_addr(&self, leader: &Pubkey) -> Option<SocketAddr> {
+        self.tpu_addrs.read().get(leader).copied()
+    }
+
+    /// Leaders a transaction sent now would go to
+    pub fn upcoming_leaders(&self) -> Option<(u64, Vec<Pubkey>)> {
+        let slot = self.slot_clock.current_slot()?;
+        let schedule = self.schedule.read();
+        let leaders = schedule
+            .as_ref()?
+            .upcoming_leaders(slot, self.config.leader_fanout);
+        (!leaders.is_empty()).then_some((slot, leaders))
+    }
+
+    pub fn leader_stats(&self, leader: &Pubkey) -> Option<LeaderStats> {
+        self.leader_stats.get(leader).map(|stats| stats.clone())
+    }
+
+    pub fn stats(&self) -> TpuStats {
+        let (sent, failed) = self
+            .leader_stats
+            .iter()
+            .fold((0, 0), |(sent, failed), stats| {
+                (sent + stats.sent, failed + stats.failed)
+            });
+        TpuStats {
+            schedule: self
+                .schedule
+                .read()
+                .as_ref()
+                .map(|schedule| (schedule.first_slot, schedule.end_slot())),
+            known_tpu_addrs: self.tpu_addrs.read().len(),
+            connections: self.connections.len(),
+            sent,
+            failed,
+        }
+    }
+
+    /// Fetch the leader schedule starting at the current slot
+    pub async fn refresh_leader_schedule(&self, pool: &RpcPool) -> Result<(), RpcManagerError> {
+        let first_slot = self
+            .slot_clock
+            .current_slot()
+            .ok_or_else(|| RpcManagerError::Internal("slot clock is stale".to_string()))?;
+        let limit = self.config.schedule_lookahead_slots;
+        let leaders = pool
+            .hedged(&HedgeConfig::default(), |client| async move {
+                client.get_slot_leaders(first_slot, limit).await
+            })
+            .await?;
+        debug!(
+            first_slot,
+            slots = leaders.len(),
+            "Leader schedule refreshed"
+        );
+        self.set_leader_schedule(LeaderSchedule {
+            first_slot,
+            leaders,
+        });
+        Ok(())
+    }
+
+    /// Fetch the TPU QUIC addresses of all cluster nodes
+    pub async fn refresh_cluster_nodes(&self, pool: &RpcPool) -> Result<(), RpcManagerError> {
+        let nodes = pool
+            .hedged(&HedgeConfig::default(), |client| async move {
+                client.get_cluster_nodes().await
+            })
+            .await?;
+        self.set_cluster_nodes(&nodes);
+        debug!(nodes = nodes.len(), "Cluster contact info refreshed");
+        Ok(())
+    }
+
+    /// Whether fewer than half of the fetched slots are still ahead of `slot`
+    fn schedule_needs_refresh(&self, slot: u64) -> bool {
+        self.schedule.read().as_ref().is_none_or(|schedule| {
+            slot < schedule.first_slot
+                || slot + self.config.schedule_lookahead_slots / 2 >= schedule.end_slot()
+        })
+    }
+
+    /// Serialize and send `tx` to the upcoming leaders
+    pub async fn send_transaction(
+        &self,
+        tx: &VersionedTransaction,
+    ) -> Result<TpuSendReport, RpcManagerError> {
+        let signature = tx.signatures.first().copied().unwrap_or_default();
+        let wire = bincode::serialize(tx)
+            .map_err(|e| RpcManagerError::Validation(format!("serialize transaction: {}", e)))?;
+        let mut report = self.send_wire_transaction(&wire).await?;
+        report.signature = signature;
+        Ok(report)
+    }
+
+    /// Send an already serialized transaction to the current and next
+    /// `leader_fanout` leaders
+    ///
+    /// Fails only if no leader accepted it.
+    pub async fn send_wire_transaction(
+        &self,
+        wire: &[u8],
+    ) -> Result<TpuSendReport, RpcManagerError> {
+        let Some((slot, leaders)) = self.upcoming_leaders() else {
+            self.undelivered.fetch_add(1, Ordering::Relaxed);
+            return Err(RpcManagerError::Internal(
+                "no leader schedule for the current slot".to_string(),
+            ));
+        };
+
+        let sends = leaders.iter().map(|leader| async move {
+            let result = match self.tpu_addr(leader) {
+                Some(addr) => self.send_to(addr, wire).await,
+                None => Err("no TPU QUIC address in contact info".to_string()),
+            };
+            self.record(leader, &result);
+            (*leader, result)
+        });
+        let report = TpuSendReport {
+            signature: Signature::default(),
+            slot,
+            leaders: futures::future::join_all(sends).await,
+        };
+
+        if report.delivered() == 0 {
+            self.undelivered.fetch_add(1, Ordering::Relaxed);
+            let errors: Vec<String> = report
+                .leaders
+                .iter()
+                .filter_map(|(leader, r)| r.as_ref().err().map(|e| format!("{}: {}", leader, e)))
+                .collect();
+            return Err(RpcManagerError::Transport {
+                endpoint: "tpu".to_string(),
+                message: errors.join("; "),
+                source: None,
+            });
+        }
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+        Ok(report)
+    }
+
+    /// Write `wire` on a new unidirectional stream, retrying once on a fresh
+    /// connection if the cached one turns out to be dead
+    async fn send_to(&self, addr: SocketAddr, wire: &[u8]) -> Result<(), String> {
+        let cached = self.connections.get(&addr).map(|c| c.clone());
+        if let Some(connection) = cached {
+            match self.write_stream(&connection, wire).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    debug!(%addr, error = %e, "Cached TPU connection failed; reconnecting");
+                    self.connections
+                        .remove_if(&addr, |_, c| c.stable_id() == connection.stable_id());
+                }
+            }
+        }
+
+        let connection = self.connect(addr).await?;
+        let result = self.write_stream(&connection, wire).await;
+        if result.is_ok() {
+            self.connections.insert(addr, connection);
+        }
+        result
+    }
+
+    async fn connect(&self, addr: SocketAddr) -> Result<quinn::Connection, String> {
+        // Drop connections that went idle or were closed by the peer
+        self.connections.retain(|_, c| c.close_reason().is_none());
+
+        let connecting = self
+            .endpoint
+            .connect(addr, "localhost")
+            .map_err(|e| e.to_string())?;
+        tokio::time::timeout(self.config.connect_timeout, connecting)
+            .await
+            .map_err(|_| "connect timed out".to_string())?
+            .map_err(|e| e.to_string())
+    }
+
+    async fn write_stream(
+        &self,
+        connection: &quinn::Connection,
+        wire: &[u8],
+    ) -> Result<(), String> {
+        tokio::time::timeout(self.config.send_timeout, async {
+            let mut stream = connection.open_uni().await.map_err(|e| e.to_string())?;
+            stream.write_all(wire).await.map_err(|e| e.to_string())?;
+            stream.finish().map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|_| "send timed out".to_string())?
+    }
+
+    fn record(&self, leader: &Pubkey, result: &Result<(), String>) {
+        let mut stats = self.leader_stats.entry(*leader).or_default();
+        match result {
+            Ok(()) => {
+                stats.sent += 1;
+                stats.last_sent = Some(Instant::now());
+            }
+            Err(e) => {
+                stats.failed += 1;
+                stats.last_error = Some(e.clone());
+            }
+        }
+    }
+
+    /// Keep the leader schedule and contact info current until aborted
+    pub fn spawn(self: Arc<Self>, pool: Arc<RpcPool>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.schedule_check_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut nodes_refreshed: Option<Instant> = None;
+            loop {
+                interval.tick().await;
+                if nodes_refreshed
+                    .is_none_or(|at| at.elapsed() >= self.config.cluster_nodes_refresh_interval)
+                {
+                    match self.refresh_cluster_nodes(&pool).await {
+                        Ok(()) => nodes_refreshed = Some(Instant::now()),
+                        Err(e) => warn!(error = %e, "Cluster contact info refresh failed"),
+                    }
+                }
+                let Some(slot) = self.slot_clock.current_slot() else {
+                    continue;
+                };
+                if self.schedule_needs_refresh(slot) {
+                    if let Err(e) = self.refresh_leader_schedule(&pool).await {
+                        warn!(error = %e, "Leader schedule refresh failed");
+                    } else {
+                        info!(slot, "Leader schedule loaded");
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl MetricSource for TpuSender {
+    fn collect_metrics(&self, families: &mut FamilyBuilder) {
+        families
+            .counter(
+                "tpu_transactions_total",
+                "Transactions sent over TPU, by whether any leader accepted them",
+                &["outcome"],
+            )
+            .sample(
+                &["delivered"],
+                self.delivered.load(Ordering::Relaxed) as f64,
+            )
+            .sample(&["failed"], self.undelivered.load(Ordering::Relaxed) as f64);
+
+        let mut sends = families.counter(
+            "tpu_leader_sends_total",
+            "Transaction streams per leader and outcome",
+            &["leader", "outcome"],
+        );
+        for entry in self.leader_stats.iter() {
+            let leader = entry.key().to_string();
+            sends
+                .sample(&[&leader, "sent"], entry.sent as f64)
+                .sample(&[&leader, "failed"], entry.failed as f64);
+        }
+
+        families
+            .gauge(
+                "tpu_connections",
+                "Cached QUIC connections to leader TPUs",
+                &[],
+            )
+            .sample(&[], self.connections.len() as f64);
+    }
+}
+
+/// Validators present self-signed certificates, so the server certificate is
+/// not verified; the handshake signature still is
+#[derive(Debug)]
+struct SkipServerVerification(Arc<rustls::crypto::CryptoProvider>);
+
+impl ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_manager::SlotClockConfig;
+    use quinn::crypto::rustls::QuicServerConfig;
+    use tokio::sync::mpsc;
+
+    /// Local stand-in for a validator TPU: accepts QUIC connections and
+    /// forwards every unidirectional stream it receives
+    fn tpu_listener() -> (SocketAddr, mpsc::UnboundedReceiver<Vec<u8>>) {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let key = PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der());
+        let mut crypto = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .unwrap()
+        .with_no_client_auth()
+        .with_single_cert(vec![certified.cert.der().clone()], key.into())
+        .unwrap();
+        crypto.alpn_protocols = vec![TPU_ALPN.to_vec()];
+        let server_config =
+            quinn::ServerConfig::with_crypto(Arc::new(QuicServerConfig::try_from(crypto).unwrap()));
+        let endpoint =
+            quinn::Endpoint::server(server_config, SocketAddr::from(([127, 0, 0, 1], 0))).unwrap();
+        let addr = endpoint.local_addr().unwrap();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(incoming) = endpoint.accept().await {
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let Ok(connection) = incoming.await else {
+                        return;
+                    };
+                    while let Ok(mut stream) = connection.accept_uni().await {
+                        if let Ok(bytes) = stream.read_to_end(1232).await {
+                            let _ = tx.send(bytes);
+                        }
+                    }
+                });
+            }
+        });
+        (addr, rx)
+    }
+
+    fn contact_info(leader: &Pubkey, tpu_quic: SocketAddr) -> RpcContactInfo {
+        RpcContactInfo {
+            pubkey: leader.to_string(),
+            gossip: None,
+            tvu: None,
+            tpu: None,
+            tpu_quic: Some(tpu_quic),
+            tpu_forwards: None,
+            tpu_forwards_quic: None,
+            tpu_vote: None,
+            serve_repair: None,
+            rpc: None,
+            pubsub: None,
+            version: None,
+            feature_set: None,
+            shred_version: None,
+        }
+    }
+
+    fn sender_at_slot(slot: u64, config: TpuConfig) -> TpuSender {
+        let clock = Arc::new(SlotClock::new(SlotClockConfig::default()));
+        clock.observe_slot(slot);
+        TpuSender::new(config, clock).unwrap()
+    }
+
+    #[test]
+    fn test_upcoming_leaders_are_distinct_and_bounded() {
+        let (a, b, c, d) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut leaders = Vec::new();
+        for leader in [a, b, a, c, d] {
+            leaders.extend([leader; 4]);
+        }
+        let schedule = LeaderSchedule {
+            first_slot: 100,
+            leaders,
+        };
+
+        assert_eq!(schedule.leader_at(99), None);
+        assert_eq!(schedule.leader_at(105), Some(b));
+        // Slot 102 is mid-window; a fanout of 2 covers the windows at 100, 104 and 108
+        assert_eq!(schedule.upcoming_leaders(102, 2), vec![a, b]);
+        assert_eq!(schedule.upcoming_leaders(102, 3), vec![a, b, c]);
+        // The schedule ends at slot 120
+        assert_eq!(schedule.upcoming_leaders(116, 5), vec![d]);
+        assert!(schedule.upcoming_leaders(120, 5).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sends_to_leader_tpu_over_quic() {
+        let (addr, mut received) = tpu_listener();
+        let leader = Pubkey::new_unique();
+        let sender = sender_at_slot(1_000, TpuConfig::default());
+        sender.set_leader_schedule(LeaderSchedule {
+            first_slot: 1_000,
+            leaders: vec![leader; 16],
+        });
+        sender.set_cluster_nodes(&[contact_info(&leader, addr)]);
+
+        for payload in [b"first tx".as_slice(), b"second tx".as_slice()] {
+            let report = sender.send_wire_transaction(payload).await.unwrap();
+            assert_eq!(report.slot, 1_000);
+            assert_eq!(report.delivered(), 1);
+            let bytes = tokio::time::timeout(Duration::from_secs(5), received.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(bytes, payload);
+        }
+
+        // The second send reused the cached connection
+        assert_eq!(sender.stats().connections, 1);
+        let stats = sender.leader_stats(&leader).unwrap();
+        assert_eq!(stats.sent, 2);
+        assert_eq!(stats.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_leaders_are_accounted_per_leader() {
+        let (addr, mut received) = tpu_listener();
+        let (reachable, unreachable, unknown) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        // Nothing listens on this port once the socket is dropped
+        let dead_addr = std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let sender = sender_at_slot(
+            400,
+            TpuConfig {
+                connect_timeout: Duration::from_millis(300),
+                ..Default::default()
+            },
+        );
+        let mut leaders = vec![unreachable; 4];
+        leaders.extend([reachable; 4]);
+        leaders.extend([unknown; 4]);
+        sender.set_leader_schedule(LeaderSchedule {
+            first_slot: 400,
+            leaders,
+        });
+        sender.set_cluster_nodes(&[
+            contact_info(&reachable, addr),
+            contact_info(&unreachable, dead_addr),
+        ]);
+
+        let report = sender.send_wire_transaction(b"tx").await.unwrap();
+        assert_eq!(report.delivered(), 1);
+        assert_eq!(
+            report.leaders.iter().map(|(l, _)| *l).collect::<Vec<_>>(),
+            vec![unreachable, reachable, unknown]
+        );
+        assert_eq!(received.recv().await.unwrap(), b"tx");
+        assert_eq!(sender.leader_stats(&unreachable).unwrap().failed, 1);
+        assert_eq!(sender.leader_stats(&unknown).unwrap().failed, 1);
+        assert_eq!(sender.leader_stats(&reachable).unwrap().sent, 1);
+
+        // With only the dead leader ahead the send fails outright
+        sender.set_cluster_nodes(&[contact_info(&unreachable, dead_addr)]);
+        sender.set_leader_schedule(LeaderSchedule {
+            first_slot: 400,
+            leaders: vec![unreachable; 4],
+        });
+        assert!(sender.send_wire_transaction(b"tx").await.is_err());
+        assert_eq!(sender.stats().failed, 3);
+
+        let mut families = FamilyBuilder::default();
+        sender.collect_metrics(&mut families);
+        assert_eq!(
+            families.names(),
+            [
+                "tpu_transactions_total",
+                "tpu_leader_sends_total",
+                "tpu_connections"
+            ]
+        );
+    }
+}
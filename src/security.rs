@@ -134,6 +134,589 @@ pub mod validator {
     }
 }
 
+/// Token safety screening performed before buying a candidate
+///
+/// Fetches the mint account, decodes authorities and Token-2022 extensions,
+/// checks decimals/supply sanity and top-holder concentration, and produces a
+/// per-check [`ScreeningReport`] that is attached to the candidate.
+pub mod screening {
+    use super::*;
+    use crate::config::ScreeningConfig;
+    use crate::token_program::{MintExtension, MintInfo};
+    use crate::types::{
+        CheckOutcome, CheckResult, PremintCandidate, ScreeningCheck, ScreeningReport,
+    };
+    use async_trait::async_trait;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// Source of on-chain mint data used by the screener
+    #[async_trait]
+    pub trait MintDataSource: Send + Sync {
+        /// Fetch `(owner, data)` of the mint account; `None` if it does not exist
+        async fn get_mint_account(
+            &self,
+            mint: &Pubkey,
+        ) -> anyhow::Result<Option<(Pubkey, Vec<u8>)>>;
+
+        /// Largest token accounts of the mint as `(token_account, raw_amount)`
+        async fn get_largest_holders(&self, mint: &Pubkey) -> anyhow::Result<Vec<(Pubkey, u64)>>;
+    }
+
+    #[async_trait]
+    impl MintDataSource for RpcClient {
+        async fn get_mint_account(
+            &self,
+            mint: &Pubkey,
+        ) -> anyhow::Result<Option<(Pubkey, Vec<u8>)>> {
+            let response = self
+                .get_account_with_commitment(mint, self.commitment())
+                .await?;
+            Ok(response.value.map(|account| (account.owner, account.data)))
+        }
+
+        async fn get_largest_holders(&self, mint: &Pubkey) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+            let accounts = self.get_token_largest_accounts(mint).await?;
+            accounts
+                .into_iter()
+                .map(|acc| {
+                    let address = Pubkey::from_str(&acc.address).map_err(|e| {
+                        anyhow::anyhow!("Invalid holder address {}: {}", acc.address, e)
+                    })?;
+                    let amount = acc
+                        .amount
+                        .amount
+                        .parse::<u64>()
+                        .map_err(|e| anyhow::anyhow!("Invalid holder amount: {}", e))?;
+                    Ok((address, amount))
+                })
+                .collect()
+        }
+    }
+
+    /// Pre-buy token safety screener
+    pub struct TokenScreener {
+        source: Arc<dyn MintDataSource>,
+        config: ScreeningConfig,
+        excluded: HashSet<Pubkey>,
+    }
+
+    impl TokenScreener {
+        /// Create a screener; invalid entries in `excluded_holders` are ignored
+        pub fn new(source: Arc<dyn MintDataSource>, config: ScreeningConfig) -> Self {
+            let excluded = config
+                .excluded_holders
+                .iter()
+                .filter_map(|s| Pubkey::from_str(s).ok())
+                .collect();
+            Self {
+                source,
+                config,
+                excluded,
+            }
+        }
+
+        /// Screening rules in effect
+        pub fn config(&self) -> &ScreeningConfig {
+            &self.config
+        }
+
+        /// Run every check for a candidate, bounded by `timeout_ms`
+        ///
+        /// A screen that does not finish in time is reported like an RPC
+        /// failure of the mint fetch, so `fail_open_on_rpc_error` decides
+        /// whether the candidate proceeds.
+        pub async fn screen(&self, candidate: &PremintCandidate) -> ScreeningReport {
+            let timeout = Duration::from_millis(self.config.timeout_ms);
+            let checks = match tokio::time::timeout(timeout, self.run_checks(candidate)).await {
+                Ok(checks) => checks,
+                Err(_) => vec![self.rpc_failure(
+                    ScreeningCheck::MintAccount,
+                    &anyhow::anyhow!("timed out after {}ms", self.config.timeout_ms),
+                )],
+            };
+
+            ScreeningReport {
+                checks,
+                screened_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+            }
+        }
+
+        async fn run_checks(&self, candidate: &PremintCandidate) -> Vec<CheckResult> {
+            let mut checks = Vec::new();
+
+            let mint_info = match self.source.get_mint_account(&candidate.mint).await {
+                Ok(Some((owner, data))) => match MintInfo::decode(&owner, &data) {
+                    Ok(info) if info.is_initialized => {
+                        checks.push(result(
+                            ScreeningCheck::MintAccount,
+                            CheckOutcome::Pass,
+                            format!("{:?} mint", info.token_program),
+                        ));
+                        Some(info)
+                    }
+                    Ok(_) => {
+                        checks.push(result(
+                            ScreeningCheck::MintAccount,
+                            CheckOutcome::Fail,
+                            "mint not initialized".to_string(),
+                        ));
+                        None
+                    }
+                    Err(e) => {
+                        checks.push(result(
+                            ScreeningCheck::MintAccount,
+                            CheckOutcome::Fail,
+                            e.to_string(),
+                        ));
+                        None
+                    }
+                },
+                Ok(None) => {
+                    checks.push(result(
+                        ScreeningCheck::MintAccount,
+                        CheckOutcome::Fail,
+                        "mint account not found".to_string(),
+                    ));
+                    None
+                }
+                Err(e) => {
+                    checks.push(self.rpc_failure(ScreeningCheck::MintAccount, &e));
+                    None
+                }
+            };
+
+            if let Some(info) = &mint_info {
+                checks.extend(evaluate_mint(&self.config, info));
+
+                if self.config.check_holders {
+                    let check = match self.source.get_largest_holders(&candidate.mint).await {
+                        Ok(holders) => {
+                            let mut excluded = self.excluded.clone();
+                            excluded.extend(candidate.accounts.iter().copied());
+                            evaluate_holders(&self.config, info.supply, &holders, &excluded)
+                        }
+                        Err(e) => self.rpc_failure(ScreeningCheck::HolderConcentration, &e),
+                    };
+                    checks.push(check);
+                }
+            }
+
+            checks
+        }
+
+        fn rpc_failure(&self, check: ScreeningCheck, err: &anyhow::Error) -> CheckResult {
+            let outcome = if self.config.fail_open_on_rpc_error {
+                CheckOutcome::Skipped
+            } else {
+                CheckOutcome::Fail
+            };
+            result(check, outcome, format!("RPC error: {}", err))
+        }
+    }
+
+    fn result(check: ScreeningCheck, outcome: CheckOutcome, detail: String) -> CheckResult {
+        CheckResult {
+            check,
+            outcome,
+            detail,
+        }
+    }
+
+    /// `Fail` if the rule rejects, otherwise `Warn`
+    fn risk(reject: bool) -> CheckOutcome {
+        if reject {
+            CheckOutcome::Fail
+        } else {
+            CheckOutcome::Warn
+        }
+    }
+
+    /// Evaluate authority, extension, decimals and supply checks for a decoded mint
+    pub fn evaluate_mint(config: &ScreeningConfig, info: &MintInfo) -> Vec<CheckResult> {
+        let mut checks = Vec::new();
+
+        checks.push(match info.mint_authority {
+            None => result(
+                ScreeningCheck::MintAuthority,
+                CheckOutcome::Pass,
+                "revoked".to_string(),
+            ),
+            Some(auth) => result(
+                ScreeningCheck::MintAuthority,
+                risk(config.reject_mint_authority),
+                format!("mint authority set: {}", auth),
+            ),
+        });
+
+        checks.push(match info.freeze_authority {
+            None => result(
+                ScreeningCheck::FreezeAuthority,
+                CheckOutcome::Pass,
+                "revoked".to_string(),
+            ),
+            Some(auth) => result(
+                ScreeningCheck::FreezeAuthority,
+                risk(config.reject_freeze_authority),
+                format!("freeze authority set: {}", auth),
+            ),
+        });
+
+        checks.push(match info.transfer_fee_config() {
+            None => result(
+                ScreeningCheck::TransferFee,
+                CheckOutcome::Pass,
+                "none".to_string(),
+            ),
+            Some(fee) if fee.max_basis_points() <= config.max_transfer_fee_bps => result(
+                ScreeningCheck::TransferFee,
+                CheckOutcome::Pass,
+                format!("{} bps", fee.max_basis_points()),
+            ),
+            Some(fee) => result(
+                ScreeningCheck::TransferFee,
+                risk(config.reject_transfer_fee),
+                format!(
+                    "{} bps exceeds {} bps",
+                    fee.max_basis_points(),
+                    config.max_transfer_fee_bps
+                ),
+            ),
+        });
+
+        checks.push(match info.permanent_delegate() {
+            None | Some(None) => result(
+                ScreeningCheck::PermanentDelegate,
+                CheckOutcome::Pass,
+                "none".to_string(),
+            ),
+            Some(Some(delegate)) => result(
+                ScreeningCheck::PermanentDelegate,
+                risk(config.reject_permanent_delegate),
+                format!("permanent delegate: {}", delegate),
+            ),
+        });
+
+        checks.push(match info.transfer_hook_program() {
+            None => result(
+                ScreeningCheck::TransferHook,
+                CheckOutcome::Pass,
+                "none".to_string(),
+            ),
+            Some(program) => result(
+                ScreeningCheck::TransferHook,
+                risk(config.reject_transfer_hook),
+                format!("transfer hook program: {}", program),
+            ),
+        });
+
+        checks.push(
+            if info.has_extension(|e| matches!(e, MintExtension::NonTransferable)) {
+                result(
+                    ScreeningCheck::NonTransferable,
+                    risk(config.reject_non_transferable),
+                    "non-transferable".to_string(),
+                )
+            } else {
+                result(
+                    ScreeningCheck::NonTransferable,
+                    CheckOutcome::Pass,
+                    "transferable".to_string(),
+                )
+            },
+        );
+
+        checks.push(
+            if info
+                .has_extension(|e| matches!(e, MintExtension::DefaultAccountState { frozen: true }))
+            {
+                result(
+                    ScreeningCheck::DefaultAccountState,
+                    risk(config.reject_default_frozen),
+                    "accounts frozen by default".to_string(),
+                )
+            } else {
+                result(
+                    ScreeningCheck::DefaultAccountState,
+                    CheckOutcome::Pass,
+                    "initialized".to_string(),
+                )
+            },
+        );
+
+        checks.push(
+            if (config.min_decimals..=config.max_decimals).contains(&info.decimals) {
+                result(
+                    ScreeningCheck::Decimals,
+                    CheckOutcome::Pass,
+                    format!("{}", info.decimals),
+                )
+            } else {
+                result(
+                    ScreeningCheck::Decimals,
+                    CheckOutcome::Fail,
+                    format!(
+                        "{} outside {}..={}",
+                        info.decimals, config.min_decimals, config.max_decimals
+                    ),
+                )
+            },
+        );
+
+        let whole_tokens = info.supply / 10u64.saturating_pow(info.decimals as u32).max(1);
+        checks.push(if info.supply == 0 {
+            result(
+                ScreeningCheck::Supply,
+                CheckOutcome::Fail,
+                "zero supply".to_string(),
+            )
+        } else if config.min_supply_tokens > 0 && whole_tokens < config.min_supply_tokens {
+            result(
+                ScreeningCheck::Supply,
+                CheckOutcome::Fail,
+                format!(
+                    "{} below minimum {}",
+                    whole_tokens, config.min_supply_tokens
+                ),
+            )
+        } else if config.max_supply_tokens > 0 && whole_tokens > config.max_supply_tokens {
+            result(
+                ScreeningCheck::Supply,
+                CheckOutcome::Fail,
+                format!(
+                    "{} above maximum {}",
+                    whole_tokens, config.max_supply_tokens
+                ),
+            )
+        } else {
+            result(
+                ScreeningCheck::Supply,
+                CheckOutcome::Pass,
+                format!("{}", whole_tokens),
+            )
+        });
+
+        checks
+    }
+
+    /// Evaluate top-holder concentration, ignoring `excluded` token accounts
+    pub fn evaluate_holders(
+        config: &ScreeningConfig,
+        supply: u64,
+        holders: &[(Pubkey, u64)],
+        excluded: &HashSet<Pubkey>,
+    ) -> CheckResult {
+        if supply == 0 {
+            return result(
+                ScreeningCheck::HolderConcentration,
+                CheckOutcome::Skipped,
+                "zero supply".to_string(),
+            );
+        }
+
+        let mut amounts: Vec<u64> = holders
+            .iter()
+            .filter(|(address, _)| !excluded.contains(address))
+            .map(|(_, amount)| *amount)
+            .collect();
+        amounts.sort_unstable_by(|a, b| b.cmp(a));
+
+        let top1 = amounts.first().copied().unwrap_or(0) as f64 / supply as f64;
+        let top10 = amounts.iter().take(10).sum::<u64>() as f64 / supply as f64;
+        let detail = format!("top1={:.1}% top10={:.1}%", top1 * 100.0, top10 * 100.0);
+
+        if top1 > config.max_top_holder_pct || top10 > config.max_top10_holder_pct {
+            result(
+                ScreeningCheck::HolderConcentration,
+                CheckOutcome::Fail,
+                detail,
+            )
+        } else {
+            result(
+                ScreeningCheck::HolderConcentration,
+                CheckOutcome::Pass,
+                detail,
+            )
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::token_program::tests::{mint_bytes, transfer_fee_ext};
+        use crate::token_program::TOKEN_2022_PROGRAM_ID;
+        use crate::types::PriorityLevel;
+
+        struct MockSource {
+            owner: Pubkey,
+            data: Vec<u8>,
+            holders: Vec<(Pubkey, u64)>,
+        }
+
+        #[async_trait]
+        impl MintDataSource for MockSource {
+            async fn get_mint_account(
+                &self,
+                _mint: &Pubkey,
+            ) -> anyhow::Result<Option<(Pubkey, Vec<u8>)>> {
+                Ok(Some((self.owner, self.data.clone())))
+            }
+
+            async fn get_largest_holders(
+                &self,
+                _mint: &Pubkey,
+            ) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+                Ok(self.holders.clone())
+            }
+        }
+
+        /// Source whose RPC calls never complete
+        struct StalledSource;
+
+        #[async_trait]
+        impl MintDataSource for StalledSource {
+            async fn get_mint_account(
+                &self,
+                _mint: &Pubkey,
+            ) -> anyhow::Result<Option<(Pubkey, Vec<u8>)>> {
+                std::future::pending().await
+            }
+
+            async fn get_largest_holders(
+                &self,
+                _mint: &Pubkey,
+            ) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+                std::future::pending().await
+            }
+        }
+
+        fn candidate(accounts: Vec<Pubkey>) -> PremintCandidate {
+            PremintCandidate {
+                mint: Pubkey::new_unique(),
+                program: "pump.fun".to_string(),
+                accounts,
+                priority: PriorityLevel::High,
+                timestamp: 0,
+                price_hint: None,
+                signature: None,
+                screening: None,
+                trace: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_clean_mint_passes_with_excluded_vault() {
+            let vault = Pubkey::new_unique();
+            let supply = 1_000_000_000 * 1_000_000;
+            let source = MockSource {
+                owner: spl_token::id(),
+                data: mint_bytes(None, supply, 6, None, &[]),
+                holders: vec![
+                    (vault, supply / 10 * 8),
+                    (Pubkey::new_unique(), supply / 100),
+                ],
+            };
+            let screener = TokenScreener::new(Arc::new(source), ScreeningConfig::default());
+
+            let report = screener.screen(&candidate(vec![vault])).await;
+            assert!(report.passed(), "failures: {:?}", report.failures());
+            assert_eq!(
+                report
+                    .get(ScreeningCheck::HolderConcentration)
+                    .unwrap()
+                    .outcome,
+                CheckOutcome::Pass
+            );
+        }
+
+        #[tokio::test]
+        async fn test_authorities_and_extensions_rejected() {
+            let source = MockSource {
+                owner: TOKEN_2022_PROGRAM_ID,
+                data: mint_bytes(
+                    Some(Pubkey::new_unique()),
+                    1_000_000,
+                    6,
+                    Some(Pubkey::new_unique()),
+                    &[
+                        transfer_fee_ext(500, u64::MAX),
+                        (12, Pubkey::new_unique().to_bytes().to_vec()),
+                    ],
+                ),
+                holders: vec![],
+            };
+            let screener = TokenScreener::new(Arc::new(source), ScreeningConfig::default());
+
+            let report = screener.screen(&candidate(vec![])).await;
+            assert!(!report.passed());
+            let failed: HashSet<ScreeningCheck> =
+                report.failures().iter().map(|c| c.check).collect();
+            assert!(failed.contains(&ScreeningCheck::MintAuthority));
+            assert!(failed.contains(&ScreeningCheck::FreezeAuthority));
+            assert!(failed.contains(&ScreeningCheck::TransferFee));
+            assert!(failed.contains(&ScreeningCheck::PermanentDelegate));
+        }
+
+        #[tokio::test]
+        async fn test_stalled_rpc_times_out_per_fail_open_policy() {
+            let config = ScreeningConfig {
+                timeout_ms: 10,
+                ..ScreeningConfig::default()
+            };
+            let screener = TokenScreener::new(Arc::new(StalledSource), config.clone());
+            let report = screener.screen(&candidate(vec![])).await;
+            assert!(!report.passed());
+            assert_eq!(
+                report.get(ScreeningCheck::MintAccount).unwrap().outcome,
+                CheckOutcome::Fail
+            );
+
+            let config = ScreeningConfig {
+                fail_open_on_rpc_error: true,
+                ..config
+            };
+            let screener = TokenScreener::new(Arc::new(StalledSource), config);
+            let report = screener.screen(&candidate(vec![])).await;
+            assert!(report.passed(), "failures: {:?}", report.failures());
+            assert_eq!(
+                report.get(ScreeningCheck::MintAccount).unwrap().outcome,
+                CheckOutcome::Skipped
+            );
+        }
+
+        #[test]
+        fn test_disabled_rule_downgrades_to_warning() {
+            let config = ScreeningConfig {
+                reject_freeze_authority: false,
+                ..ScreeningConfig::default()
+            };
+            let data = mint_bytes(None, 1_000_000, 6, Some(Pubkey::new_unique()), &[]);
+            let info = MintInfo::decode(&spl_token::id(), &data).unwrap();
+
+            let checks = evaluate_mint(&config, &info);
+            let freeze = checks
+                .iter()
+                .find(|c| c.check == ScreeningCheck::FreezeAuthority)
+                .unwrap();
+            assert_eq!(freeze.outcome, CheckOutcome::Warn);
+        }
+
+        #[test]
+        fn test_holder_concentration_rejected() {
+            let config = ScreeningConfig::default();
+            let holders = vec![(Pubkey::new_unique(), 300), (Pubkey::new_unique(), 100)];
+            let check = evaluate_holders(&config, 1_000, &holders, &HashSet::new());
+            assert_eq!(check.outcome, CheckOutcome::Fail);
+        }
+    }
+}
+
 /// Taint tracking for security
 pub struct TaintTracker {
     // Placeholder for taint tracking implementation
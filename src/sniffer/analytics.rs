@@ -82,9 +82,9 @@ pub struct PredictiveAnalytics {
     threshold: AtomicF64,
 
     /// Smoothing factor for short window (typically 0.1-0.3)
-    alpha_short: f64,
+    alpha_short: AtomicF64,
     /// Smoothing factor for long window (typically 0.01-0.05)
-    alpha_long: f64,
+    alpha_long: AtomicF64,
 }
 
 impl PredictiveAnalytics {
@@ -96,8 +96,8 @@ impl PredictiveAnalytics {
             short_window_ema: AtomicF64::new(0.0),
             long_window_ema: AtomicF64::new(0.0),
             threshold: AtomicF64::new(initial_threshold),
-            alpha_short,
-            alpha_long,
+            alpha_short: AtomicF64::new(alpha_short),
+            alpha_long: AtomicF64::new(alpha_long),
         }
     }
 
@@ -126,17 +126,19 @@ impl PredictiveAnalytics {
         // Update EMAs
         let short_ema = self.short_window_ema.load(Ordering::Relaxed);
         let long_ema = self.long_window_ema.load(Ordering::Relaxed);
+        let alpha_short = self.alpha_short.load(Ordering::Relaxed);
+        let alpha_long = self.alpha_long.load(Ordering::Relaxed);
 
         let new_short_ema = if short_ema == 0.0 {
             avg_volume
         } else {
-            self.alpha_short * avg_volume + (1.0 - self.alpha_short) * short_ema
+            alpha_short * avg_volume + (1.0 - alpha_short) * short_ema
         };
 
         let new_long_ema = if long_ema == 0.0 {
             avg_volume
         } else {
-            self.alpha_long * avg_volume + (1.0 - self.alpha_long) * long_ema
+            alpha_long * avg_volume + (1.0 - alpha_long) * long_ema
         };
 
         self.short_window_ema
@@ -190,6 +192,25 @@ impl PredictiveAnalytics {
         )
     }
 
+    /// Replace the EMA smoothing factors (takes effect on the next update)
+    pub fn set_alphas(&self, alpha_short: f64, alpha_long: f64) {
+        self.alpha_short.store(alpha_short, Ordering::Relaxed);
+        self.alpha_long.store(alpha_long, Ordering::Relaxed);
+    }
+
+    /// Get current EMA smoothing factors (short, long)
+    pub fn get_alphas(&self) -> (f64, f64) {
+        (
+            self.alpha_short.load(Ordering::Relaxed),
+            self.alpha_long.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Reset the dynamic threshold to a new value
+    pub fn set_threshold(&self, threshold: f64) {
+        self.threshold.store(threshold, Ordering::Relaxed);
+    }
+
     /// Get current threshold value
     pub fn get_threshold(&self) -> f64 {
         self.threshold.load(Ordering::Relaxed)
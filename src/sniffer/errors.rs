@@ -72,6 +72,29 @@ impl fmt::Display for MintExtractError {
 
 impl std::error::Error for MintExtractError {}
 
+/// Error type for Anchor event decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventDecodeError {
+    /// "Program data:" payload is not valid base64
+    InvalidBase64,
+    /// Payload ended before all required fields were read
+    Truncated,
+    /// String field is not valid UTF-8
+    InvalidString,
+}
+
+impl fmt::Display for EventDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBase64 => write!(f, "Invalid base64 event payload"),
+            Self::Truncated => write!(f, "Event payload truncated"),
+            Self::InvalidString => write!(f, "Invalid UTF-8 in event string"),
+        }
+    }
+}
+
+impl std::error::Error for EventDecodeError {}
+
 /// Error type for account extraction
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AccountExtractError {
@@ -118,13 +141,18 @@ impl ExponentialBackoff {
 
     /// Get the next backoff duration with jitter
     pub fn next_backoff(&mut self) -> Duration {
-        let backoff_ms =
-            (self.initial_backoff_ms * 2_u64.pow(self.current_attempt)).min(self.max_backoff_ms);
+        let backoff_ms = self
+            .initial_backoff_ms
+            .saturating_mul(2_u64.saturating_pow(self.current_attempt))
+            .min(self.max_backoff_ms);
 
-        self.current_attempt += 1;
+        self.current_attempt = self.current_attempt.saturating_add(1);
 
         // Add jitter (±20%)
         let jitter = (backoff_ms / 5) as i64;
+        if jitter == 0 {
+            return Duration::from_millis(backoff_ms);
+        }
         let jitter_amount = (rand::random::<i64>() % (2 * jitter)) - jitter;
         let final_backoff = (backoff_ms as i64 + jitter_amount).max(0) as u64;
 
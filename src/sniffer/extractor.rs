@@ -2,6 +2,7 @@
 
 use super::errors::{AccountExtractError, MintExtractError};
 use super::prefilter;
+use crate::observability::SpanContext;
 use smallvec::SmallVec;
 use solana_sdk::pubkey::Pubkey;
 
@@ -28,6 +29,8 @@ pub struct PremintCandidate {
     pub trace_id: u64,
     /// Priority level
     pub priority: PriorityLevel,
+    /// Exported trace context, opened at extraction
+    pub span: SpanContext,
 }
 
 impl PremintCandidate {
@@ -46,6 +49,7 @@ impl PremintCandidate {
             price_hint,
             trace_id,
             priority,
+            span: SpanContext::new_root(),
         }
     }
 
@@ -78,6 +82,27 @@ impl PremintCandidate {
     pub fn is_high_priority(&self) -> bool {
         matches!(self.priority, PriorityLevel::High)
     }
+
+    /// Buy engine candidate for `program`, carrying the trace context
+    pub fn into_buy_candidate(self, program: &str) -> crate::types::PremintCandidate {
+        crate::types::PremintCandidate {
+            mint: self.mint,
+            program: program.to_string(),
+            accounts: self.accounts.to_vec(),
+            priority: match self.priority {
+                PriorityLevel::High => crate::types::PriorityLevel::High,
+                PriorityLevel::Low => crate::types::PriorityLevel::Low,
+            },
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            price_hint: Some(self.price_hint),
+            signature: None,
+            screening: None,
+            trace: Some(self.span),
+        }
+    }
 }
 
 /// Extraction error type
@@ -121,6 +146,23 @@ mod tests {
         assert!(candidate.is_high_priority());
     }
 
+    #[test]
+    fn test_buy_candidate_keeps_trace() {
+        let candidate = PremintCandidate::new(
+            Pubkey::new_unique(),
+            SmallVec::new(),
+            1.5,
+            7,
+            PriorityLevel::Low,
+        );
+        let span = candidate.span;
+
+        let buy_candidate = candidate.clone().into_buy_candidate("pump.fun");
+        assert_eq!(buy_candidate.mint, candidate.mint);
+        assert_eq!(buy_candidate.program, "pump.fun");
+        assert_eq!(buy_candidate.trace, Some(span));
+    }
+
     #[test]
     fn test_priority_level() {
         let mint = Pubkey::new_unique();
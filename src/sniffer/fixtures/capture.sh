@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# Capture a pump.fun transaction's logs as an events.rs fixture
+#
+#   capture.sh <signature> <fixture.log> [rpc_url]
+#
+# Writes `# source: <signature>` followed by the transaction's
+# `meta.logMessages` from `getTransaction`, one line each.
+set -euo pipefail
+
+sig="${1:?usage: capture.sh <signature> <fixture.log> [rpc_url]}"
+out="${2:?usage: capture.sh <signature> <fixture.log> [rpc_url]}"
+rpc="${3:-https://api.mainnet-beta.solana.com}"
+
+response=$(curl -sf "$rpc" -H 'Content-Type: application/json' -d "{
+  \"jsonrpc\": \"2.0\", \"id\": 1, \"method\": \"getTransaction\",
+  \"params\": [\"$sig\", {\"encoding\": \"json\", \"maxSupportedTransactionVersion\": 0}]
+}")
+
+if ! jq -e '.result.meta.logMessages' <<<"$response" >/dev/null; then
+  echo "no logs for $sig: $(jq -c '.error // .result' <<<"$response")" >&2
+  exit 1
+fi
+
+{
+  echo "# source: $sig"
+  jq -r '.result.meta.logMessages[]' <<<"$response"
+} >"$out"
+echo "wrote $(wc -l <"$out") lines to $out"
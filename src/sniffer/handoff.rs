@@ -3,6 +3,8 @@
 use super::config::DropPolicy;
 use super::extractor::PremintCandidate;
 use super::telemetry::{HandoffDiagnostics, SnifferMetrics};
+use crate::observability::Span;
+use crate::trace_export;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -28,6 +30,25 @@ pub fn try_send_candidate(
     candidate: PremintCandidate,
     metrics: &Arc<SnifferMetrics>,
     diagnostics: Option<&Arc<HandoffDiagnostics>>,
+) -> HandoffResult {
+    let span =
+        trace_export::enabled().then(|| Span::start("sniffer.handoff", Some(candidate.span)));
+    let result = send_candidate(tx, candidate, metrics, diagnostics);
+    if let Some(mut span) = span {
+        match result {
+            HandoffResult::Sent => span.set_ok(),
+            HandoffResult::Dropped | HandoffResult::Backpressure => span.set_error("dropped"),
+        }
+        span.end();
+    }
+    result
+}
+
+fn send_candidate(
+    tx: &mpsc::Sender<PremintCandidate>,
+    candidate: PremintCandidate,
+    metrics: &Arc<SnifferMetrics>,
+    diagnostics: Option<&Arc<HandoffDiagnostics>>,
 ) -> HandoffResult {
     match tx.try_send(candidate.clone()) {
         Ok(_) => {
@@ -16,8 +16,10 @@ use super::extractor::{PremintCandidate, PriorityLevel};
 use super::handoff;
 use super::prefilter;
 use super::security;
-use super::supervisor::{Supervisor, WorkerHandle};
+use super::supervisor::{worker_factory, RestartPolicy, Supervisor, SupervisorConfig};
 use super::telemetry::{HandoffDiagnostics, SnifferMetrics};
+use crate::observability::Span;
+use crate::trace_export;
 
 /// Public API trait for Sniffer operations
 pub trait SnifferApi {
@@ -114,9 +116,15 @@ impl Sniffer {
             config.initial_threshold,
         ));
 
+        let metrics = Arc::new(SnifferMetrics::new());
+        let supervisor = Arc::new(Supervisor::with_config(
+            SupervisorConfig::default(),
+            Some(Arc::clone(&metrics)),
+        ));
+
         Self {
             config,
-            metrics: Arc::new(SnifferMetrics::new()),
+            metrics,
             analytics,
             running: Arc::new(AtomicBool::new(false)),
             paused: Arc::new(AtomicBool::new(false)),
@@ -124,7 +132,7 @@ impl Sniffer {
             health_ok: Arc::new(AtomicBool::new(true)),
             event_collector: Arc::new(EventCollector::new(10000)),
             handoff_diagnostics: Arc::new(HandoffDiagnostics::new()),
-            supervisor: Arc::new(Supervisor::new()),
+            supervisor,
         }
     }
 
@@ -259,7 +267,10 @@ impl Sniffer {
 
                     // HOT-PATH: Prefilter (zero-copy)
                     let prefilter_start = Instant::now();
-                    if !prefilter::should_process(&tx_bytes) {
+                    let passed = prefilter::should_process(&tx_bytes);
+                    let prefilter_latency_us = prefilter_start.elapsed().as_micros() as u64;
+                    metrics.record_prefilter_latency(prefilter_latency_us);
+                    if !passed {
                         metrics.tx_filtered.fetch_add(1, Ordering::Relaxed);
                         event_collector.collect(SnifferEvent::PrefilterRejected {
                             trace_id,
@@ -273,7 +284,7 @@ impl Sniffer {
                     event_collector.collect(SnifferEvent::PrefilterPassed {
                         trace_id,
                         timestamp: Instant::now(),
-                        latency_us: prefilter_start.elapsed().as_micros() as u64,
+                        latency_us: prefilter_latency_us,
                     });
 
                     // Accumulate volume for analytics (atomic operation)
@@ -289,19 +300,22 @@ impl Sniffer {
 
                     // Extract candidate
                     let extraction_start = Instant::now();
-                    let candidate = match PremintCandidate::try_extract_candidate(
+                    let extracted = PremintCandidate::try_extract_candidate(
                         &tx_bytes,
                         trace_id,
                         volume_hint,
                         priority,
                         config.safe_offsets,
-                    ) {
+                    );
+                    let extraction_latency_us = extraction_start.elapsed().as_micros() as u64;
+                    metrics.record_extract_latency(extraction_latency_us);
+                    let candidate = match extracted {
                         Ok(c) => {
                             // Emit: CandidateExtracted event
                             event_collector.collect(SnifferEvent::CandidateExtracted {
                                 trace_id,
                                 timestamp: Instant::now(),
-                                latency_us: extraction_start.elapsed().as_micros() as u64,
+                                latency_us: extraction_latency_us,
                                 priority,
                             });
                             c
@@ -341,6 +355,20 @@ impl Sniffer {
                         latency_us: security_start.elapsed().as_micros() as u64,
                     });
 
+                    // Root span of the candidate's trace: receive to validated
+                    if trace_export::enabled() {
+                        let mut span = Span::started_at(
+                            "sniffer.extract",
+                            candidate.span,
+                            None,
+                            processing_start,
+                        );
+                        span.set_attribute("sniffer.trace_id", trace_id);
+                        span.set_attribute("mint", candidate.mint.to_string());
+                        span.set_attribute("priority", format!("{:?}", priority));
+                        span.end();
+                    }
+
                     // HOT-PATH: Add to batch (note: candidate will be moved, not cloned)
                     batch_sender.add(candidate);
 
@@ -498,78 +526,103 @@ impl SnifferApi for Sniffer {
         let health_ok = Arc::clone(&self.health_ok);
         let event_collector = Arc::clone(&self.event_collector);
         let handoff_diagnostics = Arc::clone(&self.handoff_diagnostics);
-        let _supervisor = Arc::clone(&self.supervisor);
-
-        // Spawn main processing loop and register with supervisor
-        let process_handle = tokio::spawn(async move {
-            if let Err(e) = Self::process_loop(
-                config.clone(),
-                tx,
-                metrics.clone(),
-                analytics.clone(),
-                running.clone(),
-                paused.clone(),
-                trace_id_counter.clone(),
-                health_ok.clone(),
-                event_collector.clone(),
-                handoff_diagnostics.clone(),
-            )
-            .await
-            {
-                error!("Sniffer process loop error: {}", e);
-            }
-        });
+
+        // Spawn main processing loop and register with supervisor.
+        // Permanent: the loop only returns on shutdown or stream failure,
+        // and a stream failure should be retried.
         self.supervisor
-            .register_worker(WorkerHandle::new(
-                "process_loop".to_string(),
-                process_handle,
+            .spawn_worker(
+                "process_loop",
                 true, // critical worker
-            ))
+                RestartPolicy::Permanent,
+                worker_factory(move || {
+                    let config = config.clone();
+                    let tx = tx.clone();
+                    let metrics = Arc::clone(&metrics);
+                    let analytics = Arc::clone(&analytics);
+                    let running = Arc::clone(&running);
+                    let paused = Arc::clone(&paused);
+                    let trace_id_counter = Arc::clone(&trace_id_counter);
+                    let health_ok = Arc::clone(&health_ok);
+                    let event_collector = Arc::clone(&event_collector);
+                    let handoff_diagnostics = Arc::clone(&handoff_diagnostics);
+                    async move {
+                        if let Err(e) = Self::process_loop(
+                            config,
+                            tx,
+                            metrics,
+                            analytics,
+                            running,
+                            paused,
+                            trace_id_counter,
+                            health_ok,
+                            event_collector,
+                            handoff_diagnostics,
+                        )
+                        .await
+                        {
+                            error!("Sniffer process loop error: {}", e);
+                        }
+                    }
+                }),
+            )
             .await;
 
         // Spawn telemetry exporter and register with supervisor
         let metrics_clone = Arc::clone(&self.metrics);
         let running_clone = Arc::clone(&self.running);
         let telemetry_interval = self.config.telemetry_interval_secs;
-        let telemetry_handle = tokio::spawn(async move {
-            Self::telemetry_loop(metrics_clone, running_clone, telemetry_interval).await;
-        });
         self.supervisor
-            .register_worker(WorkerHandle::new(
-                "telemetry_loop".to_string(),
-                telemetry_handle,
+            .spawn_worker(
+                "telemetry_loop",
                 false, // non-critical worker
-            ))
+                RestartPolicy::Transient,
+                worker_factory(move || {
+                    Self::telemetry_loop(
+                        Arc::clone(&metrics_clone),
+                        Arc::clone(&running_clone),
+                        telemetry_interval,
+                    )
+                }),
+            )
             .await;
 
         // Spawn analytics_updater task and register with supervisor
         let analytics_clone = Arc::clone(&self.analytics);
         let running_clone = Arc::clone(&self.running);
         let ema_update_interval = self.config.ema_update_interval_ms;
-        let analytics_handle = tokio::spawn(async move {
-            Self::analytics_updater_loop(analytics_clone, running_clone, ema_update_interval).await;
-        });
         self.supervisor
-            .register_worker(WorkerHandle::new(
-                "analytics_updater".to_string(),
-                analytics_handle,
+            .spawn_worker(
+                "analytics_updater",
                 false, // non-critical worker
-            ))
+                RestartPolicy::Transient,
+                worker_factory(move || {
+                    Self::analytics_updater_loop(
+                        Arc::clone(&analytics_clone),
+                        Arc::clone(&running_clone),
+                        ema_update_interval,
+                    )
+                }),
+            )
             .await;
 
         // Spawn threshold updater and register with supervisor
         let analytics_clone = Arc::clone(&self.analytics);
         let running_clone = Arc::clone(&self.running);
         let config_clone = self.config.clone();
-        let threshold_handle = tokio::spawn(async move {
-            Self::threshold_update_loop(analytics_clone, running_clone, config_clone).await;
-        });
         self.supervisor
-            .register_worker(WorkerHandle::new(
-                "threshold_updater".to_string(),
-                threshold_handle,
+            .spawn_worker(
+                "threshold_updater",
                 false, // non-critical worker
-            ))
+                RestartPolicy::Transient,
+                worker_factory(move || {
+                    Self::threshold_update_loop(
+                        Arc::clone(&analytics_clone),
+                        Arc::clone(&running_clone),
+                        config_clone.clone(),
+                    )
+                }),
+            )
             .await;
 
         // Spawn config reload handler
@@ -577,27 +630,34 @@ impl SnifferApi for Sniffer {
         let analytics_clone = Arc::clone(&self.analytics);
         let handoff_diagnostics_clone = Arc::clone(&self.handoff_diagnostics);
         let running_clone = Arc::clone(&self.running);
-        let config_reload_handle = tokio::spawn(async move {
-            Self::config_reload_loop(
-                config_path,
-                analytics_clone,
-                handoff_diagnostics_clone,
-                running_clone,
-            )
-            .await;
-        });
         self.supervisor
-            .register_worker(WorkerHandle::new(
-                "config_reload".to_string(),
-                config_reload_handle,
+            .spawn_worker(
+                "config_reload",
                 false, // non-critical worker
-            ))
+                RestartPolicy::Transient,
+                worker_factory(move || {
+                    Self::config_reload_loop(
+                        config_path.clone(),
+                        Arc::clone(&analytics_clone),
+                        Arc::clone(&handoff_diagnostics_clone),
+                        Arc::clone(&running_clone),
+                    )
+                }),
+            )
             .await;
 
+        // Monitor workers and restart them according to their policies
+        let supervisor = Arc::clone(&self.supervisor);
+        tokio::spawn(async move {
+            supervisor.monitor_workers().await;
+        });
+
         Ok(rx)
     }
 
     fn stop(&self) {
+        // Suppress restarts before the workers observe the shutdown flag
+        self.supervisor.begin_stop();
         self.running.store(false, Ordering::Release);
         // Stop supervisor gracefully
         let supervisor = Arc::clone(&self.supervisor);
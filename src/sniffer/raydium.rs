@@ -0,0 +1,140 @@
+//! Raydium AMM v4 swap decoding from transaction logs
+//!
+//! The AMM logs every swap as `Program log: ray_log: <base64>`, a packed
+//! little-endian struct led by a one-byte log type. Swap logs carry the
+//! amounts that moved but neither the mints nor the user; callers resolve
+//! those from the transaction's token balances.
+
+use super::errors::EventDecodeError;
+use super::events::program_logs;
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+use solana_sdk::pubkey::Pubkey;
+
+/// Raydium AMM v4 liquidity pool program
+pub const RAYDIUM_AMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+
+const RAY_LOG_PREFIX: &str = "Program log: ray_log: ";
+
+/// `LogType::SwapBaseIn`: the input amount was fixed
+const SWAP_BASE_IN: u8 = 3;
+/// `LogType::SwapBaseOut`: the output amount was fixed
+const SWAP_BASE_OUT: u8 = 4;
+
+/// Both swap logs are the log type followed by seven u64 fields
+const SWAP_LOG_LEN: usize = 1 + 7 * 8;
+
+/// One AMM swap, in raw token units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaydiumSwap {
+    /// Amount of the input token the user paid
+    pub amount_in: u64,
+    /// Amount of the output token the user received
+    pub amount_out: u64,
+}
+
+/// Decode a `ray_log` payload; `None` for logs other than swaps
+pub fn decode_ray_log(encoded: &str) -> Result<Option<RaydiumSwap>, EventDecodeError> {
+    let data = BASE64_STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| EventDecodeError::InvalidBase64)?;
+    let Some(&log_type) = data.first() else {
+        return Ok(None);
+    };
+    if log_type != SWAP_BASE_IN && log_type != SWAP_BASE_OUT {
+        return Ok(None);
+    }
+    if data.len() < SWAP_LOG_LEN {
+        return Err(EventDecodeError::Truncated);
+    }
+
+    let field = |index: usize| {
+        let start = 1 + index * 8;
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&data[start..start + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    // SwapBaseIn:  amount_in, minimum_out, direction, user_source, pool_coin, pool_pc, out_amount
+    // SwapBaseOut: max_in, amount_out, direction, user_source, pool_coin, pool_pc, deduct_in
+    let swap = if log_type == SWAP_BASE_IN {
+        RaydiumSwap {
+            amount_in: field(0),
+            amount_out: field(6),
+        }
+    } else {
+        RaydiumSwap {
+            amount_in: field(6),
+            amount_out: field(1),
+        }
+    };
+    Ok(Some(swap))
+}
+
+/// Decode every swap the Raydium AMM logged in a transaction
+///
+/// Only `ray_log` lines emitted while the AMM is the executing program are
+/// decoded; malformed payloads are skipped.
+pub fn parse_ray_logs<S: AsRef<str>>(logs: &[S]) -> Vec<RaydiumSwap> {
+    program_logs(logs, &RAYDIUM_AMM_PROGRAM_ID)
+        .into_iter()
+        .filter_map(|line| line.strip_prefix(RAY_LOG_PREFIX))
+        .filter_map(|payload| decode_ray_log(payload).ok().flatten())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray_log(log_type: u8, fields: [u64; 7]) -> String {
+        let mut data = vec![log_type];
+        for field in fields {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        format!("{}{}", RAY_LOG_PREFIX, BASE64_STANDARD.encode(data))
+    }
+
+    fn invoked(program: &Pubkey, lines: &[String]) -> Vec<String> {
+        let mut logs = vec![format!("Program {} invoke [1]", program)];
+        logs.extend_from_slice(lines);
+        logs.push(format!("Program {} success", program));
+        logs
+    }
+
+    #[test]
+    fn test_decode_swap_logs() {
+        let base_in = ray_log(SWAP_BASE_IN, [1_000, 900, 1, 0, 50, 60, 950]);
+        let base_out = ray_log(SWAP_BASE_OUT, [1_100, 800, 2, 0, 50, 60, 1_050]);
+        let deposit = ray_log(1, [0; 7]);
+
+        assert_eq!(
+            parse_ray_logs(&invoked(
+                &RAYDIUM_AMM_PROGRAM_ID,
+                &[base_in, base_out, deposit]
+            )),
+            vec![
+                RaydiumSwap {
+                    amount_in: 1_000,
+                    amount_out: 950
+                },
+                RaydiumSwap {
+                    amount_in: 1_050,
+                    amount_out: 800
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_other_programs_and_truncated_logs() {
+        let swap = ray_log(SWAP_BASE_IN, [1_000, 900, 1, 0, 50, 60, 950]);
+        assert!(parse_ray_logs(&invoked(&Pubkey::new_unique(), &[swap])).is_empty());
+
+        let truncated = BASE64_STANDARD.encode([SWAP_BASE_IN, 0, 0]);
+        assert_eq!(decode_ray_log(&truncated), Err(EventDecodeError::Truncated));
+        assert_eq!(
+            decode_ray_log("not base64!"),
+            Err(EventDecodeError::InvalidBase64)
+        );
+    }
+}
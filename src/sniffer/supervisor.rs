@@ -5,14 +5,28 @@
 //! - Coordinated pause/resume/stop operations
 //! - Panic recovery with exponential backoff
 //! - Unified state tracking (Running, Paused, Stopped, Error)
-
+//!
+//! Workers registered through [`Supervisor::spawn_worker`] carry a
+//! [`WorkerFactory`] and a [`RestartPolicy`]. When such a worker terminates,
+//! `monitor_workers` classifies the exit (normal, panic, cancelled), captures
+//! the panic payload and, if the policy allows it, re-spawns the worker after
+//! an exponential backoff (one-for-one strategy). Restarts are bounded by a
+//! restart intensity (`max_restarts` within `restart_window`); exceeding it
+//! puts the supervisor into the `Error` state and stops monitoring.
+
+use futures::future::BoxFuture;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
-use tokio::task::JoinHandle;
+use tokio::task::{JoinError, JoinHandle};
 use tracing::{debug, error, info, warn};
 
+use super::errors::ExponentialBackoff;
+use super::telemetry::SnifferMetrics;
+
 /// Sniffer state enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -60,22 +74,170 @@ pub enum SupervisorCommand {
     RestartWorker,
 }
 
+/// Factory used to (re-)create a worker future
+///
+/// Called once on initial spawn and again for every restart, so it must
+/// capture everything the worker needs by `Arc`/clone.
+pub type WorkerFactory = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Wrap a closure returning a future into a [`WorkerFactory`]
+pub fn worker_factory<F, Fut>(f: F) -> WorkerFactory
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    Arc::new(move || -> BoxFuture<'static, ()> { Box::pin(f()) })
+}
+
+/// Restart policy for a single worker (Erlang/OTP semantics)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Always restart, whether the worker returned or panicked
+    Permanent,
+    /// Restart only after an abnormal exit (panic)
+    Transient,
+    /// Never restart
+    Temporary,
+}
+
+impl RestartPolicy {
+    /// Whether a worker with this policy should be restarted after `exit`
+    pub fn should_restart(&self, exit: &WorkerExit) -> bool {
+        match self {
+            Self::Permanent => !matches!(exit, WorkerExit::Cancelled),
+            Self::Transient => matches!(exit, WorkerExit::Panicked(_)),
+            Self::Temporary => false,
+        }
+    }
+}
+
+/// Restart strategy applied when a worker fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Only the failed worker is restarted
+    OneForOne,
+}
+
+/// How a worker task terminated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerExit {
+    /// Worker future returned
+    Normal,
+    /// Worker panicked; contains the panic payload message
+    Panicked(String),
+    /// Worker task was aborted
+    Cancelled,
+}
+
+impl WorkerExit {
+    /// Classify the result of awaiting a worker's `JoinHandle`
+    pub fn from_join(result: Result<(), JoinError>) -> Self {
+        match result {
+            Ok(()) => Self::Normal,
+            Err(e) if e.is_panic() => Self::Panicked(panic_message(e.into_panic())),
+            Err(_) => Self::Cancelled,
+        }
+    }
+}
+
+/// Extract a readable message from a panic payload
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&'static str>() {
+        (*msg).to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+/// Supervisor configuration (restart intensity and backoff)
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Restart strategy
+    pub strategy: RestartStrategy,
+    /// Maximum restarts allowed within `restart_window`
+    pub max_restarts: u32,
+    /// Sliding window for restart intensity
+    pub restart_window: Duration,
+    /// Initial restart backoff in milliseconds
+    pub initial_backoff_ms: u64,
+    /// Maximum restart backoff in milliseconds
+    pub max_backoff_ms: u64,
+    /// Uptime after which a worker's backoff is reset
+    pub backoff_reset_after: Duration,
+    /// Interval between worker health checks
+    pub check_interval: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            strategy: RestartStrategy::OneForOne,
+            max_restarts: 10,
+            restart_window: Duration::from_secs(60),
+            initial_backoff_ms: 100,
+            max_backoff_ms: 30_000,
+            backoff_reset_after: Duration::from_secs(60),
+            check_interval: Duration::from_millis(250),
+        }
+    }
+}
+
 /// Worker registration information
 pub struct WorkerHandle {
     pub name: String,
     pub handle: JoinHandle<()>,
     pub critical: bool, // If true, failure triggers global error state
+    policy: RestartPolicy,
+    factory: Option<WorkerFactory>,
+    backoff: Option<ExponentialBackoff>,
+    started_at: Instant,
 }
 
 impl WorkerHandle {
     /// Create a new worker handle
+    ///
+    /// Workers registered this way have no factory and are never restarted.
     pub fn new(name: String, handle: JoinHandle<()>, critical: bool) -> Self {
         Self {
             name,
             handle,
             critical,
+            policy: RestartPolicy::Temporary,
+            factory: None,
+            backoff: None,
+            started_at: Instant::now(),
         }
     }
+
+    /// Restart policy of this worker
+    pub fn policy(&self) -> RestartPolicy {
+        self.policy
+    }
+}
+
+/// Worker waiting for its backoff to elapse before being re-spawned
+struct PendingRestart {
+    name: String,
+    critical: bool,
+    policy: RestartPolicy,
+    factory: WorkerFactory,
+    backoff: ExponentialBackoff,
+    due: Instant,
+}
+
+/// Per-worker restart bookkeeping
+#[derive(Debug, Clone, Default)]
+pub struct WorkerRecord {
+    /// Number of restarts performed
+    pub restarts: u64,
+    /// Number of panics observed
+    pub panics: u64,
+    /// Last observed exit
+    pub last_exit: Option<WorkerExit>,
+    /// Payload of the last panic
+    pub last_panic: Option<String>,
 }
 
 /// Lifecycle supervisor for coordinating all async workers
@@ -86,20 +248,40 @@ pub struct Supervisor {
     command_tx: broadcast::Sender<SupervisorCommand>,
     /// Worker handles (using tokio::sync::Mutex for Send across await)
     workers: Arc<tokio::sync::Mutex<Vec<WorkerHandle>>>,
+    /// Workers scheduled for restart
+    pending: parking_lot::Mutex<Vec<PendingRestart>>,
+    /// Restart timestamps for intensity accounting
+    restart_log: parking_lot::Mutex<VecDeque<Instant>>,
+    /// Per-worker restart records
+    records: parking_lot::Mutex<HashMap<String, WorkerRecord>>,
     /// Error count for backoff
     error_count: Arc<std::sync::atomic::AtomicU32>,
+    /// Restart intensity and backoff settings
+    config: SupervisorConfig,
+    /// Sniffer metrics for publishing restart counts
+    metrics: Option<Arc<SnifferMetrics>>,
 }
 
 impl Supervisor {
     /// Create a new supervisor
     pub fn new() -> Self {
+        Self::with_config(SupervisorConfig::default(), None)
+    }
+
+    /// Create a supervisor with custom restart settings and optional metrics sink
+    pub fn with_config(config: SupervisorConfig, metrics: Option<Arc<SnifferMetrics>>) -> Self {
         let (command_tx, _) = broadcast::channel(16);
 
         Self {
             state: Arc::new(AtomicU8::new(SnifferState::Stopped as u8)),
             command_tx,
             workers: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            pending: parking_lot::Mutex::new(Vec::new()),
+            restart_log: parking_lot::Mutex::new(VecDeque::new()),
+            records: parking_lot::Mutex::new(HashMap::new()),
             error_count: Arc::new(std::sync::atomic::AtomicU32::new(0)),
+            config,
+            metrics,
         }
     }
 
@@ -123,6 +305,34 @@ impl Supervisor {
         self.workers.lock().await.push(worker);
     }
 
+    /// Spawn a restartable worker from a factory and register it
+    pub async fn spawn_worker(
+        &self,
+        name: impl Into<String>,
+        critical: bool,
+        policy: RestartPolicy,
+        factory: WorkerFactory,
+    ) {
+        let name = name.into();
+        let handle = tokio::spawn((factory)());
+        info!(
+            "Registering worker: {} (critical: {}, policy: {:?})",
+            name, critical, policy
+        );
+        self.workers.lock().await.push(WorkerHandle {
+            name,
+            handle,
+            critical,
+            policy,
+            factory: Some(factory),
+            backoff: Some(ExponentialBackoff::new(
+                self.config.initial_backoff_ms,
+                self.config.max_backoff_ms,
+            )),
+            started_at: Instant::now(),
+        });
+    }
+
     /// Start all workers
     pub async fn start(&self) -> anyhow::Result<()> {
         if self.state() != SnifferState::Stopped {
@@ -158,6 +368,19 @@ impl Supervisor {
         let _ = self.command_tx.send(SupervisorCommand::Resume);
     }
 
+    /// Enter the `Stopping` state and broadcast the stop command
+    ///
+    /// Synchronous so callers can suppress restarts before signalling their
+    /// workers to exit; `stop` performs the actual drain.
+    pub fn begin_stop(&self) {
+        if matches!(self.state(), SnifferState::Stopped | SnifferState::Stopping) {
+            return;
+        }
+        self.set_state(SnifferState::Stopping);
+        self.pending.lock().clear();
+        let _ = self.command_tx.send(SupervisorCommand::Stop);
+    }
+
     /// Stop all workers gracefully
     pub async fn stop(&self, timeout: Duration) -> anyhow::Result<()> {
         if self.state() == SnifferState::Stopped {
@@ -165,10 +388,7 @@ impl Supervisor {
         }
 
         info!("Supervisor stopping all workers (timeout: {:?})", timeout);
-        self.set_state(SnifferState::Stopping);
-
-        // Send stop command
-        let _ = self.command_tx.send(SupervisorCommand::Stop);
+        self.begin_stop();
 
         // Wait for workers to finish with timeout
         let deadline = tokio::time::Instant::now() + timeout;
@@ -202,30 +422,76 @@ impl Supervisor {
     }
 
     /// Monitor workers for panics and restart with backoff
+    ///
+    /// Runs until the supervisor is stopped or the restart intensity is
+    /// exceeded, in which case the supervisor enters the `Error` state.
     pub async fn monitor_workers(&self) {
-        let mut check_interval = tokio::time::interval(Duration::from_secs(1));
+        let mut check_interval = tokio::time::interval(self.config.check_interval);
 
         loop {
             check_interval.tick().await;
 
-            if self.state() == SnifferState::Stopped {
+            match self.state() {
+                SnifferState::Stopped => break,
+                SnifferState::Stopping => continue,
+                _ => {}
+            }
+
+            if !self.reap_finished_workers().await {
+                error!("Too many worker failures - stopping supervisor");
                 break;
             }
 
+            self.restart_due_workers().await;
+        }
+    }
+
+    /// Collect finished workers and schedule restarts
+    ///
+    /// Returns `false` if the restart intensity was exceeded.
+    async fn reap_finished_workers(&self) -> bool {
+        let finished: Vec<WorkerHandle> = {
             let mut workers = self.workers.lock().await;
-            let mut failed_workers = Vec::new();
+            let mut finished = Vec::new();
+            let mut idx = 0;
+            while idx < workers.len() {
+                if workers[idx].handle.is_finished() {
+                    finished.push(workers.remove(idx));
+                } else {
+                    idx += 1;
+                }
+            }
+            finished
+        };
 
-            // Check for finished workers
-            for (idx, worker) in workers.iter_mut().enumerate() {
-                if worker.handle.is_finished() {
-                    failed_workers.push(idx);
+        for worker in finished {
+            let uptime = worker.started_at.elapsed();
+            let exit = WorkerExit::from_join(worker.handle.await);
+            self.record_exit(&worker.name, &exit);
+
+            match &exit {
+                WorkerExit::Panicked(msg) => {
+                    error!("Worker '{}' panicked: {}", worker.name, msg)
                 }
+                WorkerExit::Cancelled => warn!("Worker '{}' was cancelled", worker.name),
+                WorkerExit::Normal => debug!("Worker '{}' exited", worker.name),
             }
 
-            // Handle failed workers
-            for idx in failed_workers.iter().rev() {
-                let worker = workers.remove(*idx);
+            // A stop may have been requested while we were reaping. An earlier
+            // failure (`Error`) does not suppress restarts; only the restart
+            // intensity limit ends them.
+            if matches!(self.state(), SnifferState::Stopping | SnifferState::Stopped) {
+                continue;
+            }
 
+            let restart = match (&worker.factory, worker.backoff) {
+                (Some(factory), Some(backoff)) if worker.policy.should_restart(&exit) => {
+                    Some((Arc::clone(factory), backoff))
+                }
+                _ => None,
+            };
+
+            let Some((factory, mut backoff)) = restart else {
                 if worker.critical {
                     error!(
                         "Critical worker '{}' failed - entering error state",
@@ -233,19 +499,142 @@ impl Supervisor {
                     );
                     self.set_state(SnifferState::Error);
                     self.error_count.fetch_add(1, Ordering::Relaxed);
-                } else {
+                } else if exit != WorkerExit::Normal {
                     warn!("Non-critical worker '{}' failed", worker.name);
                 }
+                continue;
+            };
+
+            if !self.admit_restart() {
+                error!(
+                    "Restart intensity exceeded ({} restarts in {:?}) while restarting '{}'",
+                    self.config.max_restarts, self.config.restart_window, worker.name
+                );
+                self.pending.lock().clear();
+                self.set_state(SnifferState::Error);
+                self.error_count.fetch_add(1, Ordering::Relaxed);
+                return false;
             }
 
-            drop(workers);
+            if uptime >= self.config.backoff_reset_after {
+                backoff.reset();
+            }
+            let delay = backoff.next_backoff();
+            info!(
+                "Scheduling restart of worker '{}' in {:?} (attempt {})",
+                worker.name,
+                delay,
+                backoff.attempt()
+            );
+
+            self.pending.lock().push(PendingRestart {
+                name: worker.name,
+                critical: worker.critical,
+                policy: worker.policy,
+                factory,
+                backoff,
+                due: Instant::now() + delay,
+            });
+        }
 
-            // If in error state with too many errors, stop monitoring
-            if self.state() == SnifferState::Error && self.error_count.load(Ordering::Relaxed) > 5 {
-                error!("Too many worker failures - stopping supervisor");
+        true
+    }
+
+    /// Re-spawn workers whose backoff has elapsed
+    async fn restart_due_workers(&self) {
+        let now = Instant::now();
+        let due: Vec<PendingRestart> = {
+            let mut pending = self.pending.lock();
+            let mut due = Vec::new();
+            let mut idx = 0;
+            while idx < pending.len() {
+                if pending[idx].due <= now {
+                    due.push(pending.swap_remove(idx));
+                } else {
+                    idx += 1;
+                }
+            }
+            due
+        };
+
+        if due.is_empty() {
+            return;
+        }
+
+        let mut workers = self.workers.lock().await;
+        for restart in due {
+            info!("Restarting worker '{}'", restart.name);
+            let handle = tokio::spawn((restart.factory)());
+
+            self.records
+                .lock()
+                .entry(restart.name.clone())
+                .or_default()
+                .restarts += 1;
+            if let Some(metrics) = &self.metrics {
+                metrics.record_worker_restart(&restart.name);
+            }
+
+            workers.push(WorkerHandle {
+                name: restart.name,
+                handle,
+                critical: restart.critical,
+                policy: restart.policy,
+                factory: Some(restart.factory),
+                backoff: Some(restart.backoff),
+                started_at: Instant::now(),
+            });
+        }
+    }
+
+    /// Record a restart against the intensity window
+    ///
+    /// Returns `false` if the restart would exceed `max_restarts` within
+    /// `restart_window`.
+    fn admit_restart(&self) -> bool {
+        let now = Instant::now();
+        let mut log = self.restart_log.lock();
+        while let Some(oldest) = log.front() {
+            if now.duration_since(*oldest) > self.config.restart_window {
+                log.pop_front();
+            } else {
                 break;
             }
         }
+        if log.len() as u32 >= self.config.max_restarts {
+            return false;
+        }
+        log.push_back(now);
+        true
+    }
+
+    /// Update the worker's record with its exit reason
+    fn record_exit(&self, name: &str, exit: &WorkerExit) {
+        let mut records = self.records.lock();
+        let record = records.entry(name.to_string()).or_default();
+        if let WorkerExit::Panicked(msg) = exit {
+            record.panics += 1;
+            record.last_panic = Some(msg.clone());
+            if let Some(metrics) = &self.metrics {
+                metrics.worker_panics.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        record.last_exit = Some(exit.clone());
+    }
+
+    /// Restart bookkeeping for a worker
+    pub fn worker_record(&self, name: &str) -> Option<WorkerRecord> {
+        self.records.lock().get(name).cloned()
+    }
+
+    /// Total restarts performed across all workers
+    pub fn total_restarts(&self) -> u64 {
+        self.records.lock().values().map(|r| r.restarts).sum()
+    }
+
+    /// Number of workers waiting for their restart backoff
+    pub fn pending_restarts(&self) -> usize {
+        self.pending.lock().len()
     }
 
     /// Subscribe to supervisor commands
@@ -343,4 +732,218 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(supervisor.state(), SnifferState::Stopped);
     }
+
+    fn fast_config() -> SupervisorConfig {
+        SupervisorConfig {
+            max_restarts: 3,
+            restart_window: Duration::from_secs(10),
+            initial_backoff_ms: 10,
+            max_backoff_ms: 50,
+            check_interval: Duration::from_millis(5),
+            ..SupervisorConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_restart_policy_semantics() {
+        let panicked = WorkerExit::Panicked("boom".to_string());
+
+        assert!(RestartPolicy::Permanent.should_restart(&WorkerExit::Normal));
+        assert!(RestartPolicy::Permanent.should_restart(&panicked));
+        assert!(!RestartPolicy::Transient.should_restart(&WorkerExit::Normal));
+        assert!(RestartPolicy::Transient.should_restart(&panicked));
+        assert!(!RestartPolicy::Temporary.should_restart(&panicked));
+        assert!(!RestartPolicy::Permanent.should_restart(&WorkerExit::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_panicked_worker_is_restarted_with_payload() {
+        let metrics = Arc::new(SnifferMetrics::new());
+        let supervisor = Arc::new(Supervisor::with_config(
+            fast_config(),
+            Some(Arc::clone(&metrics)),
+        ));
+        supervisor.start().await.unwrap();
+
+        let runs = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let runs_clone = Arc::clone(&runs);
+        supervisor
+            .spawn_worker(
+                "flaky",
+                true,
+                RestartPolicy::Transient,
+                worker_factory(move || {
+                    let runs = Arc::clone(&runs_clone);
+                    async move {
+                        if runs.fetch_add(1, Ordering::SeqCst) == 0 {
+                            panic!("first run fails");
+                        }
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                    }
+                }),
+            )
+            .await;
+
+        let monitor = {
+            let sup = Arc::clone(&supervisor);
+            tokio::spawn(async move { sup.monitor_workers().await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        let record = supervisor.worker_record("flaky").unwrap();
+        assert_eq!(record.restarts, 1);
+        assert_eq!(record.panics, 1);
+        assert_eq!(record.last_panic.as_deref(), Some("first run fails"));
+        assert_eq!(supervisor.state(), SnifferState::Running);
+        assert_eq!(metrics.get_worker_restarts("flaky"), 1);
+        assert_eq!(metrics.worker_panics.load(Ordering::Relaxed), 1);
+
+        supervisor.stop(Duration::from_millis(100)).await.unwrap();
+        monitor.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_temporary_and_transient_workers_not_restarted_on_normal_exit() {
+        let supervisor = Arc::new(Supervisor::with_config(fast_config(), None));
+        supervisor.start().await.unwrap();
+
+        let runs = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        for (name, policy) in [
+            ("transient", RestartPolicy::Transient),
+            ("temporary", RestartPolicy::Temporary),
+        ] {
+            let runs = Arc::clone(&runs);
+            supervisor
+                .spawn_worker(
+                    name,
+                    false,
+                    policy,
+                    worker_factory(move || {
+                        let runs = Arc::clone(&runs);
+                        async move {
+                            runs.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }),
+                )
+                .await;
+        }
+
+        let monitor = {
+            let sup = Arc::clone(&supervisor);
+            tokio::spawn(async move { sup.monitor_workers().await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(supervisor.total_restarts(), 0);
+        assert_eq!(supervisor.state(), SnifferState::Running);
+
+        supervisor.stop(Duration::from_millis(100)).await.unwrap();
+        monitor.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_workers_still_restarted_after_error_state() {
+        let supervisor = Arc::new(Supervisor::with_config(fast_config(), None));
+        supervisor.start().await.unwrap();
+
+        supervisor
+            .spawn_worker(
+                "critical_once",
+                true,
+                RestartPolicy::Temporary,
+                worker_factory(|| async { panic!("critical failure") }),
+            )
+            .await;
+        let runs = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let runs_clone = Arc::clone(&runs);
+        supervisor
+            .spawn_worker(
+                "flaky",
+                false,
+                RestartPolicy::Transient,
+                worker_factory(move || {
+                    let runs = Arc::clone(&runs_clone);
+                    async move {
+                        let run = runs.fetch_add(1, Ordering::SeqCst);
+                        // Fail only once the supervisor is already in Error
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        if run == 0 {
+                            panic!("late failure");
+                        }
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                    }
+                }),
+            )
+            .await;
+
+        let monitor = {
+            let sup = Arc::clone(&supervisor);
+            tokio::spawn(async move { sup.monitor_workers().await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert_eq!(supervisor.state(), SnifferState::Error);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(supervisor.worker_record("flaky").unwrap().restarts, 1);
+        assert_eq!(
+            supervisor.worker_record("critical_once").unwrap().restarts,
+            0
+        );
+
+        supervisor.stop(Duration::from_millis(100)).await.unwrap();
+        monitor.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restart_intensity_exceeded_enters_error_state() {
+        let supervisor = Arc::new(Supervisor::with_config(fast_config(), None));
+        supervisor.start().await.unwrap();
+
+        supervisor
+            .spawn_worker(
+                "crash_loop",
+                false,
+                RestartPolicy::Permanent,
+                worker_factory(|| async {}),
+            )
+            .await;
+
+        // Monitor returns on its own once intensity is exceeded
+        tokio::time::timeout(Duration::from_secs(5), supervisor.monitor_workers())
+            .await
+            .expect("monitor should give up after max_restarts");
+
+        assert_eq!(supervisor.state(), SnifferState::Error);
+        assert_eq!(supervisor.total_restarts(), 3);
+        assert_eq!(supervisor.pending_restarts(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_no_restart_after_begin_stop() {
+        let supervisor = Arc::new(Supervisor::with_config(fast_config(), None));
+        supervisor.start().await.unwrap();
+
+        supervisor
+            .spawn_worker(
+                "permanent",
+                false,
+                RestartPolicy::Permanent,
+                worker_factory(|| async {}),
+            )
+            .await;
+
+        supervisor.begin_stop();
+        assert_eq!(supervisor.state(), SnifferState::Stopping);
+
+        supervisor.stop(Duration::from_millis(100)).await.unwrap();
+        supervisor.monitor_workers().await;
+
+        assert_eq!(supervisor.total_restarts(), 0);
+        assert_eq!(supervisor.state(), SnifferState::Stopped);
+    }
 }
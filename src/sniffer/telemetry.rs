@@ -1,7 +1,11 @@
 //! Telemetry module with atomic counters and metrics export
 
+use crate::histogram::WindowedHistogram;
+use crate::metrics::{metrics, FamilyBuilder, MetricSource};
 use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 /// Atomic metrics for zero-overhead tracking in hot path
 #[derive(Debug)]
@@ -32,8 +36,22 @@ pub struct SnifferMetrics {
     pub account_extract_errors: AtomicU64,
     /// Current stream buffer depth (approximate)
     pub stream_buffer_depth: AtomicU64,
-    /// Latency samples for P50/P95/P99 calculation
-    pub latency_samples: Mutex<Vec<u64>>,
+    /// Worker restarts performed by the supervisor
+    pub worker_restarts: AtomicU64,
+    /// Worker panics observed by the supervisor
+    pub worker_panics: AtomicU64,
+    /// Restart count per worker name
+    pub worker_restart_counts: Mutex<HashMap<String, u64>>,
+    /// End-to-end processing latency (microseconds) for P50/P95/P99
+    pub latency: WindowedHistogram,
+    /// Prefilter stage latency (microseconds)
+    pub prefilter_latency: WindowedHistogram,
+    /// Candidate extraction stage latency (microseconds)
+    pub extract_latency: WindowedHistogram,
+    /// Global `sniffer_stage_latency_seconds` series for the stages above
+    exported_latency: Arc<WindowedHistogram>,
+    exported_prefilter_latency: Arc<WindowedHistogram>,
+    exported_extract_latency: Arc<WindowedHistogram>,
     /// Correlation tracking: latency → confidence/priority → drop_rate
     pub latency_correlation: Mutex<LatencyCorrelation>,
 }
@@ -131,6 +149,11 @@ impl LatencyCorrelation {
     }
 }
 
+/// Global Prometheus series for a sniffer pipeline stage
+fn stage_latency(stage: &str) -> Arc<WindowedHistogram> {
+    metrics().sniffer_stage_latency.with_label_values(&[stage])
+}
+
 impl SnifferMetrics {
     /// Create a new metrics instance
     pub fn new() -> Self {
@@ -148,7 +171,15 @@ impl SnifferMetrics {
             mint_extract_errors: AtomicU64::new(0),
             account_extract_errors: AtomicU64::new(0),
             stream_buffer_depth: AtomicU64::new(0),
-            latency_samples: Mutex::new(Vec::with_capacity(1000)),
+            worker_restarts: AtomicU64::new(0),
+            worker_panics: AtomicU64::new(0),
+            worker_restart_counts: Mutex::new(HashMap::new()),
+            latency: WindowedHistogram::default(),
+            prefilter_latency: WindowedHistogram::default(),
+            extract_latency: WindowedHistogram::default(),
+            exported_latency: stage_latency("process"),
+            exported_prefilter_latency: stage_latency("prefilter"),
+            exported_extract_latency: stage_latency("extract"),
             latency_correlation: Mutex::new(LatencyCorrelation::new(1000)),
         }
     }
@@ -156,7 +187,7 @@ impl SnifferMetrics {
     /// Export metrics as JSON snapshot for Prometheus/Grafana
     pub fn snapshot(&self) -> String {
         format!(
-            r#"{{"tx_seen":{},"tx_filtered":{},"candidates_sent":{},"dropped_full_buffer":{},"security_drop_count":{},"backpressure_events":{},"reconnect_count":{},"high_priority_sent":{},"low_priority_sent":{},"high_priority_dropped":{},"mint_extract_errors":{},"account_extract_errors":{},"stream_buffer_depth":{}}}"#,
+            r#"{{"tx_seen":{},"tx_filtered":{},"candidates_sent":{},"dropped_full_buffer":{},"security_drop_count":{},"backpressure_events":{},"reconnect_count":{},"high_priority_sent":{},"low_priority_sent":{},"high_priority_dropped":{},"mint_extract_errors":{},"account_extract_errors":{},"stream_buffer_depth":{},"worker_restarts":{},"worker_panics":{}}}"#,
             self.tx_seen.load(Ordering::Relaxed),
             self.tx_filtered.load(Ordering::Relaxed),
             self.candidates_sent.load(Ordering::Relaxed),
@@ -170,35 +201,52 @@ impl SnifferMetrics {
             self.mint_extract_errors.load(Ordering::Relaxed),
             self.account_extract_errors.load(Ordering::Relaxed),
             self.stream_buffer_depth.load(Ordering::Relaxed),
+            self.worker_restarts.load(Ordering::Relaxed),
+            self.worker_panics.load(Ordering::Relaxed),
         )
     }
 
-    /// Record latency sample (lightweight, sampled approach)
-    /// Uses circular buffer with pseudo-random replacement
+    /// Record a supervisor restart of the named worker
+    pub fn record_worker_restart(&self, worker: &str) {
+        self.worker_restarts.fetch_add(1, Ordering::Relaxed);
+        *self
+            .worker_restart_counts
+            .lock()
+            .entry(worker.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Get restart count for the named worker
+    pub fn get_worker_restarts(&self, worker: &str) -> u64 {
+        self.worker_restart_counts
+            .lock()
+            .get(worker)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Record end-to-end processing latency (lock-free)
     pub fn record_latency(&self, latency_us: u64) {
-        let mut samples = self.latency_samples.lock();
-        if samples.len() < 1000 {
-            samples.push(latency_us);
-        } else {
-            // Use round-robin replacement for true circular buffer
-            let idx = (latency_us % 1000) as usize;
-            samples[idx] = latency_us;
-        }
+        self.latency.record(latency_us);
+        self.exported_latency.record(latency_us);
     }
 
-    /// Calculate percentile latency (P50/P95/P99)
-    /// Returns None if no samples available
-    pub fn get_percentile_latency(&self, percentile: f64) -> Option<u64> {
-        let samples = self.latency_samples.lock();
-        if samples.is_empty() {
-            return None;
-        }
+    /// Record prefilter stage latency
+    pub fn record_prefilter_latency(&self, latency_us: u64) {
+        self.prefilter_latency.record(latency_us);
+        self.exported_prefilter_latency.record(latency_us);
+    }
 
-        let mut sorted = samples.clone();
-        sorted.sort_unstable();
+    /// Record candidate extraction stage latency
+    pub fn record_extract_latency(&self, latency_us: u64) {
+        self.extract_latency.record(latency_us);
+        self.exported_extract_latency.record(latency_us);
+    }
 
-        let idx = ((sorted.len() as f64 * percentile) as usize).min(sorted.len() - 1);
-        Some(sorted[idx])
+    /// Calculate percentile latency (P50/P95/P99) over the last five minutes
+    /// Returns None if no samples available
+    pub fn get_percentile_latency(&self, percentile: f64) -> Option<u64> {
+        self.latency.recent().value_at_quantile(percentile)
     }
 
     /// Reset all counters (useful for testing)
@@ -216,7 +264,12 @@ impl SnifferMetrics {
         self.mint_extract_errors.store(0, Ordering::Relaxed);
         self.account_extract_errors.store(0, Ordering::Relaxed);
         self.stream_buffer_depth.store(0, Ordering::Relaxed);
-        self.latency_samples.lock().clear();
+        self.worker_restarts.store(0, Ordering::Relaxed);
+        self.worker_panics.store(0, Ordering::Relaxed);
+        self.worker_restart_counts.lock().clear();
+        self.latency.reset();
+        self.prefilter_latency.reset();
+        self.extract_latency.reset();
         self.latency_correlation.lock().samples.clear();
     }
 
@@ -255,6 +308,84 @@ impl Default for SnifferMetrics {
     }
 }
 
+impl MetricSource for SnifferMetrics {
+    fn collect_metrics(&self, families: &mut FamilyBuilder) {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed) as f64;
+
+        families
+            .counter(
+                "sniffer_transactions_seen_total",
+                "Transactions received from the stream",
+                &[],
+            )
+            .sample(&[], load(&self.tx_seen));
+        families
+            .counter(
+                "sniffer_transactions_filtered_total",
+                "Transactions rejected by the prefilter",
+                &[],
+            )
+            .sample(&[], load(&self.tx_filtered));
+        families
+            .counter(
+                "sniffer_candidates_sent_total",
+                "Candidates handed to the buy engine per priority",
+                &["priority"],
+            )
+            .sample(&["high"], load(&self.high_priority_sent))
+            .sample(&["low"], load(&self.low_priority_sent));
+        families
+            .counter(
+                "sniffer_candidates_dropped_total",
+                "Candidates dropped before handoff",
+                &["reason"],
+            )
+            .sample(&["full_buffer"], load(&self.dropped_full_buffer))
+            .sample(&["security"], load(&self.security_drop_count));
+        families
+            .counter(
+                "sniffer_extract_errors_total",
+                "Candidate extraction failures",
+                &["reason"],
+            )
+            .sample(&["mint"], load(&self.mint_extract_errors))
+            .sample(&["account"], load(&self.account_extract_errors));
+        families
+            .counter(
+                "sniffer_backpressure_events_total",
+                "Handoff backpressure events",
+                &[],
+            )
+            .sample(&[], load(&self.backpressure_events));
+        families
+            .counter("sniffer_reconnects_total", "Stream reconnects", &[])
+            .sample(&[], load(&self.reconnect_count));
+        families
+            .gauge(
+                "sniffer_stream_buffer_depth",
+                "Approximate stream buffer depth",
+                &[],
+            )
+            .sample(&[], load(&self.stream_buffer_depth));
+        families
+            .counter(
+                "sniffer_worker_panics_total",
+                "Worker panics observed by the supervisor",
+                &[],
+            )
+            .sample(&[], load(&self.worker_panics));
+
+        let mut restarts = families.counter(
+            "sniffer_worker_restarts_total",
+            "Supervisor restarts per worker",
+            &["worker"],
+        );
+        for (worker, count) in self.worker_restart_counts.lock().iter() {
+            restarts.sample(&[worker.as_str()], *count as f64);
+        }
+    }
+}
+
 /// Handoff diagnostics for backpressure analysis
 #[derive(Debug)]
 pub struct HandoffDiagnostics {
@@ -384,6 +515,29 @@ mod tests {
         assert_eq!(p50.unwrap(), 200);
     }
 
+    #[test]
+    fn test_exported_metric_names() {
+        let metrics = SnifferMetrics::new();
+        metrics.record_worker_restart("stream");
+        let mut families = FamilyBuilder::default();
+        metrics.collect_metrics(&mut families);
+        assert_eq!(
+            families.names(),
+            [
+                "sniffer_transactions_seen_total",
+                "sniffer_transactions_filtered_total",
+                "sniffer_candidates_sent_total",
+                "sniffer_candidates_dropped_total",
+                "sniffer_extract_errors_total",
+                "sniffer_backpressure_events_total",
+                "sniffer_reconnects_total",
+                "sniffer_stream_buffer_depth",
+                "sniffer_worker_panics_total",
+                "sniffer_worker_restarts_total",
+            ]
+        );
+    }
+
     #[test]
     fn test_snapshot() {
         let metrics = SnifferMetrics::new();
@@ -5,7 +5,10 @@
 
 use futures_util::StreamExt;
 use solana_client::nonblocking::pubsub_client::PubsubClient;
-use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsConfig,
+    RpcTransactionLogsFilter,
+};
 use solana_client::rpc_response::Response;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
@@ -91,6 +94,54 @@ impl WebSocketStream {
         Ok(())
     }
 
+    /// Subscribe to logs of transactions mentioning a program
+    ///
+    /// Feed the updates to [`LogsUpdate::pump_events`] to decode pump.fun
+    /// create/trade/complete events.
+    pub async fn subscribe_logs(
+        &self,
+        client: Arc<PubsubClient>,
+        program_id: &Pubkey,
+        tx: mpsc::UnboundedSender<LogsUpdate>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        log::info!("Subscribing to logs mentioning: {}", program_id);
+
+        let commitment = self.commitment;
+        let program_id = *program_id;
+
+        // Spawn the subscription task
+        tokio::spawn(async move {
+            // Subscribe to log notifications - the stream will hold a reference to client
+            let (mut notifications, unsubscribe) = match client
+                .logs_subscribe(
+                    RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]),
+                    RpcTransactionLogsConfig {
+                        commitment: Some(commitment),
+                    },
+                )
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    log::error!("Failed to subscribe to logs of {}: {}", program_id, e);
+                    return;
+                }
+            };
+
+            // Process notifications - client is kept alive by being in the task scope
+            while let Some(response) = notifications.next().await {
+                if tx.send(LogsUpdate::from_response(response)).is_err() {
+                    log::warn!("Receiver dropped, unsubscribing");
+                    break;
+                }
+            }
+            unsubscribe().await;
+            // client is dropped here when the task ends
+        });
+
+        Ok(())
+    }
+
     /// Subscribe to transaction signatures
     pub async fn subscribe_signatures(
         &self,
@@ -139,6 +190,38 @@ impl WebSocketStream {
     }
 }
 
+/// Transaction logs mentioning a subscribed program
+#[derive(Debug, Clone)]
+pub struct LogsUpdate {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<String>,
+    pub logs: Vec<String>,
+}
+
+impl LogsUpdate {
+    /// Create from pubsub response
+    pub fn from_response(
+        response: Response<solana_client::rpc_response::RpcLogsResponse>,
+    ) -> Self {
+        let value = response.value;
+        Self {
+            signature: value.signature,
+            slot: response.context.slot,
+            err: value.err.map(|e| e.to_string()),
+            logs: value.logs,
+        }
+    }
+
+    /// pump.fun events emitted by this transaction (none if it failed)
+    pub fn pump_events(&self) -> Vec<crate::sniffer::events::PumpEvent> {
+        if self.err.is_some() {
+            return Vec::new();
+        }
+        crate::sniffer::events::parse_logs(&self.logs)
+    }
+}
+
 /// Program account update event
 #[derive(Debug, Clone)]
 pub struct ProgramUpdate {
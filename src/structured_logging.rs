@@ -1,5 +1,6 @@
 //! Structured logging and pipeline context
 
+use crate::observability::SpanContext;
 use uuid::Uuid;
 
 /// Structured logger for pipeline events
@@ -95,7 +96,7 @@ pub struct PipelineContext {
     /// Unique request ID
     pub request_id: String,
 
-    /// Trace ID for distributed tracing
+    /// Trace ID for distributed tracing (hex, as exported)
     pub trace_id: String,
 
     /// Span ID
@@ -120,6 +121,11 @@ pub struct PipelineContext {
 impl PipelineContext {
     /// Create a new pipeline context
     pub fn new(operation: &str) -> Self {
+        Self::with_span_context(operation, SpanContext::new_root())
+    }
+
+    /// Pipeline context logging under an exported span's ids
+    pub fn with_span_context(operation: &str, context: SpanContext) -> Self {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -130,8 +136,8 @@ impl PipelineContext {
 
         Self {
             request_id: request_id.clone(),
-            trace_id: Uuid::new_v4().to_string(),
-            span_id: Uuid::new_v4().to_string(),
+            trace_id: context.trace_id_hex(),
+            span_id: context.span_id_hex(),
             parent_span_id: None,
             operation: operation.to_string(),
             timestamp: now,
@@ -150,7 +156,7 @@ impl PipelineContext {
         Self {
             request_id: self.request_id.clone(),
             trace_id: self.trace_id.clone(),
-            span_id: Uuid::new_v4().to_string(),
+            span_id: SpanContext::new_root().span_id_hex(),
             parent_span_id: Some(self.span_id.clone()),
             operation: operation.to_string(),
             timestamp: now,
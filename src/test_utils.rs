@@ -231,6 +231,8 @@ mod tests {
             timestamp: 0,
             price_hint: None,
             signature: None,
+            screening: None,
+            trace: None,
         };
 
         // Should succeed
@@ -258,6 +260,8 @@ mod tests {
             timestamp: 0,
             price_hint: None,
             signature: None,
+            screening: None,
+            trace: None,
         };
 
         // Should fail
@@ -26,6 +26,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -57,6 +59,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position1 = crate::types::TokenPosition::new(candidate1, 1.0);
         state.set_position(mint1, position1);
@@ -74,6 +78,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.5),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position2 = crate::types::TokenPosition::new(candidate2, 1.5);
         state.set_position(mint2, position2);
@@ -99,6 +105,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(entry_price),
             signature: None,
+            screening: None,
+            trace: None,
         };
         
         let position = crate::types::TokenPosition::new(candidate.clone(), entry_price);
@@ -134,6 +142,8 @@ mod tests {
                 timestamp: 1234567890 + i,
                 price_hint: Some((i + 1) as f64),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             let position = crate::types::TokenPosition::new(candidate, (i + 1) as f64);
             state.set_position(mint, position);
@@ -172,6 +182,8 @@ mod tests {
                 timestamp: 1234567890,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             let position = crate::types::TokenPosition::new(candidate, 1.0);
             state.set_position(mint, position);
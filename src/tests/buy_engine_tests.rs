@@ -342,4 +342,27 @@ mod universe_class_tests {
         assert!(p99.is_some());
         assert!(p99.unwrap() >= 98_000 && p99.unwrap() <= 100_000);
     }
+
+    #[test]
+    fn test_universe_metrics_exported_families() {
+        let metrics = UniverseMetrics::new();
+        metrics.record_rpc_error("RateLimit");
+        metrics.record_program_result("pump", true);
+        metrics.record_program_result("pump", false);
+
+        let mut families = FamilyBuilder::default();
+        metrics.collect_metrics(&mut families);
+
+        assert_eq!(
+            families.names(),
+            [
+                "buy_engine_rpc_errors_total",
+                "buy_engine_program_results_total",
+                "buy_engine_simulation_failures_total",
+                "buy_engine_simulation_critical_failures_total",
+                "buy_engine_inflight_queue_depth",
+                "buy_engine_mempool_rejections_total",
+            ]
+        );
+    }
 }
@@ -8,7 +8,7 @@
 #[cfg(test)]
 mod tests {
     use crate::components::gui_bridge::GuiCommand;
-    use crate::position_tracker::{ActivePosition, PositionTracker};
+    use crate::position_tracker::{PositionTracker};
     use solana_sdk::pubkey::Pubkey;
     use std::sync::Arc;
     use tokio::sync::mpsc;
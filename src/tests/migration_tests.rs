@@ -35,6 +35,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -60,6 +62,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -98,6 +102,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -192,6 +198,8 @@ mod tests {
                 timestamp: 1234567890,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             let position = crate::types::TokenPosition::new(candidate, (i + 1) as f64);
             state.set_position(mint, position);
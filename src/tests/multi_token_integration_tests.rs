@@ -28,6 +28,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         state.set_position(mint_a, crate::types::TokenPosition::new(candidate_a, 1.0));
         assert!(state.can_buy(), "Should allow buy (1/3)");
@@ -42,6 +44,8 @@ mod tests {
             timestamp: 1234567891,
             price_hint: Some(1.5),
             signature: None,
+            screening: None,
+            trace: None,
         };
         state.set_position(mint_b, crate::types::TokenPosition::new(candidate_b, 1.5));
         assert!(state.can_buy(), "Should allow buy (2/3)");
@@ -56,6 +60,8 @@ mod tests {
             timestamp: 1234567892,
             price_hint: Some(2.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         state.set_position(mint_c, crate::types::TokenPosition::new(candidate_c, 2.0));
         
@@ -80,6 +86,8 @@ mod tests {
             timestamp: 1234567893,
             price_hint: Some(2.5),
             signature: None,
+            screening: None,
+            trace: None,
         };
         state.set_position(mint_d, crate::types::TokenPosition::new(candidate_d, 2.5));
         
@@ -111,6 +119,8 @@ mod tests {
                 timestamp: 1234567890 + i,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             state.set_position(mint, crate::types::TokenPosition::new(candidate, 1.0));
         }
@@ -129,6 +139,8 @@ mod tests {
             timestamp: 1234567892,
             price_hint: Some(2.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         
         // In real implementation, buy would check can_buy() and skip
@@ -151,6 +163,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         state.set_position(mint1, crate::types::TokenPosition::new(candidate1, 1.0));
         
@@ -181,6 +195,8 @@ mod tests {
                 timestamp: 1234567890 + i as u64,
                 price_hint: Some((i + 1) as f64),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             state.set_position(*mint, crate::types::TokenPosition::new(candidate, (i + 1) as f64));
         }
@@ -239,6 +255,8 @@ mod tests {
                 timestamp: 1234567890 + i as u64,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             state.set_position(*mint, crate::types::TokenPosition::new(candidate, 1.0));
         }
@@ -286,6 +304,8 @@ mod tests {
                 timestamp: 1234567890,
                 price_hint: Some(*price),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             state.set_position(*mint, crate::types::TokenPosition::new(candidate, *price));
         }
@@ -334,6 +354,8 @@ mod tests {
                 timestamp: 1234567890 + i,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             state.set_position(mint, crate::types::TokenPosition::new(candidate, 1.0));
         }
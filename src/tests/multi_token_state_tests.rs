@@ -18,6 +18,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         }
     }
 
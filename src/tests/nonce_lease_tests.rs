@@ -129,7 +129,12 @@ mod nonce_lease_tests {
         let released = Arc::new(RwLock::new(false));
 
         watchdog
-            .register_lease(nonce_pubkey, Instant::now(), released.clone())
+            .register_lease(
+                nonce_pubkey,
+                Hash::default(),
+                Instant::now(),
+                released.clone(),
+            )
             .await;
 
         assert_eq!(watchdog.active_lease_count().await, 1);
@@ -22,6 +22,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -58,6 +60,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -95,6 +99,8 @@ mod tests {
                 timestamp: 1234567890,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             let position = crate::types::TokenPosition::new(candidate, 1.0);
             state.set_position(mint, position);
@@ -136,6 +142,8 @@ mod tests {
             timestamp: 1234567890,
             price_hint: Some(1.0),
             signature: None,
+            screening: None,
+            trace: None,
         };
         let position = crate::types::TokenPosition::new(candidate, 1.5);
         state.set_position(mint, position);
@@ -188,6 +196,8 @@ mod tests {
                 timestamp: 1234567890,
                 price_hint: Some(*price),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             let position = crate::types::TokenPosition::new(candidate, *price);
             state.set_position(*mint, position);
@@ -242,6 +252,8 @@ mod tests {
                 timestamp: 1234567890,
                 price_hint: Some(1.0),
                 signature: None,
+                screening: None,
+                trace: None,
             };
             let position = crate::types::TokenPosition::new(candidate, 1.0);
             state.set_position(mint, position);
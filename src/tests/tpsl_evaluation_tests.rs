@@ -24,6 +24,7 @@ fn create_test_position(mint: Pubkey, initial_price: f64, current_price: f64) ->
         total_sol_from_sales: 0,
         last_seen_price: current_price,
         last_update: Instant::now(),
+        leader: None,
     }
 }
 
@@ -0,0 +1,908 @@
+//! SPL Token / Token-2022 mint decoding
+//!
+//! Decodes the base `Mint` layout shared by both token programs and walks the
+//! Token-2022 TLV extension area so callers can inspect transfer fees,
+//! permanent delegates, transfer hooks and other extensions without pulling
+//! in the full `spl-token-2022` crate.
+//!
+//! Also provides the pieces the transaction builders need to trade
+//! Token-2022 mints: program-aware ATA derivation, a `CloseAccount`
+//! instruction, transfer-hook extra account resolution and a per-mint cache
+//! so detection costs one RPC call per mint.
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::{
+    clock::DEFAULT_SLOTS_PER_EPOCH,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Token-2022 program id
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Size of the base SPL `Mint` layout
+pub const MINT_BASE_LEN: usize = 82;
+
+/// Size of the base SPL token `Account` layout; Token-2022 pads mints to this
+/// length before the account-type byte so mints and accounts are distinguishable
+const ACCOUNT_BASE_LEN: usize = 165;
+
+/// Account-type discriminator written by Token-2022 after the padding
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Token program owning a mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenProgram {
+    /// Original SPL Token program
+    SplToken,
+    /// Token-2022 (token extensions) program
+    Token2022,
+}
+
+impl TokenProgram {
+    /// Program id
+    pub fn id(&self) -> Pubkey {
+        match self {
+            Self::SplToken => spl_token::id(),
+            Self::Token2022 => TOKEN_2022_PROGRAM_ID,
+        }
+    }
+
+    /// Detect the token program from a mint account owner
+    pub fn from_owner(owner: &Pubkey) -> Option<Self> {
+        if *owner == spl_token::id() {
+            Some(Self::SplToken)
+        } else if *owner == TOKEN_2022_PROGRAM_ID {
+            Some(Self::Token2022)
+        } else {
+            None
+        }
+    }
+}
+
+/// Error decoding a mint account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MintDecodeError {
+    /// Account is not owned by a token program
+    NotTokenProgram(Pubkey),
+    /// Account data shorter than the base mint layout
+    TooShort(usize),
+    /// Token-2022 account type byte is not `Mint`
+    NotAMint,
+    /// COption tag was neither 0 nor 1
+    InvalidOption,
+    /// TLV entry runs past the end of the account
+    MalformedExtension(u16),
+    /// Transfer-hook extra account could not be resolved offline
+    UnresolvableHookAccount(String),
+}
+
+impl fmt::Display for MintDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotTokenProgram(owner) => {
+                write!(f, "Account owner {} is not a token program", owner)
+            }
+            Self::TooShort(len) => write!(f, "Mint data too short: {} bytes", len),
+            Self::NotAMint => write!(f, "Account is not a mint"),
+            Self::InvalidOption => write!(f, "Invalid COption tag"),
+            Self::MalformedExtension(ty) => write!(f, "Malformed extension of type {}", ty),
+            Self::UnresolvableHookAccount(reason) => {
+                write!(f, "Cannot resolve transfer-hook account: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MintDecodeError {}
+
+/// Transfer fee parameters for one epoch range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TransferFee {
+    /// First epoch where the fee takes effect
+    pub epoch: u64,
+    /// Maximum fee assessed on a transfer, in raw token units
+    pub maximum_fee: u64,
+    /// Fee in basis points of the transfer amount
+    pub transfer_fee_basis_points: u16,
+}
+
+impl TransferFee {
+    /// Fee withheld from a transfer of `amount` (rounded up, capped at `maximum_fee`)
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        if self.transfer_fee_basis_points == 0 || amount == 0 {
+            return 0;
+        }
+        let raw = (amount as u128 * self.transfer_fee_basis_points as u128).div_ceil(10_000);
+        (raw.min(self.maximum_fee as u128)) as u64
+    }
+}
+
+/// Token-2022 `TransferFeeConfig` extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferFeeConfig {
+    /// Authority allowed to change the fee
+    pub transfer_fee_config_authority: Option<Pubkey>,
+    /// Fee in effect before `newer_transfer_fee.epoch`
+    pub older_transfer_fee: TransferFee,
+    /// Fee in effect from `newer_transfer_fee.epoch`
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Fee parameters in effect at `epoch`
+    pub fn get_epoch_fee(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    /// Highest basis-point fee across both configured epochs
+    pub fn max_basis_points(&self) -> u16 {
+        self.older_transfer_fee
+            .transfer_fee_basis_points
+            .max(self.newer_transfer_fee.transfer_fee_basis_points)
+    }
+}
+
+/// Decoded Token-2022 mint extension
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MintExtension {
+    /// Fee withheld on every transfer
+    TransferFeeConfig(TransferFeeConfig),
+    /// Authority able to close the mint
+    MintCloseAuthority(Option<Pubkey>),
+    /// New token accounts start in this state
+    DefaultAccountState { frozen: bool },
+    /// Tokens cannot be transferred
+    NonTransferable,
+    /// Interest-bearing UI amount
+    InterestBearingConfig,
+    /// Delegate able to transfer or burn from any account
+    PermanentDelegate(Option<Pubkey>),
+    /// Program invoked on every transfer
+    TransferHook {
+        authority: Option<Pubkey>,
+        program_id: Option<Pubkey>,
+    },
+    /// Confidential transfers enabled
+    ConfidentialTransferMint,
+    /// Any other extension, by TLV type
+    Other(u16),
+}
+
+/// Decoded mint account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MintInfo {
+    /// Owning token program
+    pub token_program: TokenProgram,
+    /// Authority allowed to mint new tokens
+    pub mint_authority: Option<Pubkey>,
+    /// Total supply in raw units
+    pub supply: u64,
+    /// Decimals
+    pub decimals: u8,
+    /// Whether the mint is initialized
+    pub is_initialized: bool,
+    /// Authority allowed to freeze token accounts
+    pub freeze_authority: Option<Pubkey>,
+    /// Token-2022 extensions (empty for SPL Token mints)
+    pub extensions: Vec<MintExtension>,
+}
+
+impl MintInfo {
+    /// Decode a mint from its owner and raw account data
+    pub fn decode(owner: &Pubkey, data: &[u8]) -> Result<Self, MintDecodeError> {
+        let token_program =
+            TokenProgram::from_owner(owner).ok_or(MintDecodeError::NotTokenProgram(*owner))?;
+
+        if data.len() < MINT_BASE_LEN {
+            return Err(MintDecodeError::TooShort(data.len()));
+        }
+
+        let mint_authority = read_coption_pubkey(&data[0..36])?;
+        let supply = read_u64(&data[36..44]);
+        let decimals = data[44];
+        let is_initialized = data[45] != 0;
+        let freeze_authority = read_coption_pubkey(&data[46..82])?;
+
+        let extensions = if token_program == TokenProgram::Token2022 && data.len() > MINT_BASE_LEN {
+            decode_extensions(data)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            token_program,
+            mint_authority,
+            supply,
+            decimals,
+            is_initialized,
+            freeze_authority,
+            extensions,
+        })
+    }
+
+    /// Transfer fee configuration, if present
+    pub fn transfer_fee_config(&self) -> Option<&TransferFeeConfig> {
+        self.extensions.iter().find_map(|ext| match ext {
+            MintExtension::TransferFeeConfig(cfg) => Some(cfg),
+            _ => None,
+        })
+    }
+
+    /// Permanent delegate, if the extension is present
+    pub fn permanent_delegate(&self) -> Option<Option<Pubkey>> {
+        self.extensions.iter().find_map(|ext| match ext {
+            MintExtension::PermanentDelegate(delegate) => Some(*delegate),
+            _ => None,
+        })
+    }
+
+    /// Transfer hook program, if the extension is present with a program set
+    pub fn transfer_hook_program(&self) -> Option<Pubkey> {
+        self.extensions.iter().find_map(|ext| match ext {
+            MintExtension::TransferHook { program_id, .. } => *program_id,
+            _ => None,
+        })
+    }
+
+    /// Whether the mint carries the given extension kind
+    pub fn has_extension(&self, predicate: impl Fn(&MintExtension) -> bool) -> bool {
+        self.extensions.iter().any(predicate)
+    }
+
+    /// Supply expressed in whole tokens
+    pub fn ui_supply(&self) -> f64 {
+        self.supply as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Fee withheld from a transfer of `amount` at `epoch` (0 without a fee extension)
+    pub fn transfer_fee(&self, epoch: u64, amount: u64) -> u64 {
+        self.transfer_fee_config()
+            .map(|cfg| cfg.get_epoch_fee(epoch).calculate_fee(amount))
+            .unwrap_or(0)
+    }
+
+    /// Amount the recipient actually receives from a transfer of `amount`
+    pub fn net_amount(&self, epoch: u64, amount: u64) -> u64 {
+        amount.saturating_sub(self.transfer_fee(epoch, amount))
+    }
+}
+
+/// Associated token account of `owner` for `mint` under the mint's token program
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, program: TokenProgram) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        owner,
+        mint,
+        &program.id(),
+    )
+}
+
+/// `CloseAccount` for either token program (the instruction layout is shared)
+pub fn close_account(
+    program: TokenProgram,
+    account: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: program.id(),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: vec![9],
+    }
+}
+
+/// Seed of the transfer-hook `ExtraAccountMetaList` PDA
+const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Size of one packed `ExtraAccountMeta`
+const EXTRA_ACCOUNT_META_LEN: usize = 35;
+
+/// Validation account holding the extra accounts a transfer hook requires
+pub fn extra_account_metas_address(mint: &Pubkey, hook_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], hook_program).0
+}
+
+/// The transfer a hook is being resolved for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HookedTransfer {
+    pub source: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+/// `Execute` instruction discriminator of the transfer-hook interface
+fn execute_discriminator() -> [u8; 8] {
+    let digest = Sha256::digest(b"spl-transfer-hook-interface:execute");
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&digest[..8]);
+    out
+}
+
+/// Accounts to append to a `TransferChecked` (or a DEX instruction that CPIs
+/// into one) for a mint with a transfer hook
+///
+/// Returns the resolved extra accounts followed by the hook program and its
+/// validation account, matching the order Token-2022 expects. `validation_data`
+/// is the raw data of [`extra_account_metas_address`]; `None` means the hook
+/// declares no extra accounts. Seeds that read other accounts' data cannot be
+/// resolved offline and produce [`MintDecodeError::UnresolvableHookAccount`].
+pub fn resolve_transfer_hook_accounts(
+    hook_program: &Pubkey,
+    transfer: &HookedTransfer,
+    validation_data: Option<&[u8]>,
+) -> Result<Vec<AccountMeta>, MintDecodeError> {
+    let validation = extra_account_metas_address(&transfer.mint, hook_program);
+
+    // Account list of the hook's Execute instruction; seeds index into it
+    let mut execute_accounts = vec![
+        AccountMeta::new(transfer.source, false),
+        AccountMeta::new_readonly(transfer.mint, false),
+        AccountMeta::new(transfer.destination, false),
+        AccountMeta::new_readonly(transfer.authority, false),
+        AccountMeta::new_readonly(validation, false),
+    ];
+    let mut execute_data = execute_discriminator().to_vec();
+    execute_data.extend_from_slice(&transfer.amount.to_le_bytes());
+
+    if let Some(data) = validation_data {
+        for entry in extra_account_meta_entries(data)? {
+            let pubkey = match entry[0] {
+                0 => read_pubkey(&entry[1..33]),
+                1 => {
+                    let seeds = unpack_seeds(&entry[1..33], &execute_data, &execute_accounts)?;
+                    derive_pda(&seeds, hook_program)
+                }
+                d if d >= 128 => {
+                    let program = execute_accounts
+                        .get((d - 128) as usize)
+                        .ok_or_else(|| {
+                            MintDecodeError::UnresolvableHookAccount(format!(
+                                "program account index {} out of range",
+                                d - 128
+                            ))
+                        })?
+                        .pubkey;
+                    let seeds = unpack_seeds(&entry[1..33], &execute_data, &execute_accounts)?;
+                    derive_pda(&seeds, &program)
+                }
+                d => {
+                    return Err(MintDecodeError::UnresolvableHookAccount(format!(
+                        "unsupported account discriminator {}",
+                        d
+                    )))
+                }
+            };
+            execute_accounts.push(AccountMeta {
+                pubkey,
+                is_signer: entry[33] != 0,
+                is_writable: entry[34] != 0,
+            });
+        }
+    }
+
+    let mut extra: Vec<AccountMeta> = execute_accounts.split_off(5);
+    extra.push(AccountMeta::new_readonly(*hook_program, false));
+    extra.push(AccountMeta::new_readonly(validation, false));
+    Ok(extra)
+}
+
+/// Packed `ExtraAccountMeta` entries stored under the Execute discriminator
+fn extra_account_meta_entries(data: &[u8]) -> Result<Vec<&[u8]>, MintDecodeError> {
+    let malformed =
+        || MintDecodeError::UnresolvableHookAccount("malformed validation account".into());
+    let discriminator = execute_discriminator();
+
+    let mut offset = 0;
+    while offset + 12 <= data.len() {
+        let len = u32::from_le_bytes([
+            data[offset + 8],
+            data[offset + 9],
+            data[offset + 10],
+            data[offset + 11],
+        ]) as usize;
+        let start = offset + 12;
+        let end = start.checked_add(len).ok_or_else(malformed)?;
+        if end > data.len() {
+            return Err(malformed());
+        }
+        if data[offset..offset + 8] == discriminator {
+            let value = &data[start..end];
+            if value.len() < 4 {
+                return Err(malformed());
+            }
+            let count = u32::from_le_bytes([value[0], value[1], value[2], value[3]]) as usize;
+            let entries = &value[4..];
+            if entries.len() < count * EXTRA_ACCOUNT_META_LEN {
+                return Err(malformed());
+            }
+            return Ok(entries
+                .chunks_exact(EXTRA_ACCOUNT_META_LEN)
+                .take(count)
+                .collect());
+        }
+        offset = end;
+    }
+
+    Ok(Vec::new())
+}
+
+/// Unpack the seed configuration of a PDA `ExtraAccountMeta`
+fn unpack_seeds(
+    config: &[u8],
+    execute_data: &[u8],
+    accounts: &[AccountMeta],
+) -> Result<Vec<Vec<u8>>, MintDecodeError> {
+    let err = |reason: &str| MintDecodeError::UnresolvableHookAccount(reason.to_string());
+    let byte = |i: usize| {
+        config
+            .get(i)
+            .copied()
+            .ok_or_else(|| err("truncated seed config"))
+    };
+
+    let mut seeds = Vec::new();
+    let mut i = 0;
+    while i < config.len() {
+        match config[i] {
+            0 => break,
+            1 => {
+                let len = byte(i + 1)? as usize;
+                let bytes = config
+                    .get(i + 2..i + 2 + len)
+                    .ok_or_else(|| err("truncated literal seed"))?;
+                seeds.push(bytes.to_vec());
+                i += 2 + len;
+            }
+            2 => {
+                let index = byte(i + 1)? as usize;
+                let len = byte(i + 2)? as usize;
+                let bytes = execute_data
+                    .get(index..index + len)
+                    .ok_or_else(|| err("instruction data seed out of range"))?;
+                seeds.push(bytes.to_vec());
+                i += 3;
+            }
+            3 => {
+                let index = byte(i + 1)? as usize;
+                let account = accounts
+                    .get(index)
+                    .ok_or_else(|| err("account key seed out of range"))?;
+                seeds.push(account.pubkey.to_bytes().to_vec());
+                i += 2;
+            }
+            4 => return Err(err("account data seeds require fetching accounts")),
+            _ => return Err(err("unknown seed type")),
+        }
+    }
+    Ok(seeds)
+}
+
+fn derive_pda(seeds: &[Vec<u8>], program: &Pubkey) -> Pubkey {
+    let seed_refs: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seed_refs, program).0
+}
+
+/// Mint info as observed at a given slot
+#[derive(Debug, Clone)]
+pub struct CachedMint {
+    pub info: MintInfo,
+    /// Slot of the RPC response the mint was decoded from
+    pub slot: u64,
+    /// Raw data of the transfer-hook validation account, if the mint has a hook
+    /// and the account exists
+    pub hook_validation_data: Option<Vec<u8>>,
+    fetched_at: Instant,
+}
+
+impl CachedMint {
+    pub fn new(info: MintInfo, slot: u64, hook_validation_data: Option<Vec<u8>>) -> Self {
+        Self {
+            info,
+            slot,
+            hook_validation_data,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    /// Epoch the mint was observed in, used to pick the active transfer fee
+    pub fn epoch(&self) -> u64 {
+        self.slot / DEFAULT_SLOTS_PER_EPOCH
+    }
+
+    pub fn token_program(&self) -> TokenProgram {
+        self.info.token_program
+    }
+
+    /// Amount received by the destination of a transfer of `amount`
+    pub fn net_amount(&self, amount: u64) -> u64 {
+        self.info.net_amount(self.epoch(), amount)
+    }
+
+    /// Extra accounts for a transfer of this mint (empty without a transfer hook)
+    pub fn transfer_hook_accounts(
+        &self,
+        transfer: &HookedTransfer,
+    ) -> Result<Vec<AccountMeta>, MintDecodeError> {
+        match self.info.transfer_hook_program() {
+            Some(program) => resolve_transfer_hook_accounts(
+                &program,
+                transfer,
+                self.hook_validation_data.as_deref(),
+            ),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Per-mint cache of token-program detection results
+///
+/// The owning program of a mint never changes, but fee schedules and hook
+/// configuration can, so entries older than the TTL are reported stale and
+/// should be refetched by the caller.
+pub struct MintCache {
+    entries: DashMap<Pubkey, Arc<CachedMint>>,
+    ttl: Duration,
+}
+
+impl MintCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl,
+        }
+    }
+
+    /// Cached entry regardless of age
+    pub fn get(&self, mint: &Pubkey) -> Option<Arc<CachedMint>> {
+        self.entries.get(mint).map(|e| e.value().clone())
+    }
+
+    /// Cached entry if it is younger than the TTL
+    pub fn get_fresh(&self, mint: &Pubkey) -> Option<Arc<CachedMint>> {
+        self.get(mint)
+            .filter(|entry| entry.fetched_at.elapsed() < self.ttl)
+    }
+
+    pub fn insert(&self, mint: Pubkey, entry: CachedMint) -> Arc<CachedMint> {
+        let entry = Arc::new(entry);
+        self.entries.insert(mint, entry.clone());
+        entry
+    }
+
+    pub fn invalidate(&self, mint: &Pubkey) {
+        self.entries.remove(mint);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for MintCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300))
+    }
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[..8]);
+    u64::from_le_bytes(buf)
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    u16::from_le_bytes([bytes[0], bytes[1]])
+}
+
+fn read_pubkey(bytes: &[u8]) -> Pubkey {
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&bytes[..32]);
+    Pubkey::new_from_array(buf)
+}
+
+/// `COption<Pubkey>`: u32 tag followed by 32 bytes
+fn read_coption_pubkey(bytes: &[u8]) -> Result<Option<Pubkey>, MintDecodeError> {
+    match u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) {
+        0 => Ok(None),
+        1 => Ok(Some(read_pubkey(&bytes[4..36]))),
+        _ => Err(MintDecodeError::InvalidOption),
+    }
+}
+
+/// `OptionalNonZeroPubkey`: 32 bytes, all-zero means `None`
+fn read_optional_nonzero_pubkey(bytes: &[u8]) -> Option<Pubkey> {
+    let key = read_pubkey(bytes);
+    if key == Pubkey::default() {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+fn read_transfer_fee(bytes: &[u8]) -> TransferFee {
+    TransferFee {
+        epoch: read_u64(&bytes[0..8]),
+        maximum_fee: read_u64(&bytes[8..16]),
+        transfer_fee_basis_points: read_u16(&bytes[16..18]),
+    }
+}
+
+fn decode_extensions(data: &[u8]) -> Result<Vec<MintExtension>, MintDecodeError> {
+    if data.len() <= ACCOUNT_BASE_LEN {
+        return Err(MintDecodeError::TooShort(data.len()));
+    }
+    if data[ACCOUNT_BASE_LEN] != ACCOUNT_TYPE_MINT {
+        return Err(MintDecodeError::NotAMint);
+    }
+
+    let mut extensions = Vec::new();
+    let mut offset = ACCOUNT_BASE_LEN + 1;
+
+    while offset + 4 <= data.len() {
+        let ty = read_u16(&data[offset..offset + 2]);
+        let len = read_u16(&data[offset + 2..offset + 4]) as usize;
+        // Type 0 marks uninitialized trailing space
+        if ty == 0 {
+            break;
+        }
+        let start = offset + 4;
+        let end = start + len;
+        if end > data.len() {
+            return Err(MintDecodeError::MalformedExtension(ty));
+        }
+        let value = &data[start..end];
+
+        let ext = match ty {
+            1 if len >= 108 => MintExtension::TransferFeeConfig(TransferFeeConfig {
+                transfer_fee_config_authority: read_optional_nonzero_pubkey(&value[0..32]),
+                older_transfer_fee: read_transfer_fee(&value[72..90]),
+                newer_transfer_fee: read_transfer_fee(&value[90..108]),
+            }),
+            3 if len >= 32 => {
+                MintExtension::MintCloseAuthority(read_optional_nonzero_pubkey(value))
+            }
+            4 => MintExtension::ConfidentialTransferMint,
+            6 if len >= 1 => MintExtension::DefaultAccountState {
+                frozen: value[0] == 2,
+            },
+            9 => MintExtension::NonTransferable,
+            10 => MintExtension::InterestBearingConfig,
+            12 if len >= 32 => {
+                MintExtension::PermanentDelegate(read_optional_nonzero_pubkey(value))
+            }
+            14 if len >= 64 => MintExtension::TransferHook {
+                authority: read_optional_nonzero_pubkey(&value[0..32]),
+                program_id: read_optional_nonzero_pubkey(&value[32..64]),
+            },
+            1 | 3 | 6 | 12 | 14 => return Err(MintDecodeError::MalformedExtension(ty)),
+            other => MintExtension::Other(other),
+        };
+        extensions.push(ext);
+        offset = end;
+    }
+
+    Ok(extensions)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Build raw mint data for tests; `extensions` are (type, value) TLV pairs
+    pub(crate) fn mint_bytes(
+        mint_authority: Option<Pubkey>,
+        supply: u64,
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
+        extensions: &[(u16, Vec<u8>)],
+    ) -> Vec<u8> {
+        let mut data = Vec::with_capacity(ACCOUNT_BASE_LEN + 1);
+        let push_coption = |data: &mut Vec<u8>, key: Option<Pubkey>| match key {
+            Some(k) => {
+                data.extend_from_slice(&1u32.to_le_bytes());
+                data.extend_from_slice(k.as_ref());
+            }
+            None => data.extend_from_slice(&[0u8; 36]),
+        };
+        push_coption(&mut data, mint_authority);
+        data.extend_from_slice(&supply.to_le_bytes());
+        data.push(decimals);
+        data.push(1);
+        push_coption(&mut data, freeze_authority);
+
+        if !extensions.is_empty() {
+            data.resize(ACCOUNT_BASE_LEN, 0);
+            data.push(ACCOUNT_TYPE_MINT);
+            for (ty, value) in extensions {
+                data.extend_from_slice(&ty.to_le_bytes());
+                data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+                data.extend_from_slice(value);
+            }
+        }
+        data
+    }
+
+    pub(crate) fn transfer_fee_ext(bps: u16, maximum_fee: u64) -> (u16, Vec<u8>) {
+        let mut value = vec![0u8; 72];
+        for _ in 0..2 {
+            value.extend_from_slice(&0u64.to_le_bytes());
+            value.extend_from_slice(&maximum_fee.to_le_bytes());
+            value.extend_from_slice(&bps.to_le_bytes());
+        }
+        (1, value)
+    }
+
+    #[test]
+    fn test_decode_spl_token_mint() {
+        let authority = Pubkey::new_unique();
+        let data = mint_bytes(Some(authority), 1_000_000, 6, None, &[]);
+
+        let info = MintInfo::decode(&spl_token::id(), &data).unwrap();
+        assert_eq!(info.token_program, TokenProgram::SplToken);
+        assert_eq!(info.mint_authority, Some(authority));
+        assert_eq!(info.freeze_authority, None);
+        assert_eq!(info.supply, 1_000_000);
+        assert_eq!(info.decimals, 6);
+        assert!(info.is_initialized);
+        assert!(info.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_decode_token_2022_extensions() {
+        let delegate = Pubkey::new_unique();
+        let hook = Pubkey::new_unique();
+        let mut hook_value = vec![0u8; 32];
+        hook_value.extend_from_slice(hook.as_ref());
+
+        let data = mint_bytes(
+            None,
+            10,
+            9,
+            None,
+            &[
+                transfer_fee_ext(250, 5_000),
+                (12, delegate.as_ref().to_vec()),
+                (14, hook_value),
+                (9, vec![]),
+                (19, vec![1, 2, 3]),
+            ],
+        );
+
+        let info = MintInfo::decode(&TOKEN_2022_PROGRAM_ID, &data).unwrap();
+        assert_eq!(info.token_program, TokenProgram::Token2022);
+        assert_eq!(info.transfer_fee_config().unwrap().max_basis_points(), 250);
+        assert_eq!(info.permanent_delegate(), Some(Some(delegate)));
+        assert_eq!(info.transfer_hook_program(), Some(hook));
+        assert!(info.has_extension(|e| matches!(e, MintExtension::NonTransferable)));
+        assert!(info.has_extension(|e| matches!(e, MintExtension::Other(19))));
+    }
+
+    #[test]
+    fn test_decode_rejects_foreign_owner_and_short_data() {
+        let data = mint_bytes(None, 1, 6, None, &[]);
+        assert!(matches!(
+            MintInfo::decode(&Pubkey::new_unique(), &data),
+            Err(MintDecodeError::NotTokenProgram(_))
+        ));
+        assert_eq!(
+            MintInfo::decode(&spl_token::id(), &data[..40]),
+            Err(MintDecodeError::TooShort(40))
+        );
+    }
+
+    #[test]
+    fn test_transfer_fee_calculation() {
+        let fee = TransferFee {
+            epoch: 0,
+            maximum_fee: 1_000,
+            transfer_fee_basis_points: 100,
+        };
+        assert_eq!(fee.calculate_fee(0), 0);
+        assert_eq!(fee.calculate_fee(10_000), 100);
+        // Rounds up
+        assert_eq!(fee.calculate_fee(1), 1);
+        // Capped at maximum_fee
+        assert_eq!(fee.calculate_fee(10_000_000), 1_000);
+    }
+
+    #[test]
+    fn test_ata_and_instructions_follow_token_program() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let legacy = associated_token_address(&owner, &mint, TokenProgram::SplToken);
+        let t22 = associated_token_address(&owner, &mint, TokenProgram::Token2022);
+        assert_ne!(legacy, t22);
+        assert_eq!(
+            legacy,
+            spl_associated_token_account::get_associated_token_address(&owner, &mint)
+        );
+
+        let close = close_account(TokenProgram::Token2022, &t22, &owner, &owner);
+        assert_eq!(close.program_id, TOKEN_2022_PROGRAM_ID);
+        assert_eq!(close.data, vec![9]);
+    }
+
+    #[test]
+    fn test_resolve_transfer_hook_accounts() {
+        let hook = Pubkey::new_unique();
+        let fixed = Pubkey::new_unique();
+        let transfer = HookedTransfer {
+            source: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            amount: 42,
+        };
+
+        // Entry 1: fixed writable pubkey; entry 2: PDA of ["counter", authority]
+        let mut entries = Vec::new();
+        entries.push(0u8);
+        entries.extend_from_slice(fixed.as_ref());
+        entries.extend_from_slice(&[0, 1]);
+        let mut config = vec![1u8, 7];
+        config.extend_from_slice(b"counter");
+        config.extend_from_slice(&[3, 3]);
+        config.resize(32, 0);
+        entries.push(1);
+        entries.extend_from_slice(&config);
+        entries.extend_from_slice(&[0, 0]);
+
+        let mut value = 2u32.to_le_bytes().to_vec();
+        value.extend_from_slice(&entries);
+        let mut data = execute_discriminator().to_vec();
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(&value);
+
+        let accounts = resolve_transfer_hook_accounts(&hook, &transfer, Some(&data)).unwrap();
+        let validation = extra_account_metas_address(&transfer.mint, &hook);
+        let counter =
+            Pubkey::find_program_address(&[b"counter", transfer.authority.as_ref()], &hook).0;
+
+        assert_eq!(accounts.len(), 4);
+        assert_eq!(accounts[0], AccountMeta::new(fixed, false));
+        assert_eq!(accounts[1], AccountMeta::new_readonly(counter, false));
+        assert_eq!(accounts[2], AccountMeta::new_readonly(hook, false));
+        assert_eq!(accounts[3], AccountMeta::new_readonly(validation, false));
+
+        // Without a validation account only the program and PDA are needed
+        let bare = resolve_transfer_hook_accounts(&hook, &transfer, None).unwrap();
+        assert_eq!(bare.len(), 2);
+    }
+
+    #[test]
+    fn test_cached_mint_net_amount() {
+        let data = mint_bytes(None, 1_000, 6, None, &[transfer_fee_ext(100, 500)]);
+        let info = MintInfo::decode(&TOKEN_2022_PROGRAM_ID, &data).unwrap();
+        let cached = CachedMint::new(info, 10, None);
+        assert_eq!(cached.token_program(), TokenProgram::Token2022);
+        assert_eq!(cached.net_amount(10_000), 9_900);
+        assert_eq!(cached.net_amount(1_000_000), 999_500);
+
+        let cache = MintCache::new(Duration::from_secs(60));
+        let mint = Pubkey::new_unique();
+        cache.insert(mint, cached);
+        assert!(cache.get_fresh(&mint).is_some());
+        cache.invalidate(&mint);
+        assert!(cache.is_empty());
+    }
+}
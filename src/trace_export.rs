@@ -0,0 +1,308 @@
+//! OTLP span export for the candidate-to-landing pipeline
+//!
+//! Finished [`Span`](crate::observability::Span)s are queued without blocking
+//! and shipped in batches by a background task: to an OpenTelemetry collector
+//! over OTLP/HTTP with JSON encoding, or as OTLP JSON lines to a file or stdout
+//! for offline inspection. Spans are dropped (and counted) when the queue is
+//! full rather than slowing the hot path.
+
+use crate::observability::{AttributeValue, SpanData, SpanStatus};
+use anyhow::{Context, Result};
+use once_cell::sync::OnceCell;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// Instrumentation scope reported with every span
+const SCOPE_NAME: &str = "bot.pipeline";
+
+/// Destination for exported spans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpanExporter {
+    /// OTLP/HTTP traces endpoint (e.g. `http://localhost:4318/v1/traces`)
+    Otlp { endpoint: String },
+    /// Append OTLP JSON lines to a file
+    File(PathBuf),
+    /// Write OTLP JSON lines to stdout
+    Stdout,
+}
+
+/// Span export configuration
+#[derive(Debug, Clone)]
+pub struct TraceExportConfig {
+    pub exporter: SpanExporter,
+
+    /// `service.name` resource attribute
+    pub service_name: String,
+
+    /// Spans buffered before new ones are dropped
+    pub queue_capacity: usize,
+
+    /// Spans per export request
+    pub max_batch_size: usize,
+
+    /// Longest a finished span waits before export
+    pub flush_interval: Duration,
+}
+
+impl TraceExportConfig {
+    pub fn new(exporter: SpanExporter) -> Self {
+        Self {
+            exporter,
+            service_name: "bot".to_string(),
+            queue_capacity: 8192,
+            max_batch_size: 512,
+            flush_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+struct Exporter {
+    tx: mpsc::Sender<SpanData>,
+    dropped: AtomicU64,
+}
+
+static EXPORTER: OnceCell<Exporter> = OnceCell::new();
+
+/// Install the span exporter and spawn its background task
+///
+/// Must be called from within a Tokio runtime, at most once.
+pub fn init(config: TraceExportConfig) -> Result<tokio::task::JoinHandle<()>> {
+    let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+    EXPORTER
+        .set(Exporter {
+            tx,
+            dropped: AtomicU64::new(0),
+        })
+        .map_err(|_| anyhow::anyhow!("span exporter already initialized"))?;
+
+    info!(exporter = ?config.exporter, "Exporting pipeline spans");
+    Ok(tokio::spawn(run(config, rx)))
+}
+
+/// Whether an exporter is installed
+#[inline]
+pub fn enabled() -> bool {
+    EXPORTER.get().is_some()
+}
+
+/// Spans dropped because the export queue was full
+pub fn dropped_spans() -> u64 {
+    EXPORTER
+        .get()
+        .map_or(0, |exporter| exporter.dropped.load(Ordering::Relaxed))
+}
+
+/// Queue a finished span for export
+pub(crate) fn export(span: SpanData) {
+    if let Some(exporter) = EXPORTER.get() {
+        if exporter.tx.try_send(span).is_err() {
+            exporter.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+async fn run(config: TraceExportConfig, mut rx: mpsc::Receiver<SpanData>) {
+    let client = reqwest::Client::new();
+    let mut batch = Vec::with_capacity(config.max_batch_size);
+
+    loop {
+        let closed = match tokio::time::timeout(config.flush_interval, rx.recv()).await {
+            Ok(Some(span)) => {
+                batch.push(span);
+                while batch.len() < config.max_batch_size {
+                    match rx.try_recv() {
+                        Ok(span) => batch.push(span),
+                        Err(_) => break,
+                    }
+                }
+                false
+            }
+            Ok(None) => true,
+            Err(_) => false,
+        };
+
+        if !batch.is_empty() {
+            if let Err(e) = write_batch(&config, &client, &batch).await {
+                warn!(error = %e, spans = batch.len(), "Span export failed");
+            }
+            batch.clear();
+        }
+        if closed {
+            break;
+        }
+    }
+}
+
+async fn write_batch(
+    config: &TraceExportConfig,
+    client: &reqwest::Client,
+    spans: &[SpanData],
+) -> Result<()> {
+    let request = encode_otlp_json(&config.service_name, spans);
+    match &config.exporter {
+        SpanExporter::Otlp { endpoint } => {
+            client
+                .post(endpoint)
+                .json(&request)
+                .send()
+                .await
+                .context("OTLP request failed")?
+                .error_for_status()
+                .context("OTLP collector rejected spans")?;
+        }
+        SpanExporter::File(path) => {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await
+                .with_context(|| format!("Failed to open {}", path.display()))?;
+            file.write_all(format!("{}\n", request).as_bytes()).await?;
+            file.flush().await?;
+        }
+        SpanExporter::Stdout => println!("{}", request),
+    }
+    Ok(())
+}
+
+/// Encode spans as an OTLP `ExportTraceServiceRequest` (JSON encoding)
+pub fn encode_otlp_json(service_name: &str, spans: &[SpanData]) -> Value {
+    let spans: Vec<Value> = spans.iter().map(encode_span).collect();
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [attribute("service.name", &AttributeValue::from(service_name))],
+            },
+            "scopeSpans": [{
+                "scope": { "name": SCOPE_NAME },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn encode_span(span: &SpanData) -> Value {
+    let mut encoded = json!({
+        "traceId": span.context.trace_id_hex(),
+        "spanId": span.context.span_id_hex(),
+        "name": span.name,
+        // SPAN_KIND_INTERNAL
+        "kind": 1,
+        "startTimeUnixNano": span.start_unix_nanos.to_string(),
+        "endTimeUnixNano": span.end_unix_nanos.to_string(),
+        "attributes": span
+            .attributes
+            .iter()
+            .map(|(key, value)| attribute(key, value))
+            .collect::<Vec<_>>(),
+        "status": match &span.status {
+            SpanStatus::Unset => json!({ "code": 0 }),
+            SpanStatus::Ok => json!({ "code": 1 }),
+            SpanStatus::Error(message) => json!({ "code": 2, "message": message }),
+        },
+    });
+    if let Some(parent) = span.parent_span_id {
+        encoded["parentSpanId"] = Value::String(format!("{:016x}", parent));
+    }
+    encoded
+}
+
+fn attribute(key: &str, value: &AttributeValue) -> Value {
+    let value = match value {
+        AttributeValue::String(value) => json!({ "stringValue": value }),
+        // 64-bit integers are strings in OTLP/JSON
+        AttributeValue::Int(value) => json!({ "intValue": value.to_string() }),
+        AttributeValue::Double(value) => json!({ "doubleValue": value }),
+        AttributeValue::Bool(value) => json!({ "boolValue": value }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::SpanContext;
+
+    fn span(parent: Option<u64>, status: SpanStatus) -> SpanData {
+        SpanData {
+            name: "buy_engine.submit",
+            context: SpanContext {
+                trace_id: 0xabc,
+                span_id: 0x12,
+            },
+            parent_span_id: parent,
+            start_unix_nanos: 1_000,
+            end_unix_nanos: 2_500,
+            attributes: vec![
+                (
+                    "mint",
+                    AttributeValue::from("So11111111111111111111111111111111111111112"),
+                ),
+                ("attempts", AttributeValue::from(3u64)),
+            ],
+            status,
+        }
+    }
+
+    #[test]
+    fn test_encode_otlp_json() {
+        let request = encode_otlp_json(
+            "bot",
+            &[span(Some(0x34), SpanStatus::Error("timeout".to_string()))],
+        );
+        let resource = &request["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "bot"
+        );
+
+        let encoded = &resource["scopeSpans"][0]["spans"][0];
+        assert_eq!(encoded["traceId"], "00000000000000000000000000000abc");
+        assert_eq!(encoded["spanId"], "0000000000000012");
+        assert_eq!(encoded["parentSpanId"], "0000000000000034");
+        assert_eq!(encoded["startTimeUnixNano"], "1000");
+        assert_eq!(encoded["endTimeUnixNano"], "2500");
+        assert_eq!(encoded["attributes"][1]["value"]["intValue"], "3");
+        assert_eq!(encoded["status"]["code"], 2);
+        assert_eq!(encoded["status"]["message"], "timeout");
+    }
+
+    #[test]
+    fn test_root_span_has_no_parent() {
+        let request = encode_otlp_json("bot", &[span(None, SpanStatus::Ok)]);
+        let encoded = &request["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert!(encoded.get("parentSpanId").is_none());
+        assert_eq!(encoded["status"]["code"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_exporter_writes_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spans.jsonl");
+        let config = TraceExportConfig::new(SpanExporter::File(path.clone()));
+        let client = reqwest::Client::new();
+
+        write_batch(&config, &client, &[span(None, SpanStatus::Unset)])
+            .await
+            .unwrap();
+        write_batch(&config, &client, &[span(Some(1), SpanStatus::Ok)])
+            .await
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1]["resourceSpans"][0]["scopeSpans"][0]["spans"][0]["parentSpanId"],
+            "0000000000000001"
+        );
+    }
+}
@@ -207,7 +207,7 @@ where
     ) -> Result<Signature, TransactionBuilderError> {
         use crate::metrics::Timer;
 
-        let _timer = Timer::with_name("prepare_bundle_ms");
+        let _timer = Timer::with_name("prepare_bundle_seconds");
 
         info!(
             tip_lamports = tip_lamports,
@@ -103,6 +103,7 @@
 //!         min_responses: 3,
 //!         max_slot_diff: 5,
 //!         enable_slot_validation: true,
+//!         ..Default::default()
 //!     },
 //!     
 //!     // NEW: Rate limiting
@@ -185,6 +186,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256}; // Task 2: For deterministic message hashing
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
+    commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
     hash::Hash,
     instruction::{AccountMeta, Instruction},
@@ -204,8 +206,12 @@ use thiserror::Error;
 use tokio::sync::{RwLock, Semaphore};
 use tracing::{debug, info, warn};
 
-use crate::nonce_manager::{NonceError, NonceManager};
+use crate::config::Config;
+use crate::nonce_manager::{NonceClass, NonceError, NonceManager};
 use crate::rpc_manager::rpc_errors::RpcManagerError;
+use crate::rpc_manager::{SlotClock, SubmissionRoute};
+use crate::sniffer::events::PUMPFUN_PROGRAM_ID;
+use crate::token_program::{self, CachedMint, HookedTransfer, MintCache, MintInfo, TokenProgram};
 use crate::types::PremintCandidate;
 use crate::wallet::WalletManager;
 
@@ -213,10 +219,6 @@ use crate::wallet::WalletManager;
 #[cfg(feature = "pumpfun")]
 use pumpfun::{accounts::BondingCurveAccount, common::types::Cluster, PumpFun};
 
-use spl_associated_token_account::get_associated_token_address;
-use spl_token::id as token_program_id;
-use spl_token::instruction::close_account;
-
 // ============================================================================
 // Transaction Build Output (Phase 1: RAII Nonce Management)
 // ============================================================================
@@ -604,26 +606,8 @@ impl RetryPolicy {
     }
 }
 
-/// Quorum configuration for blockhash consensus
-#[derive(Debug, Clone)]
-pub struct QuorumConfig {
-    /// Minimum number of RPC responses required for quorum
-    pub min_responses: usize,
-    /// Maximum allowed slot difference between responses
-    pub max_slot_diff: u64,
-    /// Enable slot-based validation
-    pub enable_slot_validation: bool,
-}
-
-impl Default for QuorumConfig {
-    fn default() -> Self {
-        Self {
-            min_responses: 2,
-            max_slot_diff: 10,
-            enable_slot_validation: true,
-        }
-    }
-}
+/// Quorum configuration for blockhash consensus (shared with `RpcPool::quorum`)
+pub use crate::rpc_manager::QuorumConfig;
 
 /// Simulation cache entry with TTL
 #[derive(Debug, Clone)]
@@ -831,7 +815,7 @@ impl ExecutionContext {
 }
 
 /// Operation priority for nonce vs blockhash decision (Task 6)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum OperationPriority {
     /// Critical sniper operations - require lease, fail fast on exhaustion
     CriticalSniper,
@@ -865,6 +849,16 @@ impl OperationPriority {
             OperationPriority::Bulk => true,
         }
     }
+
+    /// Submission path used when a TPU sender is installed and no route has
+    /// been set for this priority
+    pub fn default_submission_route(&self) -> SubmissionRoute {
+        match self {
+            OperationPriority::CriticalSniper => SubmissionRoute::TpuAndRpc,
+            OperationPriority::Utility => SubmissionRoute::Rpc,
+            OperationPriority::Bulk => SubmissionRoute::Rpc,
+        }
+    }
 }
 
 /// Transaction configuration with Universe Class enhancements
@@ -1040,6 +1034,39 @@ impl Default for TransactionConfig {
 }
 
 impl TransactionConfig {
+    /// Build from the application config (`[tx_builder]`, `[trading]`, `[rpc]`, `[nonce]`)
+    pub fn from_config(config: &Config) -> Self {
+        let tx = &config.tx_builder;
+        Self {
+            priority_fee_lamports: tx.priority_fee_lamports,
+            compute_unit_limit: tx.compute_unit_limit,
+            min_cu_limit: tx.min_cu_limit,
+            max_cu_limit: tx.max_cu_limit,
+            adaptive_priority_fee_base: tx.adaptive_priority_fee_base,
+            adaptive_priority_fee_multiplier: tx.adaptive_priority_fee_multiplier,
+            buy_amount_lamports: (config.trading.buy_amount_sol * 1_000_000_000.0) as u64,
+            slippage_bps: config.trading.slippage_bps as u64,
+            rpc_endpoints: config.rpc.endpoint_urls().into(),
+            rpc_retry_attempts: config.rpc.max_retries as usize,
+            rpc_timeout_ms: config.rpc.timeout_secs.saturating_mul(1000),
+            pumpportal_url: tx.pumpportal_url.clone(),
+            pumpportal_api_key: tx.pumpportal_api_key.clone(),
+            letsbonk_api_url: tx.letsbonk_api_url.clone(),
+            letsbonk_api_key: tx.letsbonk_api_key.clone(),
+            jito_bundle_enabled: config.trading.enable_jito,
+            nonce_count: config.nonce_count,
+            min_liquidity_lamports: config.trading.min_liquidity_lamports,
+            enable_simulation: tx.enable_simulation,
+            enable_ml_slippage: tx.enable_ml_slippage,
+            rpc_rate_limit_rps: config.rpc.rate_limit_rps as f64,
+            circuit_breaker_failure_threshold: config.rpc.circuit_breaker_failure_threshold,
+            circuit_breaker_timeout_secs: config.rpc.circuit_breaker_timeout_secs,
+            max_concurrent_builds: tx.max_concurrent_builds,
+            nonce_lease_ttl_secs: config.nonce.lease_ttl_secs,
+            ..Self::default()
+        }
+    }
+
     pub fn validate(&self) -> Result<(), TransactionBuilderError> {
         if self.buy_amount_lamports == 0 {
             return Err(TransactionBuilderError::ConfigValidation(
@@ -1251,6 +1278,8 @@ pub struct TransactionBuilder {
     adaptive_ttl_enabled: bool,
     nonce_manager: Arc<NonceManager>,
     rpc_clients: Vec<Arc<RpcClient>>,
+    // Shared slot clock; its blockhash and slot replace RPC calls while live
+    slot_clock: Option<Arc<SlotClock>>,
     slippage_predictor: RwLock<SlippagePredictor>,
     tx_counter: AtomicU64, // For signer rotation
 
@@ -1276,6 +1305,9 @@ pub struct TransactionBuilder {
     // Worker pool semaphore for batch operations
     worker_pool_semaphore: Arc<Semaphore>,
 
+    // Token program / extension detection per mint
+    mint_cache: Arc<MintCache>,
+
     #[cfg(feature = "pumpfun")]
     pumpfun_client: PumpFun,
 }
@@ -1355,6 +1387,7 @@ impl TransactionBuilder {
             adaptive_ttl_enabled: true,
             nonce_manager,
             rpc_clients,
+            slot_clock: None,
             slippage_predictor: RwLock::new(SlippagePredictor::new(100)),
             tx_counter: AtomicU64::new(0),
             rpc_rate_limiter,
@@ -1369,15 +1402,35 @@ impl TransactionBuilder {
             blockhash_quorum_success_count: AtomicU64::new(0),
             blockhash_fallback_count: AtomicU64::new(0),
             worker_pool_semaphore: Arc::new(Semaphore::new(config.max_concurrent_builds)),
+            mint_cache: Arc::new(MintCache::default()),
             #[cfg(feature = "pumpfun")]
             pumpfun_client,
         })
     }
 
+    /// Use the shared slot clock for blockhashes and the current slot
+    pub fn set_slot_clock(&mut self, slot_clock: Arc<SlotClock>) {
+        self.slot_clock = Some(slot_clock);
+    }
+
     pub async fn get_recent_blockhash(
         &self,
         config: &TransactionConfig,
     ) -> Result<Hash, TransactionBuilderError> {
+        // A slot clock blockhash with enough slots left needs no RPC round trip
+        if let Some(blockhash) = self
+            .slot_clock
+            .as_ref()
+            .and_then(|clock| clock.fresh_blockhash())
+        {
+            debug!(
+                hash = %blockhash.hash,
+                expiry_slot = blockhash.expiry_slot(),
+                "Using slot clock blockhash"
+            );
+            return Ok(blockhash.hash);
+        }
+
         // Apply rate limiting for RPC calls
         if let Some(limiter) = &self.rpc_rate_limiter {
             limiter.consume(1.0).await;
@@ -1567,7 +1620,7 @@ impl TransactionBuilder {
         // Fallback: Check cache first (slot-based staleness detection)
         if config.quorum_config.enable_slot_validation {
             // Get current slot first, before acquiring the cache lock
-            if let Ok(current_slot) = self.rpc_clients[0].get_slot().await {
+            if let Ok(current_slot) = self.get_current_slot().await {
                 let cache = self.blockhash_cache.read().await;
                 let _now = Instant::now();
 
@@ -1714,6 +1767,7 @@ impl TransactionBuilder {
     /// * `config` - Transaction configuration
     /// * `enforce_nonce` - When `true`, always acquire nonce lease or fail (no fallback to blockhash).
     ///                     When `false`, skip nonce acquisition entirely (use recent blockhash).
+    /// * `class` - Operation class the nonce lease is taken for (buys vs. sells)
     ///
     /// # Behavior
     ///
@@ -1725,6 +1779,7 @@ impl TransactionBuilder {
         &self,
         config: &TransactionConfig,
         enforce_nonce: bool,
+        class: NonceClass,
     ) -> Result<ExecutionContext, TransactionBuilderError> {
         if !enforce_nonce {
             // Skip nonce acquisition entirely - use recent blockhash
@@ -1745,9 +1800,24 @@ impl TransactionBuilder {
         }
 
         // enforce_nonce = true: Always acquire nonce lease or fail
-        // Phase 1, Task 1.3: Use try_acquire for atomic, TOCTTOU-safe acquisition
+        // Phase 1, Task 1.3: Use try_acquire for atomic, TOCTTOU-safe acquisition.
+        // Exits instead wait their turn, ahead of (and preempting) queued
+        // lower-class acquires, rather than failing on a momentarily busy pool.
         let ttl = Duration::from_secs(config.nonce_lease_ttl_secs);
-        match self.nonce_manager.try_acquire_nonce(ttl, 2000).await {
+        let lease = match class {
+            NonceClass::Exit => self
+                .nonce_manager
+                .acquire_nonce_for_class(class, ttl, 2000)
+                .await
+                .inspect_err(|e| warn!(class = %class, error = %e, "Nonce acquire failed"))
+                .ok(),
+            _ => {
+                self.nonce_manager
+                    .try_acquire_nonce_for_class(class, ttl, 2000)
+                    .await
+            }
+        };
+        match lease {
             #[cfg(feature = "zk_enabled")]
             Some(mut lease) => {
                 // Record successful nonce acquisition
@@ -1849,7 +1919,11 @@ impl TransactionBuilder {
 
         if use_nonce {
             // Critical operation - require nonce lease
-            match self.nonce_manager.acquire_nonce().await {
+            match self
+                .nonce_manager
+                .acquire_nonce_for_class(NonceClass::Entry, Duration::from_secs(60), 2000)
+                .await
+            {
                 #[cfg(feature = "zk_enabled")]
                 Ok(mut lease) => {
                     // Record successful nonce acquisition
@@ -2300,7 +2374,11 @@ impl TransactionBuilder {
 
         // Phase 1, Task 1.4: Use prepare_execution_context_with_enforcement
         let exec_ctx = self
-            .prepare_execution_context_with_enforcement(&effective_config, enforce_nonce)
+            .prepare_execution_context_with_enforcement(
+                &effective_config,
+                enforce_nonce,
+                NonceClass::Entry,
+            )
             .await?;
         let recent_blockhash = exec_ctx.blockhash;
 
@@ -2329,10 +2407,7 @@ impl TransactionBuilder {
         let buy_instruction = match dex_program {
             DexProgram::PumpFun => self.build_pumpfun_instruction(candidate, config).await,
             DexProgram::LetsBonk => self.build_letsbonk_instruction(candidate, config).await,
-            DexProgram::Raydium => {
-                self.build_placeholder_buy_instruction(candidate, config)
-                    .await
-            } // TODO: implement Raydium
+            DexProgram::Raydium => self.build_raydium_instruction(candidate, config).await,
             DexProgram::Orca => {
                 self.build_placeholder_buy_instruction(candidate, config)
                     .await
@@ -2344,10 +2419,11 @@ impl TransactionBuilder {
         }?;
 
         // Check if this is a placeholder instruction (no adaptive fee for placeholders)
-        let is_placeholder = matches!(
-            dex_program,
-            DexProgram::Unknown(_) | DexProgram::Raydium | DexProgram::Orca
-        );
+        let is_placeholder = matches!(dex_program, DexProgram::Unknown(_) | DexProgram::Orca);
+
+        // Token-2022: hooked mints need the hook's accounts on the DEX instruction
+        let buy_instruction =
+            self.with_transfer_hook_accounts(&dex_program, &candidate.mint, true, buy_instruction);
 
         // Universe Class: Pre-simulation for CU estimation with caching
         if config.enable_simulation {
@@ -2758,7 +2834,11 @@ impl TransactionBuilder {
 
         // Phase 1, Task 1.4: Use prepare_execution_context_with_enforcement
         let exec_ctx = self
-            .prepare_execution_context_with_enforcement(&effective_config, enforce_nonce)
+            .prepare_execution_context_with_enforcement(
+                &effective_config,
+                enforce_nonce,
+                NonceClass::Exit,
+            )
             .await?;
         let recent_blockhash = exec_ctx.blockhash;
 
@@ -2799,6 +2879,10 @@ impl TransactionBuilder {
         // Check if this is a placeholder instruction (no adaptive fee for placeholders)
         let is_placeholder = matches!(dex_program, DexProgram::Unknown(_));
 
+        // Token-2022: hooked mints need the hook's accounts on the DEX instruction
+        let sell_instruction =
+            self.with_transfer_hook_accounts(&dex_program, mint, false, sell_instruction);
+
         // Task 2: Pre-simulation for CU estimation with caching (same as buy)
         if config.enable_simulation {
             // Apply rate limiting for simulations
@@ -3188,6 +3272,158 @@ impl TransactionBuilder {
         self.rpc_clients[index].clone()
     }
 
+    // --- Token program detection ---
+
+    /// Start fetching the token program and extensions of `mint` in the background
+    ///
+    /// Called when a candidate arrives so the build path can read the result
+    /// from the cache instead of paying the RPC round trips itself. Fresh
+    /// entries are not fetched again.
+    pub fn prefetch_mint(&self, mint: Pubkey) {
+        if self.mint_cache.get_fresh(&mint).is_some() {
+            return;
+        }
+
+        let rpc = self.rpc_client_for(0);
+        let cache = self.mint_cache.clone();
+        tokio::spawn(async move {
+            if let Err(e) = Self::fetch_mint(&rpc, &cache, &mint).await {
+                debug!(mint = %mint, error = %e, "Mint prefetch failed");
+            }
+        });
+    }
+
+    /// Fetch and cache the token program and extensions of `mint`
+    ///
+    /// For mints with a transfer hook the hook's validation account is fetched
+    /// alongside so extra accounts can be resolved without another round trip.
+    async fn fetch_mint(
+        rpc: &RpcClient,
+        cache: &MintCache,
+        mint: &Pubkey,
+    ) -> Result<Arc<CachedMint>, TransactionBuilderError> {
+        let response = rpc
+            .get_account_with_commitment(mint, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| TransactionBuilderError::RpcConnection(e.to_string()))?;
+        let account = response
+            .value
+            .ok_or_else(|| TransactionBuilderError::InstructionBuild {
+                program: "token_program".to_string(),
+                reason: format!("mint account {} not found", mint),
+            })?;
+        let info = MintInfo::decode(&account.owner, &account.data).map_err(|e| {
+            TransactionBuilderError::InstructionBuild {
+                program: "token_program".to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+
+        let hook_validation_data = match info.transfer_hook_program() {
+            Some(hook_program) => {
+                let validation = token_program::extra_account_metas_address(mint, &hook_program);
+                rpc.get_account_with_commitment(&validation, CommitmentConfig::confirmed())
+                    .await
+                    .map_err(|e| TransactionBuilderError::RpcConnection(e.to_string()))?
+                    .value
+                    .map(|account| account.data)
+            }
+            None => None,
+        };
+
+        debug!(
+            mint = %mint,
+            token_program = ?info.token_program,
+            extensions = info.extensions.len(),
+            "Resolved mint token program"
+        );
+        Ok(cache.insert(
+            *mint,
+            CachedMint::new(info, response.context.slot, hook_validation_data),
+        ))
+    }
+
+    /// Cached mint detection result, without touching RPC
+    pub fn cached_mint(&self, mint: &Pubkey) -> Option<Arc<CachedMint>> {
+        self.mint_cache.get(mint)
+    }
+
+    /// Append transfer-hook accounts to a DEX instruction trading a hooked mint
+    ///
+    /// The DEX program CPIs into Token-2022 `TransferChecked`, which in turn
+    /// invokes the hook; every account the hook needs has to be passed to the
+    /// DEX instruction as a remaining account. Only pump.fun's transfer layout
+    /// is known here; other programs and mints not yet in the cache (see
+    /// [`Self::prefetch_mint`]) are left untouched.
+    fn with_transfer_hook_accounts(
+        &self,
+        dex_program: &DexProgram,
+        mint: &Pubkey,
+        is_buy: bool,
+        mut instruction: Instruction,
+    ) -> Instruction {
+        if !matches!(dex_program, DexProgram::PumpFun) || instruction.program_id != PUMPFUN_PROGRAM_ID
+        {
+            return instruction;
+        }
+
+        let Some(cached) = self.mint_cache.get(mint) else {
+            debug!(mint = %mint, "Mint not resolved yet, assuming no transfer hook");
+            return instruction;
+        };
+        if cached.info.transfer_hook_program().is_none() {
+            return instruction;
+        }
+
+        // pump.fun buy/sell data: 8-byte discriminator, token amount, SOL bound
+        let amount = instruction
+            .data
+            .get(8..16)
+            .map(|b| u64::from_le_bytes(b.try_into().expect("slice is 8 bytes")))
+            .unwrap_or(0);
+        let program = cached.token_program();
+        let wallet = self.wallet.pubkey();
+        let bonding_curve = Pubkey::find_program_address(
+            &[b"bonding-curve", mint.as_ref()],
+            &PUMPFUN_PROGRAM_ID,
+        )
+        .0;
+        let curve_ata = token_program::associated_token_address(&bonding_curve, mint, program);
+        let wallet_ata = token_program::associated_token_address(&wallet, mint, program);
+
+        let transfer = if is_buy {
+            HookedTransfer {
+                source: curve_ata,
+                mint: *mint,
+                destination: wallet_ata,
+                authority: bonding_curve,
+                amount,
+            }
+        } else {
+            HookedTransfer {
+                source: wallet_ata,
+                mint: *mint,
+                destination: curve_ata,
+                authority: wallet,
+                amount,
+            }
+        };
+
+        match cached.transfer_hook_accounts(&transfer) {
+            Ok(extra) => {
+                for meta in extra {
+                    if !instruction.accounts.iter().any(|a| a.pubkey == meta.pubkey) {
+                        instruction.accounts.push(meta);
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(mint = %mint, error = %e, "Could not resolve transfer-hook accounts");
+            }
+        }
+        instruction
+    }
+
     // --- Instruction builders ---
 
     async fn build_pumpfun_instruction(
@@ -3204,7 +3440,18 @@ impl TransactionBuilder {
         }
 
         // Fallback do HTTP PumpPortal, gdy feature pumpfun wyłączony
-        self.build_pumpportal_or_memo(candidate, config).await
+        self.build_pumpportal_or_memo(candidate, config, "pump")
+            .await
+    }
+
+    /// Raydium AMM buys go through PumpPortal, which resolves the pool keys
+    async fn build_raydium_instruction(
+        &self,
+        candidate: &PremintCandidate,
+        config: &TransactionConfig,
+    ) -> Result<Instruction, TransactionBuilderError> {
+        self.build_pumpportal_or_memo(candidate, config, "raydium")
+            .await
     }
 
     async fn build_letsbonk_instruction(
@@ -3257,6 +3504,7 @@ impl TransactionBuilder {
         &self,
         candidate: &PremintCandidate,
         config: &TransactionConfig,
+        pool: &str,
     ) -> Result<Instruction, TransactionBuilderError> {
         if let Some(url) = &config.pumpportal_url {
             let payload = serde_json::json!({
@@ -3264,6 +3512,7 @@ impl TransactionBuilder {
                 "buy_amount": config.buy_amount_lamports,
                 "slippage": config.slippage_bps as f64 / 100.0,
                 "payer": self.wallet.pubkey().to_string(),
+                "pool": pool,
             });
 
             // Apply HTTP rate limiting
@@ -3565,20 +3814,20 @@ impl TransactionBuilder {
         &self,
         config: &TransactionConfig,
     ) -> Result<Signature, TransactionBuilderError> {
+        // Native mint is always owned by the original SPL Token program
         let wsol_mint = Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap();
-        let wsol_ata = get_associated_token_address(&self.wallet.pubkey(), &wsol_mint);
+        let wsol_ata = token_program::associated_token_address(
+            &self.wallet.pubkey(),
+            &wsol_mint,
+            TokenProgram::SplToken,
+        );
 
-        let close_ix = close_account(
-            &token_program_id(),
+        let close_ix = token_program::close_account(
+            TokenProgram::SplToken,
             &wsol_ata,
             &self.wallet.pubkey(),
             &self.wallet.pubkey(),
-            &[],
-        )
-        .map_err(|e| TransactionBuilderError::InstructionBuild {
-            program: "unwrap_wsol".to_string(),
-            reason: e.to_string(),
-        })?;
+        );
 
         let recent_blockhash = self.get_recent_blockhash(config).await?;
 
@@ -3761,7 +4010,16 @@ impl TransactionBuilder {
     }
 
     /// Get current slot for stale detection (Universe Class)
+    ///
+    /// Taken from the slot clock when it is live.
     pub async fn get_current_slot(&self) -> Result<u64, TransactionBuilderError> {
+        if let Some(slot) = self
+            .slot_clock
+            .as_ref()
+            .and_then(|clock| clock.current_slot())
+        {
+            return Ok(slot);
+        }
         let rpc = self.rpc_client_for(0);
         rpc.get_slot().await.map_err(|e| {
             TransactionBuilderError::RpcConnection(format!("Failed to fetch slot: {}", e))
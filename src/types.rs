@@ -1,6 +1,7 @@
 //! Common types used throughout the application
 
 use crate::components::gui_bridge::GuiSnapshotProvider;
+use crate::observability::SpanContext;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
@@ -46,6 +47,94 @@ pub struct PremintCandidate {
 
     /// Signature of the transaction that created this candidate
     pub signature: Option<String>,
+
+    /// Result of the pre-buy token safety screening (None until screened)
+    #[serde(default)]
+    pub screening: Option<ScreeningReport>,
+
+    /// Trace opened at sniffer extraction; buy engine spans join it
+    #[serde(default)]
+    pub trace: Option<SpanContext>,
+}
+
+/// Individual token safety check performed before buying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScreeningCheck {
+    /// Mint account fetched and decoded
+    MintAccount,
+    /// Mint authority revoked
+    MintAuthority,
+    /// Freeze authority revoked
+    FreezeAuthority,
+    /// Token-2022 transfer fee
+    TransferFee,
+    /// Token-2022 permanent delegate
+    PermanentDelegate,
+    /// Token-2022 transfer hook
+    TransferHook,
+    /// Token-2022 non-transferable flag
+    NonTransferable,
+    /// Token-2022 default account state (frozen by default)
+    DefaultAccountState,
+    /// Decimals within the configured range
+    Decimals,
+    /// Supply within the configured range
+    Supply,
+    /// Top-holder concentration
+    HolderConcentration,
+}
+
+/// Outcome of a single screening check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckOutcome {
+    /// Check passed
+    Pass,
+    /// Risk detected but the reject rule is disabled
+    Warn,
+    /// Risk detected and the candidate must be rejected
+    Fail,
+    /// Check could not be performed
+    Skipped,
+}
+
+/// Per-check screening result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// Which check
+    pub check: ScreeningCheck,
+    /// Outcome
+    pub outcome: CheckOutcome,
+    /// Human-readable detail
+    pub detail: String,
+}
+
+/// Screening report attached to a candidate
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreeningReport {
+    /// Results of every check that ran
+    pub checks: Vec<CheckResult>,
+    /// Unix timestamp (seconds) of the screening
+    pub screened_at: u64,
+}
+
+impl ScreeningReport {
+    /// True if no check failed
+    pub fn passed(&self) -> bool {
+        !self.checks.iter().any(|c| c.outcome == CheckOutcome::Fail)
+    }
+
+    /// Failed checks
+    pub fn failures(&self) -> Vec<&CheckResult> {
+        self.checks
+            .iter()
+            .filter(|c| c.outcome == CheckOutcome::Fail)
+            .collect()
+    }
+
+    /// Result of a specific check
+    pub fn get(&self, check: ScreeningCheck) -> Option<&CheckResult> {
+        self.checks.iter().find(|c| c.check == check)
+    }
 }
 
 /// Priority level for candidates
@@ -312,6 +401,7 @@ impl AppState {
 ///
 /// Controls how the bot manages multiple concurrent positions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct PortfolioConfig {
     /// Enable multi-token portfolio management
     /// When false, bot operates in single-token mode (default, safer)
@@ -5,9 +5,13 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::{Keypair, Signer},
 };
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::config::WalletConfig;
+use crate::keystore::{self, PassphraseSource};
+
 /// Wallet manager for handling keypairs and signing
 /// Enhanced with authority rotation support (Security Enhancement 2)
 pub struct WalletManager {
@@ -17,32 +21,39 @@ pub struct WalletManager {
 }
 
 impl WalletManager {
+    /// Create a wallet manager from the `[wallet]` config section
+    ///
+    /// Hardware wallets are not supported for the trading wallet yet, so
+    /// `use_hardware_wallet = true` is rejected instead of silently falling
+    /// back to the key file.
+    pub fn from_config(config: &WalletConfig) -> Result<Self> {
+        if config.use_hardware_wallet {
+            anyhow::bail!(
+                "use_hardware_wallet is enabled but hardware wallet signing is not supported; \
+                 import the key into an encrypted keystore instead"
+            );
+        }
+
+        let passphrase = match (config.passphrase_fd, &config.passphrase_env) {
+            (Some(fd), _) => PassphraseSource::Fd(fd),
+            (None, Some(var)) => PassphraseSource::Env(var.clone()),
+            (None, None) => PassphraseSource::from_env(),
+        };
+        Self::from_keystore(&config.keypair_path, &passphrase)
+    }
+
     /// Create a new wallet manager from a keypair file
+    ///
+    /// Accepts an encrypted keystore or a plaintext keypair; the passphrase is
+    /// taken from the environment or prompted for on the TTY.
     pub fn from_file(path: &str) -> Result<Self> {
-        let keypair_bytes = std::fs::read(path)
-            .with_context(|| format!("Failed to read keypair file: {}", path))?;
-
-        let keypair = if keypair_bytes.len() == 64 {
-            // Raw bytes format - validate before conversion
-            if keypair_bytes.iter().all(|&b| b == 0) {
-                anyhow::bail!("Invalid keypair: all-zero key rejected");
-            }
-            Keypair::try_from(keypair_bytes.as_slice()).context("Invalid keypair bytes")?
-        } else {
-            // JSON format
-            let json: Vec<u8> =
-                serde_json::from_slice(&keypair_bytes).context("Failed to parse keypair JSON")?;
-            if json.len() != 64 {
-                anyhow::bail!(
-                    "Invalid keypair length: expected 64 bytes, got {}",
-                    json.len()
-                );
-            }
-            if json.iter().all(|&b| b == 0) {
-                anyhow::bail!("Invalid keypair: all-zero key rejected");
-            }
-            Keypair::try_from(json.as_slice()).context("Invalid keypair from JSON")?
-        };
+        Self::from_keystore(path, &PassphraseSource::from_env())
+    }
+
+    /// Create a new wallet manager from a keystore using an explicit passphrase source
+    pub fn from_keystore(path: &str, passphrase: &PassphraseSource) -> Result<Self> {
+        let keypair = keystore::load_keypair(Path::new(path), passphrase)
+            .with_context(|| format!("Failed to load keypair file: {}", path))?;
 
         Ok(Self {
             keypair: Arc::new(keypair),